@@ -122,6 +122,7 @@ use store::{
 use task_executor::{ShutdownReason, TaskExecutor};
 use tokio::sync::mpsc::Receiver;
 use tokio_stream::Stream;
+use tracing::{info_span, Instrument};
 use tree_hash::TreeHash;
 use types::blob_sidecar::FixedBlobSidecarList;
 use types::data_column_sidecar::{ColumnIndex, DataColumnIdentifier};
@@ -2664,6 +2665,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         F: FnOnce() -> R + Send + 'static,
         R: Send + 'static,
     {
+        // Keep the task within the current span, so that it is included in its trace.
+        let span = tracing::Span::current();
+        let task = move || span.in_scope(task);
         let handle = self
             .task_executor
             .spawn_blocking_handle(task, name)
@@ -2919,6 +2923,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
     /// ## Errors
     ///
     /// Returns an `Err` if the given block was invalid, or an error was encountered during
+    #[tracing::instrument(skip_all, name = "gossip_verification")]
     pub async fn verify_block_for_gossip(
         self: &Arc<Self>,
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
@@ -3314,14 +3319,24 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             );
         }
 
+        // The root span of the block's trace, if traces are being exported.
+        let block_span = info_span!(
+            "process_block",
+            %block_root,
+            slot = %block_slot,
+            source = %block_source,
+        );
+
         // A small closure to group the verification and import errors.
         let chain = self.clone();
         let import_block = async move {
-            let execution_pending = unverified_block.into_execution_pending_block(
-                block_root,
-                &chain,
-                notify_execution_layer,
-            )?;
+            let execution_pending = info_span!("consensus_verification").in_scope(|| {
+                unverified_block.into_execution_pending_block(
+                    block_root,
+                    &chain,
+                    notify_execution_layer,
+                )
+            })?;
             publish_fn()?;
 
             // Record the time it took to complete consensus verification.
@@ -3331,7 +3346,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     .set_time_consensus_verified(block_root, block_slot, timestamp)
             }
 
-            let executed_block = chain.into_executed_block(execution_pending).await?;
+            let executed_block = chain
+                .into_executed_block(execution_pending)
+                .instrument(info_span!("execution_verification"))
+                .await?;
 
             // Record the *additional* time it took to wait for execution layer verification.
             if let Some(timestamp) = self.slot_clock.now_duration() {
@@ -3342,16 +3360,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
             match executed_block {
                 ExecutedBlock::Available(block) => {
-                    self.import_available_block(Box::new(block), None).await
+                    self.import_available_block(Box::new(block), None)
+                        .instrument(info_span!("import_block"))
+                        .await
                 }
                 ExecutedBlock::AvailabilityPending(block) => {
-                    self.check_block_availability_and_import(block).await
+                    self.check_block_availability_and_import(block)
+                        .instrument(info_span!("data_availability_check"))
+                        .await
                 }
             }
         };
 
         // Verify and import the block.
-        match import_block.await {
+        match import_block.instrument(block_span).await {
             // The block was successfully verified and imported. Yay.
             Ok(status @ AvailabilityProcessingStatus::Imported(block_root)) => {
                 debug!(
@@ -3660,6 +3682,13 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             consensus_context,
         } = import_data;
 
+        // Record the time at which this block passed the data availability check.
+        if let Some(available) = self.slot_clock.now_duration() {
+            self.block_times_cache
+                .write()
+                .set_time_available(block_root, block.slot(), available);
+        }
+
         // Record the time at which this block's blobs became available.
         if let Some(blobs_available) = block.blobs_available_timestamp() {
             self.block_times_cache.write().set_time_blob_observed(
@@ -3783,6 +3812,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // Register the new block with the fork choice service.
         {
+            let _fork_choice_span = info_span!("fork_choice").entered();
             let block_delay = self
                 .slot_clock
                 .seconds_from_current_slot_start()
@@ -3799,6 +3829,14 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                     &self.spec,
                 )
                 .map_err(|e| BlockError::BeaconChainError(e.into()))?;
+
+            if let Some(fork_choice_applied) = self.slot_clock.now_duration() {
+                self.block_times_cache.write().set_time_fork_choice_applied(
+                    block_root,
+                    block.slot(),
+                    fork_choice_applied,
+                );
+            }
         }
 
         // If the block is recent enough and it was not optimistically imported, check to see if it
@@ -5387,10 +5425,11 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         let mut attestations = self
             .op_pool
-            .get_attestations(
+            .get_attestations_with_strategy(
                 &state,
                 prev_attestation_filter,
                 curr_attestation_filter,
+                &self.config.attestation_packing_strategy,
                 &self.spec,
             )
             .map_err(BlockProductionError::OpPoolError)?;
@@ -6583,7 +6622,12 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             // Always run the light-weight pruning tasks (these structures should be empty during
             // sync anyway).
             self.naive_aggregation_pool.write().prune(slot);
-            self.block_times_cache.write().prune(slot);
+            let timeline_log_entries = self.block_times_cache.write().prune(slot);
+            // Write the timings of the pruned blocks without holding the lock on the cache.
+            if let Some(entries) = timeline_log_entries {
+                self.task_executor
+                    .spawn_blocking(move || entries.write(), "block_times_timeline_log");
+            }
 
             // Don't run heavy-weight tasks during sync.
             if self.best_slot() + MAX_PER_SLOT_FORK_CHOICE_DISTANCE < slot {
@@ -6614,6 +6658,41 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 },
                 "per_slot_task_fc_signal_tx",
             );
+
+            if self.config.fork_choice_snapshot_slots > 0 {
+                let chain = self.clone();
+                self.task_executor.clone().spawn_blocking(
+                    move || {
+                        if let Err(e) = chain.persist_fork_choice_snapshot(slot) {
+                            warn!(
+                                chain.log,
+                                "Failed to persist fork choice snapshot";
+                                "error" => ?e,
+                                "slot" => slot,
+                            );
+                        }
+                    },
+                    "per_slot_task_fork_choice_snapshot",
+                );
+            }
+
+            let op_pool_persist_interval = self.config.op_pool_persist_interval_slots;
+            if op_pool_persist_interval > 0 && slot.as_u64() % op_pool_persist_interval == 0 {
+                let chain = self.clone();
+                self.task_executor.clone().spawn_blocking(
+                    move || {
+                        if let Err(e) = chain.persist_op_pool() {
+                            warn!(
+                                chain.log,
+                                "Failed to persist op pool";
+                                "error" => ?e,
+                                "slot" => slot,
+                            );
+                        }
+                    },
+                    "per_slot_task_persist_op_pool",
+                );
+            }
         }
     }
 
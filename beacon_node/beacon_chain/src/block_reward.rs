@@ -1,11 +1,15 @@
 use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::{AttestationRewards, BlockReward, BlockRewardMeta};
-use operation_pool::{AttMaxCover, MaxCover, RewardCache, SplitAttestation};
+use operation_pool::{
+    AttMaxCover, MaxCover, OperationPool, PackingStrategy, RewardCache, SplitAttestation,
+};
 use state_processing::{
     common::get_attesting_indices_from_state,
     per_block_processing::altair::sync_committee::compute_sync_aggregate_rewards,
 };
-use types::{AbstractExecPayload, BeaconBlockRef, BeaconState, EthSpec, Hash256};
+use types::{
+    AbstractExecPayload, Attestation, AttestationRef, BeaconBlockRef, BeaconState, EthSpec, Hash256,
+};
 
 impl<T: BeaconChainTypes> BeaconChain<T> {
     pub fn compute_block_reward<Payload: AbstractExecPayload<T::EthSpec>>(
@@ -20,15 +24,61 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             return Err(BeaconChainError::BlockRewardSlotError);
         }
 
+        let attestation_rewards = self.compute_attestation_rewards(
+            block.body().attestations(),
+            state,
+            reward_cache,
+            include_attestations,
+        )?;
+        let attestation_total = attestation_rewards.total;
+
+        // Sync committee rewards.
+        let sync_committee_rewards = if let Ok(sync_aggregate) = block.body().sync_aggregate() {
+            let (_, proposer_reward_per_bit) = compute_sync_aggregate_rewards(state, &self.spec)
+                .map_err(|_| BeaconChainError::BlockRewardSyncError)?;
+            sync_aggregate.sync_committee_bits.num_set_bits() as u64 * proposer_reward_per_bit
+        } else {
+            0
+        };
+
+        // Total, metadata
+        let total = attestation_total + sync_committee_rewards;
+
+        let meta = BlockRewardMeta {
+            slot: block.slot(),
+            parent_slot: state.latest_block_header().slot,
+            proposer_index: block.proposer_index(),
+            graffiti: block.body().graffiti().as_utf8_lossy(),
+        };
+
+        Ok(BlockReward {
+            total,
+            block_root,
+            meta,
+            attestation_rewards,
+            sync_committee_rewards,
+        })
+    }
+
+    /// Compute the proposer reward for including `attestations` in a block at `state.slot()`.
+    ///
+    /// Each attestation is only credited for validators not covered by an earlier attestation.
+    fn compute_attestation_rewards<'a>(
+        &self,
+        attestations: impl Iterator<Item = AttestationRef<'a, T::EthSpec>>,
+        state: &BeaconState<T::EthSpec>,
+        reward_cache: &mut RewardCache,
+        include_attestations: bool,
+    ) -> Result<AttestationRewards, BeaconChainError> {
         reward_cache.update(state)?;
 
         let total_active_balance = state.get_total_active_balance()?;
 
-        let split_attestations = block
-            .body()
-            .attestations()
+        let attestations = attestations.collect::<Vec<_>>();
+        let split_attestations = attestations
+            .iter()
             .map(|att| {
-                let attesting_indices = get_attesting_indices_from_state(state, att)?;
+                let attesting_indices = get_attesting_indices_from_state(state, *att)?;
                 Ok(SplitAttestation::new(
                     att.clone_as_attestation(),
                     attesting_indices,
@@ -75,8 +125,6 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             }
         }
 
-        let attestation_total = prev_epoch_total + curr_epoch_total;
-
         // Drop the covers.
         let per_attestation_rewards = per_attestation_rewards
             .into_iter()
@@ -85,48 +133,56 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
 
         // Add the attestation data if desired.
         let attestations = if include_attestations {
-            block
-                .body()
-                .attestations()
-                .map(|a| a.data().clone())
-                .collect()
+            attestations.iter().map(|a| a.data().clone()).collect()
         } else {
             vec![]
         };
 
-        let attestation_rewards = AttestationRewards {
-            total: attestation_total,
+        Ok(AttestationRewards {
+            total: prev_epoch_total + curr_epoch_total,
             prev_epoch_total,
             curr_epoch_total,
             per_attestation_rewards,
             attestations,
-        };
-
-        // Sync committee rewards.
-        let sync_committee_rewards = if let Ok(sync_aggregate) = block.body().sync_aggregate() {
-            let (_, proposer_reward_per_bit) = compute_sync_aggregate_rewards(state, &self.spec)
-                .map_err(|_| BeaconChainError::BlockRewardSyncError)?;
-            sync_aggregate.sync_committee_bits.num_set_bits() as u64 * proposer_reward_per_bit
-        } else {
-            0
-        };
-
-        // Total, metadata
-        let total = attestation_total + sync_committee_rewards;
+        })
+    }
 
-        let meta = BlockRewardMeta {
-            slot: block.slot(),
-            parent_slot: state.latest_block_header().slot,
-            proposer_index: block.proposer_index(),
-            graffiti: block.body().graffiti().as_utf8_lossy(),
-        };
+    /// Compute the attestation rewards of the best block that could have been packed at
+    /// `state.slot()` from `candidates`, using `strategy`.
+    ///
+    /// The candidates are loaded into an empty operation pool, so they are subject to the same
+    /// inclusion rules as attestations during block production. Candidates which can't be
+    /// included at this slot are ignored. The state must have its committee and epoch caches
+    /// built.
+    pub fn compute_optimal_attestation_rewards<'a>(
+        &self,
+        candidates: impl IntoIterator<Item = AttestationRef<'a, T::EthSpec>>,
+        state: &BeaconState<T::EthSpec>,
+        reward_cache: &mut RewardCache,
+        strategy: &impl PackingStrategy,
+    ) -> Result<AttestationRewards, BeaconChainError> {
+        let op_pool = OperationPool::<T::EthSpec>::new();
+        for att in candidates {
+            // Attestations from outside the previous and current epochs have no committees in
+            // `state`, and couldn't be included anyway.
+            if let Ok(attesting_indices) = get_attesting_indices_from_state(state, att) {
+                op_pool.insert_attestation(att.clone_as_attestation(), attesting_indices)?;
+            }
+        }
 
-        Ok(BlockReward {
-            total,
-            block_root,
-            meta,
-            attestation_rewards,
-            sync_committee_rewards,
-        })
+        let attestations = op_pool.get_attestations_with_strategy(
+            state,
+            |_| true,
+            |_| true,
+            strategy,
+            &self.spec,
+        )?;
+
+        self.compute_attestation_rewards(
+            attestations.iter().map(Attestation::to_ref),
+            state,
+            reward_cache,
+            false,
+        )
     }
 }
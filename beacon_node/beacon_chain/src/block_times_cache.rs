@@ -8,23 +8,40 @@
 //! - The block was observed late.
 //! - We were too slow to import it.
 //! - We were too slow to set it as head.
+//!
+//! If a timeline log is configured, the timings of each block are also written to a rotating
+//! JSON-lines file as `BlockTimings` when the block is pruned from the cache. The file is written
+//! by `TimelineLogEntries::write`, so that callers can write it without holding a lock on the cache.
 
+pub use eth2::lighthouse::DataComponentKind;
+use eth2::lighthouse::{BlockTimings, DataComponentTiming};
 use eth2::types::{Hash256, Slot};
+use parking_lot::Mutex;
 use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
 
 type BlockRoot = Hash256;
 
 #[derive(Clone, Default)]
 pub struct Timestamps {
     pub observed: Option<Duration>,
+    pub gossip_verified: Option<Duration>,
     pub all_blobs_observed: Option<Duration>,
     pub consensus_verified: Option<Duration>,
     pub started_execution: Option<Duration>,
+    pub payload_verified: Option<Duration>,
     pub executed: Option<Duration>,
+    pub available: Option<Duration>,
+    pub fork_choice_applied: Option<Duration>,
     pub attestable: Option<Duration>,
     pub imported: Option<Duration>,
     pub set_as_head: Option<Duration>,
+    /// The arrival times of the blobs and data columns of the block, in order of arrival.
+    pub data_components: Vec<(DataComponentKind, u64, Duration)>,
 }
 
 // Helps arrange delay data so it is more relevant to metrics.
@@ -119,15 +136,95 @@ impl BlockTimesCacheValue {
             peer_info: Default::default(),
         }
     }
+
+    fn block_timings(&self, block_root: BlockRoot) -> BlockTimings {
+        let millis = |timestamp: Option<Duration>| timestamp.map(|t| t.as_millis() as u64);
+        let times = &self.timestamps;
+        BlockTimings {
+            block_root,
+            slot: self.slot,
+            peer_id: self.peer_info.id.clone(),
+            peer_client: self.peer_info.client.clone(),
+            observed: millis(times.observed),
+            gossip_verified: millis(times.gossip_verified),
+            consensus_verified: millis(times.consensus_verified),
+            started_execution: millis(times.started_execution),
+            payload_verified: millis(times.payload_verified),
+            executed: millis(times.executed),
+            all_blobs_observed: millis(times.all_blobs_observed),
+            available: millis(times.available),
+            fork_choice_applied: millis(times.fork_choice_applied),
+            attestable: millis(times.attestable),
+            imported: millis(times.imported),
+            set_as_head: millis(times.set_as_head),
+            data_components: times
+                .data_components
+                .iter()
+                .map(|(kind, index, observed)| DataComponentTiming {
+                    kind: *kind,
+                    index: *index,
+                    observed: observed.as_millis() as u64,
+                })
+                .collect(),
+        }
+    }
+
+    /// Blocks from sync of historic slots are not imported into the cache, see
+    /// `BeaconChain::import_block_update_metrics_and_events`, and are not worth logging.
+    fn should_log(&self) -> bool {
+        self.timestamps.imported.is_some() || self.timestamps.gossip_verified.is_some()
+    }
 }
 
 #[derive(Default)]
 pub struct BlockTimesCache {
     pub cache: HashMap<BlockRoot, BlockTimesCacheValue>,
+    /// Receives the `BlockTimings` of each block pruned from the cache, one JSON object per line.
+    timeline_log: Option<Arc<Mutex<RollingFileAppender>>>,
+}
+
+/// The timings of blocks pruned from a `BlockTimesCache`, which are yet to be written to its
+/// timeline log.
+#[must_use]
+pub struct TimelineLogEntries {
+    timeline_log: Arc<Mutex<RollingFileAppender>>,
+    timings: Vec<BlockTimings>,
+}
+
+impl TimelineLogEntries {
+    /// Write the timings to the timeline log. This performs file I/O, and should be called from a
+    /// blocking task.
+    pub fn write(mut self) {
+        let mut timeline_log = self.timeline_log.lock();
+        self.timings.sort_by_key(|timings| timings.slot);
+        for timings in self.timings {
+            // Logging is best-effort, the cache is still useful without it.
+            if let Ok(mut line) = serde_json::to_vec(&timings) {
+                line.push(b'\n');
+                let _ = timeline_log.write_all(&line);
+            }
+        }
+    }
 }
 
 /// Helper methods to read from and write to the cache.
 impl BlockTimesCache {
+    /// Create a cache which logs the timings of blocks to daily files in `dir`, keeping at most
+    /// `max_files` of them.
+    pub fn with_timeline_log(dir: &Path, max_files: usize) -> Result<Self, String> {
+        let timeline_log = RollingFileAppender::builder()
+            .rotation(Rotation::DAILY)
+            .max_log_files(max_files)
+            .filename_prefix("block_timings")
+            .filename_suffix("jsonl")
+            .build(dir)
+            .map_err(|e| format!("Unable to create block timings log: {:?}", e))?;
+        Ok(Self {
+            cache: HashMap::new(),
+            timeline_log: Some(Arc::new(Mutex::new(timeline_log))),
+        })
+    }
+
     /// Set the observation time for `block_root` to `timestamp` if `timestamp` is less than
     /// any previous timestamp at which this block was observed.
     pub fn set_time_observed(
@@ -200,6 +297,47 @@ impl BlockTimesCache {
         }
     }
 
+    /// Record the arrival of a blob or data column, keeping the earliest time for each index.
+    pub fn set_time_data_component_observed(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        kind: DataComponentKind,
+        index: u64,
+        timestamp: Duration,
+    ) {
+        let block_times = self
+            .cache
+            .entry(block_root)
+            .or_insert_with(|| BlockTimesCacheValue::new(slot));
+        let components = &mut block_times.timestamps.data_components;
+        if let Some(position) = components
+            .iter()
+            .position(|(k, i, _)| *k == kind && *i == index)
+        {
+            if components[position].2 <= timestamp {
+                return;
+            }
+            components.remove(position);
+        }
+        components.push((kind, index, timestamp));
+        components.sort_by_key(|(_, _, observed)| *observed);
+    }
+
+    pub fn set_time_gossip_verified(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        timestamp: Duration,
+    ) {
+        self.set_time_if_less(
+            block_root,
+            slot,
+            |timestamps| &mut timestamps.gossip_verified,
+            timestamp,
+        )
+    }
+
     pub fn set_time_consensus_verified(
         &mut self,
         block_root: BlockRoot,
@@ -237,6 +375,43 @@ impl BlockTimesCache {
         )
     }
 
+    pub fn set_time_payload_verified(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        timestamp: Duration,
+    ) {
+        self.set_time_if_less(
+            block_root,
+            slot,
+            |timestamps| &mut timestamps.payload_verified,
+            timestamp,
+        )
+    }
+
+    pub fn set_time_available(&mut self, block_root: BlockRoot, slot: Slot, timestamp: Duration) {
+        self.set_time_if_less(
+            block_root,
+            slot,
+            |timestamps| &mut timestamps.available,
+            timestamp,
+        )
+    }
+
+    pub fn set_time_fork_choice_applied(
+        &mut self,
+        block_root: BlockRoot,
+        slot: Slot,
+        timestamp: Duration,
+    ) {
+        self.set_time_if_less(
+            block_root,
+            slot,
+            |timestamps| &mut timestamps.fork_choice_applied,
+            timestamp,
+        )
+    }
+
     pub fn set_time_attestable(&mut self, block_root: BlockRoot, slot: Slot, timestamp: Duration) {
         self.set_time_if_less(
             block_root,
@@ -284,10 +459,48 @@ impl BlockTimesCache {
         }
     }
 
-    // Prune the cache to only store the most recent 2 epochs.
-    pub fn prune(&mut self, current_slot: Slot) {
+    pub fn get_block_timings(&self, block_root: BlockRoot) -> Option<BlockTimings> {
         self.cache
-            .retain(|_, cache| cache.slot > current_slot.saturating_sub(64_u64));
+            .get(&block_root)
+            .map(|block_times| block_times.block_timings(block_root))
+    }
+
+    // Prune the cache to only store the most recent 2 epochs.
+    //
+    // Returns the timings of the pruned blocks if a timeline log is configured. They are not
+    // written until `TimelineLogEntries::write` is called.
+    pub fn prune(&mut self, current_slot: Slot) -> Option<TimelineLogEntries> {
+        let min_slot = current_slot.saturating_sub(64_u64);
+        let entries =
+            self.timeline_log_entries(|cache| cache.slot <= min_slot && cache.should_log());
+        self.cache.retain(|_, cache| cache.slot > min_slot);
+        entries
+    }
+
+    fn timeline_log_entries(
+        &self,
+        filter: impl Fn(&BlockTimesCacheValue) -> bool,
+    ) -> Option<TimelineLogEntries> {
+        let timeline_log = self.timeline_log.clone()?;
+        let timings = self
+            .cache
+            .iter()
+            .filter(|(_, cache)| filter(cache))
+            .map(|(block_root, cache)| cache.block_timings(*block_root))
+            .collect();
+        Some(TimelineLogEntries {
+            timeline_log,
+            timings,
+        })
+    }
+}
+
+impl Drop for BlockTimesCache {
+    /// Log the blocks which are still in the cache, so that they are not lost on shutdown.
+    fn drop(&mut self) {
+        if let Some(entries) = self.timeline_log_entries(BlockTimesCacheValue::should_log) {
+            entries.write();
+        }
     }
 }
 
@@ -358,4 +571,99 @@ mod test {
         );
         assert_eq!(cache.get_peer_info(block_root), peer_info3);
     }
+
+    #[test]
+    fn data_components_use_minimum_in_arrival_order() {
+        let mut cache = BlockTimesCache::default();
+
+        let block_root = Hash256::zero();
+        let slot = Slot::new(100);
+
+        cache.set_time_data_component_observed(
+            block_root,
+            slot,
+            DataComponentKind::Blob,
+            1,
+            Duration::from_millis(500),
+        );
+        cache.set_time_data_component_observed(
+            block_root,
+            slot,
+            DataComponentKind::Blob,
+            0,
+            Duration::from_millis(700),
+        );
+        // A later arrival of the same blob is ignored, an earlier one replaces it.
+        cache.set_time_data_component_observed(
+            block_root,
+            slot,
+            DataComponentKind::Blob,
+            1,
+            Duration::from_millis(900),
+        );
+        cache.set_time_data_component_observed(
+            block_root,
+            slot,
+            DataComponentKind::Blob,
+            0,
+            Duration::from_millis(300),
+        );
+
+        let timings = cache.get_block_timings(block_root).unwrap();
+        assert_eq!(
+            timings.data_components,
+            vec![
+                DataComponentTiming {
+                    kind: DataComponentKind::Blob,
+                    index: 0,
+                    observed: 300,
+                },
+                DataComponentTiming {
+                    kind: DataComponentKind::Blob,
+                    index: 1,
+                    observed: 500,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn pruned_blocks_are_logged() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = BlockTimesCache::with_timeline_log(dir.path(), 1).unwrap();
+
+        let imported_root = Hash256::repeat_byte(1);
+        let synced_root = Hash256::repeat_byte(2);
+        let recent_root = Hash256::repeat_byte(3);
+        let slot = Slot::new(100);
+
+        cache.set_time_observed(imported_root, slot, Duration::from_secs(1), None, None);
+        cache.set_time_imported(imported_root, slot, Duration::from_secs(2));
+        // Blocks from sync of historic slots are never imported into the cache.
+        cache.set_time_observed(synced_root, slot, Duration::from_secs(1), None, None);
+        cache.set_time_imported(recent_root, slot + 64, Duration::from_secs(3));
+
+        cache.prune(slot + 64).unwrap().write();
+        assert!(cache.get_block_timings(imported_root).is_none());
+        assert!(cache.get_block_timings(recent_root).is_some());
+        drop(cache);
+
+        let files = std::fs::read_dir(dir.path())
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(files.len(), 1);
+        let logged = std::fs::read_to_string(files[0].path())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<BlockTimings>(line).unwrap())
+            .collect::<Vec<_>>();
+
+        // The recent block is logged when the cache is dropped.
+        assert_eq!(logged.len(), 2);
+        assert_eq!(logged[0].block_root, imported_root);
+        assert_eq!(logged[0].observed, Some(1000));
+        assert_eq!(logged[0].imported, Some(2000));
+        assert_eq!(logged[1].block_root, recent_root);
+    }
 }
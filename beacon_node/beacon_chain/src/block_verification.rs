@@ -93,6 +93,7 @@ use std::io::Write;
 use std::sync::Arc;
 use store::{Error as DBError, HotStateSummary, KeyValueStore, StoreOp};
 use task_executor::JoinHandle;
+use tracing::{info_span, Instrument};
 use types::{
     data_column_sidecar::DataColumnSidecarError, BeaconBlockRef, BeaconState, BeaconStateError,
     BlobsList, ChainSpec, DataColumnSidecarList, Epoch, EthSpec, ExecutionBlockHash, FullPayload,
@@ -1381,6 +1382,13 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
                 );
             }
             let payload_verification_status = payload_notifier.notify_new_payload().await?;
+            if let Some(payload_verified) = chain.slot_clock.now_duration() {
+                chain.block_times_cache.write().set_time_payload_verified(
+                    block_root,
+                    block.slot(),
+                    payload_verified,
+                );
+            }
 
             Ok(PayloadVerificationOutcome {
                 payload_verification_status,
@@ -1393,7 +1401,7 @@ impl<T: BeaconChainTypes> ExecutionPendingBlock<T> {
         let payload_verification_handle = chain
             .task_executor
             .spawn_handle(
-                payload_verification_future,
+                payload_verification_future.instrument(info_span!("new_payload")),
                 "execution_payload_verification",
             )
             .ok_or(BeaconChainError::RuntimeShutdown)?;
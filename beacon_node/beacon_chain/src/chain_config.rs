@@ -1,5 +1,7 @@
+pub use operation_pool::PackingStrategyKind;
 pub use proto_array::{DisallowedReOrgOffsets, ReOrgThreshold};
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 use std::time::Duration;
 use types::{Checkpoint, Epoch};
 
@@ -16,6 +18,9 @@ pub const DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR: u32 = 3;
 /// Fraction of a slot lookahead for fork choice in the state advance timer (500ms on mainnet).
 pub const FORK_CHOICE_LOOKAHEAD_FACTOR: u32 = 24;
 
+/// Default number of daily block timings files to keep.
+pub const DEFAULT_BLOCK_TIMINGS_LOG_MAX_FILES: usize = 30;
+
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct ChainConfig {
     /// Maximum number of slots to skip when importing an attestation.
@@ -94,6 +99,19 @@ pub struct ChainConfig {
     /// The delay in milliseconds applied by the node between sending each blob or data column batch.
     /// This doesn't apply if the node is the block proposer.
     pub blob_publication_batch_interval: Duration,
+    /// The number of slots for which to keep a snapshot of fork choice, taken at the start of each
+    /// slot. Snapshots are disabled if set to `0`.
+    pub fork_choice_snapshot_slots: u64,
+    /// The number of slots between writes of the op pool to disk, in addition to the write on
+    /// shutdown. Periodic writes are disabled if set to `0`.
+    pub op_pool_persist_interval_slots: u64,
+    /// The algorithm used to choose which attestations to include in produced blocks.
+    pub attestation_packing_strategy: PackingStrategyKind,
+    /// The directory to which the import timings of each block are written, as JSON lines.
+    /// Timings are only kept in memory if `None`.
+    pub block_timings_log_dir: Option<PathBuf>,
+    /// The number of daily block timings files to keep in `block_timings_log_dir`.
+    pub block_timings_log_max_files: usize,
 }
 
 impl Default for ChainConfig {
@@ -129,6 +147,11 @@ impl Default for ChainConfig {
             enable_sampling: false,
             blob_publication_batches: 4,
             blob_publication_batch_interval: Duration::from_millis(300),
+            fork_choice_snapshot_slots: 0,
+            op_pool_persist_interval_slots: 32,
+            attestation_packing_strategy: PackingStrategyKind::default(),
+            block_timings_log_dir: None,
+            block_timings_log_max_files: DEFAULT_BLOCK_TIMINGS_LOG_MAX_FILES,
         }
     }
 }
@@ -24,6 +24,7 @@ pub struct ServerSentEventHandler<E: EthSpec> {
     attester_slashing_tx: Sender<EventKind<E>>,
     bls_to_execution_change_tx: Sender<EventKind<E>>,
     block_gossip_tx: Sender<EventKind<E>>,
+    payload_disagreement_tx: Sender<EventKind<E>>,
     log: Logger,
 }
 
@@ -53,6 +54,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         let (attester_slashing_tx, _) = broadcast::channel(capacity);
         let (bls_to_execution_change_tx, _) = broadcast::channel(capacity);
         let (block_gossip_tx, _) = broadcast::channel(capacity);
+        let (payload_disagreement_tx, _) = broadcast::channel(capacity);
 
         Self {
             attestation_tx,
@@ -72,6 +74,7 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
             attester_slashing_tx,
             bls_to_execution_change_tx,
             block_gossip_tx,
+            payload_disagreement_tx,
             log,
         }
     }
@@ -154,6 +157,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
                 .block_gossip_tx
                 .send(kind)
                 .map(|count| log_count("block gossip", count)),
+            EventKind::PayloadDisagreement(_) => self
+                .payload_disagreement_tx
+                .send(kind)
+                .map(|count| log_count("payload disagreement", count)),
         };
         if let Err(SendError(event)) = result {
             trace!(self.log, "No receivers registered to listen for event"; "event" => ?event);
@@ -228,6 +235,10 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
         self.block_gossip_tx.subscribe()
     }
 
+    pub fn subscribe_payload_disagreement(&self) -> Receiver<EventKind<E>> {
+        self.payload_disagreement_tx.subscribe()
+    }
+
     pub fn has_attestation_subscribers(&self) -> bool {
         self.attestation_tx.receiver_count() > 0
     }
@@ -287,4 +298,8 @@ impl<E: EthSpec> ServerSentEventHandler<E> {
     pub fn has_block_gossip_subscribers(&self) -> bool {
         self.block_gossip_tx.receiver_count() > 0
     }
+
+    pub fn has_payload_disagreement_subscribers(&self) -> bool {
+        self.payload_disagreement_tx.receiver_count() > 0
+    }
 }
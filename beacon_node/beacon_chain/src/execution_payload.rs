@@ -12,6 +12,7 @@ use crate::{
     BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, BlockProductionError,
     ExecutionPayloadError,
 };
+use eth2::types::{EventKind, SsePayloadDisagreement};
 use execution_layer::{
     BlockProposalContents, BlockProposalContentsType, BuilderParams, NewPayloadRequest,
     PayloadAttributes, PayloadStatus,
@@ -138,7 +139,29 @@ async fn notify_new_payload<'a, T: BeaconChainTypes>(
         .ok_or(ExecutionPayloadError::NoExecutionConnection)?;
 
     let execution_block_hash = block.execution_payload()?.block_hash();
-    let new_payload_response = execution_layer.notify_new_payload(block.try_into()?).await;
+    let new_payload_response = execution_layer
+        .notify_new_payload_with_verification(block.try_into()?)
+        .await
+        .map(|(status, disagreement)| {
+            if let Some(disagreement) = disagreement {
+                if let Some(event_handler) = chain.event_handler.as_ref() {
+                    if event_handler.has_payload_disagreement_subscribers() {
+                        event_handler.register(EventKind::PayloadDisagreement(Box::new(
+                            SsePayloadDisagreement {
+                                slot: block.slot(),
+                                block: block.tree_hash_root(),
+                                execution_block_hash: disagreement.block_hash,
+                                primary_status: <&'static str>::from(disagreement.primary_status)
+                                    .to_string(),
+                                verifier_status: <&'static str>::from(disagreement.verifier_status)
+                                    .to_string(),
+                            },
+                        )));
+                    }
+                }
+            }
+            status
+        });
 
     match new_payload_response {
         Ok(status) => match status {
@@ -0,0 +1,100 @@
+//! Persists a compact snapshot of fork choice at the start of every slot, so that reorgs can be
+//! analysed using the view of fork choice at the time rather than its current view.
+//!
+//! Snapshots are stored in the hot database keyed by slot and pruned once they fall outside of
+//! `ChainConfig::fork_choice_snapshot_slots`.
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{ForkChoiceSnapshot, ForkChoiceSnapshotNode};
+use ssz::{Decode, Encode};
+use store::{get_key_for_col, DBColumn, KeyValueStore, KeyValueStoreOp};
+use types::Slot;
+
+/// Slots are stored big-endian so that iterating the column visits snapshots in slot order.
+fn snapshot_key(slot: Slot) -> [u8; 8] {
+    slot.as_u64().to_be_bytes()
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Returns a snapshot of the current state of fork choice, labelled with `slot`.
+    pub fn fork_choice_snapshot(&self, slot: Slot) -> ForkChoiceSnapshot {
+        let head_root = self.canonical_head.cached_head().head_block_root();
+        let fork_choice = self.canonical_head.fork_choice_read_lock();
+        let proto_array = fork_choice.proto_array().core_proto_array();
+
+        let nodes = proto_array
+            .nodes
+            .iter()
+            .map(|node| ForkChoiceSnapshotNode {
+                slot: node.slot,
+                root: node.root,
+                parent_root: node
+                    .parent
+                    .and_then(|index| proto_array.nodes.get(index))
+                    .map(|parent| parent.root),
+                justified_epoch: node.justified_checkpoint.epoch,
+                finalized_epoch: node.finalized_checkpoint.epoch,
+                weight: node.weight,
+            })
+            .collect();
+
+        ForkChoiceSnapshot {
+            slot,
+            head_root,
+            proposer_boost_root: fork_choice.proposer_boost_root(),
+            justified_checkpoint: proto_array.justified_checkpoint,
+            finalized_checkpoint: proto_array.finalized_checkpoint,
+            nodes,
+        }
+    }
+
+    /// Stores a snapshot of fork choice for `slot` and deletes any snapshots which have fallen out
+    /// of the configured window.
+    ///
+    /// Does nothing if fork choice snapshots are disabled.
+    pub fn persist_fork_choice_snapshot(&self, slot: Slot) -> Result<(), BeaconChainError> {
+        let window = self.config.fork_choice_snapshot_slots;
+        if window == 0 {
+            return Ok(());
+        }
+
+        let column = DBColumn::ForkChoiceSnapshot;
+        let snapshot = self.fork_choice_snapshot(slot);
+        let mut ops = vec![KeyValueStoreOp::PutKeyValue(
+            get_key_for_col(column.into(), &snapshot_key(slot)),
+            snapshot.as_ssz_bytes(),
+        )];
+
+        // Delete every snapshot outside of the window, rather than just the one which has most
+        // recently left it, since the per-slot task does not run whilst syncing.
+        let oldest_slot = slot.saturating_sub(window - 1);
+        for key in self.store.hot_db.iter_column_keys::<Vec<u8>>(column) {
+            let key = key?;
+            if key.as_slice() >= snapshot_key(oldest_slot).as_slice() {
+                break;
+            }
+            ops.push(KeyValueStoreOp::DeleteKey(get_key_for_col(
+                column.into(),
+                &key,
+            )));
+        }
+
+        self.store.hot_db.do_atomically(ops)?;
+
+        Ok(())
+    }
+
+    /// Returns the fork choice snapshot taken at the start of `slot`, if any.
+    pub fn get_fork_choice_snapshot(
+        &self,
+        slot: Slot,
+    ) -> Result<Option<ForkChoiceSnapshot>, BeaconChainError> {
+        self.store
+            .hot_db
+            .get_bytes(DBColumn::ForkChoiceSnapshot.into(), &snapshot_key(slot))?
+            .map(|bytes| {
+                ForkChoiceSnapshot::from_ssz_bytes(&bytes)
+                    .map_err(|e| BeaconChainError::DBError(store::Error::SszDecodeError(e)))
+            })
+            .transpose()
+    }
+}
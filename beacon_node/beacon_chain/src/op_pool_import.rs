@@ -0,0 +1,249 @@
+//! Imports the contents of an operation pool exported by another beacon node.
+//!
+//! The exporting node is not trusted, so every operation is verified against our own view of the
+//! chain before it is added to the op pool. Operations which fail verification are skipped rather
+//! than failing the whole import, since an exported pool will usually contain some operations
+//! which have since been included on chain.
+use crate::attestation_verification::verify_attestation_signature;
+use crate::observed_operations::ObservationOutcome;
+use crate::{BeaconChain, BeaconChainError, BeaconChainTypes};
+use eth2::lighthouse::{OpPoolImportCount, OpPoolImportSummary};
+use operation_pool::{PersistedOperationPoolV20, ReceivedPreCapella};
+use slog::{debug, info};
+use state_processing::common::{attesting_indices_base, attesting_indices_electra};
+use state_processing::per_block_processing::signature_sets::sync_committee_contribution_signature_set_from_pubkeys;
+use state_processing::{SigVerifiedOp, TransformPersist};
+use std::borrow::Cow;
+use types::{Attestation, AttestationRef, BeaconState, EthSpec, SyncCommitteeContribution};
+
+fn record(count: &mut OpPoolImportCount, imported: bool) {
+    if imported {
+        count.imported += 1;
+    } else {
+        count.rejected += 1;
+    }
+}
+
+impl<T: BeaconChainTypes> BeaconChain<T> {
+    /// Verifies each operation in `pool` and adds the valid ones to the op pool, which is then
+    /// persisted to disk.
+    pub fn import_persisted_op_pool(
+        &self,
+        pool: PersistedOperationPoolV20<T::EthSpec>,
+    ) -> Result<OpPoolImportSummary, BeaconChainError> {
+        let mut summary = OpPoolImportSummary::default();
+
+        for slashing in pool.proposer_slashings {
+            let outcome = self.verify_proposer_slashing_for_gossip(slashing.into_inner());
+            let imported = self.import_verified_op("proposer slashing", outcome, |slashing| {
+                self.import_proposer_slashing(slashing);
+                true
+            });
+            record(&mut summary.proposer_slashings, imported);
+        }
+
+        for slashing in pool.attester_slashings {
+            let outcome = self.verify_attester_slashing_for_gossip(slashing.into_inner());
+            let imported = self.import_verified_op("attester slashing", outcome, |slashing| {
+                self.import_attester_slashing(slashing);
+                true
+            });
+            record(&mut summary.attester_slashings, imported);
+        }
+
+        for exit in pool.voluntary_exits {
+            let outcome = self.verify_voluntary_exit_for_gossip(exit.into_inner());
+            let imported = self.import_verified_op("voluntary exit", outcome, |exit| {
+                self.import_voluntary_exit(exit);
+                true
+            });
+            record(&mut summary.voluntary_exits, imported);
+        }
+
+        let received_pre_capella = if self.current_slot_is_post_capella()? {
+            ReceivedPreCapella::No
+        } else {
+            ReceivedPreCapella::Yes
+        };
+        for change in pool.bls_to_execution_changes {
+            let outcome = self.verify_bls_to_execution_change_for_http_api(change.into_inner());
+            let imported = self.import_verified_op("BLS to execution change", outcome, |change| {
+                self.import_bls_to_execution_change(change, received_pre_capella)
+            });
+            record(&mut summary.bls_to_execution_changes, imported);
+        }
+
+        // Attestations are verified against the committees in the head state, so any attestation
+        // from outside the previous or current epoch of the head is rejected. Such attestations are
+        // unlikely to be includable anyway.
+        let head_snapshot = self.head_snapshot();
+        for (attestation, _) in pool.attestations {
+            let attestation = AttestationRef::from(attestation.to_ref()).clone_as_attestation();
+            let imported =
+                match self.verify_imported_attestation(&head_snapshot.beacon_state, &attestation) {
+                    Ok(attesting_indices) => {
+                        if self.eth1_chain.is_some() {
+                            self.op_pool
+                                .insert_attestation(attestation, attesting_indices)
+                                .map_err(BeaconChainError::from)?;
+                        }
+                        true
+                    }
+                    Err(e) => {
+                        debug!(
+                            self.log,
+                            "Rejected imported attestation";
+                            "slot" => attestation.data().slot,
+                            "reason" => e,
+                        );
+                        false
+                    }
+                };
+            record(&mut summary.attestations, imported);
+        }
+        drop(head_snapshot);
+
+        let current_slot = self.slot()?;
+        for (_, contributions) in pool.sync_contributions {
+            for contribution in contributions {
+                // Contributions are only included in the block at the following slot.
+                let imported = if contribution.slot + 1 < current_slot {
+                    false
+                } else {
+                    match self.verify_imported_sync_contribution(&contribution) {
+                        Ok(()) => {
+                            if self.eth1_chain.is_some() {
+                                self.op_pool.insert_sync_contribution(contribution)?;
+                            }
+                            true
+                        }
+                        Err(e) => {
+                            debug!(
+                                self.log,
+                                "Rejected imported sync contribution";
+                                "slot" => contribution.slot,
+                                "reason" => e,
+                            );
+                            false
+                        }
+                    }
+                };
+                record(&mut summary.sync_contributions, imported);
+            }
+        }
+
+        self.persist_op_pool()?;
+
+        info!(
+            self.log,
+            "Imported operation pool";
+            "attestations" => summary.attestations.imported,
+            "sync_contributions" => summary.sync_contributions.imported,
+            "attester_slashings" => summary.attester_slashings.imported,
+            "proposer_slashings" => summary.proposer_slashings.imported,
+            "voluntary_exits" => summary.voluntary_exits.imported,
+            "bls_to_execution_changes" => summary.bls_to_execution_changes.imported,
+        );
+
+        Ok(summary)
+    }
+
+    /// Passes a newly observed operation to `import`, returning `true` if it was imported.
+    fn import_verified_op<O: TransformPersist>(
+        &self,
+        kind: &'static str,
+        outcome: Result<ObservationOutcome<O, T::EthSpec>, BeaconChainError>,
+        import: impl FnOnce(SigVerifiedOp<O, T::EthSpec>) -> bool,
+    ) -> bool {
+        match outcome {
+            Ok(ObservationOutcome::New(op)) => import(op),
+            Ok(ObservationOutcome::AlreadyKnown) => false,
+            Err(e) => {
+                debug!(
+                    self.log,
+                    "Rejected imported operation";
+                    "kind" => kind,
+                    "reason" => ?e,
+                );
+                false
+            }
+        }
+    }
+
+    /// Verifies the signature of `attestation` using the committees in `state`, returning its
+    /// attesting indices.
+    fn verify_imported_attestation(
+        &self,
+        state: &BeaconState<T::EthSpec>,
+        attestation: &Attestation<T::EthSpec>,
+    ) -> Result<Vec<u64>, String> {
+        let indexed_attestation = match attestation.to_ref() {
+            AttestationRef::Base(att) => {
+                let committee = state
+                    .get_beacon_committee(att.data.slot, att.data.index)
+                    .map_err(|e| format!("{e:?}"))?;
+                attesting_indices_base::get_indexed_attestation(committee.committee, att)
+            }
+            AttestationRef::Electra(att) => {
+                attesting_indices_electra::get_indexed_attestation_from_state(state, att)
+            }
+        }
+        .map_err(|e| format!("{e:?}"))?;
+
+        verify_attestation_signature(self, &indexed_attestation).map_err(|e| format!("{e:?}"))?;
+
+        Ok(indexed_attestation.attesting_indices_to_vec())
+    }
+
+    /// Verifies the aggregate signature of `contribution` against the sync committee.
+    ///
+    /// The persisted pool does not retain the `SignedContributionAndProof`, so the aggregator
+    /// and selection proof cannot be checked. This does not matter for block production.
+    fn verify_imported_sync_contribution(
+        &self,
+        contribution: &SyncCommitteeContribution<T::EthSpec>,
+    ) -> Result<(), String> {
+        if contribution.aggregation_bits.is_zero() {
+            return Err("empty aggregation bitfield".into());
+        }
+
+        let subcommittee_pubkeys = self
+            .sync_committee_at_next_slot(contribution.slot)
+            .map_err(|e| format!("{e:?}"))?
+            .get_subcommittee_pubkeys(contribution.subcommittee_index)
+            .map_err(|e| format!("{e:?}"))?;
+        let participant_pubkeys = subcommittee_pubkeys
+            .into_iter()
+            .zip(contribution.aggregation_bits.iter())
+            .filter_map(|(pubkey, bit)| bit.then_some(pubkey))
+            .collect::<Vec<_>>();
+
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let fork = self
+            .spec
+            .fork_at_epoch((contribution.slot + 1).epoch(slots_per_epoch));
+        let pubkey_cache = self.validator_pubkey_cache.read();
+        let signature_set =
+            sync_committee_contribution_signature_set_from_pubkeys::<T::EthSpec, _>(
+                |pubkey| {
+                    pubkey_cache
+                        .get_pubkey_from_pubkey_bytes(pubkey)
+                        .map(Cow::Borrowed)
+                },
+                &participant_pubkeys,
+                &contribution.signature,
+                contribution.slot.epoch(slots_per_epoch),
+                contribution.beacon_block_root,
+                &fork,
+                self.genesis_validators_root,
+                &self.spec,
+            )
+            .map_err(|e| format!("{e:?}"))?;
+
+        if signature_set.verify() {
+            Ok(())
+        } else {
+            Err("invalid signature".into())
+        }
+    }
+}
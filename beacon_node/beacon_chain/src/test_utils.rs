@@ -519,6 +519,19 @@ where
         self
     }
 
+    /// Like `mock_execution_layer`, but with a second mock execution engine used as the verifier
+    /// engine.
+    pub fn mock_execution_layer_with_verifier(mut self) -> Self {
+        let mock = mock_execution_layer_from_parts::<E>(
+            self.spec.as_ref().expect("cannot build without spec"),
+            self.runtime.task_executor.clone(),
+        )
+        .with_verifier();
+        self.execution_layer = Some(mock.el.clone());
+        self.mock_execution_layer = Some(mock);
+        self
+    }
+
     /// Instruct the mock execution engine to always return a "valid" response to any payload it is
     /// asked to execute.
     pub fn mock_execution_layer_all_payloads_valid(self) -> Self {
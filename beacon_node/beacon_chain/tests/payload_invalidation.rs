@@ -7,6 +7,7 @@ use beacon_chain::{
     OverrideForkchoiceUpdate, StateSkipConfig, WhenSlotSkipped,
     INVALID_JUSTIFIED_PAYLOAD_SHUTDOWN_REASON,
 };
+use eth2::types::{EventKind, SsePayloadDisagreement};
 use execution_layer::{
     json_structures::{JsonForkchoiceStateV1, JsonPayloadAttributes, JsonPayloadAttributesV1},
     ExecutionLayer, ForkchoiceState, PayloadAttributes,
@@ -46,21 +47,33 @@ impl InvalidPayloadRig {
         Self::new_with_spec(spec)
     }
 
-    fn new_with_spec(mut spec: ChainSpec) -> Self {
+    fn new_with_spec(spec: ChainSpec) -> Self {
+        Self::new_with_spec_and_verifier(spec, false)
+    }
+
+    /// Use a second mock execution engine as the verifier engine.
+    fn new_with_verifier() -> Self {
+        Self::new_with_spec_and_verifier(E::default_spec(), true)
+    }
+
+    fn new_with_spec_and_verifier(mut spec: ChainSpec, verifier: bool) -> Self {
         spec.altair_fork_epoch = Some(Epoch::new(0));
         spec.bellatrix_fork_epoch = Some(Epoch::new(0));
 
-        let harness = BeaconChainHarness::builder(MainnetEthSpec)
+        let builder = BeaconChainHarness::builder(MainnetEthSpec)
             .spec(spec.into())
             .chain_config(ChainConfig {
                 reconstruct_historic_states: true,
                 ..ChainConfig::default()
             })
             .logger(test_logger())
-            .deterministic_keypairs(VALIDATOR_COUNT)
-            .mock_execution_layer()
-            .fresh_ephemeral_store()
-            .build();
+            .deterministic_keypairs(VALIDATOR_COUNT);
+        let builder = if verifier {
+            builder.mock_execution_layer_with_verifier()
+        } else {
+            builder.mock_execution_layer()
+        };
+        let harness = builder.fresh_ephemeral_store().build();
 
         // Move to slot 1.
         harness.advance_slot();
@@ -404,6 +417,110 @@ async fn valid_invalid_syncing() {
     rig.import_block(Payload::Syncing).await;
 }
 
+/// A block is imported optimistically, and a `payload_disagreement` event is emitted, when the
+/// verifier engine disagrees with the other engines about the validity of its payload.
+#[tokio::test]
+async fn verifier_disagreement_imports_optimistically() {
+    let mut rig = InvalidPayloadRig::new_with_verifier();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await;
+
+    let mut disagreements = rig
+        .harness
+        .chain
+        .event_handler
+        .as_ref()
+        .unwrap()
+        .subscribe_payload_disagreement();
+
+    let head = rig.harness.chain.head_snapshot();
+    let parent_hash = rig.block_hash(head.beacon_block_root);
+    let slot = head.beacon_state.slot() + 1;
+    let ((block, blobs), _) = rig
+        .harness
+        .make_block(head.beacon_state.clone(), slot)
+        .await;
+    let execution_block_hash = block
+        .message()
+        .body()
+        .execution_payload()
+        .unwrap()
+        .block_hash();
+
+    // The other engines consider the payload valid, whilst the verifier does not.
+    let mock_execution_layer = rig.harness.mock_execution_layer.as_ref().unwrap();
+    mock_execution_layer
+        .server
+        .all_payloads_valid_on_new_payload();
+    mock_execution_layer
+        .server
+        .all_payloads_syncing_on_forkchoice_updated();
+    mock_execution_layer
+        .verifier
+        .as_ref()
+        .unwrap()
+        .all_payloads_invalid_on_new_payload(parent_hash);
+
+    let block_root = rig
+        .harness
+        .process_block(slot, block.canonical_root(), (block, blobs))
+        .await
+        .unwrap()
+        .into();
+
+    assert!(rig.execution_status(block_root).is_strictly_optimistic());
+    assert_eq!(
+        disagreements.try_recv().unwrap(),
+        EventKind::PayloadDisagreement(Box::new(SsePayloadDisagreement {
+            slot,
+            block: block_root,
+            execution_block_hash,
+            primary_status: "valid".to_string(),
+            verifier_status: "invalid".to_string(),
+        }))
+    );
+}
+
+/// Ensure that a `VALID` forkchoice update from the other engines cannot mark a payload which is
+/// disputed by the verifier as valid.
+#[tokio::test]
+async fn verifier_disagreement_ignores_valid_forkchoice_updated() {
+    let mut rig = InvalidPayloadRig::new_with_verifier();
+    rig.move_to_terminal_block();
+    rig.import_block(Payload::Valid).await;
+
+    let head = rig.harness.chain.head_snapshot();
+    let parent_hash = rig.block_hash(head.beacon_block_root);
+    let slot = head.beacon_state.slot() + 1;
+    let ((block, blobs), _) = rig
+        .harness
+        .make_block(head.beacon_state.clone(), slot)
+        .await;
+
+    // The other engines consider the payload valid, whilst the verifier does not.
+    let mock_execution_layer = rig.harness.mock_execution_layer.as_ref().unwrap();
+    mock_execution_layer
+        .server
+        .all_payloads_valid_on_new_payload();
+    mock_execution_layer
+        .server
+        .all_payloads_valid_on_forkchoice_updated();
+    let verifier = mock_execution_layer.verifier.as_ref().unwrap();
+    verifier.all_payloads_invalid_on_new_payload(parent_hash);
+    verifier.all_payloads_invalid_on_forkchoice_updated(parent_hash);
+
+    let block_root = rig
+        .harness
+        .process_block(slot, block.canonical_root(), (block, blobs))
+        .await
+        .unwrap()
+        .into();
+    rig.recompute_head().await;
+
+    assert_eq!(rig.cached_head().head_block_root(), block_root);
+    assert!(rig.execution_status(block_root).is_strictly_optimistic());
+}
+
 /// Ensure that an invalid payload can invalidate its parent too (given the right
 /// `latest_valid_hash`.
 #[tokio::test]
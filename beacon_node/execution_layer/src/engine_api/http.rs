@@ -3,13 +3,14 @@
 use super::*;
 use crate::auth::Auth;
 use crate::json_structures::*;
+use crate::recording::EngineApiRecorder;
 use lighthouse_version::{COMMIT_PREFIX, VERSION};
 use reqwest::header::CONTENT_TYPE;
 use sensitive_url::SensitiveUrl;
 use serde::de::DeserializeOwned;
 use serde_json::json;
 use std::collections::HashSet;
-use std::sync::LazyLock;
+use std::sync::{Arc, LazyLock};
 use tokio::sync::Mutex;
 
 use std::time::{Duration, Instant};
@@ -603,6 +604,7 @@ pub struct HttpJsonRpc {
     pub engine_capabilities_cache: Mutex<Option<CachedResponse<EngineCapabilities>>>,
     pub engine_version_cache: Mutex<Option<CachedResponse<Vec<ClientVersionV1>>>>,
     auth: Option<Auth>,
+    recorder: Option<Arc<EngineApiRecorder>>,
 }
 
 impl HttpJsonRpc {
@@ -617,6 +619,7 @@ impl HttpJsonRpc {
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
             auth: None,
+            recorder: None,
         })
     }
 
@@ -632,9 +635,15 @@ impl HttpJsonRpc {
             engine_capabilities_cache: Mutex::new(None),
             engine_version_cache: Mutex::new(None),
             auth: Some(auth),
+            recorder: None,
         })
     }
 
+    /// Record every request and response to `recorder`.
+    pub fn set_recorder(&mut self, recorder: Arc<EngineApiRecorder>) {
+        self.recorder = Some(recorder);
+    }
+
     pub async fn rpc_request<D: DeserializeOwned>(
         &self,
         method: &str,
@@ -660,9 +669,13 @@ impl HttpJsonRpc {
             request = request.bearer_auth(auth.generate_token()?);
         };
 
-        let body: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
+        let response: JsonResponseBody = request.send().await?.error_for_status()?.json().await?;
+
+        if let Some(recorder) = &self.recorder {
+            recorder.record(self.url.to_string(), method, &body.params, &response);
+        }
 
-        match (body.result, body.error) {
+        match (response.result, response.error) {
             (result, None) => serde_json::from_value(result).map_err(Into::into),
             (_, Some(error)) => {
                 if error.message.contains(EIP155_ERROR_STR) {
@@ -2090,4 +2103,49 @@ mod test {
             )
             .await;
     }
+
+    #[tokio::test]
+    async fn record_and_replay_requests() {
+        let record_file = tempfile::NamedTempFile::new().unwrap();
+        let recorder =
+            Arc::new(EngineApiRecorder::open(record_file.path(), logging::test_logger()).unwrap());
+        let new_client = |server: &MockServer<MainnetEthSpec>| {
+            let auth = Auth::new(JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap(), None, None);
+            HttpJsonRpc::new_with_auth(SensitiveUrl::parse(&server.url()).unwrap(), auth, None)
+                .unwrap()
+        };
+
+        // Record a request to the mock execution layer.
+        let server = MockServer::unit_testing();
+        let mut client = new_client(&server);
+        client.set_recorder(recorder);
+        let recorded_block = client
+            .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+            .await
+            .unwrap();
+
+        let records = crate::recording::read_records(record_file.path()).unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].method, ETH_GET_BLOCK_BY_NUMBER);
+
+        // Replay the recorded response from another server. Repeated requests receive the same
+        // response.
+        let replay_server = MockServer::unit_testing();
+        replay_server.replay(records);
+        let replay_client = new_client(&replay_server);
+        for _ in 0..2 {
+            let replayed_block = replay_client
+                .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
+                .await
+                .unwrap();
+            assert_eq!(replayed_block, recorded_block);
+        }
+
+        // Requests which were not recorded are rejected, even though the mock execution layer
+        // could otherwise serve them.
+        assert!(replay_client
+            .get_block_by_number(BlockByNumberQuery::Tag("earliest"))
+            .await
+            .is_err());
+    }
 }
@@ -18,7 +18,7 @@ pub struct JsonRequestBody<'a> {
     pub id: serde_json::Value,
 }
 
-#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct JsonError {
     pub code: i64,
     pub message: String,
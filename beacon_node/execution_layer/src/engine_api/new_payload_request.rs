@@ -210,6 +210,71 @@ impl<'a, E: EthSpec> TryFrom<ExecutionPayloadRef<'a, E>> for NewPayloadRequest<'
     }
 }
 
+/// An owned copy of a `NewPayloadRequest`.
+///
+/// Allows a request to be sent to an execution engine from a spawned task which may outlive the
+/// block the request was created from.
+#[derive(Clone, Debug, PartialEq)]
+pub struct OwnedNewPayloadRequest<E: EthSpec> {
+    execution_payload: ExecutionPayload<E>,
+    versioned_hashes: Vec<VersionedHash>,
+    parent_beacon_block_root: Hash256,
+    execution_requests: Option<ExecutionRequests<E>>,
+}
+
+impl<E: EthSpec> OwnedNewPayloadRequest<E> {
+    /// Returns a `NewPayloadRequest` which borrows from `self`.
+    pub fn as_request(&self) -> Result<NewPayloadRequest<'_, E>, BeaconStateError> {
+        match &self.execution_payload {
+            ExecutionPayload::Bellatrix(execution_payload) => {
+                Ok(NewPayloadRequest::Bellatrix(NewPayloadRequestBellatrix {
+                    execution_payload,
+                }))
+            }
+            ExecutionPayload::Capella(execution_payload) => {
+                Ok(NewPayloadRequest::Capella(NewPayloadRequestCapella {
+                    execution_payload,
+                }))
+            }
+            ExecutionPayload::Deneb(execution_payload) => {
+                Ok(NewPayloadRequest::Deneb(NewPayloadRequestDeneb {
+                    execution_payload,
+                    versioned_hashes: self.versioned_hashes.clone(),
+                    parent_beacon_block_root: self.parent_beacon_block_root,
+                }))
+            }
+            ExecutionPayload::Electra(execution_payload) => {
+                Ok(NewPayloadRequest::Electra(NewPayloadRequestElectra {
+                    execution_payload,
+                    versioned_hashes: self.versioned_hashes.clone(),
+                    parent_beacon_block_root: self.parent_beacon_block_root,
+                    execution_requests_list: self
+                        .execution_requests
+                        .as_ref()
+                        .ok_or(BeaconStateError::IncorrectStateVariant)?,
+                }))
+            }
+        }
+    }
+}
+
+impl<'a, E: EthSpec> From<&NewPayloadRequest<'a, E>> for OwnedNewPayloadRequest<E> {
+    fn from(request: &NewPayloadRequest<'a, E>) -> Self {
+        Self {
+            execution_payload: request.clone().into_execution_payload(),
+            versioned_hashes: request.versioned_hashes().cloned().unwrap_or_default(),
+            parent_beacon_block_root: request
+                .parent_beacon_block_root()
+                .copied()
+                .unwrap_or_default(),
+            execution_requests: request
+                .execution_requests_list()
+                .ok()
+                .map(|requests| (*requests).clone()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use crate::versioned_hashes::Error as VersionedHashError;
@@ -0,0 +1,120 @@
+//! Records engine API requests and responses so that they can be replayed later.
+//!
+//! Records are stored as newline-delimited JSON, with one `EngineApiRecord` per line. JWT tokens
+//! are never recorded.
+use super::json_structures::{JsonError, JsonResponseBody};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use slog::{warn, Logger};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single engine API request and the response returned by the execution engine.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EngineApiRecord {
+    /// Milliseconds since the UNIX epoch at which the response was received.
+    pub timestamp_ms: u64,
+    /// The redacted URL of the execution engine.
+    pub endpoint: String,
+    pub method: String,
+    pub params: serde_json::Value,
+    #[serde(default)]
+    pub result: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<JsonError>,
+}
+
+/// Appends engine API records to a file.
+pub struct EngineApiRecorder {
+    path: PathBuf,
+    file: Mutex<File>,
+    log: Logger,
+}
+
+impl EngineApiRecorder {
+    /// Opens `path` for appending, creating it if it does not exist.
+    pub fn open(path: &Path, log: Logger) -> Result<Self, String> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Unable to open {}: {:?}", path.display(), e))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+            log,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a record of a request to `endpoint` and its `response`.
+    ///
+    /// Failing to record is logged rather than returned, since it should never cause the request
+    /// itself to fail.
+    pub fn record(
+        &self,
+        endpoint: String,
+        method: &str,
+        params: &serde_json::Value,
+        response: &JsonResponseBody,
+    ) {
+        if let Err(e) = self.try_record(endpoint, method, params, response) {
+            warn!(
+                self.log,
+                "Failed to record engine API request";
+                "method" => method,
+                "error" => e,
+            );
+        }
+    }
+
+    fn try_record(
+        &self,
+        endpoint: String,
+        method: &str,
+        params: &serde_json::Value,
+        response: &JsonResponseBody,
+    ) -> Result<(), String> {
+        let record = EngineApiRecord {
+            timestamp_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_millis() as u64)
+                .unwrap_or_default(),
+            endpoint,
+            method: method.to_string(),
+            params: params.clone(),
+            result: response.result.clone(),
+            error: response.error.clone(),
+        };
+        let mut line = serde_json::to_string(&record)
+            .map_err(|e| format!("Unable to serialize record: {:?}", e))?;
+        line.push('\n');
+
+        // Write each record in a single call so that concurrent requests are not interleaved.
+        self.file
+            .lock()
+            .write_all(line.as_bytes())
+            .map_err(|e| format!("Unable to write to {}: {:?}", self.path.display(), e))
+    }
+}
+
+/// Reads every record from a file written by an `EngineApiRecorder`, skipping blank lines.
+pub fn read_records(path: &Path) -> Result<Vec<EngineApiRecord>, String> {
+    let file =
+        File::open(path).map_err(|e| format!("Unable to open {}: {:?}", path.display(), e))?;
+    BufReader::new(file)
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| line.as_ref().map_or(true, |line| !line.trim().is_empty()))
+        .map(|(i, line)| {
+            let line = line.map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?;
+            serde_json::from_str(&line)
+                .map_err(|e| format!("Invalid record on line {}: {:?}", i + 1, e))
+        })
+        .collect()
+}
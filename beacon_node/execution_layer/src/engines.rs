@@ -4,15 +4,18 @@ use crate::engine_api::{
     EngineCapabilities, Error as EngineApiError, ForkchoiceUpdatedResponse, PayloadAttributes,
     PayloadId,
 };
-use crate::{ClientVersionV1, HttpJsonRpc};
+use crate::{metrics, ClientVersionV1, HttpJsonRpc};
+use eth2::lighthouse::ExecutionEngineHealth;
+use futures::future::join_all;
 use lru::LruCache;
 use slog::{debug, error, info, warn, Logger};
 use std::future::Future;
 use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use task_executor::TaskExecutor;
-use tokio::sync::{watch, Mutex, RwLock};
+use tokio::sync::{mpsc, watch, Mutex, RwLock};
 use tokio_stream::wrappers::WatchStream;
 use types::non_zero_usize::new_non_zero_usize;
 use types::ExecutionBlockHash;
@@ -33,6 +36,27 @@ enum EngineStateInternal {
     AuthFailed,
 }
 
+impl EngineStateInternal {
+    fn as_str(&self) -> &'static str {
+        match self {
+            EngineStateInternal::Synced => "synced",
+            EngineStateInternal::Offline => "offline",
+            EngineStateInternal::Syncing => "syncing",
+            EngineStateInternal::AuthFailed => "auth_failed",
+        }
+    }
+
+    /// Lower values indicate a healthier engine.
+    fn health_rank(&self) -> u8 {
+        match self {
+            EngineStateInternal::Synced => 0,
+            EngineStateInternal::Syncing => 1,
+            EngineStateInternal::Offline => 2,
+            EngineStateInternal::AuthFailed => 3,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 enum ResponseCacheAction {
     #[default]
@@ -151,6 +175,15 @@ impl Engine {
         self.state.read().await.watch()
     }
 
+    /// The redacted URL of the engine, used in logs and metrics.
+    pub fn label(&self) -> String {
+        self.api.url.to_string()
+    }
+
+    async fn state(&self) -> EngineStateInternal {
+        **self.state.read().await
+    }
+
     pub async fn get_payload_id(
         &self,
         head_block_hash: &ExecutionBlockHash,
@@ -417,6 +450,243 @@ impl Engine {
     }
 }
 
+/// An ordered set of execution engines.
+///
+/// The first engine is the primary. Every engine is kept up to date with the latest fork choice
+/// state, while requests which only need a single response are made to the healthiest engine and
+/// fail over to the next healthiest engine on error. Ties are broken by the configured order.
+pub struct Engines {
+    engines: Vec<Arc<Engine>>,
+    /// The index of the engine which was healthiest when last checked.
+    active: AtomicUsize,
+    /// The combined state of all engines. The engines are online if any engine is online.
+    state: RwLock<State>,
+    log: Logger,
+}
+
+impl Engines {
+    /// Creates a new set of engines. The first engine is the primary.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `engines` is empty.
+    pub fn new(engines: Vec<Engine>, log: &Logger) -> Self {
+        assert!(
+            !engines.is_empty(),
+            "at least one execution engine is required"
+        );
+        Self {
+            engines: engines.into_iter().map(Arc::new).collect(),
+            active: AtomicUsize::new(0),
+            state: Default::default(),
+            log: log.clone(),
+        }
+    }
+
+    /// Returns every engine in the configured order.
+    pub fn all(&self) -> &[Arc<Engine>] {
+        &self.engines
+    }
+
+    /// Returns the primary engine.
+    pub fn primary(&self) -> &Arc<Engine> {
+        &self.engines[0]
+    }
+
+    /// Returns the engines ordered from healthiest to least healthy, breaking ties using the
+    /// configured order.
+    pub async fn by_health(&self) -> Vec<&Arc<Engine>> {
+        let mut ranked = Vec::with_capacity(self.engines.len());
+        for (index, engine) in self.engines.iter().enumerate() {
+            ranked.push((engine.state().await.health_rank(), index, engine));
+        }
+        ranked.sort_by_key(|(rank, index, _)| (*rank, *index));
+
+        if let Some((_, best_index, best)) = ranked.first() {
+            let previous = self.active.swap(*best_index, Ordering::Relaxed);
+            if previous != *best_index {
+                metrics::inc_counter(&metrics::EXECUTION_LAYER_ENGINE_FAILOVERS);
+                warn!(
+                    self.log,
+                    "Switching preferred execution engine";
+                    "engine" => best.label(),
+                    "primary" => *best_index == 0,
+                );
+            }
+        }
+
+        ranked.into_iter().map(|(_, _, engine)| engine).collect()
+    }
+
+    /// Returns the healthiest engine.
+    pub async fn best(&self) -> &Arc<Engine> {
+        self.by_health()
+            .await
+            .first()
+            .copied()
+            .unwrap_or_else(|| self.primary())
+    }
+
+    /// Gives access to a channel containing the combined state of all engines.
+    ///
+    /// This can be called several times.
+    pub async fn watch_state(&self) -> WatchStream<EngineState> {
+        self.state.read().await.watch()
+    }
+
+    /// Returns `true` if any engine has a "synced" status.
+    pub async fn is_synced(&self) -> bool {
+        for engine in &self.engines {
+            if engine.is_synced().await {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Returns `true` if every engine has a status other than synced or syncing.
+    pub async fn is_offline(&self) -> bool {
+        for engine in &self.engines {
+            if !engine.is_offline().await {
+                return false;
+            }
+        }
+        true
+    }
+
+    pub async fn set_latest_forkchoice_state(&self, state: ForkchoiceState) {
+        for engine in &self.engines {
+            engine.set_latest_forkchoice_state(state).await;
+        }
+    }
+
+    /// Run `Engine::upcheck` on every engine concurrently and update the combined state.
+    pub async fn upcheck(&self) {
+        join_all(self.engines.iter().map(|engine| engine.upcheck())).await;
+
+        let mut combined = EngineStateInternal::Offline;
+        for engine in &self.engines {
+            let state = engine.state().await;
+            metrics::set_gauge_vec(
+                &metrics::EXECUTION_LAYER_ENGINE_ONLINE,
+                &[engine.label().as_str()],
+                (EngineState::from(state) == EngineState::Online) as i64,
+            );
+            if state.health_rank() < combined.health_rank() {
+                combined = state;
+            }
+        }
+        self.state.write().await.update(combined);
+
+        // Refresh the preferred engine, logging if it has changed.
+        self.by_health().await;
+    }
+
+    /// Returns the health of every engine, in the configured order.
+    pub async fn health(&self) -> Vec<ExecutionEngineHealth> {
+        let active = self.active.load(Ordering::Relaxed);
+        let mut health = Vec::with_capacity(self.engines.len());
+        for (index, engine) in self.engines.iter().enumerate() {
+            health.push(ExecutionEngineHealth {
+                endpoint: engine.label(),
+                primary: index == 0,
+                active: index == active,
+                state: engine.state().await.as_str().to_string(),
+            });
+        }
+        health
+    }
+
+    /// Run `func` on the healthiest engine, falling back to the next healthiest engine if the
+    /// request fails.
+    ///
+    /// The error from the last engine is returned if every engine fails.
+    pub async fn request<'a, F, G, H>(&'a self, func: F) -> Result<H, EngineError>
+    where
+        F: Fn(&'a Engine) -> G,
+        G: Future<Output = Result<H, EngineApiError>>,
+    {
+        let mut last_error = EngineError::Offline;
+        for engine in self.by_health().await {
+            match engine.request(&func).await {
+                Ok(result) => return Ok(result),
+                Err(error) => {
+                    if self.engines.len() > 1 {
+                        debug!(
+                            self.log,
+                            "Trying next execution engine";
+                            "failed_engine" => engine.label(),
+                        );
+                    }
+                    last_error = error;
+                }
+            }
+        }
+        Err(last_error)
+    }
+
+    /// Run `func` on every online engine concurrently, or on every engine if none are online.
+    ///
+    /// Returns once the healthiest engine which has not failed has responded, so that the
+    /// response of a less healthy engine is never used in place of the preferred engine's. Engines
+    /// which have not responded by then continue processing the request in the background, so
+    /// that a single slow or unresponsive secondary engine does not delay the others.
+    ///
+    /// The received results are returned alongside their engine, ordered from the healthiest
+    /// engine to the least healthy.
+    pub async fn broadcast<F, G, H>(&self, func: F) -> Vec<(Arc<Engine>, Result<H, EngineError>)>
+    where
+        F: Fn(Arc<Engine>) -> G,
+        G: Future<Output = Result<H, EngineApiError>> + Send + 'static,
+        H: Send + 'static,
+    {
+        let ranked = self.by_health().await;
+        let mut engines = Vec::with_capacity(ranked.len());
+        for engine in &ranked {
+            if !engine.is_offline().await {
+                engines.push(engine.clone());
+            }
+        }
+        if engines.is_empty() {
+            engines = ranked.into_iter().cloned().collect();
+        }
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        for (index, engine) in engines.iter().enumerate() {
+            let request = func(engine.clone());
+            let inner_engine = engine.clone();
+            let tx = tx.clone();
+            engine.executor.spawn(
+                async move {
+                    let result = inner_engine.request(|_| request).await;
+                    // The receiver is dropped once enough responses have been received.
+                    let _ = tx.send((index, result));
+                },
+                "engine_broadcast",
+            );
+        }
+        drop(tx);
+
+        let mut results = engines.iter().map(|_| None).collect::<Vec<_>>();
+        while let Some((index, result)) = rx.recv().await {
+            results[index] = Some(result);
+
+            let leader = results
+                .iter()
+                .position(|result| !matches!(result, Some(Err(_))));
+            if leader.map_or(true, |leader| results[leader].is_some()) {
+                break;
+            }
+        }
+
+        engines
+            .into_iter()
+            .zip(results)
+            .filter_map(|(engine, result)| Some((engine, result?)))
+            .collect()
+    }
+}
+
 impl PayloadIdCacheKey {
     fn new(head_block_hash: &ExecutionBlockHash, attributes: &PayloadAttributes) -> Self {
         Self {
@@ -429,7 +699,37 @@ impl PayloadIdCacheKey {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::auth::{Auth, JwtKey};
+    use crate::engine_api::{PayloadStatusV1, PayloadStatusV1Status};
+    use crate::test_utils::{MockServer, DEFAULT_JWT_SECRET};
+    use sensitive_url::SensitiveUrl;
+    use std::net::SocketAddr;
+    use task_executor::test_utils::TestRuntime;
     use tokio_stream::StreamExt;
+    use types::MainnetEthSpec;
+    use warp::Filter;
+
+    /// Starts an execution engine which accepts connections but never responds.
+    fn start_unresponsive_engine() -> String {
+        let route = warp::any().then(|| std::future::pending::<String>());
+        let (addr, server) =
+            warp::serve(route).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+        tokio::spawn(server);
+        format!("http://{addr}")
+    }
+
+    async fn synced_engine(url: &str, runtime: &TestRuntime) -> Engine {
+        let auth = Auth::new(JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap(), None, None);
+        let api =
+            HttpJsonRpc::new_with_auth(SensitiveUrl::parse(url).unwrap(), auth, None).unwrap();
+        let engine = Engine::new(api, runtime.task_executor.clone(), &runtime.log);
+        engine
+            .state
+            .write()
+            .await
+            .update(EngineStateInternal::Synced);
+        engine
+    }
 
     #[tokio::test]
     async fn test_state_notifier() {
@@ -443,4 +743,68 @@ mod tests {
         let new_state = watcher.next().await.expect("Last state is always present");
         assert_eq!(new_state, EngineState::Online);
     }
+
+    #[test]
+    fn test_health_rank_order() {
+        let mut states = vec![
+            EngineStateInternal::AuthFailed,
+            EngineStateInternal::Offline,
+            EngineStateInternal::Syncing,
+            EngineStateInternal::Synced,
+        ];
+        states.sort_by_key(EngineStateInternal::health_rank);
+        assert_eq!(
+            states,
+            vec![
+                EngineStateInternal::Synced,
+                EngineStateInternal::Syncing,
+                EngineStateInternal::Offline,
+                EngineStateInternal::AuthFailed,
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn broadcast_does_not_wait_for_unresponsive_secondary() {
+        let runtime = TestRuntime::default();
+        let server = MockServer::<MainnetEthSpec>::unit_testing();
+        server.set_forkchoice_updated_response(PayloadStatusV1 {
+            status: PayloadStatusV1Status::Valid,
+            latest_valid_hash: None,
+            validation_error: None,
+        });
+
+        // The responsive engine is the primary, so it is preferred while both are synced.
+        let responsive = synced_engine(&server.url(), &runtime).await;
+        let unresponsive = synced_engine(&start_unresponsive_engine(), &runtime).await;
+        let engines = Engines::new(vec![responsive, unresponsive], &runtime.log);
+
+        let forkchoice_state = ForkchoiceState {
+            head_block_hash: ExecutionBlockHash::repeat_byte(1),
+            safe_block_hash: ExecutionBlockHash::zero(),
+            finalized_block_hash: ExecutionBlockHash::zero(),
+        };
+        let log = runtime.log.clone();
+        let responses = tokio::time::timeout(
+            Duration::from_secs(2),
+            engines.broadcast(|engine| {
+                let log = log.clone();
+                async move {
+                    engine
+                        .notify_forkchoice_updated(forkchoice_state, None, &log)
+                        .await
+                }
+            }),
+        )
+        .await
+        .expect("should not wait for the unresponsive engine");
+
+        assert_eq!(responses.len(), 1);
+        let (engine, response) = &responses[0];
+        assert!(Arc::ptr_eq(engine, &engines.all()[0]));
+        assert_eq!(
+            response.as_ref().unwrap().payload_status.status,
+            PayloadStatusV1Status::Valid
+        );
+    }
 }
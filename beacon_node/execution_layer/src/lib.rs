@@ -9,12 +9,12 @@ use crate::payload_cache::PayloadCache;
 use arc_swap::ArcSwapOption;
 use auth::{strip_prefix, Auth, JwtKey};
 pub use block_hash::calculate_execution_block_hash;
-use builder_client::BuilderHttpClient;
+use engine_api::recording::EngineApiRecorder;
 pub use engine_api::EngineCapabilities;
 use engine_api::Error as ApiError;
 pub use engine_api::*;
 pub use engine_api::{http, http::deposit_methods, http::HttpJsonRpc};
-use engines::{Engine, EngineError};
+use engines::{Engine, EngineError, Engines};
 pub use engines::{EngineState, ForkchoiceState};
 use eth2::types::FullPayloadContents;
 use eth2::types::{builder_bid::SignedBuilderBid, BlobsBundle, ForkVersionedResponse};
@@ -24,6 +24,7 @@ use fork_choice::ForkchoiceUpdateParameters;
 use lru::LruCache;
 use payload_status::process_payload_status;
 pub use payload_status::PayloadStatus;
+pub use relays::{BuilderRelays, Relay};
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
@@ -33,7 +34,7 @@ use std::fmt;
 use std::future::Future;
 use std::io::Write;
 use std::num::NonZeroUsize;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use strum::AsRefStr;
@@ -65,6 +66,7 @@ mod keccak;
 mod metrics;
 pub mod payload_cache;
 mod payload_status;
+mod relays;
 pub mod test_utils;
 pub mod versioned_hashes;
 
@@ -78,6 +80,18 @@ pub const DEFAULT_JWT_FILE: &str = "jwt.hex";
 /// in an LRU cache to avoid redundant lookups. This is the size of that cache.
 const EXECUTION_BLOCKS_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(128);
 
+/// The maximum time to wait for the verifier engine to respond.
+///
+/// The verifier only cross-checks the other engines, so it must not be able to hold up block
+/// import for as long as the engine API timeouts would allow.
+const VERIFIER_ENGINE_TIMEOUT: Duration = Duration::from_secs(4);
+
+/// The maximum number of payloads which are tracked as disputed by the verifier engine.
+///
+/// Descendants of a disputed payload are disputed too, so this bounds the length of chain which
+/// can be built atop a disputed payload before its oldest ancestors are forgotten.
+const DISPUTED_PAYLOADS_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(8192);
+
 /// A fee recipient address for use during block production. Only used as a very last resort if
 /// there is no address provided by the user.
 ///
@@ -151,6 +165,7 @@ pub enum Error {
     },
     PayloadBodiesByRangeNotSupported,
     InvalidJWTSecret(String),
+    EngineApiRecording(String),
     InvalidForkForPayload,
     InvalidPayloadBody(String),
     InvalidPayloadConversion,
@@ -362,12 +377,18 @@ pub enum FailedCondition {
 type PayloadContentsRefTuple<'a, E> = (ExecutionPayloadRef<'a, E>, Option<&'a BlobsBundle<E>>);
 
 struct Inner<E: EthSpec> {
-    engine: Arc<Engine>,
-    builder: ArcSwapOption<BuilderHttpClient>,
+    engines: Engines,
+    verifier: Option<Arc<Engine>>,
+    builder: ArcSwapOption<BuilderRelays>,
     execution_engine_forkchoice_lock: Mutex<()>,
     suggested_fee_recipient: Option<Address>,
     proposer_preparation_data: Mutex<HashMap<u64, ProposerPreparationDataEntry>>,
     execution_blocks: Mutex<LruCache<ExecutionBlockHash, ExecutionBlock>>,
+    /// Maps payloads which the verifier engine disputes, or which descend from a disputed payload,
+    /// to their parent hashes.
+    ///
+    /// A `VALID` status is never returned for these payloads until the engines agree on them.
+    disputed_payloads: Mutex<LruCache<ExecutionBlockHash, ExecutionBlockHash>>,
     proposers: RwLock<HashMap<ProposerKey, Proposer>>,
     executor: TaskExecutor,
     payload_cache: PayloadCache<E>,
@@ -384,7 +405,7 @@ pub struct Config {
     /// Endpoint url for EL nodes that are running the engine api.
     pub execution_endpoint: Option<SensitiveUrl>,
     /// Endpoint urls for services providing the builder api.
-    pub builder_url: Option<SensitiveUrl>,
+    pub builder_urls: Vec<SensitiveUrl>,
     /// The timeout value used when making a request to fetch a block header
     /// from the builder api.
     pub builder_header_timeout: Option<Duration>,
@@ -392,6 +413,16 @@ pub struct Config {
     pub builder_user_agent: Option<String>,
     /// JWT secret for the above endpoint running the engine api.
     pub secret_file: Option<PathBuf>,
+    /// Additional EL nodes which are preferred in order if the primary `execution_endpoint` is
+    /// unhealthy. Every engine is sent each `newPayload` and `forkchoiceUpdated` call.
+    pub secondary_engines: Vec<SecondaryEngineConfig>,
+    /// An EL node running a different client which is sent every `newPayload` and
+    /// `forkchoiceUpdated` call in order to cross-validate the responses of the other engines. It
+    /// is never used to build payloads.
+    pub verifier_engine: Option<SecondaryEngineConfig>,
+    /// If set, every engine API request and response is appended to this file so that it can be
+    /// replayed by the mock execution layer.
+    pub engine_api_record_file: Option<PathBuf>,
     /// The default fee recipient to use on the beacon node if none if provided from
     /// the validator client during block preparation.
     pub suggested_fee_recipient: Option<Address>,
@@ -404,8 +435,78 @@ pub struct Config {
     pub execution_timeout_multiplier: Option<u32>,
 }
 
-/// Provides access to one execution engine and provides a neat interface for consumption by the
-/// `BeaconChain`.
+/// An execution engine used in addition to the primary `Config::execution_endpoint`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecondaryEngineConfig {
+    pub execution_endpoint: SensitiveUrl,
+    /// JWT secret for this endpoint. Defaults to the secret used by the primary engine.
+    pub secret_file: Option<PathBuf>,
+}
+
+/// A `newPayload` call where the verifier engine returned a different status to the other
+/// engines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadDisagreement {
+    pub block_hash: ExecutionBlockHash,
+    pub primary_status: PayloadStatusV1Status,
+    pub verifier_status: PayloadStatusV1Status,
+}
+
+/// Returns `true` if `status` is `VALID`, `INVALID` or `INVALID_BLOCK_HASH`.
+fn is_definitive_status(status: PayloadStatusV1Status) -> bool {
+    matches!(
+        status,
+        PayloadStatusV1Status::Valid
+            | PayloadStatusV1Status::Invalid
+            | PayloadStatusV1Status::InvalidBlockHash
+    )
+}
+
+/// Removes `block_hash` and all of its disputed ancestors from `disputed_payloads`.
+fn resolve_disputed_payloads(
+    disputed_payloads: &mut LruCache<ExecutionBlockHash, ExecutionBlockHash>,
+    block_hash: ExecutionBlockHash,
+) {
+    let mut block_hash = block_hash;
+    while let Some(parent_hash) = disputed_payloads.pop(&block_hash) {
+        block_hash = parent_hash;
+    }
+}
+
+/// Reads the JWT secret from `secret_file`, generating a new secret if the file does not exist.
+fn load_or_generate_jwt_key(secret_file: &Path, log: &Logger) -> Result<JwtKey, Error> {
+    if secret_file.exists() {
+        // Read secret from file if it already exists
+        std::fs::read_to_string(secret_file)
+            .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
+            .and_then(|ref s| {
+                let secret = JwtKey::from_slice(
+                    &hex::decode(strip_prefix(s.trim_end()))
+                        .map_err(|e| format!("Invalid hex string: {:?}", e))?,
+                )?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    } else {
+        // Create a new file and write a randomly generated secret to it if file does not exist
+        warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
+        std::fs::File::options()
+            .write(true)
+            .create_new(true)
+            .open(secret_file)
+            .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
+            .and_then(|mut f| {
+                let secret = auth::JwtKey::random();
+                f.write_all(secret.hex_string().as_bytes())
+                    .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
+                Ok(secret)
+            })
+            .map_err(Error::InvalidJWTSecret)
+    }
+}
+
+/// Provides access to one or more execution engines and provides a neat interface for consumption
+/// by the `BeaconChain`.
 #[derive(Clone)]
 pub struct ExecutionLayer<E: EthSpec> {
     inner: Arc<Inner<E>>,
@@ -416,10 +517,13 @@ impl<E: EthSpec> ExecutionLayer<E> {
     pub fn from_config(config: Config, executor: TaskExecutor, log: Logger) -> Result<Self, Error> {
         let Config {
             execution_endpoint: url,
-            builder_url,
+            builder_urls,
             builder_user_agent,
             builder_header_timeout,
             secret_file,
+            secondary_engines,
+            verifier_engine,
+            engine_api_record_file,
             suggested_fee_recipient,
             jwt_id,
             jwt_version,
@@ -432,51 +536,63 @@ impl<E: EthSpec> ExecutionLayer<E> {
         // Use the default jwt secret path if not provided via cli.
         let secret_file = secret_file.unwrap_or_else(|| default_datadir.join(DEFAULT_JWT_FILE));
 
-        let jwt_key = if secret_file.exists() {
-            // Read secret from file if it already exists
-            std::fs::read_to_string(&secret_file)
-                .map_err(|e| format!("Failed to read JWT secret file. Error: {:?}", e))
-                .and_then(|ref s| {
-                    let secret = JwtKey::from_slice(
-                        &hex::decode(strip_prefix(s.trim_end()))
-                            .map_err(|e| format!("Invalid hex string: {:?}", e))?,
-                    )?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        } else {
-            // Create a new file and write a randomly generated secret to it if file does not exist
-            warn!(log, "No JWT found on disk. Generating"; "path" => %secret_file.display());
-            std::fs::File::options()
-                .write(true)
-                .create_new(true)
-                .open(&secret_file)
-                .map_err(|e| format!("Failed to open JWT secret file. Error: {:?}", e))
-                .and_then(|mut f| {
-                    let secret = auth::JwtKey::random();
-                    f.write_all(secret.hex_string().as_bytes())
-                        .map_err(|e| format!("Failed to write to JWT secret file: {:?}", e))?;
-                    Ok(secret)
-                })
-                .map_err(Error::InvalidJWTSecret)
-        }?;
+        let endpoints = std::iter::once((execution_url, secret_file.clone())).chain(
+            secondary_engines.into_iter().map(|secondary| {
+                (
+                    secondary.execution_endpoint,
+                    secondary.secret_file.unwrap_or_else(|| secret_file.clone()),
+                )
+            }),
+        );
+
+        let recorder = engine_api_record_file
+            .map(|path| {
+                let recorder = EngineApiRecorder::open(&path, log.clone())
+                    .map_err(Error::EngineApiRecording)?;
+                warn!(
+                    log,
+                    "Recording engine API requests";
+                    "info" => "the recording may contain sensitive information",
+                    "path" => %path.display(),
+                );
+                Ok::<_, Error>(Arc::new(recorder))
+            })
+            .transpose()?;
 
-        let engine: Engine = {
-            let auth = Auth::new(jwt_key, jwt_id, jwt_version);
+        let new_engine = |execution_url: SensitiveUrl, secret_file: PathBuf| {
+            let jwt_key = load_or_generate_jwt_key(&secret_file, &log)?;
+            let auth = Auth::new(jwt_key, jwt_id.clone(), jwt_version.clone());
             debug!(log, "Loaded execution endpoint"; "endpoint" => %execution_url, "jwt_path" => ?secret_file.as_path());
-            let api = HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
-                .map_err(Error::ApiError)?;
-            Engine::new(api, executor.clone(), &log)
+            let mut api =
+                HttpJsonRpc::new_with_auth(execution_url, auth, execution_timeout_multiplier)
+                    .map_err(Error::ApiError)?;
+            if let Some(recorder) = &recorder {
+                api.set_recorder(recorder.clone());
+            }
+            Ok::<_, Error>(Engine::new(api, executor.clone(), &log))
         };
 
+        let engines = endpoints
+            .map(|(execution_url, secret_file)| new_engine(execution_url, secret_file))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let verifier = verifier_engine
+            .map(|verifier| {
+                let secret_file = verifier.secret_file.unwrap_or_else(|| secret_file.clone());
+                new_engine(verifier.execution_endpoint, secret_file).map(Arc::new)
+            })
+            .transpose()?;
+
         let inner = Inner {
-            engine: Arc::new(engine),
+            engines: Engines::new(engines, &log),
+            verifier,
             builder: ArcSwapOption::empty(),
             execution_engine_forkchoice_lock: <_>::default(),
             suggested_fee_recipient,
             proposer_preparation_data: Mutex::new(HashMap::new()),
             proposers: RwLock::new(HashMap::new()),
             execution_blocks: Mutex::new(LruCache::new(EXECUTION_BLOCKS_LRU_CACHE_SIZE)),
+            disputed_payloads: Mutex::new(LruCache::new(DISPUTED_PAYLOADS_CACHE_SIZE)),
             executor,
             payload_cache: PayloadCache::default(),
             log,
@@ -487,18 +603,18 @@ impl<E: EthSpec> ExecutionLayer<E> {
             inner: Arc::new(inner),
         };
 
-        if let Some(builder_url) = builder_url {
-            el.set_builder_url(builder_url, builder_user_agent, builder_header_timeout)?;
+        if !builder_urls.is_empty() {
+            el.set_builder_urls(builder_urls, builder_user_agent, builder_header_timeout)?;
         }
 
         Ok(el)
     }
 
-    fn engine(&self) -> &Arc<Engine> {
-        &self.inner.engine
+    fn engines(&self) -> &Engines {
+        &self.inner.engines
     }
 
-    pub fn builder(&self) -> Option<Arc<BuilderHttpClient>> {
+    pub fn builder(&self) -> Option<Arc<BuilderRelays>> {
         self.inner.builder.load_full()
     }
 
@@ -512,19 +628,43 @@ impl<E: EthSpec> ExecutionLayer<E> {
         builder_user_agent: Option<String>,
         builder_header_timeout: Option<Duration>,
     ) -> Result<(), Error> {
-        let builder_client = BuilderHttpClient::new(
-            builder_url.clone(),
+        self.set_builder_urls(
+            vec![builder_url],
+            builder_user_agent,
+            builder_header_timeout,
+        )
+    }
+
+    /// Set the builder relays after initialization, replacing any existing relays.
+    ///
+    /// Supplying an empty list of URLs disables the builder.
+    pub fn set_builder_urls(
+        &self,
+        builder_urls: Vec<SensitiveUrl>,
+        builder_user_agent: Option<String>,
+        builder_header_timeout: Option<Duration>,
+    ) -> Result<(), Error> {
+        if builder_urls.is_empty() {
+            self.inner.builder.swap(None);
+            return Ok(());
+        }
+
+        let relays = BuilderRelays::new(
+            builder_urls.clone(),
             builder_user_agent,
             builder_header_timeout,
+            self.log().clone(),
         )
         .map_err(Error::Builder)?;
-        info!(
-            self.log(),
-            "Using external block builder";
-            "builder_url" => ?builder_url,
-            "local_user_agent" => builder_client.get_user_agent(),
-        );
-        self.inner.builder.swap(Some(Arc::new(builder_client)));
+        for (relay, builder_url) in relays.relays().iter().zip(&builder_urls) {
+            info!(
+                self.log(),
+                "Using external block builder";
+                "builder_url" => ?builder_url,
+                "local_user_agent" => relay.client().get_user_agent(),
+            );
+        }
+        self.inner.builder.swap(Some(Arc::new(relays)));
         Ok(())
     }
 
@@ -561,7 +701,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
     /// Get the current difficulty of the PoW chain.
     pub async fn get_current_difficulty(&self) -> Result<Uint256, ApiError> {
         let block = self
-            .engine()
+            .engines()
+            .best()
+            .await
             .api
             .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
             .await?
@@ -575,11 +717,11 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.inner.execution_blocks.lock().await
     }
 
-    /// Gives access to a channel containing if the last engine state is online or not.
+    /// Gives access to a channel containing if any engine was online at the last check.
     ///
     /// This can be called several times.
     pub async fn get_responsiveness_watch(&self) -> WatchStream<EngineState> {
-        self.engine().watch_state().await
+        self.engines().watch_state().await
     }
 
     /// Note: this function returns a mutex guard, be careful to avoid deadlocks.
@@ -631,7 +773,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
 
     /// Performs a single execution of the watchdog routine.
     pub async fn watchdog_task(&self) {
-        self.engine().upcheck().await;
+        self.upcheck().await;
     }
 
     /// Spawns a routine which cleans the cached proposer data periodically.
@@ -673,9 +815,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
         self.spawn(preparation_cleaner, "exec_preparation_cleanup");
     }
 
-    /// Returns `true` if the execution engine is synced and reachable.
+    /// Returns `true` if any execution engine is synced and reachable.
     pub async fn is_synced(&self) -> bool {
-        self.engine().is_synced().await
+        self.engines().is_synced().await
     }
 
     /// Execution nodes return a "SYNCED" response when they do not have any peers.
@@ -688,7 +830,9 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let synced = self.is_synced().await;
         if synced {
             if let Ok(Some(block)) = self
-                .engine()
+                .engines()
+                .best()
+                .await
                 .api
                 .get_block_by_number(BlockByNumberQuery::Tag(LATEST_TAG))
                 .await
@@ -707,7 +851,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
     /// be used to give an indication on the HTTP API that the node's execution layer is struggling,
     /// which can in turn be used by the VC.
     pub async fn is_offline_or_erroring(&self) -> bool {
-        self.engine().is_offline().await || *self.inner.last_new_payload_errored.read().await
+        self.engines().is_offline().await || *self.inner.last_new_payload_errored.read().await
     }
 
     /// Updates the proposer preparation data provided by validators
@@ -919,14 +1063,18 @@ impl<E: EthSpec> ExecutionLayer<E> {
     }
 
     /// Fetches local and builder paylaods concurrently, Logs and returns results.
+    ///
+    /// Every builder relay is queried and the most valuable valid bid is returned.
+    #[allow(clippy::too_many_arguments)]
     async fn fetch_builder_and_local_payloads(
         &self,
-        builder: &BuilderHttpClient,
+        builder: &BuilderRelays,
         parent_hash: ExecutionBlockHash,
         builder_params: &BuilderParams,
         payload_attributes: &PayloadAttributes,
         forkchoice_update_params: ForkchoiceUpdateParameters,
         current_fork: ForkName,
+        spec: &ChainSpec,
     ) -> (
         Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, builder_client::Error>,
         Result<GetPayloadResponse<E>, Error>,
@@ -937,16 +1085,17 @@ impl<E: EthSpec> ExecutionLayer<E> {
         info!(
             self.log(),
             "Requesting blinded header from connected builder";
+            "relay_count" => builder.relays().len(),
             "slot" => ?slot,
             "pubkey" => ?pubkey,
             "parent_hash" => ?parent_hash,
         );
 
         // Wait for the builder *and* local EL to produce a payload (or return an error).
-        let ((relay_result, relay_duration), (local_result, local_duration)) = tokio::join!(
+        let ((relay_results, relay_duration), (local_result, local_duration)) = tokio::join!(
             timed_future(metrics::GET_BLINDED_PAYLOAD_BUILDER, async {
                 builder
-                    .get_builder_header::<E>(slot, parent_hash, pubkey)
+                    .get_builder_headers::<E>(slot, parent_hash, pubkey)
                     .await
             }),
             timed_future(metrics::GET_BLINDED_PAYLOAD_LOCAL, async {
@@ -964,6 +1113,18 @@ impl<E: EthSpec> ExecutionLayer<E> {
             })
         );
 
+        let local_block_number = local_result.as_ref().ok().map(|local| local.block_number());
+        let relay_result = builder.select_best_bid(relay_results, |bid| {
+            verify_builder_bid(
+                bid,
+                parent_hash,
+                payload_attributes,
+                local_block_number,
+                current_fork,
+                spec,
+            )
+        });
+
         info!(
             self.log(),
             "Requested blinded execution payload";
@@ -1053,6 +1214,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                 payload_attributes,
                 forkchoice_update_params,
                 current_fork,
+                spec,
             )
             .await;
 
@@ -1117,33 +1279,8 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     "parent_hash" => ?parent_hash,
                 );
 
-                // check relay payload validity
-                if let Err(reason) = verify_builder_bid(
-                    &relay,
-                    parent_hash,
-                    payload_attributes,
-                    Some(local.block_number()),
-                    current_fork,
-                    spec,
-                ) {
-                    // relay payload invalid -> return local
-                    metrics::inc_counter_vec(
-                        &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                        &[reason.as_ref().as_ref()],
-                    );
-                    warn!(
-                        self.log(),
-                        "Builder returned invalid payload";
-                        "info" => "using local payload",
-                        "reason" => %reason,
-                        "relay_block_hash" => ?header.block_hash(),
-                        "parent_hash" => ?parent_hash,
-                    );
-                    return Ok(ProvenancedPayload::Local(BlockProposalContentsType::Full(
-                        local.try_into()?,
-                    )));
-                }
-
+                // The relay payload has already been checked for validity in
+                // `fetch_builder_and_local_payloads`.
                 let relay_value = *relay.data.message.value();
 
                 let boosted_relay_value = match builder_boost_factor {
@@ -1202,31 +1339,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
                     "parent_hash" => ?parent_hash,
                 );
 
-                match verify_builder_bid(
-                    &relay,
-                    parent_hash,
-                    payload_attributes,
-                    None,
-                    current_fork,
-                    spec,
-                ) {
-                    Ok(()) => Ok(ProvenancedPayload::try_from(relay.data.message)?),
-                    Err(reason) => {
-                        metrics::inc_counter_vec(
-                            &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
-                            &[reason.as_ref().as_ref()],
-                        );
-                        crit!(
-                            self.log(),
-                            "Builder returned invalid payload";
-                            "info" => "no local payload either - unable to propose block",
-                            "reason" => %reason,
-                            "relay_block_hash" => ?header.block_hash(),
-                            "parent_hash" => ?parent_hash,
-                        );
-                        Err(Error::CannotProduceHeader)
-                    }
-                }
+                Ok(ProvenancedPayload::try_from(relay.data.message)?)
             }
         }
     }
@@ -1260,7 +1373,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
             PayloadContentsRefTuple<E>,
         ) -> Option<FullPayloadContents<E>>,
     ) -> Result<GetPayloadResponseType<E>, Error> {
-        self.engine()
+        self.engines()
             .request(move |engine| async move {
                 let payload_id = if let Some(id) = engine
                     .get_payload_id(&parent_hash, payload_attributes)
@@ -1361,6 +1474,22 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         new_payload_request: NewPayloadRequest<'_, E>,
     ) -> Result<PayloadStatus, Error> {
+        self.notify_new_payload_with_verification(new_payload_request)
+            .await
+            .map(|(status, _)| status)
+    }
+
+    /// Maps to the `engine_newPayload` JSON-RPC call, additionally returning any disagreement
+    /// between the verifier engine and the other engines.
+    ///
+    /// If the verifier engine returns a `VALID`, `INVALID` or `INVALID_BLOCK_HASH` status which
+    /// differs from that of the other engines, `SYNCING` is returned so that the payload is
+    /// imported optimistically rather than being marked valid or invalid. The payload and its
+    /// descendants are then treated as disputed until the verifier agrees that they are valid.
+    pub async fn notify_new_payload_with_verification(
+        &self,
+        new_payload_request: NewPayloadRequest<'_, E>,
+    ) -> Result<(PayloadStatus, Option<PayloadDisagreement>), Error> {
         let _timer = metrics::start_timer_vec(
             &metrics::EXECUTION_LAYER_REQUEST_TIMES,
             &[metrics::NEW_PAYLOAD],
@@ -1371,10 +1500,112 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let block_hash = new_payload_request.block_hash();
         let parent_hash = new_payload_request.parent_hash();
 
-        let result = self
-            .engine()
-            .request(|engine| engine.api.new_payload(new_payload_request))
-            .await;
+        // Every online engine is sent the payload so that each remains able to take over as the
+        // preferred engine.
+        let owned_request = Arc::new(OwnedNewPayloadRequest::from(&new_payload_request));
+        let broadcast = self.engines().broadcast(|engine| {
+            let owned_request = owned_request.clone();
+            async move {
+                let request = owned_request
+                    .as_request()
+                    .map_err(|_| ApiError::IncorrectStateVariant)?;
+                engine.api.new_payload(request).await
+            }
+        });
+        let verify = async {
+            let verifier = self.inner.verifier.as_ref()?;
+            let request =
+                verifier.request(|engine| engine.api.new_payload(new_payload_request.clone()));
+            Some(tokio::time::timeout(VERIFIER_ENGINE_TIMEOUT, request).await)
+        };
+        let (responses, verifier_result) = futures::future::join(broadcast, verify).await;
+        let mut result = self.select_engine_response("new_payload", responses, |status| status);
+        let verifier_status = match &verifier_result {
+            Some(Ok(Ok(verifier_status))) => Some(verifier_status.status),
+            _ => None,
+        };
+
+        let disagreement = match (&result, verifier_result) {
+            (Ok(status), Some(Ok(Ok(verifier_status))))
+                if is_definitive_status(status.status)
+                    && is_definitive_status(verifier_status.status)
+                    && status.status != verifier_status.status =>
+            {
+                Some(PayloadDisagreement {
+                    block_hash,
+                    primary_status: status.status,
+                    verifier_status: verifier_status.status,
+                })
+            }
+            (_, Some(Ok(Err(e)))) => {
+                warn!(
+                    self.log(),
+                    "Verifier engine failed to process payload";
+                    "error" => ?e,
+                    "block_hash" => ?block_hash,
+                );
+                None
+            }
+            (_, Some(Err(_))) => {
+                warn!(
+                    self.log(),
+                    "Verifier engine timed out processing payload";
+                    "timeout_ms" => VERIFIER_ENGINE_TIMEOUT.as_millis(),
+                    "block_hash" => ?block_hash,
+                );
+                None
+            }
+            _ => None,
+        };
+
+        if let Some(disagreement) = &disagreement {
+            metrics::inc_counter(&metrics::EXECUTION_LAYER_VERIFIER_DISAGREEMENTS);
+            crit!(
+                self.log(),
+                "Execution engines disagree on payload validity";
+                "info" => "this may indicate a consensus bug in an execution client, the \
+                           block will be imported optimistically",
+                "primary_status" => ?disagreement.primary_status,
+                "verifier_status" => ?disagreement.verifier_status,
+                "block_hash" => ?block_hash,
+                "block_number" => block_number,
+            );
+            // Refuse to mark the payload as valid or invalid until the engines agree.
+            result = Ok(PayloadStatusV1 {
+                status: PayloadStatusV1Status::Syncing,
+                latest_valid_hash: None,
+                validation_error: None,
+            });
+        }
+
+        {
+            let mut disputed_payloads = self.inner.disputed_payloads.lock().await;
+            if disagreement.is_some() {
+                disputed_payloads.put(block_hash, parent_hash);
+            } else if disputed_payloads.contains(&parent_hash) {
+                match &mut result {
+                    Ok(status)
+                        if status.status == PayloadStatusV1Status::Valid
+                            && verifier_status == Some(PayloadStatusV1Status::Valid) =>
+                    {
+                        // Both engines consider the payload and therefore all of its ancestors
+                        // to be valid.
+                        resolve_disputed_payloads(&mut disputed_payloads, parent_hash);
+                    }
+                    Ok(status) => {
+                        disputed_payloads.put(block_hash, parent_hash);
+                        if status.status == PayloadStatusV1Status::Valid {
+                            *status = PayloadStatusV1 {
+                                status: PayloadStatusV1Status::Syncing,
+                                latest_valid_hash: None,
+                                validation_error: None,
+                            };
+                        }
+                    }
+                    Err(_) => {}
+                }
+            }
+        }
 
         if let Ok(status) = &result {
             let status_str = <&'static str>::from(status.status);
@@ -1394,14 +1625,76 @@ impl<E: EthSpec> ExecutionLayer<E> {
         }
         *self.inner.last_new_payload_errored.write().await = result.is_err();
 
-        process_payload_status(block_hash, result, self.log())
+        let status = process_payload_status(block_hash, result, self.log())
             .map_err(Box::new)
-            .map_err(Error::EngineError)
+            .map_err(Error::EngineError)?;
+
+        Ok((status, disagreement))
+    }
+
+    /// Selects a single response from the responses of every engine to a `newPayload` or
+    /// `forkchoiceUpdated` call. The `responses` must be ordered from healthiest to least healthy.
+    ///
+    /// The response of the healthiest engine which did not fail is used, or the error from the
+    /// healthiest engine if every engine failed. If the engines returned different `VALID`,
+    /// `INVALID` or `INVALID_BLOCK_HASH` statuses, the status is replaced with `SYNCING`, since
+    /// neither `VALID` nor `INVALID` can be safely applied to fork choice until they agree.
+    fn select_engine_response<H>(
+        &self,
+        method: &str,
+        mut responses: Vec<(Arc<Engine>, Result<H, EngineError>)>,
+        payload_status: fn(&mut H) -> &mut PayloadStatusV1,
+    ) -> Result<H, EngineError> {
+        let definitive = responses
+            .iter_mut()
+            .filter_map(|(engine, response)| {
+                let status = payload_status(response.as_mut().ok()?).status;
+                is_definitive_status(status).then(|| (engine.label(), status))
+            })
+            .collect::<Vec<_>>();
+        let disagreement = definitive.windows(2).any(|pair| pair[0].1 != pair[1].1);
+
+        let index = responses
+            .iter()
+            .position(|(_, response)| response.is_ok())
+            .unwrap_or(0);
+        let mut result = responses
+            .into_iter()
+            .nth(index)
+            .map_or(Err(EngineError::Offline), |(_, response)| response);
+
+        if disagreement {
+            warn!(
+                self.log(),
+                "Execution engines disagree on payload status";
+                "info" => "the payload will be treated as syncing until the engines agree",
+                "method" => method,
+                "statuses" => ?definitive,
+            );
+            if let Ok(response) = &mut result {
+                *payload_status(response) = PayloadStatusV1 {
+                    status: PayloadStatusV1Status::Syncing,
+                    latest_valid_hash: None,
+                    validation_error: None,
+                };
+            }
+        }
+
+        result
     }
 
     /// Update engine sync status.
     pub async fn upcheck(&self) {
-        self.engine().upcheck().await;
+        if let Some(verifier) = &self.inner.verifier {
+            futures::future::join(self.engines().upcheck(), verifier.upcheck()).await;
+        } else {
+            self.engines().upcheck().await;
+        }
+    }
+
+    /// Returns the health of every configured execution engine.
+    pub async fn engine_health(&self) -> Vec<eth2::lighthouse::ExecutionEngineHealth> {
+        self.engines().health().await
     }
 
     /// Register that the given `validator_index` is going to produce a block at `slot`.
@@ -1515,18 +1808,102 @@ impl<E: EthSpec> ExecutionLayer<E> {
             finalized_block_hash,
         };
 
-        self.engine()
+        self.engines()
             .set_latest_forkchoice_state(forkchoice_state)
             .await;
 
-        let result = self
-            .engine()
-            .request(|engine| async move {
+        // The verifier follows the head so that it is able to validate new payloads. It never
+        // builds payloads so it is not sent any payload attributes.
+        let verify = self.inner.verifier.clone().map(|verifier| {
+            let log = self.log().clone();
+            async move {
+                verifier.set_latest_forkchoice_state(forkchoice_state).await;
+                let request = verifier.request(|engine| {
+                    engine.notify_forkchoice_updated(forkchoice_state, None, &log)
+                });
+                match tokio::time::timeout(VERIFIER_ENGINE_TIMEOUT, request).await {
+                    Ok(Ok(response)) => Some(response.payload_status.status),
+                    Ok(Err(e)) => {
+                        debug!(
+                            log,
+                            "Verifier engine failed to process forkchoiceUpdated";
+                            "error" => ?e,
+                        );
+                        None
+                    }
+                    Err(_) => {
+                        debug!(
+                            log,
+                            "Verifier engine timed out processing forkchoiceUpdated";
+                            "timeout_ms" => VERIFIER_ENGINE_TIMEOUT.as_millis(),
+                        );
+                        None
+                    }
+                }
+            }
+        });
+
+        let broadcast = self.engines().broadcast(|engine| {
+            let payload_attributes = payload_attributes.clone();
+            let log = self.log().clone();
+            async move {
                 engine
-                    .notify_forkchoice_updated(forkchoice_state, payload_attributes, self.log())
+                    .notify_forkchoice_updated(forkchoice_state, payload_attributes, &log)
                     .await
-            })
-            .await;
+            }
+        });
+
+        // The verifier is only waited upon when the head is disputed, otherwise it is updated in
+        // the background so that it cannot delay the other engines.
+        let head_is_disputed = self
+            .inner
+            .disputed_payloads
+            .lock()
+            .await
+            .contains(&head_block_hash);
+        let (responses, verifier_status) = match verify {
+            Some(verify) if head_is_disputed => futures::future::join(broadcast, verify).await,
+            Some(verify) => {
+                self.executor().spawn(
+                    async move {
+                        verify.await;
+                    },
+                    "verifier_forkchoice_updated",
+                );
+                (broadcast.await, None)
+            }
+            None => (broadcast.await, None),
+        };
+        let mut result = self.select_engine_response("forkchoice_updated", responses, |response| {
+            &mut response.payload_status
+        });
+
+        if head_is_disputed {
+            if let Ok(response) = &mut result {
+                let status = &mut response.payload_status;
+                if status.status == PayloadStatusV1Status::Valid
+                    && verifier_status == Some(PayloadStatusV1Status::Valid)
+                {
+                    resolve_disputed_payloads(
+                        &mut *self.inner.disputed_payloads.lock().await,
+                        head_block_hash,
+                    );
+                } else if status.status == PayloadStatusV1Status::Valid {
+                    warn!(
+                        self.log(),
+                        "Head payload is disputed by the verifier engine";
+                        "info" => "the head will remain optimistic until the engines agree",
+                        "verifier_status" => ?verifier_status,
+                        "head_block_hash" => ?head_block_hash,
+                    );
+                    *status = PayloadStatusV1 {
+                        status: PayloadStatusV1Status::Syncing,
+                        latest_valid_hash: None,
+                        validation_error: None,
+                    };
+                }
+            }
+        }
 
         if let Ok(status) = &result {
             metrics::inc_counter_vec(
@@ -1557,7 +1934,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         age_limit: Option<Duration>,
     ) -> Result<EngineCapabilities, Error> {
-        self.engine()
+        self.engines()
             .request(|engine| engine.get_engine_capabilities(age_limit))
             .await
             .map_err(Into::into)
@@ -1576,7 +1953,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         age_limit: Option<Duration>,
     ) -> Result<Vec<ClientVersionV1>, Error> {
-        self.engine()
+        self.engines()
             .request(|engine| engine.get_engine_version(age_limit))
             .await
             .map_err(Into::into)
@@ -1600,7 +1977,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         );
 
         let hash_opt = self
-            .engine()
+            .engines()
             .request(|engine| async move {
                 let terminal_block_hash = spec.terminal_block_hash;
                 if terminal_block_hash != ExecutionBlockHash::zero() {
@@ -1727,7 +2104,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
             &[metrics::IS_VALID_TERMINAL_POW_BLOCK_HASH],
         );
 
-        self.engine()
+        self.engines()
             .request(|engine| async move {
                 if let Some(pow_block) = self.get_pow_block(engine, block_hash).await? {
                     if let Some(pow_parent) =
@@ -1786,9 +2163,10 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         hashes: Vec<ExecutionBlockHash>,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<E>>>, Error> {
-        self.engine()
-            .request(|engine: &Engine| async move {
-                engine.api.get_payload_bodies_by_hash_v1(hashes).await
+        self.engines()
+            .request(|engine: &Engine| {
+                let hashes = hashes.clone();
+                async move { engine.api.get_payload_bodies_by_hash_v1(hashes).await }
             })
             .await
             .map_err(Box::new)
@@ -1801,7 +2179,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         count: u64,
     ) -> Result<Vec<Option<ExecutionPayloadBodyV1<E>>>, Error> {
         let _timer = metrics::start_timer(&metrics::EXECUTION_LAYER_GET_PAYLOAD_BODIES_BY_RANGE);
-        self.engine()
+        self.engines()
             .request(|engine: &Engine| async move {
                 engine
                     .api
@@ -1865,8 +2243,11 @@ impl<E: EthSpec> ExecutionLayer<E> {
         let capabilities = self.get_engine_capabilities(None).await?;
 
         if capabilities.get_blobs_v1 {
-            self.engine()
-                .request(|engine| async move { engine.api.get_blobs(query).await })
+            self.engines()
+                .request(|engine| {
+                    let query = query.clone();
+                    async move { engine.api.get_blobs(query).await }
+                })
                 .await
                 .map_err(Box::new)
                 .map_err(Error::EngineError)
@@ -1879,7 +2260,7 @@ impl<E: EthSpec> ExecutionLayer<E> {
         &self,
         query: BlockByNumberQuery<'_>,
     ) -> Result<Option<ExecutionBlock>, Error> {
-        self.engine()
+        self.engines()
             .request(|engine| async move { engine.api.get_block_by_number(query).await })
             .await
             .map_err(Box::new)
@@ -2158,7 +2539,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -2185,7 +2566,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_block_prior_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.get_terminal_pow_block_hash(&spec, timestamp_now())
                         .await
@@ -2213,7 +2594,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 assert_eq!(
                     el.is_valid_terminal_pow_block_hash(terminal_block.unwrap().block_hash, &spec)
                         .await
@@ -2230,7 +2611,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, terminal_block| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 let invalid_terminal_block = terminal_block.unwrap().parent_hash;
 
                 assert_eq!(
@@ -2249,7 +2630,7 @@ mod test {
         MockExecutionLayer::default_params(runtime.task_executor.clone())
             .move_to_terminal_block()
             .with_terminal_block(|spec, el, _| async move {
-                el.engine().upcheck().await;
+                el.engines().upcheck().await;
                 let missing_terminal_block = ExecutionBlockHash::repeat_byte(42);
 
                 assert_eq!(
@@ -2261,4 +2642,144 @@ mod test {
             })
             .await;
     }
+
+    fn bellatrix_payload() -> ExecutionPayloadBellatrix<MainnetEthSpec> {
+        ExecutionPayloadBellatrix {
+            parent_hash: ExecutionBlockHash::repeat_byte(1),
+            block_hash: ExecutionBlockHash::repeat_byte(2),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn engine_disagreement_on_forkchoice_updated_is_syncing() {
+        let runtime = TestRuntime::default();
+        let mock = MockExecutionLayer::default_params(runtime.task_executor.clone())
+            .with_secondary_engine();
+
+        // The secondary engine answers `INVALID` before the slower primary answers `VALID`.
+        mock.server.all_payloads_valid_on_forkchoice_updated();
+        mock.server.set_response_delay(Duration::from_millis(200));
+        mock.secondary
+            .as_ref()
+            .unwrap()
+            .all_payloads_invalid_on_forkchoice_updated(ExecutionBlockHash::zero());
+
+        let status = mock
+            .el
+            .notify_forkchoice_updated(
+                ExecutionBlockHash::repeat_byte(3),
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(0),
+                Hash256::repeat_byte(13),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, PayloadStatus::Syncing);
+    }
+
+    #[tokio::test]
+    async fn verifier_agreement_returns_status() {
+        let runtime = TestRuntime::default();
+        let mock =
+            MockExecutionLayer::default_params(runtime.task_executor.clone()).with_verifier();
+        mock.server.all_payloads_valid_on_new_payload();
+        mock.verifier
+            .as_ref()
+            .unwrap()
+            .all_payloads_valid_on_new_payload();
+
+        let payload = bellatrix_payload();
+        let (status, disagreement) = mock
+            .el
+            .notify_new_payload_with_verification(NewPayloadRequest::Bellatrix(
+                NewPayloadRequestBellatrix {
+                    execution_payload: &payload,
+                },
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(status, PayloadStatus::Valid);
+        assert_eq!(disagreement, None);
+    }
+
+    #[tokio::test]
+    async fn verifier_disagreement_imports_optimistically() {
+        let runtime = TestRuntime::default();
+        let mock =
+            MockExecutionLayer::default_params(runtime.task_executor.clone()).with_verifier();
+        let payload = bellatrix_payload();
+        mock.server.all_payloads_valid_on_new_payload();
+        mock.verifier
+            .as_ref()
+            .unwrap()
+            .all_payloads_invalid_on_new_payload(payload.parent_hash);
+
+        let (status, disagreement) = mock
+            .el
+            .notify_new_payload_with_verification(NewPayloadRequest::Bellatrix(
+                NewPayloadRequestBellatrix {
+                    execution_payload: &payload,
+                },
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(status, PayloadStatus::Syncing);
+        assert_eq!(
+            disagreement,
+            Some(PayloadDisagreement {
+                block_hash: payload.block_hash,
+                primary_status: PayloadStatusV1Status::Valid,
+                verifier_status: PayloadStatusV1Status::Invalid,
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn verifier_follows_forkchoice_updates() {
+        let runtime = TestRuntime::default();
+        let mock =
+            MockExecutionLayer::default_params(runtime.task_executor.clone()).with_verifier();
+        mock.server.all_payloads_syncing_on_forkchoice_updated();
+        let verifier = mock.verifier.as_ref().unwrap();
+        verifier.all_payloads_syncing_on_forkchoice_updated();
+
+        let head_block_hash = ExecutionBlockHash::repeat_byte(3);
+        let status = mock
+            .el
+            .notify_forkchoice_updated(
+                head_block_hash,
+                ExecutionBlockHash::zero(),
+                ExecutionBlockHash::zero(),
+                Slot::new(0),
+                Hash256::repeat_byte(13),
+            )
+            .await
+            .unwrap();
+        assert_eq!(status, PayloadStatus::Syncing);
+
+        // The verifier is updated in the background.
+        let mut forwarded = None;
+        for _ in 0..50 {
+            if let Some(request) = verifier.take_previous_request() {
+                if request["method"]
+                    .as_str()
+                    .is_some_and(|method| method.starts_with("engine_forkchoiceUpdated"))
+                {
+                    forwarded = Some(request);
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let request = forwarded.expect("verifier should be sent forkchoiceUpdated");
+        assert_eq!(
+            request["params"][0]["headBlockHash"],
+            serde_json::to_value(head_block_hash).unwrap()
+        );
+        assert!(request["params"][1].is_null());
+    }
 }
@@ -16,6 +16,13 @@ pub const LOCAL: &str = "local";
 pub const BUILDER: &str = "builder";
 pub const SUCCESS: &str = "success";
 pub const FAILURE: &str = "failure";
+pub const WON: &str = "won";
+pub const VALID: &str = "valid";
+pub const INVALID: &str = "invalid";
+pub const EMPTY: &str = "empty";
+pub const ERROR: &str = "error";
+pub const GET_HEADER: &str = "get_header";
+pub const POST_VALIDATORS: &str = "post_validators";
 
 pub static EXECUTION_LAYER_PROPOSER_INSERTED: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
     try_create_int_counter(
@@ -116,3 +123,48 @@ pub static EXECUTION_LAYER_PAYLOAD_BIDS: LazyLock<Result<IntGaugeVec>> = LazyLoc
         &["source"]
     )
 });
+pub static EXECUTION_LAYER_BUILDER_RELAY_REQUEST_TIMES: LazyLock<Result<HistogramVec>> =
+    LazyLock::new(|| {
+        try_create_histogram_vec_with_buckets(
+            "execution_layer_builder_relay_request_times",
+            "Duration of calls to each builder relay",
+            decimal_buckets(-2, 1),
+            &["relay", "method"],
+        )
+    });
+pub static EXECUTION_LAYER_BUILDER_RELAY_BIDS: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "execution_layer_builder_relay_bids",
+            "The outcome of each header request made to a builder relay",
+            &["relay", "outcome"],
+        )
+    });
+pub static EXECUTION_LAYER_BUILDER_RELAY_HEALTHY: LazyLock<Result<IntGaugeVec>> =
+    LazyLock::new(|| {
+        try_create_int_gauge_vec(
+            "execution_layer_builder_relay_healthy",
+            "Set to 1 if the builder relay responded to its last few requests, 0 otherwise",
+            &["relay"],
+        )
+    });
+pub static EXECUTION_LAYER_ENGINE_ONLINE: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "execution_layer_engine_online",
+        "Set to 1 if the execution engine is synced or syncing, 0 otherwise",
+        &["engine"],
+    )
+});
+pub static EXECUTION_LAYER_ENGINE_FAILOVERS: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "execution_layer_engine_failovers",
+        "Count of times the preferred execution engine changed",
+    )
+});
+pub static EXECUTION_LAYER_VERIFIER_DISAGREEMENTS: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "execution_layer_verifier_disagreements",
+            "Count of payloads where the verifier engine disagreed with the primary engines",
+        )
+    });
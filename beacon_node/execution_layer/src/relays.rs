@@ -0,0 +1,553 @@
+//! Provides a multiplexer over one or more builder relays.
+//!
+//! Header requests are sent to every relay in parallel and the most valuable valid bid is
+//! selected. The relay which supplied the selected bid is remembered so that the signed blinded
+//! block can be revealed to that same relay.
+
+use crate::{metrics, InvalidBuilderPayload};
+use builder_client::{BuilderHttpClient, Error};
+use eth2::types::builder_bid::SignedBuilderBid;
+use eth2::types::{ForkVersionedResponse, FullPayloadContents};
+use futures::future::{join_all, select_ok};
+use lru::LruCache;
+use parking_lot::Mutex;
+use sensitive_url::SensitiveUrl;
+use slog::{debug, info, warn, Logger};
+use std::future::Future;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use types::non_zero_usize::new_non_zero_usize;
+use types::{
+    EthSpec, ExecPayload, ExecutionBlockHash, PublicKeyBytes, SignedBlindedBeaconBlock,
+    SignedValidatorRegistrationData, Slot,
+};
+
+/// The number of consecutive failed requests after which a relay is considered unhealthy.
+pub const RELAY_UNHEALTHY_THRESHOLD: usize = 3;
+
+/// The number of selected bids for which the supplying relay is remembered.
+const BID_SOURCES_LRU_CACHE_SIZE: NonZeroUsize = new_non_zero_usize(32);
+
+type HeaderResponse<E> = Result<Option<ForkVersionedResponse<SignedBuilderBid<E>>>, Error>;
+
+/// A single builder relay and its health.
+pub struct Relay {
+    client: BuilderHttpClient,
+    /// The redacted URL of the relay, used in logs and metrics.
+    label: String,
+    consecutive_failures: AtomicUsize,
+}
+
+impl Relay {
+    fn new(
+        url: SensitiveUrl,
+        user_agent: Option<String>,
+        header_timeout: Option<Duration>,
+    ) -> Result<Self, Error> {
+        let label = url.to_string();
+        let client = BuilderHttpClient::new(url, user_agent, header_timeout)?;
+        metrics::set_gauge_vec(
+            &metrics::EXECUTION_LAYER_BUILDER_RELAY_HEALTHY,
+            &[label.as_str()],
+            1,
+        );
+        Ok(Self {
+            client,
+            label,
+            consecutive_failures: AtomicUsize::new(0),
+        })
+    }
+
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn client(&self) -> &BuilderHttpClient {
+        &self.client
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.consecutive_failures.load(Ordering::Relaxed) < RELAY_UNHEALTHY_THRESHOLD
+    }
+
+    /// Run `future` against this relay, recording its duration and updating the relay's health.
+    async fn request<F, T>(&self, method: &str, future: F, log: &Logger) -> Result<T, Error>
+    where
+        F: Future<Output = Result<T, Error>>,
+    {
+        let start = Instant::now();
+        let result = future.await;
+        metrics::observe_timer_vec(
+            &metrics::EXECUTION_LAYER_BUILDER_RELAY_REQUEST_TIMES,
+            &[self.label(), method],
+            start.elapsed(),
+        );
+
+        if result.is_ok() {
+            let previous_failures = self.consecutive_failures.swap(0, Ordering::Relaxed);
+            if previous_failures >= RELAY_UNHEALTHY_THRESHOLD {
+                info!(
+                    log,
+                    "Builder relay has recovered";
+                    "relay" => &self.label,
+                );
+            }
+            metrics::set_gauge_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_RELAY_HEALTHY,
+                &[self.label()],
+                1,
+            );
+        } else {
+            let failures = self.consecutive_failures.fetch_add(1, Ordering::Relaxed) + 1;
+            if failures == RELAY_UNHEALTHY_THRESHOLD {
+                warn!(
+                    log,
+                    "Builder relay is unhealthy";
+                    "info" => "the relay will continue to be queried",
+                    "relay" => &self.label,
+                    "consecutive_failures" => failures,
+                );
+                metrics::set_gauge_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_RELAY_HEALTHY,
+                    &[self.label()],
+                    0,
+                );
+            }
+        }
+
+        result
+    }
+}
+
+/// A set of builder relays which are queried together.
+pub struct BuilderRelays {
+    relays: Vec<Arc<Relay>>,
+    /// Maps the block hash of each selected bid to the index of the relay which supplied it.
+    bid_sources: Mutex<LruCache<ExecutionBlockHash, usize>>,
+    log: Logger,
+}
+
+impl BuilderRelays {
+    pub fn new(
+        urls: Vec<SensitiveUrl>,
+        user_agent: Option<String>,
+        header_timeout: Option<Duration>,
+        log: Logger,
+    ) -> Result<Self, Error> {
+        let relays = urls
+            .into_iter()
+            .map(|url| Relay::new(url, user_agent.clone(), header_timeout).map(Arc::new))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            relays,
+            bid_sources: Mutex::new(LruCache::new(BID_SOURCES_LRU_CACHE_SIZE)),
+            log,
+        })
+    }
+
+    pub fn relays(&self) -> &[Arc<Relay>] {
+        &self.relays
+    }
+
+    /// Request a header from every relay in parallel.
+    ///
+    /// Each request is bounded by the `--builder-header-timeout` of the underlying client.
+    pub async fn get_builder_headers<E: EthSpec>(
+        &self,
+        slot: Slot,
+        parent_hash: ExecutionBlockHash,
+        pubkey: &PublicKeyBytes,
+    ) -> Vec<HeaderResponse<E>> {
+        join_all(self.relays.iter().map(|relay| {
+            relay.request(
+                metrics::GET_HEADER,
+                relay
+                    .client
+                    .get_builder_header::<E>(slot, parent_hash, pubkey),
+                &self.log,
+            )
+        }))
+        .await
+    }
+
+    /// Choose the most valuable of the bids in `responses` which passes `verify_bid`.
+    ///
+    /// The `responses` must be in the same order as the relays, as returned by
+    /// `Self::get_builder_headers`. An error is only returned if every relay failed.
+    pub(crate) fn select_best_bid<E, F>(
+        &self,
+        responses: Vec<HeaderResponse<E>>,
+        verify_bid: F,
+    ) -> HeaderResponse<E>
+    where
+        E: EthSpec,
+        F: Fn(
+            &ForkVersionedResponse<SignedBuilderBid<E>>,
+        ) -> Result<(), Box<InvalidBuilderPayload>>,
+    {
+        let mut best: Option<(usize, ForkVersionedResponse<SignedBuilderBid<E>>)> = None;
+        let mut first_error = None;
+        let mut any_response = false;
+
+        for (index, response) in responses.into_iter().enumerate() {
+            let Some(relay) = self.relays.get(index) else {
+                break;
+            };
+
+            let bid = match response {
+                Ok(Some(bid)) => bid,
+                Ok(None) => {
+                    any_response = true;
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_RELAY_BIDS,
+                        &[relay.label(), metrics::EMPTY],
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    metrics::inc_counter_vec(
+                        &metrics::EXECUTION_LAYER_BUILDER_RELAY_BIDS,
+                        &[relay.label(), metrics::ERROR],
+                    );
+                    debug!(
+                        self.log,
+                        "Builder relay failed to return a header";
+                        "relay" => &relay.label,
+                        "error" => ?e,
+                    );
+                    first_error.get_or_insert(e);
+                    continue;
+                }
+            };
+            any_response = true;
+
+            if let Err(reason) = verify_bid(&bid) {
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_GET_PAYLOAD_BUILDER_REJECTIONS,
+                    &[reason.as_ref().as_ref()],
+                );
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_RELAY_BIDS,
+                    &[relay.label(), metrics::INVALID],
+                );
+                warn!(
+                    self.log,
+                    "Builder returned invalid payload";
+                    "info" => "ignoring bid from this relay",
+                    "relay" => &relay.label,
+                    "reason" => %reason,
+                    "relay_block_hash" => ?bid.data.message.header().block_hash(),
+                );
+                continue;
+            }
+
+            metrics::inc_counter_vec(
+                &metrics::EXECUTION_LAYER_BUILDER_RELAY_BIDS,
+                &[relay.label(), metrics::VALID],
+            );
+            debug!(
+                self.log,
+                "Received valid bid from builder relay";
+                "relay" => &relay.label,
+                "value" => %bid.data.message.value(),
+                "relay_block_hash" => ?bid.data.message.header().block_hash(),
+            );
+
+            let is_better = best.as_ref().map_or(true, |(_, best_bid)| {
+                bid.data.message.value() > best_bid.data.message.value()
+            });
+            if is_better {
+                best = Some((index, bid));
+            }
+        }
+
+        match (best, first_error) {
+            (Some((index, bid)), _) => {
+                let relay = &self.relays[index];
+                metrics::inc_counter_vec(
+                    &metrics::EXECUTION_LAYER_BUILDER_RELAY_BIDS,
+                    &[relay.label(), metrics::WON],
+                );
+                if self.relays.len() > 1 {
+                    info!(
+                        self.log,
+                        "Selected most valuable builder bid";
+                        "relay" => &relay.label,
+                        "value" => %bid.data.message.value(),
+                        "relay_count" => self.relays.len(),
+                    );
+                }
+                self.bid_sources
+                    .lock()
+                    .put(bid.data.message.header().block_hash(), index);
+                Ok(Some(bid))
+            }
+            (None, Some(e)) if !any_response => Err(e),
+            (None, _) => Ok(None),
+        }
+    }
+
+    /// Reveal a signed blinded block to the relay which supplied its header.
+    ///
+    /// If the supplying relay is unknown (e.g. the header was requested before a restart), the
+    /// block is sent to every relay and the first successful response is returned.
+    pub async fn post_builder_blinded_blocks<E: EthSpec>(
+        &self,
+        blinded_block: &SignedBlindedBeaconBlock<E>,
+    ) -> Result<ForkVersionedResponse<FullPayloadContents<E>>, Error> {
+        let source = blinded_block
+            .message()
+            .execution_payload()
+            .ok()
+            .and_then(|payload| self.bid_sources.lock().get(&payload.block_hash()).copied())
+            .and_then(|index| self.relays.get(index));
+
+        if let Some(relay) = source {
+            debug!(
+                self.log,
+                "Revealing payload to winning relay";
+                "relay" => &relay.label,
+            );
+            return relay
+                .request(
+                    metrics::POST_BLINDED_PAYLOAD_BUILDER,
+                    relay.client.post_builder_blinded_blocks(blinded_block),
+                    &self.log,
+                )
+                .await;
+        }
+
+        let requests = self.relays.iter().map(|relay| {
+            Box::pin(relay.request(
+                metrics::POST_BLINDED_PAYLOAD_BUILDER,
+                relay.client.post_builder_blinded_blocks(blinded_block),
+                &self.log,
+            ))
+        });
+        select_ok(requests).await.map(|(response, _)| response)
+    }
+
+    /// Register validators with every relay.
+    ///
+    /// Succeeds if at least one relay accepted the registrations, otherwise the first error is
+    /// returned.
+    pub async fn post_builder_validators(
+        &self,
+        validators: &[SignedValidatorRegistrationData],
+    ) -> Result<(), Error> {
+        let results = join_all(self.relays.iter().map(|relay| async move {
+            let result = relay
+                .request(
+                    metrics::POST_VALIDATORS,
+                    relay.client.post_builder_validators(validators),
+                    &self.log,
+                )
+                .await;
+            if let Err(e) = &result {
+                warn!(
+                    self.log,
+                    "Relay rejected validator registrations";
+                    "relay" => &relay.label,
+                    "error" => ?e,
+                );
+            }
+            result
+        }))
+        .await;
+
+        let mut first_error = None;
+        for result in results {
+            match result {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        first_error.map_or(Ok(()), Err)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use logging::test_logger;
+    use reqwest::StatusCode;
+    use std::net::SocketAddr;
+    use types::builder_bid::{BuilderBid, BuilderBidCapella};
+    use types::{
+        BeaconBlock, BeaconBlockCapella, BlindedPayload, BlindedPayloadCapella, EmptyBlock,
+        ExecutionPayloadHeaderCapella, ForkName, MainnetEthSpec, Signature, SignedBeaconBlock,
+        Uint256,
+    };
+    use warp::Filter;
+
+    type E = MainnetEthSpec;
+
+    fn block_hash(byte: u8) -> ExecutionBlockHash {
+        ExecutionBlockHash::repeat_byte(byte)
+    }
+
+    fn bid(value: u64, block_hash: ExecutionBlockHash) -> HeaderResponse<E> {
+        Ok(Some(ForkVersionedResponse {
+            version: Some(ForkName::Capella),
+            metadata: Default::default(),
+            data: SignedBuilderBid {
+                message: BuilderBid::Capella(BuilderBidCapella {
+                    header: ExecutionPayloadHeaderCapella {
+                        block_hash,
+                        ..Default::default()
+                    },
+                    value: Uint256::from(value),
+                    pubkey: PublicKeyBytes::empty(),
+                }),
+                signature: Signature::empty(),
+            },
+        }))
+    }
+
+    fn failure() -> HeaderResponse<E> {
+        Err(Error::StatusCode(StatusCode::INTERNAL_SERVER_ERROR))
+    }
+
+    fn relays(urls: Vec<String>) -> BuilderRelays {
+        let urls = urls
+            .iter()
+            .map(|url| SensitiveUrl::parse(url).unwrap())
+            .collect();
+        BuilderRelays::new(urls, None, None, test_logger()).unwrap()
+    }
+
+    /// Relays which are never contacted.
+    fn offline_relays(count: u16) -> BuilderRelays {
+        relays(
+            (0..count)
+                .map(|i| format!("http://127.0.0.1:{}", 1 + i))
+                .collect(),
+        )
+    }
+
+    fn selected_block_hash(response: HeaderResponse<E>) -> Option<ExecutionBlockHash> {
+        response
+            .unwrap()
+            .map(|bid| bid.data.message.header().block_hash())
+    }
+
+    /// Starts a relay which counts the blinded blocks revealed to it and then fails.
+    fn start_mock_relay() -> (String, Arc<AtomicUsize>) {
+        let reveals = Arc::new(AtomicUsize::new(0));
+        let counter = reveals.clone();
+        let route = warp::post()
+            .and(warp::path!("eth" / "v1" / "builder" / "blinded_blocks"))
+            .map(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                warp::reply::with_status(
+                    warp::reply(),
+                    warp::http::StatusCode::INTERNAL_SERVER_ERROR,
+                )
+            });
+        let (addr, server) =
+            warp::serve(route).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+        tokio::spawn(server);
+        (format!("http://{}", addr), reveals)
+    }
+
+    fn blinded_block(block_hash: ExecutionBlockHash) -> SignedBlindedBeaconBlock<E> {
+        let mut block = BeaconBlockCapella::<E, BlindedPayload<E>>::empty(&E::default_spec());
+        block.body.execution_payload = BlindedPayloadCapella {
+            execution_payload_header: ExecutionPayloadHeaderCapella {
+                block_hash,
+                ..Default::default()
+            },
+        };
+        SignedBeaconBlock::from_block(BeaconBlock::Capella(block), Signature::empty())
+    }
+
+    #[test]
+    fn selects_most_valuable_bid() {
+        let relays = offline_relays(3);
+        let responses = vec![
+            bid(10, block_hash(1)),
+            bid(30, block_hash(2)),
+            bid(20, block_hash(3)),
+        ];
+        let selected = relays.select_best_bid(responses, |_| Ok(()));
+        assert_eq!(selected_block_hash(selected), Some(block_hash(2)));
+    }
+
+    #[test]
+    fn ignores_invalid_bids() {
+        let relays = offline_relays(3);
+        let responses = vec![
+            bid(10, block_hash(1)),
+            bid(30, block_hash(2)),
+            bid(20, block_hash(3)),
+        ];
+        // The most valuable bid builds on the wrong parent.
+        let selected = relays.select_best_bid(responses, |bid| {
+            let payload = bid.data.message.header().block_hash();
+            if payload == block_hash(2) {
+                Err(Box::new(InvalidBuilderPayload::ParentHash {
+                    payload,
+                    expected: block_hash(0),
+                }))
+            } else {
+                Ok(())
+            }
+        });
+        assert_eq!(selected_block_hash(selected), Some(block_hash(3)));
+    }
+
+    #[test]
+    fn failed_relays_are_ignored() {
+        let relays = offline_relays(3);
+        let responses = vec![failure(), bid(10, block_hash(1)), Ok(None)];
+        let selected = relays.select_best_bid(responses, |_| Ok(()));
+        assert_eq!(selected_block_hash(selected), Some(block_hash(1)));
+    }
+
+    #[test]
+    fn errors_only_if_every_relay_failed() {
+        let relays = offline_relays(2);
+
+        let selected = relays.select_best_bid(vec![failure(), failure()], |_| Ok(()));
+        assert!(selected.is_err());
+
+        let selected = relays.select_best_bid(vec![failure(), Ok(None)], |_| Ok(()));
+        assert_eq!(selected_block_hash(selected), None);
+    }
+
+    #[tokio::test]
+    async fn reveals_to_winning_relay() {
+        let (urls, reveals): (Vec<_>, Vec<_>) = (0..3).map(|_| start_mock_relay()).unzip();
+        let relays = relays(urls);
+        let reveal_counts = || {
+            reveals
+                .iter()
+                .map(|reveals| reveals.load(Ordering::SeqCst))
+                .collect::<Vec<_>>()
+        };
+
+        let responses = vec![
+            bid(10, block_hash(1)),
+            bid(30, block_hash(2)),
+            bid(20, block_hash(3)),
+        ];
+        relays.select_best_bid(responses, |_| Ok(())).unwrap();
+
+        // Only the relay which supplied the winning bid learns the signed block.
+        let _ = relays
+            .post_builder_blinded_blocks(&blinded_block(block_hash(2)))
+            .await;
+        assert_eq!(reveal_counts(), vec![0, 1, 0]);
+
+        // A block built on an unknown bid is sent to every relay.
+        let _ = relays
+            .post_builder_blinded_blocks(&blinded_block(block_hash(4)))
+            .await;
+        assert_eq!(reveal_counts(), vec![1, 2, 1]);
+    }
+}
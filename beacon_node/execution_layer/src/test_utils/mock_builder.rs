@@ -1,16 +1,18 @@
 use crate::test_utils::{DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_JWT_SECRET};
 use crate::{Config, ExecutionLayer, PayloadAttributes};
-use eth2::types::{BlobsBundle, BlockId, StateId, ValidatorId};
+use eth2::types::{BlobsBundle, BlockId, FullPayloadContents, StateId, ValidatorId};
 use eth2::{BeaconNodeHttpClient, Timeouts, CONSENSUS_VERSION_HEADER};
 use fork_choice::ForkchoiceUpdateParameters;
 use parking_lot::RwLock;
 use sensitive_url::SensitiveUrl;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
 use std::future::Future;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
+use strum::{Display, EnumString};
 use task_executor::TaskExecutor;
 use tempfile::NamedTempFile;
 use tree_hash::TreeHash;
@@ -54,6 +56,30 @@ impl Operation {
     }
 }
 
+/// Adversarial behaviours which a `MockBuilder` can be configured to exhibit.
+///
+/// Unlike `Operation`s, which are applied to a single bid, behaviours persist until they are
+/// cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum Behaviour {
+    /// Accept signed blinded blocks but never reveal the corresponding payload.
+    WithholdPayload,
+    /// Return bids with an invalid signature.
+    InvalidSignature,
+    /// Return bids which build upon a random parent hash.
+    WrongParentHash,
+    /// Delay header responses by `MOCK_BUILDER_TIMEOUT_DELAY`, well beyond the header timeout.
+    Timeout,
+    /// Return bids with a gas limit above the limit in the validator's registration.
+    ExceedGasLimit,
+    /// Reveal a payload which differs from the one committed to in the bid.
+    Equivocate,
+}
+
+/// How long the `Behaviour::Timeout` builder waits before responding to a header request.
+pub const MOCK_BUILDER_TIMEOUT_DELAY: Duration = Duration::from_secs(12);
+
 #[derive(Debug)]
 // We don't use the string value directly, but it's used in the Debug impl which is required by `warp::reject::Reject`.
 struct Custom(#[allow(dead_code)] String);
@@ -214,6 +240,7 @@ pub struct MockBuilder<E: EthSpec> {
     builder_sk: SecretKey,
     operations: Arc<RwLock<Vec<Operation>>>,
     invalidate_signatures: Arc<RwLock<bool>>,
+    behaviours: Arc<RwLock<HashSet<Behaviour>>>,
 }
 
 impl<E: EthSpec> MockBuilder<E> {
@@ -224,29 +251,43 @@ impl<E: EthSpec> MockBuilder<E> {
         executor: TaskExecutor,
     ) -> (Self, (SocketAddr, impl Future<Output = ()>)) {
         let file = NamedTempFile::new().unwrap();
-        let path = file.path().into();
+        let path: PathBuf = file.path().into();
         std::fs::write(&path, hex::encode(DEFAULT_JWT_SECRET)).unwrap();
 
+        let builder =
+            MockBuilder::new_with_execution_endpoint(mock_el_url, path, beacon_url, spec, executor)
+                .unwrap();
+        let host: Ipv4Addr = Ipv4Addr::LOCALHOST;
+        let port = 0;
+        let server = serve(host, port, builder.clone()).expect("mock builder server should start");
+        (builder, server)
+    }
+
+    /// Create a builder which builds payloads using the execution engine at `execution_endpoint`
+    /// and follows the head of the beacon node at `beacon_url`.
+    pub fn new_with_execution_endpoint(
+        execution_endpoint: SensitiveUrl,
+        secret_file: PathBuf,
+        beacon_url: SensitiveUrl,
+        spec: Arc<ChainSpec>,
+        executor: TaskExecutor,
+    ) -> Result<Self, String> {
         // This EL should not talk to a builder
         let config = Config {
-            execution_endpoint: Some(mock_el_url),
-            secret_file: Some(path),
+            execution_endpoint: Some(execution_endpoint),
+            secret_file: Some(secret_file),
             suggested_fee_recipient: None,
             ..Default::default()
         };
 
-        let el =
-            ExecutionLayer::from_config(config, executor.clone(), executor.log().clone()).unwrap();
+        let el = ExecutionLayer::from_config(config, executor.clone(), executor.log().clone())
+            .map_err(|e| format!("Unable to create execution layer: {:?}", e))?;
 
-        let builder = MockBuilder::new(
+        Ok(MockBuilder::new(
             el,
             BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(Duration::from_secs(1))),
             spec,
-        );
-        let host: Ipv4Addr = Ipv4Addr::LOCALHOST;
-        let port = 0;
-        let server = serve(host, port, builder.clone()).expect("mock builder server should start");
-        (builder, server)
+        ))
     }
 
     pub fn new(
@@ -264,6 +305,7 @@ impl<E: EthSpec> MockBuilder<E> {
             builder_sk: sk,
             operations: Arc::new(RwLock::new(vec![])),
             invalidate_signatures: Arc::new(RwLock::new(false)),
+            behaviours: Arc::new(RwLock::new(HashSet::new())),
         }
     }
 
@@ -281,6 +323,25 @@ impl<E: EthSpec> MockBuilder<E> {
         *self.invalidate_signatures.write() = false;
     }
 
+    /// Start exhibiting `behaviour` for all subsequent requests.
+    pub fn set_behaviour(&self, behaviour: Behaviour) {
+        self.behaviours.write().insert(behaviour);
+    }
+
+    /// Stop exhibiting `behaviour`.
+    pub fn clear_behaviour(&self, behaviour: Behaviour) {
+        self.behaviours.write().remove(&behaviour);
+    }
+
+    /// Return to honest behaviour.
+    pub fn clear_behaviours(&self) {
+        self.behaviours.write().clear();
+    }
+
+    pub fn has_behaviour(&self, behaviour: Behaviour) -> bool {
+        self.behaviours.read().contains(&behaviour)
+    }
+
     fn apply_operations<B: BidStuff<E>>(&self, bid: &mut B) {
         let mut guard = self.operations.write();
         while let Some(op) = guard.pop() {
@@ -349,10 +410,18 @@ pub fn serve<E: EthSpec>(
                             block.message.body.execution_payload.tree_hash_root()
                         }
                     };
-                    let payload = builder
+                    if builder.has_behaviour(Behaviour::WithholdPayload) {
+                        return Err(reject("payload withheld"));
+                    }
+                    let mut payload = builder
                         .el
                         .get_payload_by_root(&root)
                         .ok_or_else(|| reject("missing payload for tx root"))?;
+                    if builder.has_behaviour(Behaviour::Equivocate) {
+                        let (mut execution_payload, blobs_bundle) = payload.deconstruct();
+                        *execution_payload.fee_recipient_mut() = Address::repeat_byte(0xee);
+                        payload = FullPayloadContents::new(execution_payload, blobs_bundle);
+                    }
                     let resp: ForkVersionedResponse<_> = ForkVersionedResponse {
                         version: Some(fork_name),
                         metadata: Default::default(),
@@ -395,6 +464,10 @@ pub fn serve<E: EthSpec>(
              parent_hash: ExecutionBlockHash,
              pubkey: PublicKeyBytes,
              builder: MockBuilder<E>| async move {
+                if builder.has_behaviour(Behaviour::Timeout) {
+                    tokio::time::sleep(MOCK_BUILDER_TIMEOUT_DELAY).await;
+                }
+
                 let fork = builder.spec.fork_name_at_slot::<E>(slot);
                 let signed_cached_data = builder
                     .val_registration_cache
@@ -650,12 +723,21 @@ pub fn serve<E: EthSpec>(
 
                 message.set_gas_limit(cached_data.gas_limit);
 
+                if builder.has_behaviour(Behaviour::ExceedGasLimit) {
+                    message.set_gas_limit(cached_data.gas_limit.saturating_mul(2));
+                }
+                if builder.has_behaviour(Behaviour::WrongParentHash) {
+                    message.set_parent_hash(Hash256::random());
+                }
+
                 builder.apply_operations(&mut message);
 
                 let mut signature =
                     message.sign_builder_message(&builder.builder_sk, &builder.spec);
 
-                if *builder.invalidate_signatures.read() {
+                if *builder.invalidate_signatures.read()
+                    || builder.has_behaviour(Behaviour::InvalidSignature)
+                {
                     signature = Signature::empty();
                 }
 
@@ -11,6 +11,10 @@ use types::{FixedBytesExtended, MainnetEthSpec};
 
 pub struct MockExecutionLayer<E: EthSpec> {
     pub server: MockServer<E>,
+    /// The verifier engine, if one has been added with `Self::with_verifier`.
+    pub verifier: Option<MockServer<E>>,
+    /// The secondary engine, if one has been added with `Self::with_secondary_engine`.
+    pub secondary: Option<MockServer<E>>,
     pub el: ExecutionLayer<E>,
     pub executor: TaskExecutor,
     pub spec: ChainSpec,
@@ -60,6 +64,22 @@ impl<E: EthSpec> MockExecutionLayer<E> {
             kzg,
         );
 
+        // The secret file must outlive the construction of the execution layer.
+        let (config, _secret_file) = Self::config(&server);
+        let el =
+            ExecutionLayer::from_config(config, executor.clone(), executor.log().clone()).unwrap();
+
+        Self {
+            server,
+            verifier: None,
+            secondary: None,
+            el,
+            executor,
+            spec,
+        }
+    }
+
+    fn config(server: &MockServer<E>) -> (Config, NamedTempFile) {
         let url = SensitiveUrl::parse(&server.url()).unwrap();
         let file = NamedTempFile::new().unwrap();
 
@@ -72,15 +92,71 @@ impl<E: EthSpec> MockExecutionLayer<E> {
             suggested_fee_recipient: Some(Address::repeat_byte(42)),
             ..Default::default()
         };
-        let el =
-            ExecutionLayer::from_config(config, executor.clone(), executor.log().clone()).unwrap();
+        (config, file)
+    }
 
-        Self {
-            server,
-            el,
-            executor,
-            spec,
-        }
+    /// Creates another mock server with the same configuration as `self.server`.
+    fn additional_server(&self) -> MockServer<E> {
+        let handle = self.executor.handle().unwrap();
+        let (shanghai_time, cancun_time, prague_time) = {
+            let block_gen = self.server.execution_block_generator();
+            (
+                block_gen.shanghai_time,
+                block_gen.cancun_time,
+                block_gen.prague_time,
+            )
+        };
+        MockServer::new(
+            &handle,
+            JwtKey::from_slice(&DEFAULT_JWT_SECRET).unwrap(),
+            self.spec.terminal_total_difficulty,
+            DEFAULT_TERMINAL_BLOCK,
+            self.spec.terminal_block_hash,
+            shanghai_time,
+            cancun_time,
+            prague_time,
+            None,
+        )
+    }
+
+    /// Replaces `self.el` with an execution layer which also uses a second mock server as a
+    /// secondary engine.
+    pub fn with_secondary_engine(mut self) -> Self {
+        let secondary = self.additional_server();
+
+        let (config, _secret_file) = Self::config(&self.server);
+        let config = Config {
+            secondary_engines: vec![SecondaryEngineConfig {
+                execution_endpoint: SensitiveUrl::parse(&secondary.url()).unwrap(),
+                secret_file: None,
+            }],
+            ..config
+        };
+        self.el =
+            ExecutionLayer::from_config(config, self.executor.clone(), self.executor.log().clone())
+                .unwrap();
+        self.secondary = Some(secondary);
+        self
+    }
+
+    /// Replaces `self.el` with an execution layer which also uses a second mock server as its
+    /// verifier engine.
+    pub fn with_verifier(mut self) -> Self {
+        let verifier = self.additional_server();
+
+        let (config, _secret_file) = Self::config(&self.server);
+        let config = Config {
+            verifier_engine: Some(SecondaryEngineConfig {
+                execution_endpoint: SensitiveUrl::parse(&verifier.url()).unwrap(),
+                secret_file: None,
+            }),
+            ..config
+        };
+        self.el =
+            ExecutionLayer::from_config(config, self.executor.clone(), self.executor.log().clone())
+                .unwrap();
+        self.verifier = Some(verifier);
+        self
     }
 
     pub async fn produce_valid_execution_payload_on_head(self) -> Self {
@@ -5,9 +5,10 @@ use crate::engine_api::{
     auth::Auth, http::JSONRPC_VERSION, ExecutionBlock, PayloadStatusV1, PayloadStatusV1Status,
 };
 use crate::json_structures::JsonClientVersionV1;
+use crate::recording::EngineApiRecord;
 use bytes::Bytes;
 use execution_block_generator::PoWBlock;
-use handle_rpc::handle_rpc;
+use handle_rpc::{handle_rpc, GENERIC_ERROR_CODE};
 use kzg::Kzg;
 use logging::test_logger;
 use parking_lot::{Mutex, RwLock, RwLockWriteGuard};
@@ -20,6 +21,7 @@ use std::future::Future;
 use std::marker::PhantomData;
 use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
 use std::sync::{Arc, LazyLock};
+use std::time::Duration;
 use tokio::{runtime, sync::oneshot};
 use types::{EthSpec, ExecutionBlockHash, Uint256};
 use warp::{http::StatusCode, Filter, Rejection};
@@ -30,8 +32,11 @@ pub use execution_block_generator::{
     static_valid_tx, Block, ExecutionBlockGenerator,
 };
 pub use hook::Hook;
-pub use mock_builder::{MockBuilder, Operation};
+pub use mock_builder::{
+    serve as serve_mock_builder, Behaviour, MockBuilder, Operation, MOCK_BUILDER_TIMEOUT_DELAY,
+};
 pub use mock_execution_layer::MockExecutionLayer;
+pub use replay::ReplayResponses;
 
 pub const DEFAULT_TERMINAL_DIFFICULTY: u64 = 6400;
 pub const DEFAULT_TERMINAL_BLOCK: u64 = 64;
@@ -69,6 +74,7 @@ mod handle_rpc;
 mod hook;
 mod mock_builder;
 mod mock_execution_layer;
+mod replay;
 
 /// Configuration for the MockExecutionLayer.
 #[derive(Clone)]
@@ -155,6 +161,7 @@ impl<E: EthSpec> MockServer<E> {
             execution_block_generator: RwLock::new(execution_block_generator),
             previous_request: <_>::default(),
             preloaded_responses,
+            replay_responses: <_>::default(),
             static_new_payload_response: <_>::default(),
             static_forkchoice_updated_response: <_>::default(),
             static_get_block_by_hash_response: <_>::default(),
@@ -162,6 +169,7 @@ impl<E: EthSpec> MockServer<E> {
             new_payload_statuses: <_>::default(),
             fcu_payload_statuses: <_>::default(),
             syncing_response: Arc::new(Mutex::new(Ok(false))),
+            response_delay: <_>::default(),
             engine_capabilities: Arc::new(RwLock::new(DEFAULT_ENGINE_CAPABILITIES)),
             _phantom: PhantomData,
         });
@@ -248,6 +256,13 @@ impl<E: EthSpec> MockServer<E> {
         self.ctx.preloaded_responses.lock().push(response)
     }
 
+    /// Serve every request from `records` rather than the execution block generator.
+    ///
+    /// Requests which are not present in `records` receive an error response.
+    pub fn replay(&self, records: Vec<EngineApiRecord>) {
+        *self.ctx.replay_responses.lock() = Some(ReplayResponses::new(records));
+    }
+
     pub fn take_previous_request(&self) -> Option<serde_json::Value> {
         self.ctx.previous_request.lock().take()
     }
@@ -260,6 +275,11 @@ impl<E: EthSpec> MockServer<E> {
         *self.ctx.static_forkchoice_updated_response.lock() = Some(status);
     }
 
+    /// Delay the response to every JSON-RPC request by `delay`, simulating a slow engine.
+    pub fn set_response_delay(&self, delay: Duration) {
+        *self.ctx.response_delay.lock() = Some(delay);
+    }
+
     fn valid_status() -> PayloadStatusV1 {
         PayloadStatusV1 {
             status: PayloadStatusV1Status::Valid,
@@ -522,6 +542,8 @@ pub struct Context<E: EthSpec> {
     pub last_echo_request: Arc<RwLock<Option<Bytes>>>,
     pub execution_block_generator: RwLock<ExecutionBlockGenerator<E>>,
     pub preloaded_responses: Arc<Mutex<Vec<serde_json::Value>>>,
+    /// If set, requests are served exclusively from these recorded responses.
+    pub replay_responses: Arc<Mutex<Option<ReplayResponses>>>,
     pub previous_request: Arc<Mutex<Option<serde_json::Value>>>,
     pub static_new_payload_response: Arc<Mutex<Option<StaticNewPayloadResponse>>>,
     pub static_forkchoice_updated_response: Arc<Mutex<Option<PayloadStatusV1>>>,
@@ -537,6 +559,8 @@ pub struct Context<E: EthSpec> {
     pub fcu_payload_statuses:
         Arc<Mutex<HashMap<ExecutionBlockHash, Result<PayloadStatusV1, String>>>>,
     pub syncing_response: Arc<Mutex<Result<bool, String>>>,
+    /// If set, every JSON-RPC request is answered only after this delay.
+    pub response_delay: Arc<Mutex<Option<Duration>>>,
 
     pub engine_capabilities: Arc<RwLock<EngineCapabilities>>,
     pub _phantom: PhantomData<E>,
@@ -672,6 +696,10 @@ pub fn serve<E: EthSpec>(
                 .get("id")
                 .and_then(serde_json::Value::as_u64)
                 .ok_or_else(|| warp::reject::custom(MissingIdField))?;
+            let response_delay = *ctx.response_delay.lock();
+            if let Some(delay) = response_delay {
+                tokio::time::sleep(delay).await;
+            }
             let preloaded_response = {
                 let mut preloaded_responses = ctx.preloaded_responses.lock();
                 if !preloaded_responses.is_empty() {
@@ -681,7 +709,41 @@ pub fn serve<E: EthSpec>(
                 }
             };
 
-            let response = if let Some(preloaded_response) = preloaded_response {
+            let replay_response = ctx.replay_responses.lock().as_mut().map(|replay| {
+                let method = body
+                    .get("method")
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or_default();
+                let params = body.get("params").unwrap_or(&serde_json::Value::Null);
+                replay
+                    .next_response(method, params)
+                    .ok_or_else(|| method.to_string())
+            });
+
+            let response = if let Some(replay_response) = replay_response {
+                match replay_response {
+                    Ok(EngineApiRecord {
+                        error: Some(error), ..
+                    }) => json!({
+                        "id": id,
+                        "jsonrpc": JSONRPC_VERSION,
+                        "error": error
+                    }),
+                    Ok(record) => json!({
+                        "id": id,
+                        "jsonrpc": JSONRPC_VERSION,
+                        "result": record.result
+                    }),
+                    Err(method) => json!({
+                        "id": id,
+                        "jsonrpc": JSONRPC_VERSION,
+                        "error": {
+                            "code": GENERIC_ERROR_CODE,
+                            "message": format!("no recorded response for {}", method)
+                        }
+                    }),
+                }
+            } else if let Some(preloaded_response) = preloaded_response {
                 preloaded_response
             } else {
                 match handle_rpc(body, ctx).await {
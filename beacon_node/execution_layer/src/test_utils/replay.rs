@@ -0,0 +1,50 @@
+use crate::recording::EngineApiRecord;
+use std::collections::{HashMap, VecDeque};
+
+/// Engine API responses captured by an `EngineApiRecorder`, served back by the `MockServer`.
+///
+/// Requests are matched against the recording by method and params. Matching responses are
+/// served in the order they were recorded, with the final response repeated once the others are
+/// exhausted so that polling requests such as `eth_syncing` keep succeeding.
+#[derive(Default)]
+pub struct ReplayResponses {
+    responses: HashMap<(String, String), VecDeque<EngineApiRecord>>,
+}
+
+impl ReplayResponses {
+    pub fn new(records: Vec<EngineApiRecord>) -> Self {
+        let mut responses: HashMap<_, VecDeque<_>> = HashMap::new();
+        for record in records {
+            responses
+                .entry((record.method.clone(), record.params.to_string()))
+                .or_default()
+                .push_back(record);
+        }
+        Self { responses }
+    }
+
+    /// The number of distinct requests in the recording.
+    pub fn len(&self) -> usize {
+        self.responses.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.responses.is_empty()
+    }
+
+    /// Returns the next recorded response to `method` with `params`, if any.
+    pub fn next_response(
+        &mut self,
+        method: &str,
+        params: &serde_json::Value,
+    ) -> Option<EngineApiRecord> {
+        let queue = self
+            .responses
+            .get_mut(&(method.to_string(), params.to_string()))?;
+        if queue.len() > 1 {
+            queue.pop_front()
+        } else {
+            queue.front().cloned()
+        }
+    }
+}
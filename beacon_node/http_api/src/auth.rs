@@ -0,0 +1,234 @@
+//! Token-based role access control for the HTTP API.
+//!
+//! Operators may provide a file of API tokens, each granting one of the roles in `Role`. Every
+//! request is classified by `required_role` and is only routed if the token in its
+//! `Authorization` header grants at least that role. Requests without a token are granted the
+//! configured public role, if any, which allows the read endpoints to be exposed safely while
+//! keeping block publishing and node management behind a token.
+use crate::metrics;
+use ring::constant_time::verify_slices_are_equal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use warp::http::Method;
+use warp::Filter;
+
+/// The roles which may be granted to an API token, in increasing order of privilege.
+///
+/// Each role may access every endpoint available to the roles before it.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Role {
+    /// Endpoints which read the chain and node state.
+    ReadOnly,
+    /// Endpoints used by validator clients: duties, block production and publishing operations.
+    Validator,
+    /// Node management: `/lighthouse/*` mutations, database, analysis, logs and debug states.
+    Admin,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Role::ReadOnly => write!(f, "read-only"),
+            Role::Validator => write!(f, "validator"),
+            Role::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+impl FromStr for Role {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "read-only" => Ok(Role::ReadOnly),
+            "validator" => Ok(Role::Validator),
+            "admin" => Ok(Role::Admin),
+            other => Err(format!(
+                "unknown role {}, expected read-only, validator or admin",
+                other
+            )),
+        }
+    }
+}
+
+/// An entry in the API token file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiToken {
+    pub token: String,
+    pub role: Role,
+}
+
+/// The tokens accepted by the HTTP API and the role granted to requests without a token.
+#[derive(Debug, Clone)]
+pub struct ApiAuth {
+    tokens: Vec<ApiToken>,
+    public_role: Option<Role>,
+}
+
+impl ApiAuth {
+    pub fn new(tokens: Vec<ApiToken>, public_role: Option<Role>) -> Result<Self, String> {
+        let mut seen = HashSet::with_capacity(tokens.len());
+        for ApiToken { token, .. } in &tokens {
+            if token.is_empty() || token.chars().any(char::is_whitespace) {
+                return Err("API tokens must be non-empty and contain no whitespace".to_string());
+            }
+            if !seen.insert(token.as_str()) {
+                return Err("API token file contains a duplicate token".to_string());
+            }
+        }
+
+        Ok(Self {
+            tokens,
+            public_role,
+        })
+    }
+
+    /// Load the tokens from a YAML file containing a list of `ApiToken`s.
+    pub fn from_file(path: &Path, public_role: Option<Role>) -> Result<Self, String> {
+        let bytes = fs::read(path)
+            .map_err(|e| format!("Unable to read API token file {}: {:?}", path.display(), e))?;
+        let tokens: Vec<ApiToken> = serde_yaml::from_slice(&bytes)
+            .map_err(|e| format!("Unable to parse API token file {}: {:?}", path.display(), e))?;
+        Self::new(tokens, public_role)
+    }
+
+    /// Check that a request to `path` with `method` and the given `Authorization` header is
+    /// permitted.
+    pub fn authorize(
+        &self,
+        method: &Method,
+        path: &str,
+        authorization: Option<&str>,
+    ) -> Result<(), AuthError> {
+        let required = required_role(method, path);
+        let granted = match authorization {
+            Some(header) => {
+                let token = token_from_header(header).ok_or(AuthError::UnknownToken)?;
+                self.token_role(token).ok_or(AuthError::UnknownToken)?
+            }
+            None => self.public_role.ok_or(AuthError::MissingToken)?,
+        };
+
+        if granted >= required {
+            Ok(())
+        } else {
+            Err(AuthError::InsufficientRole { granted, required })
+        }
+    }
+
+    /// The role granted by `token`, if it is a known token.
+    ///
+    /// Every known token is compared in constant time, so that the time taken does not reveal how
+    /// much of `token` matches a known token.
+    pub fn token_role(&self, token: &str) -> Option<Role> {
+        self.tokens.iter().fold(None, |granted, known| {
+            let matches = verify_slices_are_equal(known.token.as_bytes(), token.as_bytes()).is_ok();
+            if matches {
+                Some(known.role)
+            } else {
+                granted
+            }
+        })
+    }
+
+    /// Returns a `warp` filter which rejects requests that are not permitted by `self`.
+    pub fn filter(self) -> warp::filters::BoxedFilter<()> {
+        warp::method()
+            .and(warp::path::full())
+            .and(warp::header::optional::<String>("Authorization"))
+            .and_then(
+                move |method: Method, path: warp::path::FullPath, header: Option<String>| {
+                    let result = self.authorize(&method, path.as_str(), header.as_deref());
+                    async move {
+                        result.map_err(|e| {
+                            metrics::inc_counter_vec(
+                                &metrics::HTTP_API_AUTH_FAILURES_TOTAL,
+                                &[e.reason()],
+                            );
+                            match e {
+                                AuthError::MissingToken => warp_utils::reject::missing_auth(),
+                                e => warp_utils::reject::invalid_auth(e.to_string()),
+                            }
+                        })
+                    }
+                },
+            )
+            .untuple_one()
+            .boxed()
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum AuthError {
+    MissingToken,
+    UnknownToken,
+    InsufficientRole { granted: Role, required: Role },
+}
+
+impl AuthError {
+    /// The label used for this error in `HTTP_API_AUTH_FAILURES_TOTAL`.
+    pub fn reason(&self) -> &'static str {
+        match self {
+            AuthError::MissingToken => "missing_token",
+            AuthError::UnknownToken => "unknown_token",
+            AuthError::InsufficientRole { .. } => "insufficient_role",
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::MissingToken => write!(f, "missing API token"),
+            // Never include the token itself, since it may be valid for another node.
+            AuthError::UnknownToken => write!(f, "unknown API token"),
+            AuthError::InsufficientRole { granted, required } => write!(
+                f,
+                "the {} role is required but only {} was granted",
+                required, granted
+            ),
+        }
+    }
+}
+
+/// Extract the token from an `Authorization` header.
+///
+/// As with the validator client, the token is also accepted in a basic authentication style.
+pub fn token_from_header(header: &str) -> Option<&str> {
+    header
+        .strip_prefix("Bearer ")
+        .or_else(|| header.strip_prefix("Basic "))
+}
+
+/// The least privileged role which may make a request to `path` with `method`.
+pub fn required_role(method: &Method, path: &str) -> Role {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+    let is_get = method == Method::GET;
+
+    match segments.as_slice() {
+        // Used by validator clients for doppelganger protection.
+        ["lighthouse", "liveness", ..] => Role::Validator,
+        // The UI endpoints only read validator data, even though some are `POST`s.
+        ["lighthouse", "ui", ..] => Role::ReadOnly,
+        // The GraphQL schema has no mutations.
+        ["lighthouse", "graphql"] => Role::ReadOnly,
+        ["lighthouse", "database" | "analysis" | "logs" | "op_pool", ..] => Role::Admin,
+        ["lighthouse", ..] if !is_get => Role::Admin,
+        ["lighthouse", ..] => Role::ReadOnly,
+        // Full states are expensive to load, especially historic ones.
+        ["eth", _, "debug", "beacon", "states", ..] => Role::Admin,
+        ["eth", _, "validator", ..] => Role::Validator,
+        ["eth", _, "beacon", "blocks" | "blinded_blocks"] if !is_get => Role::Validator,
+        ["eth", _, "beacon", "pool", ..] if !is_get => Role::Validator,
+        _ => Role::ReadOnly,
+    }
+}
@@ -1,7 +1,9 @@
 use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes};
 use eth2::lighthouse::{
-    BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
+    BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, StrategyPackingEfficiency,
+    UniqueAttestation,
 };
+use operation_pool::{MaxCover, PackingStrategy, PackingStrategyKind, DEFAULT_PACKING_TIME_BUDGET};
 use parking_lot::Mutex;
 use state_processing::{
     per_epoch_processing::EpochProcessingSummary, BlockReplayError, BlockReplayer,
@@ -9,9 +11,11 @@ use state_processing::{
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
 use types::{
     AttestationRef, BeaconCommittee, BeaconState, BeaconStateError, BlindedPayload, ChainSpec,
     Epoch, EthSpec, Hash256, OwnedBeaconCommittee, RelativeEpoch, SignedBeaconBlock, Slot,
+    Unsigned,
 };
 use warp_utils::reject::{beacon_chain_error, custom_bad_request, custom_server_error};
 
@@ -53,12 +57,54 @@ impl CommitteeStore {
     }
 }
 
+/// An aggregate attestation seen on chain, for re-packing with a `PackingStrategy`.
+///
+/// Every attestation is given the same weight, since we are only interested in how many new
+/// attestations each strategy includes.
+#[derive(Clone)]
+struct AggregateCover {
+    slot: Slot,
+    fresh_attestations: HashSet<UniqueAttestation>,
+}
+
+impl MaxCover for AggregateCover {
+    type Object = ();
+    type Intermediate = Self;
+    type Set = HashSet<UniqueAttestation>;
+
+    fn intermediate(&self) -> &Self {
+        self
+    }
+
+    fn convert_to_object(_: &Self) {}
+
+    fn covering_set(&self) -> &HashSet<UniqueAttestation> {
+        &self.fresh_attestations
+    }
+
+    fn update_covering_set(&mut self, _: &Self, covered: &HashSet<UniqueAttestation>) {
+        self.fresh_attestations.retain(|x| !covered.contains(x));
+    }
+
+    fn score(&self) -> usize {
+        self.fresh_attestations.len()
+    }
+}
+
+fn voted_positions(bits: impl Iterator<Item = bool>) -> impl Iterator<Item = usize> {
+    bits.enumerate()
+        .filter_map(|(position, voted)| voted.then_some(position))
+}
+
 struct PackingEfficiencyHandler<E: EthSpec> {
     current_slot: Slot,
     current_epoch: Epoch,
     prior_skip_slots: u64,
     available_attestations: HashSet<UniqueAttestation>,
     included_attestations: HashMap<UniqueAttestation, u64>,
+    /// Aggregates included in recent blocks, which stand in for the contents of the op pool when
+    /// comparing packing strategies.
+    seen_aggregates: Vec<AggregateCover>,
     committee_store: CommitteeStore,
     _phantom: PhantomData<E>,
 }
@@ -75,6 +121,7 @@ impl<E: EthSpec> PackingEfficiencyHandler<E> {
             prior_skip_slots: 0,
             available_attestations: HashSet::new(),
             included_attestations: HashMap::new(),
+            seen_aggregates: Vec::new(),
             committee_store: CommitteeStore::new(),
             _phantom: PhantomData,
         };
@@ -101,6 +148,69 @@ impl<E: EthSpec> PackingEfficiencyHandler<E> {
         let slot = self.current_slot;
         self.available_attestations
             .retain(|x| x.slot >= (slot.as_u64().saturating_sub(E::slots_per_epoch())));
+        self.seen_aggregates
+            .retain(|x| x.slot >= (slot.as_u64().saturating_sub(E::slots_per_epoch())));
+    }
+
+    /// Record the aggregates in `block`, so that they can be re-packed into this and later blocks.
+    fn record_aggregates(&mut self, block: &SignedBeaconBlock<E, BlindedPayload<E>>) {
+        for attestation in block.message().body().attestations() {
+            let (data, positions) = match attestation {
+                AttestationRef::Base(attn) => {
+                    (&attn.data, voted_positions(attn.aggregation_bits.iter()))
+                }
+                AttestationRef::Electra(attn) => {
+                    (&attn.data, voted_positions(attn.aggregation_bits.iter()))
+                }
+            };
+            let fresh_attestations = positions
+                .map(|position| UniqueAttestation {
+                    slot: data.slot,
+                    committee_index: data.index,
+                    committee_position: position,
+                })
+                .collect::<HashSet<_>>();
+            self.seen_aggregates.push(AggregateCover {
+                slot: data.slot,
+                fresh_attestations,
+            });
+        }
+    }
+
+    /// Re-pack the block at `slot` from the aggregates seen on chain so far, returning the number
+    /// of new attestations each of `strategies` would have included.
+    fn compare_strategies(
+        &self,
+        slot: Slot,
+        limit: usize,
+        strategies: &[PackingStrategyKind],
+    ) -> Vec<StrategyPackingEfficiency> {
+        let candidates = self
+            .seen_aggregates
+            .iter()
+            .filter(|aggregate| aggregate.slot < slot)
+            .map(|aggregate| AggregateCover {
+                slot: aggregate.slot,
+                fresh_attestations: aggregate
+                    .fresh_attestations
+                    .iter()
+                    .filter(|x| !self.included_attestations.contains_key(x))
+                    .cloned()
+                    .collect(),
+            })
+            .collect::<Vec<_>>();
+
+        strategies
+            .iter()
+            .map(|strategy| StrategyPackingEfficiency {
+                strategy: strategy.name().to_string(),
+                included_attestations: strategy
+                    .pack(candidates.clone(), limit, "block_packing_efficiency")
+                    .iter()
+                    .map(MaxCover::score)
+                    .sum(),
+            })
+            .collect()
     }
 
     fn apply_block(
@@ -242,6 +352,17 @@ pub fn get_block_packing_efficiency<T: BeaconChainTypes>(
 ) -> Result<Vec<BlockPackingEfficiency>, warp::Rejection> {
     let spec = &chain.spec;
 
+    let strategies = if query.compare_strategies {
+        PackingStrategyKind::all(
+            query
+                .packing_time_budget_ms
+                .map_or(DEFAULT_PACKING_TIME_BUDGET, Duration::from_millis),
+        )
+        .to_vec()
+    } else {
+        vec![]
+    };
+
     let start_epoch = query.start_epoch;
     let start_slot = start_epoch.start_slot(T::EthSpec::slots_per_epoch());
     let prior_slot = start_slot - 1;
@@ -350,6 +471,20 @@ pub fn get_block_packing_efficiency<T: BeaconChainTypes>(
         // does not exceed the maximum possible amount given the length of available committees.
         let available_count = handler.lock().available_attestations.len();
 
+        // Re-pack the block with each strategy before its attestations are marked as included.
+        let strategies = if strategies.is_empty() {
+            vec![]
+        } else {
+            let limit = if block.fork_name_unchecked().electra_enabled() {
+                <T::EthSpec as EthSpec>::MaxAttestationsElectra::to_usize()
+            } else {
+                <T::EthSpec as EthSpec>::MaxAttestations::to_usize()
+            };
+            let mut handler = handler.lock();
+            handler.record_aggregates(block);
+            handler.compare_strategies(slot, limit, &strategies)
+        };
+
         // Get all attestations included in the block.
         let included = handler.lock().apply_block(block)?;
 
@@ -360,6 +495,7 @@ pub fn get_block_packing_efficiency<T: BeaconChainTypes>(
             available_attestations: available_count,
             included_attestations: included,
             prior_skip_slots: handler.lock().prior_skip_slots,
+            strategies,
         };
 
         // Write to response.
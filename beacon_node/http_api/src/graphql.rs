@@ -0,0 +1,472 @@
+//! A GraphQL interface to the beacon chain, served at `/lighthouse/graphql`.
+//!
+//! The schema exposes blocks, states, validators, committees and block rewards. Unlike the REST
+//! API, clients select only the fields they need and page through validators, so a query for the
+//! balances of a few thousand validators does not have to transfer the full validator list.
+//!
+//! Objects are loaded with the same `BlockId` and `StateId` accessors as the REST API. Queries are
+//! limited in depth and complexity, so that a single request cannot load an unbounded number of
+//! states. Lists are charged for each element they may return, and fields which load a state are
+//! charged `STATE_COMPLEXITY`.
+use crate::standard_block_rewards::compute_beacon_block_rewards;
+use crate::{BlockId, StateId};
+use async_graphql::{
+    Context, EmptyMutation, EmptySubscription, Error, Object, Result, Schema, SimpleObject,
+};
+use beacon_chain::{BeaconChain, BeaconChainTypes, WhenSlotSkipped};
+use eth2::types::{StateId as CoreStateId, ValidatorId, ValidatorStatus};
+use std::collections::HashSet;
+use std::marker::PhantomData;
+use std::str::FromStr;
+use std::sync::Arc;
+use types::{
+    BeaconState, CommitteeCache, Epoch, EthSpec, ExecPayload, Hash256, RelativeEpoch,
+    SignedBlindedBeaconBlock, Slot,
+};
+use warp::reply::{Reply, Response};
+use warp_utils::reject::{custom_bad_request, CustomNotFound};
+
+/// The maximum nesting of fields in a query, e.g. `block { state { validators { nodes } } }`.
+const MAX_DEPTH: usize = 8;
+/// The maximum complexity of a query, where each field costs 1 unless noted otherwise.
+const MAX_COMPLEXITY: usize = 10_000;
+/// The complexity of a field which loads a state, such as `state` or `rewards`.
+const STATE_COMPLEXITY: usize = 1_000;
+/// The maximum number of blocks returned by `blocks`.
+const MAX_BLOCKS: u64 = 64;
+/// The complexity of each validator in the cheapest page of validators, `nodes { index }`.
+const MIN_VALIDATOR_COMPLEXITY: usize = 2;
+/// The default and maximum number of validators returned by `validators`. The maximum is the
+/// largest page of the validators of a state which a query can afford.
+const DEFAULT_VALIDATORS_LIMIT: u64 = 100;
+const MAX_VALIDATORS_LIMIT: u64 =
+    ((MAX_COMPLEXITY - STATE_COMPLEXITY) / MIN_VALIDATOR_COMPLEXITY) as u64;
+
+pub type GraphQLSchema<T> = Schema<QueryRoot<T>, EmptyMutation, EmptySubscription>;
+
+pub fn schema<T: BeaconChainTypes>() -> GraphQLSchema<T> {
+    Schema::build(QueryRoot(PhantomData), EmptyMutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .finish()
+}
+
+/// Execute the JSON-encoded GraphQL request in `body` against `chain`.
+pub fn execute<T: BeaconChainTypes>(
+    schema: &GraphQLSchema<T>,
+    chain: Arc<BeaconChain<T>>,
+    body: &[u8],
+) -> std::result::Result<Response, warp::Rejection> {
+    let request: async_graphql::Request = serde_json::from_slice(body)
+        .map_err(|e| custom_bad_request(format!("invalid GraphQL request: {}", e)))?;
+
+    // The resolvers read from the database synchronously, so the query is run to completion on
+    // this blocking thread rather than on the async executor.
+    let response = futures::executor::block_on(schema.execute(request.data(chain)));
+    Ok(warp::reply::json(&response).into_response())
+}
+
+fn chain<'a, T: BeaconChainTypes>(ctx: &Context<'a>) -> Result<&'a Arc<BeaconChain<T>>> {
+    ctx.data::<Arc<BeaconChain<T>>>()
+}
+
+/// Convert the result of a REST API accessor, mapping "not found" to `None`.
+fn optional<U>(result: std::result::Result<U, warp::Rejection>) -> Result<Option<U>> {
+    match result {
+        Ok(value) => Ok(Some(value)),
+        Err(e) if e.is_not_found() || e.find::<CustomNotFound>().is_some() => Ok(None),
+        Err(e) => Err(Error::new(format!("{:?}", e))),
+    }
+}
+
+fn root_string(root: Hash256) -> String {
+    format!("{:?}", root)
+}
+
+pub struct QueryRoot<T>(PhantomData<T>);
+
+#[Object(name = "Query")]
+impl<T: BeaconChainTypes> QueryRoot<T> {
+    /// The block identified by `head`, `genesis`, `finalized`, `justified`, a slot or a root.
+    async fn block(&self, ctx: &Context<'_>, id: String) -> Result<Option<BlockObject<T>>> {
+        let chain = chain::<T>(ctx)?;
+        let block_id = BlockId::from_str(&id)?;
+        let Some((block, _, finalized)) = optional(block_id.blinded_block(chain))? else {
+            return Ok(None);
+        };
+        Ok(Some(BlockObject::new(block, finalized)))
+    }
+
+    /// The canonical blocks in the `limit` slots starting at `start_slot`, skipping empty slots.
+    #[graphql(complexity = "(limit as usize).saturating_mul(child_complexity)")]
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        start_slot: u64,
+        #[graphql(default = 32)] limit: u64,
+    ) -> Result<Vec<BlockObject<T>>> {
+        if limit > MAX_BLOCKS {
+            return Err(Error::new(format!("limit must be at most {}", MAX_BLOCKS)));
+        }
+
+        let chain = chain::<T>(ctx)?;
+        let mut blocks = vec![];
+        for slot in start_slot..start_slot.saturating_add(limit) {
+            let block_root = chain
+                .block_root_at_slot(Slot::new(slot), WhenSlotSkipped::None)
+                .map_err(|e| Error::new(format!("{:?}", e)))?;
+            let Some(block_root) = block_root else {
+                continue;
+            };
+            if let Some((block, _, finalized)) =
+                optional(BlockId::from_root(block_root).blinded_block(chain))?
+            {
+                blocks.push(BlockObject::new(block, finalized));
+            }
+        }
+        Ok(blocks)
+    }
+
+    /// The state identified by `head`, `genesis`, `finalized`, `justified`, a slot or a root.
+    #[graphql(complexity = "STATE_COMPLEXITY + child_complexity")]
+    async fn state(&self, ctx: &Context<'_>, id: String) -> Result<Option<StateObject<T>>> {
+        let chain = chain::<T>(ctx)?;
+        StateObject::load(chain, StateId::from_str(&id)?)
+    }
+}
+
+pub struct BlockObject<T: BeaconChainTypes> {
+    block: Arc<SignedBlindedBeaconBlock<T::EthSpec>>,
+    finalized: bool,
+}
+
+impl<T: BeaconChainTypes> BlockObject<T> {
+    fn new(block: Arc<SignedBlindedBeaconBlock<T::EthSpec>>, finalized: bool) -> Self {
+        Self { block, finalized }
+    }
+}
+
+#[Object(name = "Block")]
+impl<T: BeaconChainTypes> BlockObject<T> {
+    async fn root(&self) -> String {
+        root_string(self.block.canonical_root())
+    }
+
+    async fn slot(&self) -> u64 {
+        self.block.slot().as_u64()
+    }
+
+    async fn proposer_index(&self) -> u64 {
+        self.block.message().proposer_index()
+    }
+
+    async fn parent_root(&self) -> String {
+        root_string(self.block.parent_root())
+    }
+
+    async fn state_root(&self) -> String {
+        root_string(self.block.state_root())
+    }
+
+    /// Whether the block is finalized.
+    async fn finalized(&self) -> bool {
+        self.finalized
+    }
+
+    async fn graffiti(&self) -> String {
+        self.block.message().body().graffiti().as_utf8_lossy()
+    }
+
+    async fn attestation_count(&self) -> usize {
+        self.block.message().body().attestations_len()
+    }
+
+    async fn deposit_count(&self) -> usize {
+        self.block.message().body().deposits().len()
+    }
+
+    async fn voluntary_exit_count(&self) -> usize {
+        self.block.message().body().voluntary_exits().len()
+    }
+
+    async fn blob_count(&self) -> usize {
+        self.block
+            .message()
+            .body()
+            .blob_kzg_commitments()
+            .map_or(0, |commitments| commitments.len())
+    }
+
+    /// The hash of the execution block, if the block has an execution payload.
+    async fn execution_block_hash(&self) -> Option<String> {
+        let payload = self.block.message().body().execution_payload().ok()?;
+        Some(format!("{:?}", payload.block_hash()))
+    }
+
+    /// The number of the execution block, if the block has an execution payload.
+    async fn execution_block_number(&self) -> Option<u64> {
+        let payload = self.block.message().body().execution_payload().ok()?;
+        Some(payload.block_number())
+    }
+
+    /// The rewards paid to the proposer of the block, in Gwei.
+    #[graphql(complexity = "STATE_COMPLEXITY + child_complexity")]
+    async fn rewards(&self, ctx: &Context<'_>) -> Result<BlockRewards> {
+        let chain = chain::<T>(ctx)?;
+        let block_id = BlockId::from_root(self.block.canonical_root());
+        let (rewards, _, _) = compute_beacon_block_rewards(chain.clone(), block_id)
+            .map_err(|e| Error::new(format!("{:?}", e)))?;
+        Ok(BlockRewards {
+            total: rewards.total,
+            attestations: rewards.attestations,
+            sync_aggregate: rewards.sync_aggregate,
+            proposer_slashings: rewards.proposer_slashings,
+            attester_slashings: rewards.attester_slashings,
+        })
+    }
+
+    /// The state after applying the block.
+    #[graphql(complexity = "STATE_COMPLEXITY + child_complexity")]
+    async fn state(&self, ctx: &Context<'_>) -> Result<Option<StateObject<T>>> {
+        let chain = chain::<T>(ctx)?;
+        StateObject::load(chain, StateId(CoreStateId::Root(self.block.state_root())))
+    }
+}
+
+/// The rewards paid to a block proposer, in Gwei.
+#[derive(SimpleObject)]
+pub struct BlockRewards {
+    total: u64,
+    attestations: u64,
+    sync_aggregate: u64,
+    proposer_slashings: u64,
+    attester_slashings: u64,
+}
+
+pub struct StateObject<T: BeaconChainTypes> {
+    state: Arc<BeaconState<T::EthSpec>>,
+    root: Hash256,
+    execution_optimistic: bool,
+    finalized: bool,
+}
+
+impl<T: BeaconChainTypes> StateObject<T> {
+    fn load(chain: &BeaconChain<T>, state_id: StateId) -> Result<Option<Self>> {
+        let Some((state, root, execution_optimistic, finalized)) =
+            optional(state_id.state_and_root(chain))?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(Self {
+            state: Arc::new(state),
+            root,
+            execution_optimistic,
+            finalized,
+        }))
+    }
+}
+
+#[Object(name = "State")]
+impl<T: BeaconChainTypes> StateObject<T> {
+    async fn slot(&self) -> u64 {
+        self.state.slot().as_u64()
+    }
+
+    async fn root(&self) -> String {
+        root_string(self.root)
+    }
+
+    /// Whether the state is from a block whose execution payload has not been verified by the
+    /// execution engine.
+    async fn execution_optimistic(&self) -> bool {
+        self.execution_optimistic
+    }
+
+    /// Whether the state is finalized.
+    async fn finalized(&self) -> bool {
+        self.finalized
+    }
+
+    async fn fork_version(&self) -> String {
+        format!("0x{}", hex::encode(self.state.fork().current_version))
+    }
+
+    async fn justified_epoch(&self) -> u64 {
+        self.state.current_justified_checkpoint().epoch.as_u64()
+    }
+
+    async fn finalized_epoch(&self) -> u64 {
+        self.state.finalized_checkpoint().epoch.as_u64()
+    }
+
+    async fn validator_count(&self) -> usize {
+        self.state.validators().len()
+    }
+
+    /// The validators of the state, filtered by `ids` (indices or public keys) and `statuses`
+    /// if given, in index order.
+    ///
+    /// At most `limit` validators are returned, starting from the `offset`th match.
+    #[graphql(complexity = "(limit as usize).saturating_mul(child_complexity)")]
+    async fn validators(
+        &self,
+        ctx: &Context<'_>,
+        ids: Option<Vec<String>>,
+        statuses: Option<Vec<String>>,
+        #[graphql(default = 0)] offset: u64,
+        #[graphql(default = 100)] limit: u64,
+    ) -> Result<ValidatorPage> {
+        if limit > MAX_VALIDATORS_LIMIT {
+            return Err(Error::new(format!(
+                "limit must be at most {}",
+                MAX_VALIDATORS_LIMIT
+            )));
+        }
+        let chain = chain::<T>(ctx)?;
+        let ids = ids
+            .map(|ids| {
+                ids.iter()
+                    .map(|id| ValidatorId::from_str(id))
+                    .collect::<std::result::Result<HashSet<_>, _>>()
+            })
+            .transpose()?;
+        let statuses = statuses
+            .map(|statuses| {
+                statuses
+                    .iter()
+                    .map(|status| ValidatorStatus::from_str(status))
+                    .collect::<std::result::Result<Vec<_>, _>>()
+            })
+            .transpose()?;
+
+        let epoch = self.state.current_epoch();
+        let far_future_epoch = chain.spec.far_future_epoch;
+        let mut matching = self
+            .state
+            .validators()
+            .iter()
+            .zip(self.state.balances().iter())
+            .enumerate()
+            .filter(|(index, (validator, _))| {
+                ids.as_ref().map_or(true, |ids| {
+                    ids.contains(&ValidatorId::PublicKey(validator.pubkey))
+                        || ids.contains(&ValidatorId::Index(*index as u64))
+                })
+            })
+            .filter_map(|(index, (validator, balance))| {
+                let status = ValidatorStatus::from_validator(validator, epoch, far_future_epoch);
+                let status_matches = statuses.as_ref().map_or(true, |statuses| {
+                    statuses.contains(&status) || statuses.contains(&status.superstatus())
+                });
+                status_matches.then(|| ValidatorObject {
+                    index: index as u64,
+                    pubkey: validator.pubkey.as_hex_string(),
+                    withdrawal_credentials: root_string(validator.withdrawal_credentials),
+                    balance: *balance,
+                    effective_balance: validator.effective_balance,
+                    status: status.to_string(),
+                    slashed: validator.slashed,
+                    activation_eligibility_epoch: validator.activation_eligibility_epoch.as_u64(),
+                    activation_epoch: validator.activation_epoch.as_u64(),
+                    exit_epoch: validator.exit_epoch.as_u64(),
+                    withdrawable_epoch: validator.withdrawable_epoch.as_u64(),
+                })
+            });
+
+        let nodes = matching
+            .by_ref()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect::<Vec<_>>();
+        let next_offset = matching
+            .next()
+            .map(|_| offset.saturating_add(nodes.len() as u64));
+        Ok(ValidatorPage { nodes, next_offset })
+    }
+
+    /// The beacon committees of `epoch` (the current epoch by default), optionally restricted to
+    /// a `slot` and committee `index`.
+    async fn committees(
+        &self,
+        ctx: &Context<'_>,
+        epoch: Option<u64>,
+        slot: Option<u64>,
+        index: Option<u64>,
+    ) -> Result<Vec<Committee>> {
+        let chain = chain::<T>(ctx)?;
+        let current_epoch = self.state.current_epoch();
+        let epoch = epoch.map_or(current_epoch, Epoch::new);
+        let committee_cache = match RelativeEpoch::from_epoch(current_epoch, epoch) {
+            Ok(relative_epoch) if self.state.committee_cache_is_initialized(relative_epoch) => {
+                self.state.committee_cache(relative_epoch).cloned()
+            }
+            _ => CommitteeCache::initialized(&self.state, epoch, &chain.spec),
+        }
+        .map_err(|e| Error::new(format!("{:?}", e)))?;
+
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let slots = match slot {
+            Some(slot) if Slot::new(slot).epoch(slots_per_epoch) != epoch => {
+                return Err(Error::new(format!("{} is not in epoch {}", slot, epoch)));
+            }
+            Some(slot) => vec![Slot::new(slot)],
+            None => epoch.slot_iter(slots_per_epoch).collect(),
+        };
+        let indices = index.map_or_else(
+            || (0..committee_cache.committees_per_slot()).collect(),
+            |index| vec![index],
+        );
+
+        let mut committees = Vec::with_capacity(slots.len() * indices.len());
+        for slot in slots {
+            for &index in &indices {
+                let committee = committee_cache
+                    .get_beacon_committee(slot, index)
+                    .ok_or_else(|| {
+                        Error::new(format!(
+                            "committee index {} does not exist in epoch {}",
+                            index, epoch
+                        ))
+                    })?;
+                committees.push(Committee {
+                    slot: slot.as_u64(),
+                    index,
+                    validators: committee.committee.iter().map(|i| *i as u64).collect(),
+                });
+            }
+        }
+        Ok(committees)
+    }
+}
+
+/// A page of validators. If there are more matching validators, `next_offset` is the `offset`
+/// of the next page.
+#[derive(SimpleObject)]
+pub struct ValidatorPage {
+    nodes: Vec<ValidatorObject>,
+    next_offset: Option<u64>,
+}
+
+/// A validator and its balance, in Gwei.
+#[derive(SimpleObject)]
+#[graphql(name = "Validator")]
+pub struct ValidatorObject {
+    index: u64,
+    pubkey: String,
+    withdrawal_credentials: String,
+    balance: u64,
+    effective_balance: u64,
+    status: String,
+    slashed: bool,
+    activation_eligibility_epoch: u64,
+    activation_epoch: u64,
+    exit_epoch: u64,
+    withdrawable_epoch: u64,
+}
+
+#[derive(SimpleObject)]
+pub struct Committee {
+    slot: u64,
+    index: u64,
+    validators: Vec<u64>,
+}
@@ -7,20 +7,26 @@
 
 mod attestation_performance;
 mod attester_duties;
+mod auth;
 mod block_id;
 mod block_packing_efficiency;
 mod block_rewards;
 mod build_block_contents;
 mod builder_states;
 mod database;
+mod graphql;
 mod light_client;
 mod metrics;
+mod optimal_block_rewards;
 mod produce_block;
 mod proposer_duties;
 mod publish_attestations;
 mod publish_blocks;
+mod rate_limit;
+mod response_cache;
 mod standard_block_rewards;
 mod state_id;
+mod streaming;
 mod sync_committee_rewards;
 mod sync_committees;
 mod task_spawner;
@@ -34,6 +40,8 @@ mod version;
 use crate::light_client::{get_light_client_bootstrap, get_light_client_updates};
 use crate::produce_block::{produce_blinded_block_v2, produce_block_v2, produce_block_v3};
 use crate::version::fork_versioned_response;
+use auth::ApiAuth;
+pub use auth::{ApiToken, Role};
 use beacon_chain::{
     attestation_verification::VerifiedAttestation, observed_operations::ObservationOutcome,
     validator_monitor::timestamp_now, AttestationError as AttnError, BeaconChain, BeaconChainError,
@@ -54,15 +62,18 @@ use lighthouse_network::{types::SyncState, EnrExt, NetworkGlobals, PeerId, Pubsu
 use lighthouse_version::version_with_platform;
 use logging::SSELoggingComponents;
 use network::{NetworkMessage, NetworkSenders, ValidatorSubscriptionMessage};
-use operation_pool::ReceivedPreCapella;
+use operation_pool::{PersistedOperationPool, PersistedOperationPoolV20, ReceivedPreCapella};
 use parking_lot::RwLock;
 pub use publish_blocks::{
     publish_blinded_block, publish_block, reconstruct_block, ProvenancedBlock,
 };
+use rate_limit::RateLimiter;
+pub use rate_limit::{RateLimitConfig, DEFAULT_RATE_LIMIT_BURST_FACTOR};
+use response_cache::ResponseCache;
 use serde::{Deserialize, Serialize};
 use slog::{crit, debug, error, info, warn, Logger};
 use slot_clock::SlotClock;
-use ssz::Encode;
+use ssz::{Decode, Encode};
 pub use state_id::StateId;
 use std::future::Future;
 use std::net::{IpAddr, Ipv4Addr, SocketAddr};
@@ -96,6 +107,7 @@ use version::{
 };
 use warp::http::StatusCode;
 use warp::hyper::Body;
+use warp::path::FullPath;
 use warp::sse::Event;
 use warp::Reply;
 use warp::{http::Response, Filter, Rejection};
@@ -116,6 +128,9 @@ type HttpServer = (SocketAddr, Pin<Box<dyn Future<Output = ()> + Send>>);
 /// Alias for readability.
 pub type ExecutionOptimistic = bool;
 
+/// The default maximum size of the response cache, in bytes.
+pub const DEFAULT_RESPONSE_CACHE_SIZE: usize = 64 * 1024 * 1024;
+
 /// Configuration used when serving the HTTP server over TLS.
 #[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
 pub struct TlsConfig {
@@ -153,6 +168,16 @@ pub struct Config {
     pub duplicate_block_status_code: StatusCode,
     pub enable_light_client_server: bool,
     pub target_peers: usize,
+    /// Path to a YAML file of API tokens and their roles. Access control is disabled if `None`.
+    pub auth_token_file: Option<PathBuf>,
+    /// The role granted to requests without a token when access control is enabled.
+    pub public_role: Option<Role>,
+    /// Per-client quotas on the cost of requests. Rate limiting is disabled if `None`.
+    pub rate_limit: Option<RateLimitConfig>,
+    /// Serve GraphQL queries at `/lighthouse/graphql`.
+    pub enable_graphql: bool,
+    /// The maximum size of the cache of responses about states, in bytes. Disabled if 0.
+    pub response_cache_size: usize,
 }
 
 impl Default for Config {
@@ -169,6 +194,11 @@ impl Default for Config {
             duplicate_block_status_code: StatusCode::ACCEPTED,
             enable_light_client_server: false,
             target_peers: 100,
+            auth_token_file: None,
+            public_role: None,
+            rate_limit: None,
+            enable_graphql: false,
+            response_cache_size: DEFAULT_RESPONSE_CACHE_SIZE,
         }
     }
 }
@@ -225,6 +255,22 @@ pub fn slog_logging(
     })
 }
 
+/// Creates the span of a request, which continues the trace of the client if it propagated one.
+pub fn request_span(info: warp::trace::Info) -> tracing::Span {
+    let span = tracing::info_span!(
+        "http_request",
+        method = %info.method(),
+        path = info.path(),
+    );
+    logging::set_remote_parent(
+        &span,
+        info.request_headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+    );
+    span
+}
+
 /// Creates a `warp` logging wrapper which we use for Prometheus metrics (not necessarily logging,
 /// per say).
 pub fn prometheus_metrics() -> warp::filters::log::Log<impl Fn(warp::filters::log::Info) + Clone> {
@@ -330,7 +376,7 @@ pub fn serve<T: BeaconChainTypes>(
     let cors_builder = {
         let builder = warp::cors()
             .allow_methods(vec!["GET", "POST"])
-            .allow_headers(vec!["Content-Type"]);
+            .allow_headers(vec!["Content-Type", "Authorization"]);
 
         warp_utils::cors::set_builder_origins(
             builder,
@@ -347,6 +393,76 @@ pub fn serve<T: BeaconChainTypes>(
         ));
     }
 
+    // Configure access control. Requests are authorized before routing, so that no route can be
+    // reached without the role it requires.
+    let auth = config
+        .auth_token_file
+        .as_ref()
+        .map(|path| ApiAuth::from_file(path, config.public_role))
+        .transpose()?;
+    let auth_filter = match &auth {
+        Some(auth) => {
+            let public_role = config
+                .public_role
+                .map_or_else(|| "none".to_string(), |role| role.to_string());
+            info!(
+                log,
+                "HTTP API access control enabled";
+                "public_role" => public_role,
+            );
+            auth.clone().filter()
+        }
+        None => warp::any().boxed(),
+    };
+
+    // Configure rate limiting. This is applied after access control so that clients can be
+    // identified by their authenticated token, and requests with validator or admin tokens can be
+    // exempted.
+    let rate_limit_filter = match config.rate_limit {
+        Some(rate_limit) => {
+            let limiter = RateLimiter::new(rate_limit, auth)?;
+            info!(
+                log,
+                "HTTP API rate limiting enabled";
+                "cost_per_second" => rate_limit.cost_per_second,
+                "burst" => rate_limit.burst,
+            );
+            Arc::new(limiter).filter()
+        }
+        None => warp::any().boxed(),
+    };
+
+    // Create a cache of responses about states, and a `warp` filter which provides it along with a
+    // key for the request.
+    let response_cache = (config.response_cache_size > 0)
+        .then(|| Arc::new(ResponseCache::new(config.response_cache_size)));
+
+    // Remove cached responses about non-finalized states once they can no longer be served.
+    let cache_invalidation: Pin<Box<dyn Future<Output = ()> + Send>> = match (
+        response_cache.clone(),
+        ctx.chain
+            .as_ref()
+            .and_then(|chain| chain.event_handler.as_ref()),
+    ) {
+        (Some(cache), Some(event_handler)) => Box::pin(response_cache::invalidate_on_head_change(
+            cache,
+            event_handler.subscribe_head(),
+            event_handler.subscribe_reorgs(),
+        )),
+        _ => Box::pin(futures::future::pending()),
+    };
+    let inner_response_cache = response_cache.clone();
+    let response_cache_filter = warp::any()
+        .map(move || inner_response_cache.clone())
+        .and(warp::path::full())
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .map(
+            |response_cache: Option<Arc<ResponseCache>>, path: FullPath, query: String| {
+                (response_cache, format!("{}?{}", path.as_str(), query))
+            },
+        )
+        .untuple_one();
+
     // Create a filter that extracts the endpoint version.
     let any_version = warp::path(API_PREFIX).and(warp::path::param::<EndpointVersion>().or_else(
         |_| async move {
@@ -666,17 +782,22 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validator_balances"))
         .and(warp::path::end())
         .and(multi_key_query::<api_types::ValidatorBalancesQuery>())
+        .and(response_cache_filter.clone())
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query_res: Result<api_types::ValidatorBalancesQuery, warp::Rejection>| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query_res: Result<api_types::ValidatorBalancesQuery, warp::Rejection>,
+             response_cache: Option<Arc<ResponseCache>>,
+             request: String| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
                     let query = query_res?;
                     crate::validators::get_beacon_state_validator_balances(
                         state_id,
                         chain,
-                        query.id.as_deref(),
+                        response_cache.as_ref(),
+                        request,
+                        query.id,
                     )
                 })
             },
@@ -688,16 +809,22 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validator_balances"))
         .and(warp::path::end())
         .and(warp_utils::json::json())
+        .and(response_cache_filter.clone())
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: ValidatorBalancesRequestBody| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
+             query: ValidatorBalancesRequestBody,
+             response_cache: Option<Arc<ResponseCache>>,
+             path: String| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let request = format!("{} {}", path, serde_json::json!(query.ids));
                     crate::validators::get_beacon_state_validator_balances(
                         state_id,
                         chain,
-                        Some(&query.ids),
+                        response_cache.as_ref(),
+                        request,
+                        Some(query.ids),
                     )
                 })
             },
@@ -709,11 +836,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validators"))
         .and(warp::path::end())
         .and(multi_key_query::<api_types::ValidatorsQuery>())
+        .and(response_cache_filter.clone())
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query_res: Result<api_types::ValidatorsQuery, warp::Rejection>| {
+             query_res: Result<api_types::ValidatorsQuery, warp::Rejection>,
+             response_cache: Option<Arc<ResponseCache>>,
+             request: String| {
                 // Prioritise requests for validators at the head. These should be fast to service
                 // and could be required by the validator client.
                 let priority = if let StateId(eth2::types::StateId::Head) = state_id {
@@ -721,13 +851,15 @@ pub fn serve<T: BeaconChainTypes>(
                 } else {
                     Priority::P1
                 };
-                task_spawner.blocking_json_task(priority, move || {
+                task_spawner.blocking_response_task(priority, move || {
                     let query = query_res?;
                     crate::validators::get_beacon_state_validators(
                         state_id,
                         chain,
-                        &query.id,
-                        &query.status,
+                        response_cache.as_ref(),
+                        request,
+                        query.id,
+                        query.status,
                     )
                 })
             },
@@ -739,11 +871,14 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("validators"))
         .and(warp::path::end())
         .and(warp_utils::json::json())
+        .and(response_cache_filter.clone())
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: ValidatorsRequestBody| {
+             query: ValidatorsRequestBody,
+             response_cache: Option<Arc<ResponseCache>>,
+             path: String| {
                 // Prioritise requests for validators at the head. These should be fast to service
                 // and could be required by the validator client.
                 let priority = if let StateId(eth2::types::StateId::Head) = state_id {
@@ -751,12 +886,15 @@ pub fn serve<T: BeaconChainTypes>(
                 } else {
                     Priority::P1
                 };
-                task_spawner.blocking_json_task(priority, move || {
+                task_spawner.blocking_response_task(priority, move || {
+                    let request = format!("{} {}", path, serde_json::json!(query));
                     crate::validators::get_beacon_state_validators(
                         state_id,
                         chain,
-                        &query.ids,
-                        &query.statuses,
+                        response_cache.as_ref(),
+                        request,
+                        query.ids,
+                        query.statuses,
                     )
                 })
             },
@@ -847,55 +985,70 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("committees"))
         .and(warp::query::<api_types::CommitteesQuery>())
         .and(warp::path::end())
+        .and(response_cache_filter.clone())
         .then(
             |state_id: StateId,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             query: api_types::CommitteesQuery| {
-                task_spawner.blocking_json_task(Priority::P1, move || {
-                    let (data, execution_optimistic, finalized) = state_id
-                        .map_state_and_execution_optimistic_and_finalized(
-                            &chain,
-                            |state, execution_optimistic, finalized| {
-                                let current_epoch = state.current_epoch();
-                                let epoch = query.epoch.unwrap_or(current_epoch);
-
-                                // Attempt to obtain the committee_cache from the beacon chain
-                                let decision_slot = (epoch.saturating_sub(2u64))
-                                    .end_slot(T::EthSpec::slots_per_epoch());
-                                // Find the decision block and skip to another method on any kind
-                                // of failure
-                                let shuffling_id = if let Ok(Some(shuffling_decision_block)) =
-                                    chain.block_root_at_slot(decision_slot, WhenSlotSkipped::Prev)
-                                {
-                                    Some(AttestationShufflingId {
-                                        shuffling_epoch: epoch,
-                                        shuffling_decision_block,
-                                    })
-                                } else {
-                                    None
-                                };
-
-                                // Attempt to read from the chain cache if there exists a
-                                // shuffling_id
-                                let maybe_cached_shuffling = if let Some(shuffling_id) =
-                                    shuffling_id.as_ref()
-                                {
-                                    chain
-                                        .shuffling_cache
-                                        .try_write_for(std::time::Duration::from_secs(1))
-                                        .and_then(|mut cache_write| cache_write.get(shuffling_id))
-                                        .and_then(|cache_item| cache_item.wait().ok())
-                                } else {
-                                    None
-                                };
-
-                                let committee_cache = if let Some(shuffling) =
-                                    maybe_cached_shuffling
-                                {
-                                    shuffling
-                                } else {
-                                    let possibly_built_cache =
+             query: api_types::CommitteesQuery,
+             response_cache: Option<Arc<ResponseCache>>,
+             request: String| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    response_cache::cached_response(
+                        response_cache.as_ref(),
+                        &chain,
+                        &state_id,
+                        request,
+                        |capture| {
+                            let (data, state_root, execution_optimistic, finalized) = state_id
+                                .map_state_and_root(
+                                    &chain,
+                                    |state, state_root, execution_optimistic, finalized| {
+                                        let current_epoch = state.current_epoch();
+                                        let epoch = query.epoch.unwrap_or(current_epoch);
+
+                                        // Attempt to obtain the committee_cache from the beacon
+                                        // chain
+                                        let decision_slot = (epoch.saturating_sub(2u64))
+                                            .end_slot(T::EthSpec::slots_per_epoch());
+                                        // Find the decision block and skip to another method on any
+                                        // kind of failure
+                                        let shuffling_id =
+                                            if let Ok(Some(shuffling_decision_block)) = chain
+                                                .block_root_at_slot(
+                                                    decision_slot,
+                                                    WhenSlotSkipped::Prev,
+                                                )
+                                            {
+                                                Some(AttestationShufflingId {
+                                                    shuffling_epoch: epoch,
+                                                    shuffling_decision_block,
+                                                })
+                                            } else {
+                                                None
+                                            };
+
+                                        // Attempt to read from the chain cache if there exists a
+                                        // shuffling_id
+                                        let maybe_cached_shuffling = if let Some(shuffling_id) =
+                                            shuffling_id.as_ref()
+                                        {
+                                            chain
+                                                .shuffling_cache
+                                                .try_write_for(std::time::Duration::from_secs(1))
+                                                .and_then(|mut cache_write| {
+                                                    cache_write.get(shuffling_id)
+                                                })
+                                                .and_then(|cache_item| cache_item.wait().ok())
+                                        } else {
+                                            None
+                                        };
+
+                                        let committee_cache =
+                                            if let Some(shuffling) = maybe_cached_shuffling {
+                                                shuffling
+                                            } else {
+                                                let possibly_built_cache =
                                         match RelativeEpoch::from_epoch(current_epoch, epoch) {
                                             Ok(relative_epoch)
                                                 if state.committee_cache_is_initialized(
@@ -944,9 +1097,9 @@ pub fn serve<T: BeaconChainTypes>(
                                             }
                                         })?;
 
-                                    // Attempt to write to the beacon cache (only if the cache
-                                    // size is not the default value).
-                                    if chain.config.shuffling_cache_size
+                                                // Attempt to write to the beacon cache (only if the
+                                                // cache size is not the default value).
+                                                if chain.config.shuffling_cache_size
                                         != beacon_chain::shuffling_cache::DEFAULT_CACHE_SIZE
                                     {
                                         if let Some(shuffling_id) = shuffling_id {
@@ -961,62 +1114,84 @@ pub fn serve<T: BeaconChainTypes>(
                                             }
                                         }
                                     }
-                                    possibly_built_cache
-                                };
-
-                                // Use either the supplied slot or all slots in the epoch.
-                                let slots =
-                                    query.slot.map(|slot| vec![slot]).unwrap_or_else(|| {
-                                        epoch.slot_iter(T::EthSpec::slots_per_epoch()).collect()
-                                    });
-
-                                // Use either the supplied committee index or all available indices.
-                                let indices =
-                                    query.index.map(|index| vec![index]).unwrap_or_else(|| {
-                                        (0..committee_cache.committees_per_slot()).collect()
-                                    });
-
-                                let mut response = Vec::with_capacity(slots.len() * indices.len());
-
-                                for slot in slots {
-                                    // It is not acceptable to query with a slot that is not within the
-                                    // specified epoch.
-                                    if slot.epoch(T::EthSpec::slots_per_epoch()) != epoch {
-                                        return Err(warp_utils::reject::custom_bad_request(
-                                            format!("{} is not in epoch {}", slot, epoch),
-                                        ));
-                                    }
+                                                possibly_built_cache
+                                            };
+
+                                        // Use either the supplied slot or all slots in the epoch.
+                                        let slots = query
+                                            .slot
+                                            .map(|slot| vec![slot])
+                                            .unwrap_or_else(|| {
+                                                epoch
+                                                    .slot_iter(T::EthSpec::slots_per_epoch())
+                                                    .collect()
+                                            });
+
+                                        // Use either the supplied committee index or all available
+                                        // indices.
+                                        let indices = query
+                                            .index
+                                            .map(|index| vec![index])
+                                            .unwrap_or_else(|| {
+                                                (0..committee_cache.committees_per_slot()).collect()
+                                            });
+
+                                        let mut response =
+                                            Vec::with_capacity(slots.len() * indices.len());
+
+                                        for slot in slots {
+                                            // It is not acceptable to query with a slot that is not
+                                            // within the specified epoch.
+                                            if slot.epoch(T::EthSpec::slots_per_epoch()) != epoch {
+                                                return Err(
+                                                    warp_utils::reject::custom_bad_request(
+                                                        format!(
+                                                            "{} is not in epoch {}",
+                                                            slot, epoch
+                                                        ),
+                                                    ),
+                                                );
+                                            }
 
-                                    for &index in &indices {
-                                        let committee = committee_cache
-                                            .get_beacon_committee(slot, index)
-                                            .ok_or_else(|| {
-                                                warp_utils::reject::custom_bad_request(format!(
+                                            for &index in &indices {
+                                                let committee = committee_cache
+                                                    .get_beacon_committee(slot, index)
+                                                    .ok_or_else(|| {
+                                                        warp_utils::reject::custom_bad_request(
+                                                            format!(
                                                     "committee index {} does not exist in epoch {}",
                                                     index, epoch
-                                                ))
-                                            })?;
-
-                                        response.push(api_types::CommitteeData {
-                                            index,
-                                            slot,
-                                            validators: committee
-                                                .committee
-                                                .iter()
-                                                .map(|i| *i as u64)
-                                                .collect(),
-                                        });
-                                    }
-                                }
+                                                ),
+                                                        )
+                                                    })?;
+
+                                                response.push(api_types::CommitteeData {
+                                                    index,
+                                                    slot,
+                                                    validators: committee
+                                                        .committee
+                                                        .iter()
+                                                        .map(|i| *i as u64)
+                                                        .collect(),
+                                                });
+                                            }
+                                        }
 
-                                Ok((response, execution_optimistic, finalized))
-                            },
-                        )?;
-                    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-                        data,
-                        execution_optimistic: Some(execution_optimistic),
-                        finalized: Some(finalized),
-                    })
+                                        Ok((response, state_root, execution_optimistic, finalized))
+                                    },
+                                )?;
+                            let capture =
+                                capture.and_then(|capture| capture.for_state_root(state_root));
+                            response_cache::json_response(
+                                &api_types::ExecutionOptimisticFinalizedResponse {
+                                    data,
+                                    execution_optimistic: Some(execution_optimistic),
+                                    finalized: Some(finalized),
+                                },
+                                capture,
+                            )
+                        },
+                    )
                 })
             },
         );
@@ -2711,9 +2886,6 @@ pub fn serve<T: BeaconChainTypes>(
                         let fork_name = state
                             .fork_name(&chain.spec)
                             .map_err(inconsistent_fork_rejection)?;
-                        let timer = metrics::start_timer(&metrics::HTTP_API_STATE_SSZ_ENCODE_TIMES);
-                        let response_bytes = state.as_ssz_bytes();
-                        drop(timer);
                         debug!(
                             log,
                             "HTTP state load";
@@ -2721,39 +2893,39 @@ pub fn serve<T: BeaconChainTypes>(
                             "target_slot" => state.slot()
                         );
 
-                        Response::builder()
-                            .status(200)
-                            .body(response_bytes.into())
-                            .map(|res: Response<Body>| add_ssz_content_type_header(res))
-                            .map(|resp: warp::reply::Response| {
-                                add_consensus_version_header(resp, fork_name)
-                            })
-                            .map_err(|e| {
-                                warp_utils::reject::custom_server_error(format!(
-                                    "failed to create response: {}",
-                                    e
-                                ))
-                            })
+                        // The state is encoded as it is sent, so the time spent waiting for the
+                        // client is excluded from the encoding time.
+                        let response = streaming::streaming_response(None, move |writer| {
+                            let start = std::time::Instant::now();
+                            state.ssz_write(writer)?;
+                            metrics::observe_duration(
+                                &metrics::HTTP_API_STATE_SSZ_ENCODE_TIMES,
+                                start.elapsed().saturating_sub(writer.send_wait()),
+                            );
+                            Ok(())
+                        })?;
+                        Ok(add_consensus_version_header(
+                            add_ssz_content_type_header(response),
+                            fork_name,
+                        ))
+                    }
+                    _ => {
+                        let (state, execution_optimistic, finalized) = state_id.state(&chain)?;
+                        let fork_name = state
+                            .fork_name(&chain.spec)
+                            .map_err(inconsistent_fork_rejection)?;
+                        let res = execution_optimistic_finalized_fork_versioned_response(
+                            endpoint_version,
+                            fork_name,
+                            execution_optimistic,
+                            finalized,
+                            state,
+                        )?;
+                        Ok(add_consensus_version_header(
+                            streaming::json_response(res)?,
+                            fork_name,
+                        ))
                     }
-                    _ => state_id.map_state_and_execution_optimistic_and_finalized(
-                        &chain,
-                        |state, execution_optimistic, finalized| {
-                            let fork_name = state
-                                .fork_name(&chain.spec)
-                                .map_err(inconsistent_fork_rejection)?;
-                            let res = execution_optimistic_finalized_fork_versioned_response(
-                                endpoint_version,
-                                fork_name,
-                                execution_optimistic,
-                                finalized,
-                                &state,
-                            )?;
-                            Ok(add_consensus_version_header(
-                                warp::reply::json(&res).into_response(),
-                                fork_name,
-                            ))
-                        },
-                    ),
                 })
             },
         );
@@ -3199,15 +3371,26 @@ pub fn serve<T: BeaconChainTypes>(
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
         .and(log_filter.clone())
+        .and(response_cache_filter.clone())
         .then(
             |epoch: Epoch,
              not_synced_filter: Result<(), Rejection>,
              task_spawner: TaskSpawner<T::EthSpec>,
              chain: Arc<BeaconChain<T>>,
-             log: Logger| {
-                task_spawner.blocking_json_task(Priority::P0, move || {
+             log: Logger,
+             response_cache: Option<Arc<ResponseCache>>,
+             request: String| {
+                task_spawner.blocking_response_task(Priority::P0, move || {
                     not_synced_filter?;
-                    proposer_duties::proposer_duties(epoch, &chain, &log)
+                    response_cache::cached_head_response(
+                        response_cache.as_ref(),
+                        &chain,
+                        request,
+                        |capture| {
+                            let duties = proposer_duties::proposer_duties(epoch, &chain, &log)?;
+                            response_cache::json_response(&duties, capture)
+                        },
+                    )
                 })
             },
         );
@@ -3391,15 +3574,27 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp_utils::json::json())
         .and(task_spawner_filter.clone())
         .and(chain_filter.clone())
+        .and(response_cache_filter.clone())
         .then(
             |epoch: Epoch,
              not_synced_filter: Result<(), Rejection>,
              indices: api_types::ValidatorIndexData,
              task_spawner: TaskSpawner<T::EthSpec>,
-             chain: Arc<BeaconChain<T>>| {
-                task_spawner.blocking_json_task(Priority::P0, move || {
+             chain: Arc<BeaconChain<T>>,
+             response_cache: Option<Arc<ResponseCache>>,
+             request: String| {
+                task_spawner.blocking_response_task(Priority::P0, move || {
                     not_synced_filter?;
-                    attester_duties::attester_duties(epoch, &indices.0, &chain)
+                    response_cache::cached_head_response(
+                        response_cache.as_ref(),
+                        &chain,
+                        format!("{} {:?}", request, indices.0),
+                        |capture| {
+                            let duties =
+                                attester_duties::attester_duties(epoch, &indices.0, &chain)?;
+                            response_cache::json_response(&duties, capture)
+                        },
+                    )
                 })
             },
         );
@@ -4128,6 +4323,26 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // POST lighthouse/graphql
+    let graphql_schema = Arc::new(graphql::schema::<T>());
+    let post_lighthouse_graphql = warp::path("lighthouse")
+        .and(warp::path("graphql"))
+        .and(warp::path::end())
+        .and(enable(ctx.config.enable_graphql))
+        .and(warp::body::bytes())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            move |body: Bytes,
+                  task_spawner: TaskSpawner<T::EthSpec>,
+                  chain: Arc<BeaconChain<T>>| {
+                let schema = graphql_schema.clone();
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    graphql::execute(&schema, chain, &body)
+                })
+            },
+        );
+
     // GET lighthouse/syncing
     let get_lighthouse_syncing = warp::path("lighthouse")
         .and(warp::path("syncing"))
@@ -4185,7 +4400,7 @@ pub fn serve<T: BeaconChainTypes>(
         .and(warp::path("connected"))
         .and(warp::path::end())
         .and(task_spawner_filter.clone())
-        .and(network_globals)
+        .and(network_globals.clone())
         .then(
             |task_spawner: TaskSpawner<T::EthSpec>,
              network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
@@ -4203,6 +4418,24 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/peers/subnet_coverage
+    let get_lighthouse_peers_subnet_coverage = warp::path("lighthouse")
+        .and(warp::path("peers"))
+        .and(warp::path("subnet_coverage"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(network_globals)
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>,
+             network_globals: Arc<NetworkGlobals<T::EthSpec>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    Ok(api_types::GenericResponse::from(
+                        network_globals.subnet_coverage(),
+                    ))
+                })
+            },
+        );
+
     // GET lighthouse/proto_array
     let get_lighthouse_proto_array = warp::path("lighthouse")
         .and(warp::path("proto_array"))
@@ -4225,6 +4458,53 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/fork_choice/snapshots/{slot}
+    let get_lighthouse_fork_choice_snapshot = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("snapshots"))
+        .and(warp::path::param::<Slot>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |slot: Slot, task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    get_fork_choice_snapshot(&chain, slot).map(api_types::GenericResponse::from)
+                })
+            },
+        );
+
+    // GET lighthouse/fork_choice/diff
+    let get_lighthouse_fork_choice_diff = warp::path("lighthouse")
+        .and(warp::path("fork_choice"))
+        .and(warp::path("diff"))
+        .and(warp::query::<eth2::lighthouse::ForkChoiceDiffQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |query: eth2::lighthouse::ForkChoiceDiffQuery,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let from = get_fork_choice_snapshot(&chain, query.from)?;
+                    let to = get_fork_choice_snapshot(&chain, query.to)?;
+                    Ok(match query.format {
+                        eth2::lighthouse::ForkChoiceDiffFormat::Json => {
+                            warp::reply::json(&api_types::GenericResponse::from(from.diff(&to)))
+                                .into_response()
+                        }
+                        eth2::lighthouse::ForkChoiceDiffFormat::Dot => warp::reply::with_header(
+                            from.diff_dot(&to),
+                            CONTENT_TYPE_HEADER,
+                            "text/vnd.graphviz",
+                        )
+                        .into_response(),
+                    })
+                })
+            },
+        );
+
     // GET lighthouse/validator_inclusion/{epoch}/{validator_id}
     let get_lighthouse_validator_inclusion_global = warp::path("lighthouse")
         .and(warp::path("validator_inclusion"))
@@ -4395,6 +4675,54 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/op_pool
+    let get_lighthouse_op_pool = warp::path("lighthouse")
+        .and(warp::path("op_pool"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_response_task(Priority::P1, move || {
+                    let pool = PersistedOperationPool::from_operation_pool(&chain.op_pool);
+                    Response::builder()
+                        .status(200)
+                        .body(pool.as_ssz_bytes().into())
+                        .map(|res: Response<Body>| add_ssz_content_type_header(res))
+                        .map_err(|e| {
+                            warp_utils::reject::custom_server_error(format!(
+                                "failed to create response: {}",
+                                e
+                            ))
+                        })
+                })
+            },
+        );
+
+    // POST lighthouse/op_pool
+    let post_lighthouse_op_pool = warp::path("lighthouse")
+        .and(warp::path("op_pool"))
+        .and(warp::path::end())
+        .and(warp::body::bytes())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |pool_bytes: Bytes,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    let pool =
+                        PersistedOperationPoolV20::from_ssz_bytes(&pool_bytes).map_err(|e| {
+                            warp_utils::reject::custom_bad_request(format!("invalid SSZ: {e:?}"))
+                        })?;
+                    chain
+                        .import_persisted_op_pool(pool)
+                        .map(api_types::GenericResponse::from)
+                        .map_err(warp_utils::reject::beacon_chain_error)
+                })
+            },
+        );
+
     // GET lighthouse/analysis/block_rewards
     let get_lighthouse_block_rewards = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4427,6 +4755,21 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/analysis/optimal_block_rewards
+    let get_lighthouse_optimal_block_rewards = warp::path("lighthouse")
+        .and(warp::path("analysis"))
+        .and(warp::path("optimal_block_rewards"))
+        .and(warp::query::<eth2::lighthouse::OptimalBlockRewardsQuery>())
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .and(log_filter.clone())
+        .then(|query, task_spawner: TaskSpawner<T::EthSpec>, chain, log| {
+            task_spawner.blocking_json_task(Priority::P1, move || {
+                optimal_block_rewards::get_optimal_block_rewards(query, chain, log)
+            })
+        });
+
     // GET lighthouse/analysis/attestation_performance/{index}
     let get_lighthouse_attestation_performance = warp::path("lighthouse")
         .and(warp::path("analysis"))
@@ -4460,6 +4803,38 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/block_timings/{block_id}
+    let get_lighthouse_block_timings = warp::path("lighthouse")
+        .and(warp::path("block_timings"))
+        .and(block_id_or_err)
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |block_id: BlockId,
+             task_spawner: TaskSpawner<T::EthSpec>,
+             chain: Arc<BeaconChain<T>>| {
+                task_spawner.blocking_json_task(Priority::P1, move || {
+                    // Use roots directly, since blocks which failed verification are not stored.
+                    let block_root = match block_id.0 {
+                        api_types::BlockId::Root(root) => root,
+                        _ => block_id.root(&chain)?.0,
+                    };
+                    chain
+                        .block_times_cache
+                        .read()
+                        .get_block_timings(block_root)
+                        .map(api_types::GenericResponse::from)
+                        .ok_or_else(|| {
+                            warp_utils::reject::custom_not_found(format!(
+                                "no timings for block {}, only recent blocks are available",
+                                block_root
+                            ))
+                        })
+                })
+            },
+        );
+
     // GET lighthouse/merge_readiness
     let get_lighthouse_merge_readiness = warp::path("lighthouse")
         .and(warp::path("merge_readiness"))
@@ -4479,6 +4854,29 @@ pub fn serve<T: BeaconChainTypes>(
             },
         );
 
+    // GET lighthouse/execution_engines
+    let get_lighthouse_execution_engines = warp::path("lighthouse")
+        .and(warp::path("execution_engines"))
+        .and(warp::path::end())
+        .and(task_spawner_filter.clone())
+        .and(chain_filter.clone())
+        .then(
+            |task_spawner: TaskSpawner<T::EthSpec>, chain: Arc<BeaconChain<T>>| {
+                task_spawner.spawn_async_with_rejection(Priority::P1, async move {
+                    let execution_layer = chain
+                        .execution_layer
+                        .as_ref()
+                        .ok_or(BeaconChainError::ExecutionLayerMissing)
+                        .map_err(warp_utils::reject::beacon_chain_error)?;
+                    let health = execution_layer.engine_health().await;
+                    Ok::<_, warp::reject::Rejection>(
+                        warp::reply::json(&api_types::GenericResponse::from(health))
+                            .into_response(),
+                    )
+                })
+            },
+        );
+
     let get_events = eth_v1
         .and(warp::path("events"))
         .and(warp::path::end())
@@ -4544,6 +4942,9 @@ pub fn serve<T: BeaconChainTypes>(
                                 api_types::EventTopic::BlockGossip => {
                                     event_handler.subscribe_block_gossip()
                                 }
+                                api_types::EventTopic::PayloadDisagreement => {
+                                    event_handler.subscribe_payload_disagreement()
+                                }
                             };
 
                             receivers.push(
@@ -4631,143 +5032,157 @@ pub fn serve<T: BeaconChainTypes>(
 
     // Define the ultimate set of routes that will be provided to the server.
     // Use `uor` rather than `or` in order to simplify types (see `UnifyingOrFilter`).
-    let routes = warp::get()
+    let routes = auth_filter
+        .and(rate_limit_filter)
         .and(
-            get_beacon_genesis
-                .uor(get_beacon_state_root)
-                .uor(get_beacon_state_fork)
-                .uor(get_beacon_state_finality_checkpoints)
-                .uor(get_beacon_state_validator_balances)
-                .uor(get_beacon_state_validators_id)
-                .uor(get_beacon_state_validators)
-                .uor(get_beacon_state_committees)
-                .uor(get_beacon_state_sync_committees)
-                .uor(get_beacon_state_randao)
-                .uor(get_beacon_headers)
-                .uor(get_beacon_headers_block_id)
-                .uor(get_beacon_block)
-                .uor(get_beacon_block_attestations)
-                .uor(get_beacon_blinded_block)
-                .uor(get_beacon_block_root)
-                .uor(get_blobs)
-                .uor(get_beacon_pool_attestations)
-                .uor(get_beacon_pool_attester_slashings)
-                .uor(get_beacon_pool_proposer_slashings)
-                .uor(get_beacon_pool_voluntary_exits)
-                .uor(get_beacon_pool_bls_to_execution_changes)
-                .uor(get_beacon_deposit_snapshot)
-                .uor(get_beacon_rewards_blocks)
-                .uor(get_config_fork_schedule)
-                .uor(get_config_spec)
-                .uor(get_config_deposit_contract)
-                .uor(get_debug_beacon_states)
-                .uor(get_debug_beacon_heads)
-                .uor(get_debug_fork_choice)
-                .uor(get_node_identity)
-                .uor(get_node_version)
-                .uor(get_node_syncing)
-                .uor(get_node_health)
-                .uor(get_node_peers_by_id)
-                .uor(get_node_peers)
-                .uor(get_node_peer_count)
-                .uor(get_validator_duties_proposer)
-                .uor(get_validator_blocks)
-                .uor(get_validator_blinded_blocks)
-                .uor(get_validator_attestation_data)
-                .uor(get_validator_aggregate_attestation)
-                .uor(get_validator_sync_committee_contribution)
-                .uor(get_lighthouse_health)
-                .uor(get_lighthouse_ui_health)
-                .uor(get_lighthouse_ui_validator_count)
-                .uor(get_lighthouse_syncing)
-                .uor(get_lighthouse_nat)
-                .uor(get_lighthouse_peers)
-                .uor(get_lighthouse_peers_connected)
-                .uor(get_lighthouse_proto_array)
-                .uor(get_lighthouse_validator_inclusion_global)
-                .uor(get_lighthouse_validator_inclusion)
-                .uor(get_lighthouse_eth1_syncing)
-                .uor(get_lighthouse_eth1_block_cache)
-                .uor(get_lighthouse_eth1_deposit_cache)
-                .uor(get_lighthouse_staking)
-                .uor(get_lighthouse_database_info)
-                .uor(get_lighthouse_block_rewards)
-                .uor(get_lighthouse_attestation_performance)
-                .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_optimistic_update),
-                )
-                .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_finality_update),
-                )
-                .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_bootstrap),
+            warp::get()
+                .and(
+                    get_beacon_genesis
+                        .uor(get_beacon_state_root)
+                        .uor(get_beacon_state_fork)
+                        .uor(get_beacon_state_finality_checkpoints)
+                        .uor(get_beacon_state_validator_balances)
+                        .uor(get_beacon_state_validators_id)
+                        .uor(get_beacon_state_validators)
+                        .uor(get_beacon_state_committees)
+                        .uor(get_beacon_state_sync_committees)
+                        .uor(get_beacon_state_randao)
+                        .uor(get_beacon_headers)
+                        .uor(get_beacon_headers_block_id)
+                        .uor(get_beacon_block)
+                        .uor(get_beacon_block_attestations)
+                        .uor(get_beacon_blinded_block)
+                        .uor(get_beacon_block_root)
+                        .uor(get_blobs)
+                        .uor(get_beacon_pool_attestations)
+                        .uor(get_beacon_pool_attester_slashings)
+                        .uor(get_beacon_pool_proposer_slashings)
+                        .uor(get_beacon_pool_voluntary_exits)
+                        .uor(get_beacon_pool_bls_to_execution_changes)
+                        .uor(get_beacon_deposit_snapshot)
+                        .uor(get_beacon_rewards_blocks)
+                        .uor(get_config_fork_schedule)
+                        .uor(get_config_spec)
+                        .uor(get_config_deposit_contract)
+                        .uor(get_debug_beacon_states)
+                        .uor(get_debug_beacon_heads)
+                        .uor(get_debug_fork_choice)
+                        .uor(get_node_identity)
+                        .uor(get_node_version)
+                        .uor(get_node_syncing)
+                        .uor(get_node_health)
+                        .uor(get_node_peers_by_id)
+                        .uor(get_node_peers)
+                        .uor(get_node_peer_count)
+                        .uor(get_validator_duties_proposer)
+                        .uor(get_validator_blocks)
+                        .uor(get_validator_blinded_blocks)
+                        .uor(get_validator_attestation_data)
+                        .uor(get_validator_aggregate_attestation)
+                        .uor(get_validator_sync_committee_contribution)
+                        .uor(get_lighthouse_health)
+                        .uor(get_lighthouse_ui_health)
+                        .uor(get_lighthouse_ui_validator_count)
+                        .uor(get_lighthouse_syncing)
+                        .uor(get_lighthouse_nat)
+                        .uor(get_lighthouse_peers)
+                        .uor(get_lighthouse_peers_connected)
+                        .uor(get_lighthouse_peers_subnet_coverage)
+                        .uor(get_lighthouse_proto_array)
+                        .uor(get_lighthouse_fork_choice_snapshot)
+                        .uor(get_lighthouse_fork_choice_diff)
+                        .uor(get_lighthouse_validator_inclusion_global)
+                        .uor(get_lighthouse_validator_inclusion)
+                        .uor(get_lighthouse_eth1_syncing)
+                        .uor(get_lighthouse_eth1_block_cache)
+                        .uor(get_lighthouse_eth1_deposit_cache)
+                        .uor(get_lighthouse_staking)
+                        .uor(get_lighthouse_database_info)
+                        .uor(get_lighthouse_op_pool)
+                        .uor(get_lighthouse_block_rewards)
+                        .uor(get_lighthouse_optimal_block_rewards)
+                        .uor(get_lighthouse_attestation_performance)
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_optimistic_update),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_finality_update),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_bootstrap),
+                        )
+                        .uor(
+                            enable(ctx.config.enable_light_client_server)
+                                .and(get_beacon_light_client_updates),
+                        )
+                        .uor(get_lighthouse_block_packing_efficiency)
+                        .uor(get_lighthouse_block_timings)
+                        .uor(get_lighthouse_merge_readiness)
+                        .uor(get_lighthouse_execution_engines)
+                        .uor(get_events)
+                        .uor(get_expected_withdrawals)
+                        .uor(lighthouse_log_events.boxed())
+                        .recover(warp_utils::reject::handle_rejection),
                 )
+                .boxed()
                 .uor(
-                    enable(ctx.config.enable_light_client_server)
-                        .and(get_beacon_light_client_updates),
-                )
-                .uor(get_lighthouse_block_packing_efficiency)
-                .uor(get_lighthouse_merge_readiness)
-                .uor(get_events)
-                .uor(get_expected_withdrawals)
-                .uor(lighthouse_log_events.boxed())
-                .recover(warp_utils::reject::handle_rejection),
-        )
-        .boxed()
-        .uor(
-            warp::post().and(
-                warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
-                    // Routes which expect `application/octet-stream` go within this `and`.
-                    .and(
-                        post_beacon_blocks_ssz
-                            .uor(post_beacon_blocks_v2_ssz)
-                            .uor(post_beacon_blinded_blocks_ssz)
-                            .uor(post_beacon_blinded_blocks_v2_ssz),
-                    )
-                    .uor(post_beacon_blocks)
-                    .uor(post_beacon_blinded_blocks)
-                    .uor(post_beacon_blocks_v2)
-                    .uor(post_beacon_blinded_blocks_v2)
-                    .uor(post_beacon_pool_attestations)
-                    .uor(post_beacon_pool_attester_slashings)
-                    .uor(post_beacon_pool_proposer_slashings)
-                    .uor(post_beacon_pool_voluntary_exits)
-                    .uor(post_beacon_pool_sync_committees)
-                    .uor(post_beacon_pool_bls_to_execution_changes)
-                    .uor(post_beacon_state_validators)
-                    .uor(post_beacon_state_validator_balances)
-                    .uor(post_beacon_rewards_attestations)
-                    .uor(post_beacon_rewards_sync_committee)
-                    .uor(post_validator_duties_attester)
-                    .uor(post_validator_duties_sync)
-                    .uor(post_validator_aggregate_and_proofs)
-                    .uor(post_validator_contribution_and_proofs)
-                    .uor(post_validator_beacon_committee_subscriptions)
-                    .uor(post_validator_sync_committee_subscriptions)
-                    .uor(post_validator_prepare_beacon_proposer)
-                    .uor(post_validator_register_validator)
-                    .uor(post_validator_liveness_epoch)
-                    .uor(post_lighthouse_liveness)
-                    .uor(post_lighthouse_database_reconstruct)
-                    .uor(post_lighthouse_block_rewards)
-                    .uor(post_lighthouse_ui_validator_metrics)
-                    .uor(post_lighthouse_ui_validator_info)
-                    .recover(warp_utils::reject::handle_rejection),
-            ),
+                    warp::post().and(
+                        warp::header::exact(CONTENT_TYPE_HEADER, SSZ_CONTENT_TYPE_HEADER)
+                            // Routes which expect `application/octet-stream` go within this `and`.
+                            .and(
+                                post_beacon_blocks_ssz
+                                    .uor(post_beacon_blocks_v2_ssz)
+                                    .uor(post_beacon_blinded_blocks_ssz)
+                                    .uor(post_beacon_blinded_blocks_v2_ssz)
+                                    .uor(post_lighthouse_op_pool),
+                            )
+                            .uor(post_beacon_blocks)
+                            .uor(post_beacon_blinded_blocks)
+                            .uor(post_beacon_blocks_v2)
+                            .uor(post_beacon_blinded_blocks_v2)
+                            .uor(post_beacon_pool_attestations)
+                            .uor(post_beacon_pool_attester_slashings)
+                            .uor(post_beacon_pool_proposer_slashings)
+                            .uor(post_beacon_pool_voluntary_exits)
+                            .uor(post_beacon_pool_sync_committees)
+                            .uor(post_beacon_pool_bls_to_execution_changes)
+                            .uor(post_beacon_state_validators)
+                            .uor(post_beacon_state_validator_balances)
+                            .uor(post_beacon_rewards_attestations)
+                            .uor(post_beacon_rewards_sync_committee)
+                            .uor(post_validator_duties_attester)
+                            .uor(post_validator_duties_sync)
+                            .uor(post_validator_aggregate_and_proofs)
+                            .uor(post_validator_contribution_and_proofs)
+                            .uor(post_validator_beacon_committee_subscriptions)
+                            .uor(post_validator_sync_committee_subscriptions)
+                            .uor(post_validator_prepare_beacon_proposer)
+                            .uor(post_validator_register_validator)
+                            .uor(post_validator_liveness_epoch)
+                            .uor(post_lighthouse_liveness)
+                            .uor(post_lighthouse_database_reconstruct)
+                            .uor(post_lighthouse_block_rewards)
+                            .uor(post_lighthouse_ui_validator_metrics)
+                            .uor(post_lighthouse_ui_validator_info)
+                            .uor(post_lighthouse_graphql)
+                            .recover(warp_utils::reject::handle_rejection),
+                    ),
+                ),
         )
         .recover(warp_utils::reject::handle_rejection)
         .with(slog_logging(log.clone()))
         .with(prometheus_metrics())
+        .with(warp::trace(request_span))
         // Add a `Server` header.
         .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()))
         .with(cors_builder.build())
         .boxed();
 
     let http_socket: SocketAddr = SocketAddr::new(config.listen_addr, config.listen_port);
-    let http_server: HttpServer = match config.tls_config {
+    let (socket, server): HttpServer = match config.tls_config {
         Some(tls_config) => {
             let (socket, server) = warp::serve(routes)
                 .tls()
@@ -4789,6 +5204,13 @@ pub fn serve<T: BeaconChainTypes>(
             (socket, Box::pin(server))
         }
     };
+    // Stop invalidating the response cache when the server shuts down.
+    let http_server: HttpServer = (
+        socket,
+        Box::pin(async move {
+            futures::future::select(server, cache_invalidation).await;
+        }),
+    );
 
     info!(
         log,
@@ -4832,3 +5254,19 @@ fn publish_network_message<E: EthSpec>(
         ))
     })
 }
+
+/// Load the fork choice snapshot for `slot`, returning a 404 if there is none.
+fn get_fork_choice_snapshot<T: BeaconChainTypes>(
+    chain: &BeaconChain<T>,
+    slot: Slot,
+) -> Result<eth2::lighthouse::ForkChoiceSnapshot, warp::Rejection> {
+    chain
+        .get_fork_choice_snapshot(slot)
+        .map_err(warp_utils::reject::beacon_chain_error)?
+        .ok_or_else(|| {
+            warp_utils::reject::custom_not_found(format!(
+                "no fork choice snapshot for slot {}",
+                slot
+            ))
+        })
+}
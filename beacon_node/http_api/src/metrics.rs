@@ -15,6 +15,53 @@ pub static HTTP_API_STATUS_CODES_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLo
         &["status"],
     )
 });
+pub static HTTP_API_AUTH_FAILURES_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "http_api_auth_failures_total",
+        "Count of HTTP requests rejected by access control",
+        &["reason"],
+    )
+});
+pub static HTTP_API_RATE_LIMIT_COST_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "http_api_rate_limit_cost_total",
+        "Total cost of HTTP requests admitted by the rate limiter",
+        &["client"],
+    )
+});
+pub static HTTP_API_RATE_LIMITED_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "http_api_rate_limited_total",
+        "Count of HTTP requests rejected by the rate limiter",
+        &["client"],
+    )
+});
+pub static HTTP_API_RATE_LIMIT_CLIENTS: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "http_api_rate_limit_clients",
+        "Number of clients tracked by the HTTP API rate limiter",
+    )
+});
+pub static HTTP_API_RESPONSE_CACHE_REQUESTS_TOTAL: LazyLock<Result<IntCounterVec>> =
+    LazyLock::new(|| {
+        try_create_int_counter_vec(
+            "http_api_response_cache_requests_total",
+            "Count of HTTP response cache lookups, by outcome (hit or miss)",
+            &["outcome"],
+        )
+    });
+pub static HTTP_API_RESPONSE_CACHE_ENTRIES: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "http_api_response_cache_entries",
+        "Number of responses in the HTTP response cache",
+    )
+});
+pub static HTTP_API_RESPONSE_CACHE_SIZE_BYTES: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "http_api_response_cache_size_bytes",
+        "Total size of the responses in the HTTP response cache",
+    )
+});
 pub static HTTP_API_PATHS_TIMES: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
     try_create_histogram_vec(
         "http_api_paths_times",
@@ -0,0 +1,178 @@
+use beacon_chain::{BeaconChain, BeaconChainError, BeaconChainTypes, WhenSlotSkipped};
+use eth2::lighthouse::{
+    OptimalBlockReward, OptimalBlockRewards, OptimalBlockRewardsQuery, ProposerRewardGap,
+};
+use operation_pool::{BranchAndBound, DEFAULT_PACKING_TIME_BUDGET};
+use slog::{warn, Logger};
+use state_processing::{epoch_cache::initialize_epoch_cache, BlockReplayer};
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use types::{Attestation, EthSpec};
+use warp_utils::reject::{beacon_chain_error, beacon_state_error, custom_bad_request};
+
+/// Compare the attestation rewards of blocks from the canonical chain with the best rewards their
+/// proposers could have achieved.
+///
+/// The operation pool at each proposal is reconstructed from the attestations included on chain
+/// in the range and the epoch after it, since attestations seen on gossip are not stored. The
+/// optimal rewards are therefore a lower bound on what a proposer with a complete view of the
+/// network could have achieved.
+pub fn get_optimal_block_rewards<T: BeaconChainTypes>(
+    query: OptimalBlockRewardsQuery,
+    chain: Arc<BeaconChain<T>>,
+    log: Logger,
+) -> Result<OptimalBlockRewards, warp::Rejection> {
+    let start_slot = query.start_slot;
+    let end_slot = query.end_slot;
+    let prior_slot = start_slot - 1;
+
+    if start_slot > end_slot || start_slot == 0 {
+        return Err(custom_bad_request(format!(
+            "invalid start and end: {}, {}",
+            start_slot, end_slot
+        )));
+    }
+
+    // Attestations can be included up to an epoch after their slot, so load the following epoch
+    // of blocks as well to find attestations which were available at proposal time.
+    let head_slot = chain.canonical_head.cached_head().head_slot();
+    let lookahead_slot = std::cmp::min(end_slot + T::EthSpec::slots_per_epoch(), head_slot);
+    let replay_end_slot = std::cmp::max(end_slot, lookahead_slot);
+
+    let end_block_root = chain
+        .block_root_at_slot(replay_end_slot, WhenSlotSkipped::Prev)
+        .map_err(beacon_chain_error)?
+        .ok_or_else(|| {
+            custom_bad_request(format!("block at end slot {} unknown", replay_end_slot))
+        })?;
+
+    let blocks = chain
+        .store
+        .load_blocks_to_replay(start_slot, replay_end_slot, end_block_root)
+        .map_err(|e| beacon_chain_error(e.into()))?;
+
+    let candidates = blocks
+        .iter()
+        .flat_map(|block| block.message().body().attestations())
+        .map(|att| att.clone_as_attestation())
+        .collect::<Vec<Attestation<T::EthSpec>>>();
+
+    let state_root = chain
+        .state_root_at_slot(prior_slot)
+        .map_err(beacon_chain_error)?
+        .ok_or_else(|| custom_bad_request(format!("prior state at slot {} unknown", prior_slot)))?;
+
+    let mut state = chain
+        .get_state(&state_root, Some(prior_slot))
+        .and_then(|maybe_state| maybe_state.ok_or(BeaconChainError::MissingBeaconState(state_root)))
+        .map_err(beacon_chain_error)?;
+
+    state
+        .build_caches(&chain.spec)
+        .map_err(beacon_state_error)?;
+
+    let strategy = BranchAndBound {
+        time_budget: query
+            .packing_time_budget_ms
+            .map_or(DEFAULT_PACKING_TIME_BUDGET, Duration::from_millis),
+    };
+    let mut reward_cache = Default::default();
+    let mut block_rewards = Vec::with_capacity(blocks.len());
+
+    let block_replayer = BlockReplayer::new(state, &chain.spec)
+        .pre_block_hook(Box::new(|state, block| {
+            if block.slot() > end_slot {
+                return Ok(());
+            }
+
+            state.build_all_committee_caches(&chain.spec)?;
+            initialize_epoch_cache(state, &chain.spec)?;
+
+            let actual = chain.compute_block_reward(
+                block.message(),
+                block.canonical_root(),
+                state,
+                &mut reward_cache,
+                false,
+            )?;
+
+            let block_candidates = candidates
+                .iter()
+                .filter(|att| att.data().slot < block.slot())
+                .map(Attestation::to_ref)
+                .collect::<Vec<_>>();
+            let candidate_attestations = block_candidates.len();
+            let optimal = chain.compute_optimal_attestation_rewards(
+                block_candidates,
+                state,
+                &mut reward_cache,
+                &strategy,
+            )?;
+
+            // The block itself is always a feasible packing, even if the search didn't find it.
+            let actual_attestation_rewards = actual.attestation_rewards.total;
+            let optimal_attestation_rewards =
+                std::cmp::max(optimal.total, actual_attestation_rewards);
+
+            block_rewards.push(OptimalBlockReward {
+                block_root: actual.block_root,
+                meta: actual.meta,
+                actual_attestation_rewards,
+                optimal_attestation_rewards,
+                reward_gap: optimal_attestation_rewards - actual_attestation_rewards,
+                candidate_attestations,
+            });
+            Ok(())
+        }))
+        .state_root_iter(
+            chain
+                .forwards_iter_state_roots_until(prior_slot, replay_end_slot)
+                .map_err(beacon_chain_error)?,
+        )
+        .no_signature_verification()
+        .minimal_block_root_verification()
+        .apply_blocks(blocks, None)
+        .map_err(beacon_chain_error)?;
+
+    if block_replayer.state_root_miss() {
+        warn!(
+            log,
+            "Optimal block reward state root miss";
+            "start_slot" => start_slot,
+            "end_slot" => end_slot,
+        );
+    }
+
+    drop(block_replayer);
+
+    let proposers = proposer_reward_gaps(&block_rewards);
+
+    Ok(OptimalBlockRewards {
+        blocks: block_rewards,
+        proposers,
+    })
+}
+
+/// Sum the rewards of `blocks` for each proposer, sorted by descending reward gap.
+fn proposer_reward_gaps(blocks: &[OptimalBlockReward]) -> Vec<ProposerRewardGap> {
+    let mut proposers = HashMap::<u64, ProposerRewardGap>::new();
+    for block in blocks {
+        let proposer_index = block.meta.proposer_index;
+        let totals = proposers
+            .entry(proposer_index)
+            .or_insert_with(|| ProposerRewardGap {
+                proposer_index,
+                ..Default::default()
+            });
+        totals.blocks += 1;
+        totals.actual_attestation_rewards += block.actual_attestation_rewards;
+        totals.optimal_attestation_rewards += block.optimal_attestation_rewards;
+        totals.reward_gap += block.reward_gap;
+    }
+
+    let mut proposers = proposers.into_values().collect::<Vec<_>>();
+    proposers.sort_by_key(|totals| (Reverse(totals.reward_gap), totals.proposer_index));
+    proposers
+}
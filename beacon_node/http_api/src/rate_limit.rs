@@ -0,0 +1,252 @@
+//! Per-client rate limiting for the HTTP API.
+//!
+//! Each client has a token bucket which is refilled at a constant rate up to a maximum burst.
+//! Every request is assigned a cost by `request_cost` which is taken from the bucket of the client
+//! making it, so that a client loading historic states exhausts its quota much faster than one
+//! polling `/eth/v1/node/version`. Requests from a client with an insufficient balance are
+//! rejected with a `429` status code and a `Retry-After` header.
+//!
+//! Clients are identified by their API token when access control is enabled, and by their IP
+//! address otherwise. IPv6 clients are identified by their /64 prefix, since a single host is
+//! usually assigned a whole prefix. Requests with a `validator` or `admin` token are not limited,
+//! so that the operator's own validator clients are never throttled by their public quota.
+use crate::auth::{token_from_header, ApiAuth, Role};
+use crate::metrics;
+use lru::LruCache;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use types::non_zero_usize::new_non_zero_usize;
+use warp::Filter;
+
+/// The cost of a request which is cheap to serve, e.g. `/eth/v1/node/version`.
+pub const BASE_COST: u64 = 1;
+/// The cost of a request which iterates over all validators or committees in a state.
+pub const VALIDATORS_COST: u64 = 10;
+/// The cost of a request which computes rewards or analyses a range of blocks.
+pub const ANALYSIS_COST: u64 = 20;
+/// The cost of a request which serves a full state.
+pub const FULL_STATE_COST: u64 = 50;
+/// The factor applied to the cost of requests for states identified by slot or root, which may
+/// have to be reconstructed from the freezer database.
+pub const HISTORIC_STATE_MULTIPLIER: u64 = 5;
+
+/// The burst used if none is configured, as a multiple of the cost per second.
+pub const DEFAULT_RATE_LIMIT_BURST_FACTOR: u64 = 10;
+
+/// The state endpoints which cost `VALIDATORS_COST`.
+const VALIDATORS_ENDPOINTS: &[&str] = &[
+    "validators",
+    "validator_balances",
+    "committees",
+    "sync_committees",
+];
+
+/// The maximum number of clients whose buckets are tracked.
+///
+/// Once this many clients are tracked the least recently seen client is forgotten, which resets
+/// its quota. A client which has been idle long enough to be evicted has usually refilled its
+/// bucket anyway.
+const MAX_TRACKED_CLIENTS: NonZeroUsize = new_non_zero_usize(16_384);
+
+/// The length of the prefix which identifies an IPv6 client.
+const IPV6_PREFIX_LEN: u32 = 64;
+
+/// Configuration for the HTTP API rate limiter.
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RateLimitConfig {
+    /// The cost refilled into each client's bucket every second.
+    pub cost_per_second: u64,
+    /// The maximum cost a client may accumulate, i.e. the largest burst of requests it can make.
+    pub burst: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum ClientId {
+    Token(String),
+    Ip(IpAddr),
+    /// The remote address is unavailable, which is only expected in tests.
+    Unknown,
+}
+
+impl ClientId {
+    /// Identify a client by `ip`, or by its prefix for IPv6 addresses.
+    fn from_ip(ip: IpAddr) -> Self {
+        match ip {
+            IpAddr::V4(_) => ClientId::Ip(ip),
+            IpAddr::V6(ipv6) => match ipv6.to_ipv4_mapped() {
+                Some(ipv4) => ClientId::Ip(IpAddr::V4(ipv4)),
+                None => {
+                    let mask = u128::MAX << (128 - IPV6_PREFIX_LEN);
+                    ClientId::Ip(IpAddr::V6(Ipv6Addr::from(u128::from(ipv6) & mask)))
+                }
+            },
+        }
+    }
+
+    fn metrics_label(&self) -> &'static str {
+        match self {
+            ClientId::Token(_) => "token",
+            ClientId::Ip(_) | ClientId::Unknown => "ip",
+        }
+    }
+}
+
+struct Bucket {
+    available: f64,
+    updated: Instant,
+}
+
+pub struct RateLimiter {
+    cost_per_second: f64,
+    burst: f64,
+    auth: Option<ApiAuth>,
+    buckets: Mutex<LruCache<ClientId, Bucket>>,
+}
+
+impl RateLimiter {
+    /// Create a rate limiter, which identifies clients by their API token if `auth` is provided.
+    ///
+    /// Only tokens known to `auth` are used, otherwise a client could evade its quota by sending
+    /// a different token with each request.
+    pub fn new(config: RateLimitConfig, auth: Option<ApiAuth>) -> Result<Self, String> {
+        if config.cost_per_second == 0 || config.burst == 0 {
+            return Err("HTTP API rate limit and burst must be greater than zero".to_string());
+        }
+
+        Ok(Self {
+            cost_per_second: config.cost_per_second as f64,
+            burst: config.burst as f64,
+            auth,
+            buckets: Mutex::new(LruCache::new(MAX_TRACKED_CLIENTS)),
+        })
+    }
+
+    /// Take `cost` from the bucket of `client`, returning the time until the request could
+    /// succeed if the balance is insufficient.
+    ///
+    /// Requests which cost more than the burst are charged the burst, so that they remain possible.
+    fn check(&self, client: ClientId, cost: u64, now: Instant) -> Result<(), Duration> {
+        let cost = (cost as f64).min(self.burst);
+        let mut buckets = self.buckets.lock();
+
+        let bucket = buckets.get_or_insert_mut(client, || Bucket {
+            available: self.burst,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.available = (bucket.available + elapsed * self.cost_per_second).min(self.burst);
+        bucket.updated = now;
+
+        let result = if bucket.available >= cost {
+            bucket.available -= cost;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (cost - bucket.available) / self.cost_per_second,
+            ))
+        };
+
+        metrics::set_gauge(&metrics::HTTP_API_RATE_LIMIT_CLIENTS, buckets.len() as i64);
+        result
+    }
+
+    /// Identify the client making a request, returning `None` if it is exempt from rate limiting.
+    fn client_id(
+        &self,
+        remote: Option<SocketAddr>,
+        authorization: Option<&str>,
+    ) -> Option<ClientId> {
+        if let Some(auth) = &self.auth {
+            if let Some(token) = authorization.and_then(token_from_header) {
+                match auth.token_role(token) {
+                    Some(Role::Validator | Role::Admin) => return None,
+                    Some(Role::ReadOnly) => return Some(ClientId::Token(token.to_string())),
+                    None => (),
+                }
+            }
+        }
+        Some(remote.map_or(ClientId::Unknown, |addr| ClientId::from_ip(addr.ip())))
+    }
+
+    /// Returns a `warp` filter which rejects requests from clients which have exhausted their
+    /// quota.
+    pub fn filter(self: Arc<Self>) -> warp::filters::BoxedFilter<()> {
+        warp::path::full()
+            .and(warp::addr::remote())
+            .and(warp::header::optional::<String>("Authorization"))
+            .and_then(
+                move |path: warp::path::FullPath,
+                      remote: Option<SocketAddr>,
+                      header: Option<String>| {
+                    let cost = request_cost(path.as_str());
+                    let (label, result) = match self.client_id(remote, header.as_deref()) {
+                        Some(client) => {
+                            let label = client.metrics_label();
+                            (label, self.check(client, cost, Instant::now()))
+                        }
+                        None => ("exempt", Ok(())),
+                    };
+                    async move {
+                        match result {
+                            Ok(()) => {
+                                metrics::inc_counter_vec_by(
+                                    &metrics::HTTP_API_RATE_LIMIT_COST_TOTAL,
+                                    &[label],
+                                    cost,
+                                );
+                                Ok(())
+                            }
+                            Err(retry_after) => {
+                                metrics::inc_counter_vec(
+                                    &metrics::HTTP_API_RATE_LIMITED_TOTAL,
+                                    &[label],
+                                );
+                                let retry_after = retry_after.as_secs_f64().ceil().max(1.0);
+                                Err(warp_utils::reject::rate_limited(retry_after as u64))
+                            }
+                        }
+                    }
+                },
+            )
+            .untuple_one()
+            .boxed()
+    }
+}
+
+/// The cost of a request to `path`, in units of `BASE_COST`.
+pub fn request_cost(path: &str) -> u64 {
+    let segments = path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>();
+
+    match segments.as_slice() {
+        ["eth", _, "debug", "beacon", "states", state_id, ..] => {
+            FULL_STATE_COST * state_multiplier(state_id)
+        }
+        ["eth", _, "beacon", "states", state_id, endpoint, ..]
+            if VALIDATORS_ENDPOINTS.contains(endpoint) =>
+        {
+            VALIDATORS_COST * state_multiplier(state_id)
+        }
+        ["eth", _, "beacon", "states", state_id, ..] => BASE_COST * state_multiplier(state_id),
+        ["eth", _, "beacon", "rewards", ..]
+        | ["lighthouse", "analysis", ..]
+        | ["lighthouse", "graphql"]
+        | ["lighthouse", "validator_inclusion", ..] => ANALYSIS_COST,
+        _ => BASE_COST,
+    }
+}
+
+/// States other than the named ones may have to be loaded from the freezer database.
+fn state_multiplier(state_id: &str) -> u64 {
+    match state_id {
+        "head" | "genesis" | "finalized" | "justified" => 1,
+        _ => HISTORIC_STATE_MULTIPLIER,
+    }
+}
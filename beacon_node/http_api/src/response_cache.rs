@@ -0,0 +1,257 @@
+//! A cache of JSON responses to requests about states, shared between clients.
+//!
+//! Validator clients and dashboards tend to make identical requests, e.g. for the validators and
+//! committees of the head state. Responses are cached by the root of the state, the request and,
+//! unless the state is finalized, the head block root. Responses about non-finalized states are
+//! therefore never served after the head changes, and are removed from the cache on `Head` and
+//! `ChainReorg` events. Responses about finalized states remain valid forever, and are only
+//! evicted to keep the cache within its memory bound.
+//!
+//! Proposer and attester duties are requested by every validator client each epoch. They are
+//! cached by the head block root alone, since they are computed from the head rather than a state
+//! named by the request.
+use crate::metrics;
+use crate::state_id::StateId;
+use beacon_chain::events::EventKind;
+use beacon_chain::{BeaconChain, BeaconChainTypes};
+use bytes::Bytes;
+use eth2::types::StateId as CoreStateId;
+use eth2::CONTENT_TYPE_HEADER;
+use lru_cache::LRUSizeCache;
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+use types::{EthSpec, Hash256};
+use warp::hyper::Body;
+use warp::reply::{self, Reply, Response};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// The state the response is about, or `None` if it was computed from the head.
+    state_root: Option<Hash256>,
+    /// The head when the response was cached, or `None` if the state is finalized.
+    head_block_root: Option<Hash256>,
+    /// The path and query of the request, and its body for `POST` requests.
+    request: String,
+}
+
+impl CacheKey {
+    fn is_finalized(&self) -> bool {
+        self.head_block_root.is_none()
+    }
+}
+
+pub struct ResponseCache {
+    entries: Mutex<LRUSizeCache<CacheKey, Bytes>>,
+}
+
+impl ResponseCache {
+    pub fn new(max_size_bytes: usize) -> Self {
+        Self {
+            entries: Mutex::new(LRUSizeCache::new(max_size_bytes)),
+        }
+    }
+
+    /// The largest response which will be cached.
+    pub fn max_entry_bytes(&self) -> usize {
+        self.entries.lock().max_entry_bytes()
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<Bytes> {
+        let body = self.entries.lock().get(key).cloned();
+        let outcome = if body.is_some() { "hit" } else { "miss" };
+        metrics::inc_counter_vec(&metrics::HTTP_API_RESPONSE_CACHE_REQUESTS_TOTAL, &[outcome]);
+        body
+    }
+
+    fn insert(&self, key: CacheKey, body: Bytes) {
+        let mut entries = self.entries.lock();
+        let size_bytes = body.len();
+        if entries.insert(key, body, size_bytes) {
+            update_size_metrics(&entries);
+        }
+    }
+
+    /// Remove the responses about states which are not finalized.
+    pub fn invalidate(&self) {
+        let mut entries = self.entries.lock();
+        entries.retain(CacheKey::is_finalized);
+        update_size_metrics(&entries);
+    }
+}
+
+fn update_size_metrics(entries: &LRUSizeCache<CacheKey, Bytes>) {
+    metrics::set_gauge(
+        &metrics::HTTP_API_RESPONSE_CACHE_ENTRIES,
+        entries.len() as i64,
+    );
+    metrics::set_gauge(
+        &metrics::HTTP_API_RESPONSE_CACHE_SIZE_BYTES,
+        entries.size_bytes() as i64,
+    );
+}
+
+/// A response which will be added to the cache once it has been built.
+pub struct Capture {
+    cache: Arc<ResponseCache>,
+    key: CacheKey,
+}
+
+impl Capture {
+    /// The length beyond which the response will not be cached.
+    pub fn max_len(&self) -> usize {
+        self.cache.max_entry_bytes()
+    }
+
+    /// Returns the capture if the response is being built from the state with `state_root`, which
+    /// is the state the response would be cached under.
+    pub fn for_state_root(self, state_root: Hash256) -> Option<Self> {
+        (self.key.state_root == Some(state_root)).then_some(self)
+    }
+
+    pub fn complete(self, body: Bytes) {
+        self.cache.insert(self.key, body);
+    }
+}
+
+/// Serve the response to `request` about the state identified by `state_id` from `cache`.
+///
+/// If there is no cached response, the response is built by `build_response`, which is given a
+/// `Capture` if the response may be cached. The state used to build the response may not be the
+/// one the response is cached under if the head has changed in the meantime, so `build_response`
+/// must pass the capture through `Capture::for_state_root`.
+pub fn cached_response<T, F>(
+    cache: Option<&Arc<ResponseCache>>,
+    chain: &BeaconChain<T>,
+    state_id: &StateId,
+    request: String,
+    build_response: F,
+) -> Result<Response, warp::Rejection>
+where
+    T: BeaconChainTypes,
+    F: FnOnce(Option<Capture>) -> Result<Response, warp::Rejection>,
+{
+    let Some(cache) = cache else {
+        return build_response(None);
+    };
+
+    // Read the head once, so that the key of a response about the head state refers to a single
+    // head.
+    let (cached_head, head_execution_status) = chain
+        .canonical_head
+        .head_and_execution_status()
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+    let (state_root, execution_optimistic, finalized) = match &state_id.0 {
+        CoreStateId::Head => (
+            cached_head.head_state_root(),
+            head_execution_status.is_optimistic_or_invalid(),
+            false,
+        ),
+        _ => state_id.root(chain)?,
+    };
+    // The execution status of optimistic states may change without the head changing.
+    if execution_optimistic {
+        return build_response(None);
+    }
+    let key = CacheKey {
+        state_root: Some(state_root),
+        head_block_root: (!finalized).then(|| cached_head.head_block_root()),
+        request,
+    };
+
+    serve_or_build(cache, key, build_response)
+}
+
+/// Serve the response to `request`, which is computed from the head, from `cache`.
+///
+/// The response is cached until the head changes, and is not cached while the head is optimistic.
+pub fn cached_head_response<T, F>(
+    cache: Option<&Arc<ResponseCache>>,
+    chain: &BeaconChain<T>,
+    request: String,
+    build_response: F,
+) -> Result<Response, warp::Rejection>
+where
+    T: BeaconChainTypes,
+    F: FnOnce(Option<Capture>) -> Result<Response, warp::Rejection>,
+{
+    let Some(cache) = cache else {
+        return build_response(None);
+    };
+
+    let (cached_head, execution_status) = chain
+        .canonical_head
+        .head_and_execution_status()
+        .map_err(warp_utils::reject::beacon_chain_error)?;
+    if execution_status.is_optimistic_or_invalid() {
+        return build_response(None);
+    }
+    let key = CacheKey {
+        state_root: None,
+        head_block_root: Some(cached_head.head_block_root()),
+        request,
+    };
+
+    serve_or_build(cache, key, build_response)
+}
+
+fn serve_or_build<F>(
+    cache: &Arc<ResponseCache>,
+    key: CacheKey,
+    build_response: F,
+) -> Result<Response, warp::Rejection>
+where
+    F: FnOnce(Option<Capture>) -> Result<Response, warp::Rejection>,
+{
+    if let Some(body) = cache.get(&key) {
+        return Ok(json_body_response(body));
+    }
+    build_response(Some(Capture {
+        cache: cache.clone(),
+        key,
+    }))
+}
+
+/// Create a JSON response from `value`, adding it to the cache if `capture` is provided.
+pub fn json_response<T: Serialize>(
+    value: &T,
+    capture: Option<Capture>,
+) -> Result<Response, warp::Rejection> {
+    let body = Bytes::from(serde_json::to_vec(value).map_err(|e| {
+        warp_utils::reject::custom_server_error(format!("unable to serialize response: {}", e))
+    })?);
+    if let Some(capture) = capture {
+        capture.complete(body.clone());
+    }
+    Ok(json_body_response(body))
+}
+
+fn json_body_response(body: Bytes) -> Response {
+    reply::with_header(
+        Response::new(Body::from(body)),
+        CONTENT_TYPE_HEADER,
+        "application/json",
+    )
+    .into_response()
+}
+
+/// Remove responses about non-finalized states from `cache` whenever a `Head` or `ChainReorg`
+/// event is received.
+pub async fn invalidate_on_head_change<E: EthSpec>(
+    cache: Arc<ResponseCache>,
+    mut head_rx: Receiver<EventKind<E>>,
+    mut reorg_rx: Receiver<EventKind<E>>,
+) {
+    loop {
+        let result = tokio::select! {
+            result = head_rx.recv() => result.map(|_| ()),
+            result = reorg_rx.recv() => result.map(|_| ()),
+        };
+        match result {
+            // Missed events may have been head changes.
+            Ok(()) | Err(RecvError::Lagged(_)) => cache.invalidate(),
+            Err(RecvError::Closed) => break,
+        }
+    }
+}
@@ -173,6 +173,25 @@ impl StateId {
         &self,
         chain: &BeaconChain<T>,
     ) -> Result<(BeaconState<T::EthSpec>, ExecutionOptimistic, Finalized), warp::Rejection> {
+        self.state_and_root(chain)
+            .map(|(state, _, execution_optimistic, finalized)| {
+                (state, execution_optimistic, finalized)
+            })
+    }
+
+    /// Return the `BeaconState` identified by `self` and its root.
+    pub fn state_and_root<T: BeaconChainTypes>(
+        &self,
+        chain: &BeaconChain<T>,
+    ) -> Result<
+        (
+            BeaconState<T::EthSpec>,
+            Hash256,
+            ExecutionOptimistic,
+            Finalized,
+        ),
+        warp::Rejection,
+    > {
         let ((state_root, execution_optimistic, finalized), slot_opt) = match &self.0 {
             CoreStateId::Head => {
                 let (cached_head, execution_status) = chain
@@ -181,6 +200,7 @@ impl StateId {
                     .map_err(warp_utils::reject::beacon_chain_error)?;
                 return Ok((
                     cached_head.snapshot.beacon_state.clone(),
+                    cached_head.head_state_root(),
                     execution_status.is_optimistic_or_invalid(),
                     false,
                 ));
@@ -201,7 +221,7 @@ impl StateId {
                 })
             })?;
 
-        Ok((state, execution_optimistic, finalized))
+        Ok((state, state_root, execution_optimistic, finalized))
     }
 
     /// Map a function across the `BeaconState` identified by `self`.
@@ -219,7 +239,22 @@ impl StateId {
     where
         F: Fn(&BeaconState<T::EthSpec>, bool, bool) -> Result<U, warp::Rejection>,
     {
-        let (state, execution_optimistic, finalized) = match &self.0 {
+        self.map_state_and_root(chain, |state, _, execution_optimistic, finalized| {
+            func(state, execution_optimistic, finalized)
+        })
+    }
+
+    /// As `map_state_and_execution_optimistic_and_finalized`, but the root of the state is also
+    /// provided to the `func` closure.
+    pub fn map_state_and_root<T: BeaconChainTypes, F, U>(
+        &self,
+        chain: &BeaconChain<T>,
+        func: F,
+    ) -> Result<U, warp::Rejection>
+    where
+        F: FnOnce(&BeaconState<T::EthSpec>, Hash256, bool, bool) -> Result<U, warp::Rejection>,
+    {
+        let (state, state_root, execution_optimistic, finalized) = match &self.0 {
             CoreStateId::Head => {
                 let (head, execution_status) = chain
                     .canonical_head
@@ -227,14 +262,15 @@ impl StateId {
                     .map_err(warp_utils::reject::beacon_chain_error)?;
                 return func(
                     &head.snapshot.beacon_state,
+                    head.head_state_root(),
                     execution_status.is_optimistic_or_invalid(),
                     false,
                 );
             }
-            _ => self.state(chain)?,
+            _ => self.state_and_root(chain)?,
         };
 
-        func(&state, execution_optimistic, finalized)
+        func(&state, state_root, execution_optimistic, finalized)
     }
 }
 
@@ -0,0 +1,222 @@
+//! Streaming of large response bodies.
+//!
+//! Full states and validator lists are encoded incrementally on a blocking thread and sent to the
+//! client in chunks, using chunked transfer encoding. The channel between the encoder and the
+//! response body is bounded, so the encoder waits for slow clients and the memory used by a
+//! response does not grow with its size.
+//!
+//! Since each response occupies a blocking thread until it is sent, the number of responses
+//! streamed at once is limited, and a client which stops reading is disconnected after
+//! `SEND_TIMEOUT`.
+use crate::response_cache::Capture;
+use bytes::Bytes;
+use eth2::CONTENT_TYPE_HEADER;
+use serde::Serialize;
+use std::io::{self, Write};
+use std::sync::{Arc, LazyLock};
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+use tokio::sync::{mpsc, Semaphore};
+use tokio_stream::wrappers::ReceiverStream;
+use tracing::Span;
+use warp::hyper::Body;
+use warp::reply::{self, Reply, Response};
+
+/// The size of the chunks sent to the client.
+const CHUNK_SIZE: usize = 64 * 1024;
+/// The number of chunks which may be waiting to be sent before the encoder blocks.
+const MAX_BUFFERED_CHUNKS: usize = 4;
+/// The maximum number of responses which may be streamed at once. Further requests for streamed
+/// responses are rejected with a `503` status code.
+const MAX_CONCURRENT_STREAMS: usize = 32;
+/// The time a client may take to accept a chunk before the response is aborted.
+const SEND_TIMEOUT: Duration = Duration::from_secs(30);
+
+static STREAM_PERMITS: LazyLock<Arc<Semaphore>> =
+    LazyLock::new(|| Arc::new(Semaphore::new(MAX_CONCURRENT_STREAMS)));
+
+/// An `io::Write` which sends its output to the response body in chunks of `CHUNK_SIZE` bytes.
+pub struct ChunkWriter {
+    buf: Vec<u8>,
+    tx: mpsc::Sender<io::Result<Bytes>>,
+    handle: Handle,
+    /// The total time spent waiting for the client to accept chunks.
+    send_wait: Duration,
+    /// A copy of the body written so far, which is discarded if it grows too long to cache.
+    capture: Option<(Vec<u8>, Capture)>,
+}
+
+impl ChunkWriter {
+    /// The total time spent waiting for the client to accept the body written so far, which
+    /// allows the time spent encoding the body to be measured.
+    pub fn send_wait(&self) -> Duration {
+        self.send_wait
+    }
+
+    /// Send `item` to the response body, waiting at most `SEND_TIMEOUT` for the client.
+    fn send(&mut self, item: io::Result<Bytes>) -> io::Result<()> {
+        let start = Instant::now();
+        let result = self
+            .handle
+            .block_on(tokio::time::timeout(SEND_TIMEOUT, self.tx.send(item)));
+        self.send_wait += start.elapsed();
+
+        match result {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(_)) => Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "client disconnected",
+            )),
+            Err(_) => Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "client did not read the response in time",
+            )),
+        }
+    }
+
+    fn send_chunk(&mut self) -> io::Result<()> {
+        if self.buf.is_empty() {
+            return Ok(());
+        }
+        let chunk = std::mem::replace(&mut self.buf, Vec::with_capacity(CHUNK_SIZE));
+        let captured = match &mut self.capture {
+            Some((captured, capture)) if captured.len() + chunk.len() <= capture.max_len() => {
+                captured.extend_from_slice(&chunk);
+                true
+            }
+            _ => false,
+        };
+        if !captured {
+            self.capture = None;
+        }
+        self.send(Ok(chunk.into()))
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        // Accept at most the remainder of the current chunk, so that chunks never grow beyond
+        // `CHUNK_SIZE`. `write_all` will call again with the rest.
+        let len = bytes.len().min(CHUNK_SIZE - self.buf.len());
+        self.buf.extend_from_slice(&bytes[..len]);
+        if self.buf.len() >= CHUNK_SIZE {
+            self.send_chunk()?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.send_chunk()
+    }
+}
+
+/// Create a response with a body written by `write_body` on a blocking thread.
+///
+/// The status and headers are sent before the body is written, so errors from `write_body` can
+/// no longer be reported to the client. Instead, the response is aborted, so that the client sees
+/// a truncated body rather than one which appears complete.
+///
+/// If `capture` is provided, the body is also added to the response cache once it is complete.
+///
+/// Returns an error if `MAX_CONCURRENT_STREAMS` responses are already being streamed.
+pub fn streaming_response<F>(
+    capture: Option<Capture>,
+    write_body: F,
+) -> Result<Response, warp::Rejection>
+where
+    F: FnOnce(&mut ChunkWriter) -> io::Result<()> + Send + 'static,
+{
+    let permit = STREAM_PERMITS.clone().try_acquire_owned().map_err(|_| {
+        warp_utils::reject::service_unavailable(format!(
+            "too many responses are being streamed, the limit is {}",
+            MAX_CONCURRENT_STREAMS
+        ))
+    })?;
+    let handle = Handle::try_current()
+        .map_err(|e| warp_utils::reject::custom_server_error(format!("no async runtime: {}", e)))?;
+    let (tx, rx) = mpsc::channel(MAX_BUFFERED_CHUNKS);
+    let span = Span::current();
+
+    // The body is written on its own thread rather than in the task which built the response, so
+    // that a slow client does not occupy a beacon processor worker.
+    tokio::task::spawn_blocking(move || {
+        let _permit = permit;
+        let _span = span.entered();
+        let mut writer = ChunkWriter {
+            buf: Vec::with_capacity(CHUNK_SIZE),
+            tx,
+            handle,
+            send_wait: Duration::ZERO,
+            capture: capture.map(|capture| (vec![], capture)),
+        };
+        match write_body(&mut writer).and_then(|()| writer.flush()) {
+            Ok(()) => {
+                if let Some((captured, capture)) = writer.capture {
+                    capture.complete(captured.into());
+                }
+            }
+            Err(e) => {
+                // Ignore failures to send, which mean that the client has already disconnected
+                // or stopped reading.
+                let _ = writer.send(Err(e));
+            }
+        }
+    });
+
+    Ok(Response::new(Body::wrap_stream(ReceiverStream::new(rx))))
+}
+
+/// A JSON array which is written one item at a time.
+pub struct JsonList<'a> {
+    writer: &'a mut ChunkWriter,
+    is_empty: bool,
+}
+
+impl JsonList<'_> {
+    pub fn push<T: Serialize>(&mut self, item: &T) -> io::Result<()> {
+        if !self.is_empty {
+            self.writer.write_all(b",")?;
+        }
+        self.is_empty = false;
+        serde_json::to_writer(&mut *self.writer, item).map_err(Into::into)
+    }
+}
+
+/// Create a JSON response with the same layout as an `ExecutionOptimisticFinalizedResponse`,
+/// with a `data` list of the items pushed by `write_items`.
+pub fn json_list_response<F>(
+    execution_optimistic: bool,
+    finalized: bool,
+    capture: Option<Capture>,
+    write_items: F,
+) -> Result<Response, warp::Rejection>
+where
+    F: FnOnce(&mut JsonList) -> io::Result<()> + Send + 'static,
+{
+    let response = streaming_response(capture, move |writer| {
+        write!(
+            writer,
+            r#"{{"execution_optimistic":{},"finalized":{},"data":["#,
+            execution_optimistic, finalized
+        )?;
+        let mut list = JsonList {
+            writer,
+            is_empty: true,
+        };
+        write_items(&mut list)?;
+        list.writer.write_all(b"]}")
+    })?;
+    Ok(add_json_content_type_header(response))
+}
+
+/// Create a JSON response, serializing `value` as it is sent.
+pub fn json_response<T: Serialize + Send + 'static>(value: T) -> Result<Response, warp::Rejection> {
+    let response = streaming_response(None, move |writer| {
+        serde_json::to_writer(writer, &value).map_err(Into::into)
+    })?;
+    Ok(add_json_content_type_header(response))
+}
+
+fn add_json_content_type_header<T: Reply>(reply: T) -> Response {
+    reply::with_header(reply, CONTENT_TYPE_HEADER, "application/json").into_response()
+}
@@ -2,6 +2,7 @@ use beacon_processor::{BeaconProcessorSend, BlockingOrAsync, Work, WorkEvent};
 use serde::Serialize;
 use std::future::Future;
 use tokio::sync::{mpsc::error::TrySendError, oneshot};
+use tracing::{info_span, Instrument, Span};
 use types::EthSpec;
 use warp::reply::{Reply, Response};
 use warp_utils::reject::convert_rejection;
@@ -57,7 +58,10 @@ impl<E: EthSpec> TaskSpawner<E> {
             // Create a closure that will execute `func` and send the result to
             // a channel held by this thread.
             let (tx, rx) = oneshot::channel();
+            let (span, queue_span) = request_spans();
             let process_fn = move || {
+                drop(queue_span);
+                let _span = span.entered();
                 // Execute the function, collect the return value.
                 let func_result = func();
                 // Send the result down the channel. Ignore any failures; the
@@ -77,7 +81,8 @@ impl<E: EthSpec> TaskSpawner<E> {
         } else {
             // There is no beacon processor so spawn a task directly on the
             // tokio executor.
-            warp_utils::task::blocking_task(func).await
+            let span = Span::current();
+            warp_utils::task::blocking_task(move || span.in_scope(func)).await
         }
     }
 
@@ -127,13 +132,16 @@ impl<E: EthSpec> TaskSpawner<E> {
             // Create a wrapper future that will execute `func` and send the
             // result to a channel held by this thread.
             let (tx, rx) = oneshot::channel();
+            let (span, queue_span) = request_spans();
             let process_fn = async move {
+                drop(queue_span);
                 // Await the future, collect the return value.
                 let func_result = func.await;
                 // Send the result down the channel. Ignore any failures; the
                 // send can only fail if the receiver is dropped.
                 let _ = tx.send(func_result);
-            };
+            }
+            .instrument(span);
 
             // Send the function to the beacon processor for execution at some arbitrary time.
             send_to_beacon_processor(
@@ -147,7 +155,7 @@ impl<E: EthSpec> TaskSpawner<E> {
         } else {
             // There is no beacon processor so spawn a task directly on the
             // tokio executor.
-            tokio::task::spawn(func)
+            tokio::task::spawn(func.instrument(Span::current()))
                 .await
                 .map_err(|_| {
                     warp_utils::reject::custom_server_error("Tokio failed to spawn task".into())
@@ -157,6 +165,14 @@ impl<E: EthSpec> TaskSpawner<E> {
     }
 }
 
+/// Returns the span of the current request and a child span which should be dropped once its task
+/// leaves the `BeaconProcessor` queue, so that the time spent queueing is included in traces.
+fn request_spans() -> (Span, Span) {
+    let span = Span::current();
+    let queue_span = info_span!(parent: &span, "beacon_processor_queue");
+    (span, queue_span)
+}
+
 /// Send a task to the beacon processor and await execution.
 ///
 /// If the task is not executed, return an `Err` with an error message
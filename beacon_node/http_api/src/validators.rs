@@ -1,116 +1,104 @@
+use crate::response_cache::{cached_response, ResponseCache};
 use crate::state_id::StateId;
+use crate::streaming::json_list_response;
 use beacon_chain::{BeaconChain, BeaconChainTypes};
-use eth2::types::{
-    self as api_types, ExecutionOptimisticFinalizedResponse, ValidatorBalanceData, ValidatorData,
-    ValidatorId, ValidatorStatus,
-};
+use eth2::types::{self as api_types, ValidatorBalanceData, ValidatorData, ValidatorId};
 use std::{collections::HashSet, sync::Arc};
+use warp::reply::Response;
 
+/// Serve the validators of a state, streaming them to the client one at a time.
 pub fn get_beacon_state_validators<T: BeaconChainTypes>(
     state_id: StateId,
     chain: Arc<BeaconChain<T>>,
-    query_ids: &Option<Vec<ValidatorId>>,
-    query_statuses: &Option<Vec<ValidatorStatus>>,
-) -> Result<ExecutionOptimisticFinalizedResponse<Vec<ValidatorData>>, warp::Rejection> {
-    let (data, execution_optimistic, finalized) = state_id
-        .map_state_and_execution_optimistic_and_finalized(
-            &chain,
-            |state, execution_optimistic, finalized| {
-                let epoch = state.current_epoch();
-                let far_future_epoch = chain.spec.far_future_epoch;
-                let ids_filter_set: Option<HashSet<&ValidatorId>> =
-                    query_ids.as_ref().map(HashSet::from_iter);
+    response_cache: Option<&Arc<ResponseCache>>,
+    request: String,
+    query_ids: Option<Vec<ValidatorId>>,
+    query_statuses: Option<Vec<api_types::ValidatorStatus>>,
+) -> Result<Response, warp::Rejection> {
+    cached_response(response_cache, &chain, &state_id, request, |capture| {
+        let (state, state_root, execution_optimistic, finalized) =
+            state_id.state_and_root(&chain)?;
+        let capture = capture.and_then(|capture| capture.for_state_root(state_root));
+        let far_future_epoch = chain.spec.far_future_epoch;
 
-                Ok((
-                    state
-                        .validators()
-                        .iter()
-                        .zip(state.balances().iter())
-                        .enumerate()
-                        // filter by validator id(s) if provided
-                        .filter(|(index, (validator, _))| {
-                            ids_filter_set.as_ref().map_or(true, |ids_set| {
-                                ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
-                                    || ids_set.contains(&ValidatorId::Index(*index as u64))
-                            })
-                        })
-                        // filter by status(es) if provided and map the result
-                        .filter_map(|(index, (validator, balance))| {
-                            let status = api_types::ValidatorStatus::from_validator(
-                                validator,
-                                epoch,
-                                far_future_epoch,
-                            );
+        json_list_response(execution_optimistic, finalized, capture, move |list| {
+            let epoch = state.current_epoch();
+            let ids_filter_set: Option<HashSet<&ValidatorId>> =
+                query_ids.as_ref().map(HashSet::from_iter);
 
-                            let status_matches = query_statuses.as_ref().map_or(true, |statuses| {
-                                statuses.contains(&status)
-                                    || statuses.contains(&status.superstatus())
-                            });
+            for (index, (validator, balance)) in state
+                .validators()
+                .iter()
+                .zip(state.balances().iter())
+                .enumerate()
+            {
+                // filter by validator id(s) if provided
+                let id_matches = ids_filter_set.as_ref().map_or(true, |ids_set| {
+                    ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
+                        || ids_set.contains(&ValidatorId::Index(index as u64))
+                });
+                if !id_matches {
+                    continue;
+                }
 
-                            if status_matches {
-                                Some(ValidatorData {
-                                    index: index as u64,
-                                    balance: *balance,
-                                    status,
-                                    validator: validator.clone(),
-                                })
-                            } else {
-                                None
-                            }
-                        })
-                        .collect::<Vec<_>>(),
-                    execution_optimistic,
-                    finalized,
-                ))
-            },
-        )?;
+                // filter by status(es) if provided
+                let status =
+                    api_types::ValidatorStatus::from_validator(validator, epoch, far_future_epoch);
+                let status_matches = query_statuses.as_ref().map_or(true, |statuses| {
+                    statuses.contains(&status) || statuses.contains(&status.superstatus())
+                });
+                if !status_matches {
+                    continue;
+                }
 
-    Ok(ExecutionOptimisticFinalizedResponse {
-        data,
-        execution_optimistic: Some(execution_optimistic),
-        finalized: Some(finalized),
+                list.push(&ValidatorData {
+                    index: index as u64,
+                    balance: *balance,
+                    status,
+                    validator: validator.clone(),
+                })?;
+            }
+            Ok(())
+        })
     })
 }
 
+/// Serve the balances of the validators of a state, streaming them to the client one at a time.
 pub fn get_beacon_state_validator_balances<T: BeaconChainTypes>(
     state_id: StateId,
     chain: Arc<BeaconChain<T>>,
-    optional_ids: Option<&[ValidatorId]>,
-) -> Result<ExecutionOptimisticFinalizedResponse<Vec<ValidatorBalanceData>>, warp::Rejection> {
-    let (data, execution_optimistic, finalized) = state_id
-        .map_state_and_execution_optimistic_and_finalized(
-            &chain,
-            |state, execution_optimistic, finalized| {
-                let ids_filter_set: Option<HashSet<&ValidatorId>> =
-                    optional_ids.map(|f| HashSet::from_iter(f.iter()));
+    response_cache: Option<&Arc<ResponseCache>>,
+    request: String,
+    optional_ids: Option<Vec<ValidatorId>>,
+) -> Result<Response, warp::Rejection> {
+    cached_response(response_cache, &chain, &state_id, request, |capture| {
+        let (state, state_root, execution_optimistic, finalized) =
+            state_id.state_and_root(&chain)?;
+        let capture = capture.and_then(|capture| capture.for_state_root(state_root));
 
-                Ok((
-                    state
-                        .validators()
-                        .iter()
-                        .zip(state.balances().iter())
-                        .enumerate()
-                        // filter by validator id(s) if provided
-                        .filter(|(index, (validator, _))| {
-                            ids_filter_set.as_ref().map_or(true, |ids_set| {
-                                ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
-                                    || ids_set.contains(&ValidatorId::Index(*index as u64))
-                            })
-                        })
-                        .map(|(index, (_, balance))| ValidatorBalanceData {
-                            index: index as u64,
-                            balance: *balance,
-                        })
-                        .collect::<Vec<_>>(),
-                    execution_optimistic,
-                    finalized,
-                ))
-            },
-        )?;
+        json_list_response(execution_optimistic, finalized, capture, move |list| {
+            let ids_filter_set: Option<HashSet<&ValidatorId>> =
+                optional_ids.as_ref().map(HashSet::from_iter);
 
-    Ok(api_types::ExecutionOptimisticFinalizedResponse {
-        data,
-        execution_optimistic: Some(execution_optimistic),
-        finalized: Some(finalized),
+            for (index, (validator, balance)) in state
+                .validators()
+                .iter()
+                .zip(state.balances().iter())
+                .enumerate()
+            {
+                // filter by validator id(s) if provided
+                let id_matches = ids_filter_set.as_ref().map_or(true, |ids_set| {
+                    ids_set.contains(&ValidatorId::PublicKey(validator.pubkey))
+                        || ids_set.contains(&ValidatorId::Index(index as u64))
+                });
+                if id_matches {
+                    list.push(&ValidatorBalanceData {
+                        index: index as u64,
+                        balance: *balance,
+                    })?;
+                }
+            }
+            Ok(())
+        })
     })
 }
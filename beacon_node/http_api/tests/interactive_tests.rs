@@ -1,16 +1,20 @@
 //! Generic tests that make use of the (newer) `InteractiveApiTester`
 use beacon_chain::{
     chain_config::{DisallowedReOrgOffsets, ReOrgThreshold},
+    observed_operations::ObservationOutcome,
     test_utils::{AttestationStrategy, BlockStrategy, LightClientStrategy, SyncCommitteeStrategy},
     ChainConfig,
 };
 use beacon_processor::work_reprocessing_queue::ReprocessQueueMessage;
 use eth2::types::ProduceBlockV3Response;
-use eth2::types::{DepositContractData, StateId};
+use eth2::types::{BlockId, DepositContractData, StateId, ValidatorId};
+use eth2::{reqwest, BeaconNodeHttpClient, SensitiveUrl, Timeouts};
 use execution_layer::{ForkchoiceState, PayloadAttributes};
 use http_api::test_utils::InteractiveTester;
+use http_api::{ApiToken, Config, RateLimitConfig, Role};
 use parking_lot::Mutex;
 use slot_clock::SlotClock;
+use ssz::Encode;
 use state_processing::{
     per_block_processing::get_expected_withdrawals, state_advance::complete_state_advance,
 };
@@ -122,6 +126,853 @@ async fn state_by_root_pruned_from_fork_choice() {
     }
 }
 
+// Test that fork choice snapshots are served for slots within the configured window, and that
+// older snapshots are pruned.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn fork_choice_snapshots() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let snapshot_slots = 4;
+
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        Some(Box::new(move |builder| {
+            builder
+                .deterministic_keypairs(validator_count)
+                .fresh_ephemeral_store()
+                .chain_config(ChainConfig {
+                    fork_choice_snapshot_slots: snapshot_slots,
+                    ..ChainConfig::default()
+                })
+        })),
+        None,
+        Default::default(),
+    )
+    .await;
+
+    let client = &tester.client;
+    let harness = &tester.harness;
+
+    // Import one block per slot, snapshotting fork choice after each.
+    let mut slots = vec![];
+    for _ in 0..2 * snapshot_slots {
+        harness.extend_slots(1).await;
+        let slot = harness.chain.slot().unwrap();
+        harness.chain.persist_fork_choice_snapshot(slot).unwrap();
+        slots.push(slot);
+    }
+
+    let (pruned, retained) = slots.split_at(snapshot_slots as usize);
+    for slot in pruned {
+        let error = client
+            .get_lighthouse_fork_choice_snapshot(*slot)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status().unwrap(), 404);
+    }
+    for slot in retained {
+        let snapshot = client
+            .get_lighthouse_fork_choice_snapshot(*slot)
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(snapshot.slot, *slot);
+    }
+
+    let from = retained[retained.len() - 2];
+    let to = retained[retained.len() - 1];
+    let diff = client
+        .get_lighthouse_fork_choice_diff(from, to)
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(diff.from_slot, from);
+    assert_eq!(diff.to_slot, to);
+    assert_eq!(diff.to_head_root, harness.head_block_root());
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].root, diff.to_head_root);
+}
+
+// Test that the timings of recent blocks are served, including blocks which were only observed.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn block_timings() {
+    type E = MinimalEthSpec;
+
+    let validator_count = 24;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let client = &tester.client;
+    let harness = &tester.harness;
+
+    harness.extend_slots(2).await;
+    let head_root = harness.head_block_root();
+
+    let timings = client
+        .get_lighthouse_block_timings(BlockId::Head)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(timings.block_root, head_root);
+    assert_eq!(timings.slot, harness.head_slot());
+    assert!(timings.observed.is_some());
+    assert!(timings.consensus_verified.is_some());
+    assert!(timings.executed.is_some());
+    assert!(timings.available.is_some());
+    assert!(timings.fork_choice_applied.is_some());
+
+    // Blocks which are not in the database are looked up by root.
+    let observed_root = Hash256::repeat_byte(0xaa);
+    let observed_time = harness.chain.slot_clock.now_duration().unwrap();
+    harness.chain.block_times_cache.write().set_time_observed(
+        observed_root,
+        harness.head_slot(),
+        observed_time,
+        Some("peer".to_string()),
+        None,
+    );
+    let timings = client
+        .get_lighthouse_block_timings(BlockId::Root(observed_root))
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(timings.observed, Some(observed_time.as_millis() as u64));
+    assert_eq!(timings.peer_id.as_deref(), Some("peer"));
+    assert_eq!(timings.imported, None);
+
+    let unknown = client
+        .get_lighthouse_block_timings(BlockId::Root(Hash256::repeat_byte(0xbb)))
+        .await
+        .unwrap();
+    assert!(unknown.is_none());
+}
+
+// Test that the op pool can be exported from one node and imported into another, and that
+// operations are not imported twice.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn op_pool_export_import() {
+    let validator_count = 24;
+
+    let source = InteractiveTester::<E>::new(None, validator_count).await;
+    let target = InteractiveTester::<E>::new(None, validator_count).await;
+
+    let source_chain = &source.harness.chain;
+    let proposer_slashing = source.harness.make_proposer_slashing(2);
+    match source_chain
+        .verify_proposer_slashing_for_gossip(proposer_slashing)
+        .unwrap()
+    {
+        ObservationOutcome::New(slashing) => source_chain.import_proposer_slashing(slashing),
+        ObservationOutcome::AlreadyKnown => panic!("proposer slashing should be new"),
+    }
+    let attester_slashing = source.harness.make_attester_slashing(vec![3]);
+    match source_chain
+        .verify_attester_slashing_for_gossip(attester_slashing)
+        .unwrap()
+    {
+        ObservationOutcome::New(slashing) => source_chain.import_attester_slashing(slashing),
+        ObservationOutcome::AlreadyKnown => panic!("attester slashing should be new"),
+    }
+
+    let pool_ssz = source.client.get_lighthouse_op_pool_ssz().await.unwrap();
+
+    let summary = target
+        .client
+        .post_lighthouse_op_pool_ssz(pool_ssz.clone())
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(summary.proposer_slashings.imported, 1);
+    assert_eq!(summary.attester_slashings.imported, 1);
+    assert_eq!(target.harness.chain.op_pool.num_proposer_slashings(), 1);
+    assert_eq!(target.harness.chain.op_pool.num_attester_slashings(), 1);
+
+    // Importing the same pool again is a no-op.
+    let summary = target
+        .client
+        .post_lighthouse_op_pool_ssz(pool_ssz)
+        .await
+        .unwrap()
+        .data;
+    assert_eq!(summary.proposer_slashings.imported, 0);
+    assert_eq!(summary.proposer_slashings.rejected, 1);
+    assert_eq!(summary.attester_slashings.imported, 0);
+    assert_eq!(summary.attester_slashings.rejected, 1);
+
+    // Invalid SSZ is rejected.
+    let error = target
+        .client
+        .post_lighthouse_op_pool_ssz(vec![1, 2, 3])
+        .await
+        .unwrap_err();
+    assert_eq!(error.status().unwrap(), 400);
+}
+
+// Test that optimal block rewards are reported for every block in the range, and are never less
+// than the rewards actually earned.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn optimal_block_rewards() {
+    let validator_count = 24;
+    let slots_per_epoch = E::slots_per_epoch();
+
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    tester
+        .harness
+        .extend_slots(3 * slots_per_epoch as usize)
+        .await;
+
+    let start_slot = Slot::new(1);
+    let end_slot = Slot::new(slots_per_epoch);
+    let rewards = tester
+        .client
+        .get_lighthouse_analysis_optimal_block_rewards(start_slot, end_slot, None)
+        .await
+        .unwrap();
+
+    assert_eq!(rewards.blocks.len(), slots_per_epoch as usize);
+    for block in &rewards.blocks {
+        assert!(block.optimal_attestation_rewards >= block.actual_attestation_rewards);
+        assert_eq!(
+            block.reward_gap,
+            block.optimal_attestation_rewards - block.actual_attestation_rewards
+        );
+    }
+    assert_eq!(
+        rewards
+            .proposers
+            .iter()
+            .map(|proposer| proposer.blocks)
+            .sum::<usize>(),
+        rewards.blocks.len()
+    );
+
+    // An empty range is rejected.
+    let error = tester
+        .client
+        .get_lighthouse_analysis_optimal_block_rewards(end_slot, start_slot, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.status().unwrap(), 400);
+}
+
+// Test that every block is re-packed with each packing strategy, and that the strategies are never
+// worse than the block itself when all of its aggregates fit within the block.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn block_packing_strategies() {
+    let validator_count = 24;
+    let slots_per_epoch = E::slots_per_epoch();
+
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    tester
+        .harness
+        .extend_slots(3 * slots_per_epoch as usize)
+        .await;
+
+    let epoch = Epoch::new(1);
+    let blocks = tester
+        .client
+        .get_lighthouse_analysis_block_packing(epoch, epoch)
+        .await
+        .unwrap();
+    let compared = tester
+        .client
+        .get_lighthouse_analysis_block_packing_with_strategies(epoch, epoch, Some(1_000))
+        .await
+        .unwrap();
+
+    assert_eq!(compared.len(), slots_per_epoch as usize);
+    assert_eq!(compared.len(), blocks.len());
+    for (block, compared) in blocks.iter().zip(&compared) {
+        assert!(block.strategies.is_empty());
+        assert_eq!(compared.slot, block.slot);
+        assert_eq!(compared.included_attestations, block.included_attestations);
+
+        let names = compared
+            .strategies
+            .iter()
+            .map(|strategy| strategy.strategy.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["greedy", "optimal"]);
+
+        let greedy = compared.strategies[0].included_attestations;
+        let optimal = compared.strategies[1].included_attestations;
+        assert!(greedy >= compared.included_attestations);
+        assert!(optimal >= greedy);
+    }
+    assert!(compared
+        .iter()
+        .any(|block| block.strategies[1].included_attestations > 0));
+}
+
+/// A client for `tester` which sends `token` in the `Authorization` header of every request.
+fn client_with_token(tester: &InteractiveTester<E>, token: &str) -> BeaconNodeHttpClient {
+    BeaconNodeHttpClient::new(
+        SensitiveUrl::parse(tester.client.as_ref()).unwrap(),
+        Timeouts::set_all(Duration::from_secs(1)),
+    )
+    .with_api_token(token.to_string().into())
+}
+
+// Test that requests are only routed if their token grants the role required by the endpoint, and
+// that requests without a token are granted the public role.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn api_token_roles() {
+    let validator_count = 24;
+
+    let tokens = vec![
+        ApiToken {
+            token: "validator-token".to_string(),
+            role: Role::Validator,
+        },
+        ApiToken {
+            token: "admin-token".to_string(),
+            role: Role::Admin,
+        },
+    ];
+    let token_file = std::env::temp_dir().join(format!("api-tokens-{}.yaml", std::process::id()));
+    std::fs::write(&token_file, serde_yaml::to_string(&tokens).unwrap()).unwrap();
+
+    let config = Config {
+        auth_token_file: Some(token_file.clone()),
+        public_role: Some(Role::ReadOnly),
+        ..Config::default()
+    };
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        None,
+        None,
+        config,
+    )
+    .await;
+    std::fs::remove_file(&token_file).unwrap();
+
+    let public = &tester.client;
+    let validator = client_with_token(&tester, "validator-token");
+    let admin = client_with_token(&tester, "admin-token");
+    let unknown = client_with_token(&tester, "unknown-token");
+
+    // Read endpoints are public.
+    public.get_node_version().await.unwrap();
+    validator.get_node_version().await.unwrap();
+
+    // Node management requires the admin role.
+    let error = public.get_lighthouse_database_info().await.unwrap_err();
+    assert_eq!(error.status().unwrap(), 403);
+    let error = validator.get_lighthouse_database_info().await.unwrap_err();
+    assert_eq!(error.status().unwrap(), 403);
+    let error = validator
+        .post_lighthouse_database_reconstruct()
+        .await
+        .unwrap_err();
+    assert_eq!(error.status().unwrap(), 403);
+    admin.get_lighthouse_database_info().await.unwrap();
+
+    // Validator endpoints require the validator role.
+    let error = public
+        .get_validator_duties_proposer(Epoch::new(0))
+        .await
+        .unwrap_err();
+    assert_eq!(error.status().unwrap(), 403);
+    validator
+        .get_validator_duties_proposer(Epoch::new(0))
+        .await
+        .unwrap();
+    admin
+        .get_validator_duties_proposer(Epoch::new(0))
+        .await
+        .unwrap();
+
+    // An unknown token is rejected, even for public endpoints.
+    let error = unknown.get_node_version().await.unwrap_err();
+    assert_eq!(error.status().unwrap(), 403);
+}
+
+// Test that requests without a token are rejected when there is no public role.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn api_token_no_public_role() {
+    let validator_count = 24;
+
+    let tokens = vec![ApiToken {
+        token: "read-token".to_string(),
+        role: Role::ReadOnly,
+    }];
+    let token_file =
+        std::env::temp_dir().join(format!("api-tokens-no-public-{}.yaml", std::process::id()));
+    std::fs::write(&token_file, serde_yaml::to_string(&tokens).unwrap()).unwrap();
+
+    let config = Config {
+        auth_token_file: Some(token_file.clone()),
+        ..Config::default()
+    };
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        None,
+        None,
+        config,
+    )
+    .await;
+    std::fs::remove_file(&token_file).unwrap();
+
+    let error = tester.client.get_node_version().await.unwrap_err();
+    assert_eq!(error.status().unwrap(), 401);
+    client_with_token(&tester, "read-token")
+        .get_node_version()
+        .await
+        .unwrap();
+}
+
+// Test that clients are rejected with a retry hint once they exhaust their quota, and that the
+// cost of a request depends on the endpoint.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rate_limit() {
+    let validator_count = 24;
+
+    // The quota refills slowly enough that it is not replenished during the test.
+    let config = Config {
+        rate_limit: Some(RateLimitConfig {
+            cost_per_second: 1,
+            burst: 30,
+        }),
+        ..Config::default()
+    };
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        None,
+        None,
+        config,
+    )
+    .await;
+    let client = &tester.client;
+
+    // Listing the validators of the head state costs 10, and of a historic state 50.
+    client
+        .get_beacon_states_validators(StateId::Head, None, None)
+        .await
+        .unwrap();
+    let error = client
+        .get_beacon_states_validators(StateId::Slot(Slot::new(0)), None, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.status().unwrap(), 429);
+
+    // Cheap requests still succeed until the remaining quota is spent.
+    for _ in 0..20 {
+        client.get_node_version().await.unwrap();
+    }
+    let error = client.get_node_version().await.unwrap_err();
+    assert_eq!(error.status().unwrap(), 429);
+
+    // The response includes a hint of when to retry.
+    let url = format!(
+        "{}/eth/v1/node/version",
+        client.as_ref().trim_end_matches('/')
+    );
+    let response = reqwest::get(url).await.unwrap();
+    assert_eq!(response.status(), 429);
+    let retry_after: u64 = response.headers()[reqwest::header::RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(retry_after >= 1);
+}
+
+// Test that read-only tokens share a quota, while validator and admin tokens are not limited.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rate_limit_exempt_roles() {
+    let validator_count = 24;
+
+    let tokens = vec![
+        ApiToken {
+            token: "read-token".to_string(),
+            role: Role::ReadOnly,
+        },
+        ApiToken {
+            token: "validator-token".to_string(),
+            role: Role::Validator,
+        },
+        ApiToken {
+            token: "admin-token".to_string(),
+            role: Role::Admin,
+        },
+    ];
+    let token_file =
+        std::env::temp_dir().join(format!("api-tokens-rate-limit-{}.yaml", std::process::id()));
+    std::fs::write(&token_file, serde_yaml::to_string(&tokens).unwrap()).unwrap();
+
+    let config = Config {
+        auth_token_file: Some(token_file.clone()),
+        public_role: Some(Role::ReadOnly),
+        rate_limit: Some(RateLimitConfig {
+            cost_per_second: 1,
+            burst: 5,
+        }),
+        ..Config::default()
+    };
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        None,
+        None,
+        config,
+    )
+    .await;
+    std::fs::remove_file(&token_file).unwrap();
+
+    let public = &tester.client;
+    let read_only = client_with_token(&tester, "read-token");
+    let validator = client_with_token(&tester, "validator-token");
+    let admin = client_with_token(&tester, "admin-token");
+
+    // The read-only token has its own quota, separate from the public one.
+    for _ in 0..5 {
+        read_only.get_node_version().await.unwrap();
+    }
+    let error = read_only.get_node_version().await.unwrap_err();
+    assert_eq!(error.status().unwrap(), 429);
+    public.get_node_version().await.unwrap();
+
+    // Validator and admin tokens are never limited.
+    for _ in 0..20 {
+        validator.get_node_version().await.unwrap();
+        admin.get_node_version().await.unwrap();
+    }
+}
+
+// Test that states and validators are streamed, and that the streamed responses match the state.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn streamed_responses() {
+    let validator_count = 24;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+    harness.extend_slots(3).await;
+    let head_state = harness.get_current_state();
+
+    let ssz_state = client
+        .get_debug_beacon_states_ssz::<E>(StateId::Head, &harness.spec)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(ssz_state.as_ssz_bytes(), head_state.as_ssz_bytes());
+
+    let json_state = client
+        .get_debug_beacon_states::<E>(StateId::Head)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(json_state.data.as_ssz_bytes(), head_state.as_ssz_bytes());
+    assert_eq!(json_state.metadata.finalized, Some(false));
+
+    let validators = client
+        .get_beacon_states_validators(StateId::Head, None, None)
+        .await
+        .unwrap()
+        .unwrap()
+        .data;
+    assert_eq!(validators.len(), validator_count);
+    for (index, validator) in validators.iter().enumerate() {
+        assert_eq!(validator.index, index as u64);
+        assert_eq!(
+            validator.balance,
+            head_state.balances().get(index).copied().unwrap()
+        );
+        assert_eq!(
+            &validator.validator,
+            head_state.get_validator(index).unwrap()
+        );
+    }
+
+    let ids = [ValidatorId::Index(1), ValidatorId::Index(5)];
+    let balances = client
+        .get_beacon_states_validator_balances(StateId::Head, Some(&ids))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(balances.execution_optimistic, Some(false));
+    assert_eq!(
+        balances
+            .data
+            .iter()
+            .map(|balance| balance.index)
+            .collect::<Vec<_>>(),
+        vec![1, 5]
+    );
+
+    // Streamed responses are sent with chunked transfer encoding, rather than a length.
+    let url = format!(
+        "{}/eth/v1/beacon/states/head/validators",
+        client.as_ref().trim_end_matches('/')
+    );
+    let response = reqwest::get(url).await.unwrap();
+    assert_eq!(response.status(), 200);
+    assert!(response
+        .headers()
+        .get(reqwest::header::CONTENT_LENGTH)
+        .is_none());
+    assert_eq!(
+        response.headers()[reqwest::header::CONTENT_TYPE],
+        "application/json"
+    );
+}
+
+// Test that cached responses are served for repeated requests, keyed by their query and body,
+// and that responses about the head state are not served once the head changes.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn response_cache() {
+    let validator_count = 24;
+    let tester = InteractiveTester::<E>::new(None, validator_count).await;
+    let harness = &tester.harness;
+    let client = &tester.client;
+    harness.extend_slots(3).await;
+
+    let url = format!(
+        "{}/eth/v1/beacon/states/head/validators",
+        client.as_ref().trim_end_matches('/')
+    );
+    let hits = response_cache_hits();
+    let first = reqwest::get(&url).await.unwrap().bytes().await.unwrap();
+    let second = reqwest::get(&url).await.unwrap().bytes().await.unwrap();
+    assert_eq!(first, second);
+    assert_hits_since(hits, 1);
+
+    // Requests with different queries or bodies are cached separately.
+    let hits = response_cache_hits();
+    for ids in [vec![ValidatorId::Index(1)], vec![ValidatorId::Index(2)]] {
+        for _ in 0..2 {
+            let balances = client
+                .post_beacon_states_validator_balances(StateId::Head, ids.clone())
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            assert_eq!(balances.len(), 1);
+            assert_eq!(ValidatorId::Index(balances[0].index), ids[0]);
+        }
+    }
+    assert_hits_since(hits, 2);
+
+    // Responses about the previous head are not served for the new head.
+    for extend in [false, true] {
+        if extend {
+            harness.extend_slots(E::slots_per_epoch() as usize).await;
+        }
+        let hits = response_cache_hits();
+        for _ in 0..2 {
+            let committees = client
+                .get_beacon_states_committees(StateId::Head, None, None, None)
+                .await
+                .unwrap()
+                .unwrap()
+                .data;
+            let head_epoch = harness.get_current_state().current_epoch();
+            assert!(committees
+                .iter()
+                .all(|committee| committee.slot.epoch(E::slots_per_epoch()) == head_epoch));
+        }
+        assert_hits_since(hits, 1);
+    }
+
+    // Duties are cached by the head, so the next epoch's proposer duties follow the head.
+    let next_epoch = harness.get_current_state().current_epoch() + 1;
+    for extend in [false, true] {
+        if extend {
+            harness.extend_slots(1).await;
+        }
+        let hits = response_cache_hits();
+        for _ in 0..2 {
+            let duties = client
+                .get_validator_duties_proposer(next_epoch)
+                .await
+                .unwrap();
+            assert_eq!(duties.dependent_root, harness.head_block_root());
+        }
+        assert_hits_since(hits, 1);
+    }
+
+    let hits = response_cache_hits();
+    for indices in [vec![1, 2], vec![3]] {
+        for _ in 0..2 {
+            let duties = client
+                .post_validator_duties_attester(next_epoch, &indices)
+                .await
+                .unwrap()
+                .data;
+            let duty_indices = duties
+                .iter()
+                .map(|duty| duty.validator_index)
+                .collect::<Vec<_>>();
+            assert_eq!(duty_indices, indices);
+        }
+    }
+    assert_hits_since(hits, 2);
+}
+
+/// The number of responses served from the HTTP API response cache.
+fn response_cache_hits() -> u64 {
+    metrics::gather()
+        .iter()
+        .filter(|family| family.get_name() == "http_api_response_cache_requests_total")
+        .flat_map(|family| family.get_metric())
+        .filter(|metric| {
+            metric
+                .get_label()
+                .iter()
+                .any(|label| label.get_name() == "outcome" && label.get_value() == "hit")
+        })
+        .map(|metric| metric.get_counter().get_value() as u64)
+        .sum()
+}
+
+/// Assert that at least `expected` responses have been served from the cache since it had served
+/// `hits`. The counter is shared with the other tests in this binary, which may run concurrently.
+fn assert_hits_since(hits: u64, expected: u64) {
+    let served = response_cache_hits() - hits;
+    assert!(
+        served >= expected,
+        "expected {expected} cache hits, got {served}"
+    );
+}
+
+// Test that GraphQL queries return the selected fields, and that validators are paginated.
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn graphql() {
+    let validator_count = 24;
+
+    let config = Config {
+        enable_graphql: true,
+        ..Config::default()
+    };
+    let tester = InteractiveTester::<E>::new_with_initializer_and_mutator(
+        None,
+        validator_count,
+        None,
+        None,
+        config,
+    )
+    .await;
+    let harness = &tester.harness;
+    harness.extend_slots(3).await;
+
+    let url = format!(
+        "{}/lighthouse/graphql",
+        tester.client.as_ref().trim_end_matches('/')
+    );
+    let query = |query: &str| {
+        let url = url.clone();
+        let body = serde_json::json!({ "query": query });
+        async move {
+            let response = reqwest::Client::new()
+                .post(url)
+                .json(&body)
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 200);
+            response.json::<serde_json::Value>().await.unwrap()
+        }
+    };
+
+    let response = query(
+        r#"{
+            block(id: "head") {
+                root
+                slot
+                state {
+                    root
+                    executionOptimistic
+                    finalized
+                    validatorCount
+                    validators(limit: 10) { nodes { index balance } nextOffset }
+                }
+            }
+            blocks(startSlot: 1, limit: 3) { slot }
+        }"#,
+    )
+    .await;
+    assert!(response.get("errors").is_none(), "{response}");
+    let block = &response["data"]["block"];
+    assert_eq!(
+        block["root"],
+        format!("{:?}", harness.head_block_root()).as_str()
+    );
+    assert_eq!(block["slot"], 3);
+    // Fields which were not selected are not returned.
+    assert!(block.get("proposerIndex").is_none());
+
+    let state = &block["state"];
+    assert_eq!(
+        state["root"],
+        format!(
+            "{:?}",
+            harness.chain.canonical_head.cached_head().head_state_root()
+        )
+        .as_str()
+    );
+    assert_eq!(state["executionOptimistic"], false);
+    assert_eq!(state["finalized"], false);
+    assert_eq!(state["validatorCount"], validator_count);
+    let page = &state["validators"];
+    assert_eq!(page["nodes"].as_array().unwrap().len(), 10);
+    assert_eq!(page["nodes"][0]["index"], 0);
+    assert_eq!(page["nextOffset"], 10);
+
+    let slots = response["data"]["blocks"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|block| block["slot"].as_u64().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(slots, vec![1, 2, 3]);
+
+    // The last page has no next offset.
+    let response = query(
+        r#"{
+            state(id: "head") {
+                validators(offset: 20, limit: 10) { nodes { index } nextOffset }
+            }
+        }"#,
+    )
+    .await;
+    let page = &response["data"]["state"]["validators"];
+    assert_eq!(page["nodes"].as_array().unwrap().len(), 4);
+    assert_eq!(page["nodes"][0]["index"], 20);
+    assert!(page["nextOffset"].is_null());
+
+    // The largest page of validators is within the complexity limit.
+    let response =
+        query(r#"{ state(id: "head") { validators(limit: 4500) { nodes { index } } } }"#).await;
+    assert!(response.get("errors").is_none(), "{response}");
+    let page = &response["data"]["state"]["validators"];
+    assert_eq!(page["nodes"].as_array().unwrap().len(), validator_count);
+
+    // Unknown blocks are `null` rather than errors.
+    let response = query(&format!(
+        r#"{{ block(id: "{:?}") {{ slot }} }}"#,
+        Hash256::repeat_byte(0xaa)
+    ))
+    .await;
+    assert!(response.get("errors").is_none(), "{response}");
+    assert!(response["data"]["block"].is_null());
+
+    // Queries which could load a state for each of many blocks, or list many validators with
+    // many fields, are rejected before they are executed.
+    for query_str in [
+        r#"{ blocks(startSlot: 0, limit: 64) { state { slot } } }"#,
+        r#"{ state(id: "head") { validators(limit: 10000) { nodes { index balance status } } } }"#,
+    ] {
+        let response = query(query_str).await;
+        let message = response["errors"][0]["message"].as_str().unwrap();
+        assert!(message.contains("complex"), "{response}");
+        assert!(response["data"].is_null(), "{response}");
+    }
+}
+
 /// Data structure for tracking fork choice updates received by the mock execution layer.
 #[derive(Debug, Default)]
 struct ForkChoiceUpdates {
@@ -14,7 +14,8 @@ use eth2::{
     StatusCode, Timeouts,
 };
 use execution_layer::test_utils::{
-    MockBuilder, Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI, DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
+    Behaviour, MockBuilder, Operation, DEFAULT_BUILDER_PAYLOAD_VALUE_WEI,
+    DEFAULT_MOCK_EL_PAYLOAD_VALUE_WEI,
 };
 use futures::stream::{Stream, StreamExt};
 use futures::FutureExt;
@@ -22,7 +23,7 @@ use http_api::{
     test_utils::{create_api_server, ApiServer},
     BlockId, StateId,
 };
-use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId};
+use lighthouse_network::{types::SyncState, Enr, EnrExt, PeerId, SubnetCoverage};
 use logging::test_logger;
 use network::NetworkReceivers;
 use proto_array::ExecutionStatus;
@@ -37,8 +38,9 @@ use tokio::time::Duration;
 use tree_hash::TreeHash;
 use types::application_domain::ApplicationDomain;
 use types::{
-    attestation::AttestationBase, AggregateSignature, BitList, Domain, EthSpec, ExecutionBlockHash,
-    Hash256, Keypair, MainnetEthSpec, RelativeEpoch, SelectionProof, SignedRoot, Slot,
+    attestation::AttestationBase, AggregateSignature, BitList, DataColumnSubnetId, Domain, EthSpec,
+    ExecutionBlockHash, Hash256, Keypair, MainnetEthSpec, RelativeEpoch, SelectionProof,
+    SignedRoot, Slot,
 };
 
 type E = MainnetEthSpec;
@@ -4673,6 +4675,29 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_payload_v3_rejects_adversarial_relay(self, behaviour: Behaviour) -> Self {
+        self.mock_builder.as_ref().unwrap().set_behaviour(behaviour);
+
+        let slot = self.chain.slot().unwrap();
+        let epoch = self.chain.epoch().unwrap();
+
+        let (_, randao_reveal) = self.get_test_randao(slot, epoch).await;
+
+        let (payload_type, metadata) = self
+            .client
+            .get_validator_blocks_v3::<E>(slot, &randao_reveal, None, None)
+            .await
+            .unwrap();
+        Self::check_block_v3_metadata(&metadata, &payload_type);
+
+        match payload_type.data {
+            ProduceBlockV3Response::Full(_) => (),
+            ProduceBlockV3Response::Blinded(_) => panic!("Expecting a full payload"),
+        };
+
+        self
+    }
+
     pub async fn test_builder_chain_health_skips(self) -> Self {
         let slot = self.chain.slot().unwrap();
 
@@ -5467,6 +5492,28 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_peers_subnet_coverage(self) -> Self {
+        let network_globals = self.ctx.network_globals.as_ref().unwrap();
+        let expected = SubnetCoverage {
+            min_peers: 2,
+            attestation: vec![1; 64],
+            sync_committee: vec![0, 1, 2, 3],
+            data_column: vec![0, 3],
+            sampling_subnets: vec![DataColumnSubnetId::new(1)],
+        };
+        *network_globals.subnet_coverage.write() = expected.clone();
+
+        let result = self
+            .client
+            .get_lighthouse_peers_subnet_coverage()
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(result, expected);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_proto_array(self) -> Self {
         self.client.get_lighthouse_proto_array().await.unwrap();
 
@@ -5527,6 +5574,21 @@ impl ApiTester {
         self
     }
 
+    pub async fn test_get_lighthouse_execution_engines(self) -> Self {
+        let engines = self
+            .client
+            .get_lighthouse_execution_engines()
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(engines.len(), 1);
+        assert!(engines[0].primary);
+        assert!(engines[0].active);
+
+        self
+    }
+
     pub async fn test_get_lighthouse_database_info(self) -> Self {
         let info = self.client.get_lighthouse_database_info().await.unwrap();
 
@@ -6791,6 +6853,22 @@ async fn get_full_block_invalid_signature_v3() {
         .await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_full_block_adversarial_relay_wrong_parent_hash_v3() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_payload_v3_rejects_adversarial_relay(Behaviour::WrongParentHash)
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn get_full_block_adversarial_relay_timeout_v3() {
+    ApiTester::new_mev_tester()
+        .await
+        .test_payload_v3_rejects_adversarial_relay(Behaviour::Timeout)
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn builder_chain_health_skips() {
     ApiTester::new_mev_tester()
@@ -6987,6 +7065,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_syncing()
         .await
+        .test_get_lighthouse_peers_subnet_coverage()
+        .await
         .test_get_lighthouse_proto_array()
         .await
         .test_get_lighthouse_validator_inclusion()
@@ -7001,6 +7081,8 @@ async fn lighthouse_endpoints() {
         .await
         .test_get_lighthouse_staking()
         .await
+        .test_get_lighthouse_execution_engines()
+        .await
         .test_get_lighthouse_database_info()
         .await
         .test_post_lighthouse_database_reconstruct()
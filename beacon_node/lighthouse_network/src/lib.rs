@@ -119,7 +119,8 @@ pub use peer_manager::{
     peerdb::client::Client,
     peerdb::score::{PeerAction, ReportSource},
     peerdb::PeerDB,
-    ConnectionDirection, PeerConnectionStatus, PeerInfo, PeerManager, SyncInfo, SyncStatus,
+    ConnectionDirection, PeerConnectionStatus, PeerInfo, PeerManager, SubnetCoverage, SyncInfo,
+    SyncStatus,
 };
 // pub use service::{load_private_key, Context, Libp2pEvent, Service, NETWORK_KEY_FILENAME};
 pub use service::api_types::{PeerRequestId, Response};
@@ -100,6 +100,20 @@ pub static PEERS_PER_CUSTODY_SUBNET_COUNT: LazyLock<Result<IntGaugeVec>> = LazyL
         &["custody_subnet_count"],
     )
 });
+pub static PEERS_PER_LONG_LIVED_SUBNET: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "peers_per_long_lived_subnet",
+        "The current count of good peers on each long-lived subnet",
+        &["subnet_type", "subnet"],
+    )
+});
+pub static UNCOVERED_LONG_LIVED_SUBNETS: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "uncovered_long_lived_subnets",
+        "The current count of long-lived subnets with fewer peers than the minimum we aim for",
+        &["subnet_type"],
+    )
+});
 
 pub static FAILED_ATTESTATION_PUBLISHES_PER_SUBNET: LazyLock<Result<IntGaugeVec>> =
     LazyLock::new(|| {
@@ -17,7 +17,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
-use types::{DataColumnSubnetId, EthSpec, SyncSubnetId};
+use types::{DataColumnSubnetId, Epoch, EthSpec, SyncSubnetId};
 
 pub use libp2p::core::Multiaddr;
 pub use libp2p::identity::Keypair;
@@ -37,6 +37,9 @@ use strum::IntoEnumIterator;
 
 pub mod config;
 mod network_behaviour;
+mod subnet_coverage;
+
+pub use subnet_coverage::SubnetCoverage;
 
 /// The heartbeat performs regular updates such as updating reputations and performing discovery
 /// requests. This defines the interval in seconds.
@@ -49,6 +52,13 @@ pub const PEER_RECONNECTION_TIMEOUT: Duration = Duration::from_secs(600);
 /// lower our peer count below this number. Instead we favour a non-uniform distribution of subnet
 /// peers.
 pub const MIN_SYNC_COMMITTEE_PEERS: u64 = 2;
+/// The minimum number of peers we aim to maintain on every long-lived subnet. The heartbeat runs
+/// discovery queries for subnets with fewer peers, and pruning avoids lowering the peer count of
+/// our sampling data column subnets below this number.
+pub const MIN_SUBNET_COVERAGE_PEERS: usize = 2;
+/// The maximum number of subnets the heartbeat runs discovery queries for to improve subnet
+/// coverage. Discovery groups subnet queries, so this bounds the number of queries per heartbeat.
+const MAX_COVERAGE_DISCOVERIES_PER_HEARTBEAT: usize = 6;
 /// A fraction of `PeerManager::target_peers` that we allow to connect to us in excess of
 /// `PeerManager::target_peers`. For clarity, if `PeerManager::target_peers` is 50 and
 /// PEER_EXCESS_FACTOR = 0.1 we allow 10% more nodes, i.e 55.
@@ -99,6 +109,9 @@ pub struct PeerManager<E: EthSpec> {
     /// discovery queries for subnet peers if we disconnect from existing sync
     /// committee subnet peers.
     sync_committee_subnets: HashMap<SyncSubnetId, Instant>,
+    /// Whether PeerDAS is active at the current epoch. Data column subnets are only included in
+    /// the subnet coverage once it is.
+    peer_das_enabled: bool,
     /// The heartbeat interval to perform routine maintenance.
     heartbeat: tokio::time::Interval,
     /// Keeps track of whether the discovery service is enabled or not.
@@ -168,6 +181,7 @@ impl<E: EthSpec> PeerManager<E> {
             target_peers: target_peer_count,
             temporary_banned_peers: LRUTimeCache::new(PEER_RECONNECTION_TIMEOUT),
             sync_committee_subnets: Default::default(),
+            peer_das_enabled: false,
             heartbeat,
             discovery_enabled,
             metrics_enabled,
@@ -178,6 +192,14 @@ impl<E: EthSpec> PeerManager<E> {
 
     /* Public accessible functions */
 
+    /// Updates whether PeerDAS is active, given the current epoch.
+    pub fn update_current_epoch(&mut self, current_epoch: Epoch) {
+        self.peer_das_enabled = self
+            .network_globals
+            .spec
+            .is_peer_das_enabled_for_epoch(current_epoch);
+    }
+
     /// The application layer wants to disconnect from a peer for a particular reason.
     ///
     /// All instant disconnections are fatal and we ban the associated peer.
@@ -903,6 +925,66 @@ impl<E: EthSpec> PeerManager<E> {
         }
     }
 
+    /// Rebuild the coverage of the long-lived subnets by our peers and run discovery queries for
+    /// the subnets with fewer than `MIN_SUBNET_COVERAGE_PEERS`.
+    ///
+    /// The coverage is published in the `NetworkGlobals`.
+    fn maintain_subnet_coverage(&mut self) {
+        let coverage = SubnetCoverage::new(
+            &self.network_globals.peers.read(),
+            MIN_SUBNET_COVERAGE_PEERS,
+            self.peer_das_enabled
+                .then_some(self.network_globals.sampling_subnets.as_slice()),
+            &self.network_globals.spec,
+        );
+
+        if self.metrics_enabled {
+            let subnet_type_and_id = |subnet: Subnet| match subnet {
+                Subnet::Attestation(id) => ("attestation", *id),
+                Subnet::SyncCommittee(id) => ("sync_committee", *id),
+                Subnet::DataColumn(id) => ("data_column", *id),
+            };
+            let mut uncovered_subnets: HashMap<&str, i64> = HashMap::new();
+            for (subnet, peers) in coverage.iter() {
+                let (subnet_type, id) = subnet_type_and_id(subnet);
+                metrics::set_gauge_vec(
+                    &metrics::PEERS_PER_LONG_LIVED_SUBNET,
+                    &[subnet_type, &id.to_string()],
+                    peers as i64,
+                );
+                uncovered_subnets.entry(subnet_type).or_default();
+            }
+            for subnet in coverage.uncovered_subnets() {
+                let (subnet_type, _) = subnet_type_and_id(subnet);
+                *uncovered_subnets.entry(subnet_type).or_default() += 1;
+            }
+            for (subnet_type, count) in uncovered_subnets {
+                metrics::set_gauge_vec(
+                    &metrics::UNCOVERED_LONG_LIVED_SUBNETS,
+                    &[subnet_type],
+                    count,
+                );
+            }
+        }
+
+        if self.discovery_enabled {
+            let subnets_to_discover =
+                coverage.plan_discoveries(MAX_COVERAGE_DISCOVERIES_PER_HEARTBEAT);
+            if !subnets_to_discover.is_empty() {
+                debug!(
+                    self.log,
+                    "Making subnet queries for maintaining subnet coverage";
+                    "subnets" => ?subnets_to_discover.iter().map(|s| s.subnet).collect::<Vec<_>>(),
+                    "uncovered_subnets" => coverage.uncovered_subnets().count(),
+                );
+                self.events
+                    .push(PeerManagerEvent::DiscoverSubnetPeers(subnets_to_discover));
+            }
+        }
+
+        *self.network_globals.subnet_coverage.write() = coverage;
+    }
+
     /// This function checks the status of our current peers and optionally requests a discovery
     /// query if we need to find more peers to maintain the current number of peers
     fn maintain_peer_count(&mut self, dialing_peers: usize) {
@@ -1040,6 +1122,10 @@ impl<E: EthSpec> PeerManager<E> {
                 PeerId,
                 std::collections::HashSet<SyncSubnetId>,
             > = HashMap::new();
+            // Likewise for the peers custodying our sampling data column subnets, which we need
+            // to sample and sync data columns once PeerDAS is active.
+            let mut data_column_peer_count: HashMap<DataColumnSubnetId, usize> = HashMap::new();
+            let mut peer_to_data_column: HashMap<PeerId, Vec<DataColumnSubnetId>> = HashMap::new();
 
             for (peer_id, info) in self.network_globals.peers.read().connected_peers() {
                 // Ignore peers we trust or that we are already pruning
@@ -1066,12 +1152,23 @@ impl<E: EthSpec> PeerManager<E> {
                                 .or_default()
                                 .insert(id);
                         }
-                        // TODO(das) to be implemented. We're not pruning data column peers yet
-                        // because data column topics are subscribed as core topics until we
-                        // implement recomputing data column subnets.
+                        // Data column subnets are not advertised as long-lived subnets, the
+                        // custody subnets are counted below.
                         Subnet::DataColumn(_) => {}
                     }
                 }
+
+                if self.peer_das_enabled {
+                    for subnet in &self.network_globals.sampling_subnets {
+                        if info.is_assigned_to_custody_subnet(subnet) {
+                            *data_column_peer_count.entry(*subnet).or_default() += 1;
+                            peer_to_data_column
+                                .entry(*peer_id)
+                                .or_default()
+                                .push(*subnet);
+                        }
+                    }
+                }
             }
 
             // Add to the peers to prune mapping
@@ -1128,6 +1225,20 @@ impl<E: EthSpec> PeerManager<E> {
                                 }
                             }
 
+                            // Check the sampling data column subnets in the same way
+                            if let Some(subnets) = peer_to_data_column.get(candidate_peer) {
+                                if let Some(min_subnet_count) = subnets
+                                    .iter()
+                                    .filter_map(|v| data_column_peer_count.get(v).copied())
+                                    .min()
+                                {
+                                    if min_subnet_count <= MIN_SUBNET_COVERAGE_PEERS {
+                                        // Do not drop this peer in this pruning interval
+                                        continue;
+                                    }
+                                }
+                            }
+
                             if info.is_outbound_only() {
                                 outbound_peers_pruned += 1;
                             }
@@ -1159,6 +1270,16 @@ impl<E: EthSpec> PeerManager<E> {
                                     }
                                 }
                             }
+                            // Remove pruned peers from all data column counts
+                            if let Some(data_columns) = peer_to_data_column.get(&candidate_peer) {
+                                for data_column in data_columns {
+                                    if let Some(data_column_count) =
+                                        data_column_peer_count.get_mut(data_column)
+                                    {
+                                        *data_column_count = data_column_count.saturating_sub(1);
+                                    }
+                                }
+                            }
                             peers_to_prune.insert(candidate_peer);
                         } else {
                             peers_on_subnet.clear();
@@ -1218,6 +1339,10 @@ impl<E: EthSpec> PeerManager<E> {
         // a uniform distribution of subnets.
         self.prune_excess_peers();
 
+        // Maintain a minimum number of peers on every long-lived subnet. This runs after pruning
+        // so that the published coverage does not count the pruned peers.
+        self.maintain_subnet_coverage();
+
         // Unban any peers that have served their temporary ban timeout
         self.unban_temporary_banned_peers();
 
@@ -2307,6 +2432,177 @@ mod tests {
         assert!(connected_peers.contains(&peers[7]));
     }
 
+    async fn build_peer_manager_with_spec(
+        target_peer_count: usize,
+        discovery_enabled: bool,
+        spec: types::ChainSpec,
+    ) -> PeerManager<E> {
+        let config = config::Config {
+            target_peer_count,
+            discovery_enabled,
+            ..Default::default()
+        };
+        let network_config = Arc::new(NetworkConfig {
+            target_peers: target_peer_count,
+            ..Default::default()
+        });
+        let log = build_log(slog::Level::Debug, false);
+        let globals =
+            NetworkGlobals::new_test_globals(vec![], &log, network_config, Arc::new(spec));
+        PeerManager::new(config, Arc::new(globals), &log).unwrap()
+    }
+
+    /// Connect a peer advertising and subscribed to the attestation subnet `subnet`.
+    fn connect_attestation_subnet_peer(peer_manager: &mut PeerManager<E>, subnet: usize) -> PeerId {
+        let peer = PeerId::random();
+        peer_manager.inject_connect_ingoing(&peer, "/ip4/0.0.0.0".parse().unwrap(), None);
+
+        let mut attnets = crate::types::EnrAttestationBitfield::<E>::new();
+        attnets.set(subnet, true).unwrap();
+        let metadata = crate::rpc::MetaDataV2 {
+            seq_number: 0,
+            attnets,
+            syncnets: Default::default(),
+        };
+        let mut peer_db = peer_manager.network_globals.peers.write();
+        peer_db
+            .peer_info_mut(&peer)
+            .unwrap()
+            .set_meta_data(MetaData::V2(metadata));
+        peer_db.add_subscription(&peer, Subnet::Attestation((subnet as u64).into()));
+        peer
+    }
+
+    /// Test that the heartbeat publishes the subnet coverage and runs discovery queries for the
+    /// least covered subnets.
+    #[tokio::test]
+    async fn test_peer_manager_maintains_subnet_coverage() {
+        let mut peer_manager = build_peer_manager_with_spec(5, true, E::default_spec()).await;
+        for _ in 0..MIN_SUBNET_COVERAGE_PEERS {
+            connect_attestation_subnet_peer(&mut peer_manager, 1);
+        }
+        connect_attestation_subnet_peer(&mut peer_manager, 2);
+
+        // Perform the heartbeat.
+        peer_manager.heartbeat();
+
+        let coverage = peer_manager.network_globals.subnet_coverage();
+        assert_eq!(coverage.min_peers, MIN_SUBNET_COVERAGE_PEERS);
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::Attestation(1.into())),
+            Some(MIN_SUBNET_COVERAGE_PEERS)
+        );
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::Attestation(2.into())),
+            Some(1)
+        );
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::Attestation(3.into())),
+            Some(0)
+        );
+
+        // Only the uncovered subnets with no peers at all should be queried.
+        let subnets_to_discover = peer_manager
+            .events
+            .iter()
+            .find_map(|event| match event {
+                PeerManagerEvent::DiscoverSubnetPeers(subnets) => Some(subnets),
+                _ => None,
+            })
+            .expect("should discover subnet peers");
+        assert_eq!(
+            subnets_to_discover.len(),
+            MAX_COVERAGE_DISCOVERIES_PER_HEARTBEAT
+        );
+        for discovery in subnets_to_discover {
+            assert_eq!(coverage.peers_on_subnet(discovery.subnet), Some(0));
+        }
+    }
+
+    /// Test that data column subnets are only covered once PeerDAS is active, counting the peers
+    /// custodying each subnet, and that only our sampling subnets can be uncovered.
+    #[tokio::test]
+    async fn test_peer_manager_subnet_coverage_data_columns() {
+        let mut spec = E::default_spec();
+        spec.eip7594_fork_epoch = Some(types::Epoch::new(1));
+        let mut peer_manager = build_peer_manager_with_spec(5, true, spec).await;
+        let sampling_subnet = peer_manager.network_globals.sampling_subnets[0];
+
+        let peer = connect_attestation_subnet_peer(&mut peer_manager, 1);
+        peer_manager
+            .network_globals
+            .peers
+            .write()
+            .peer_info_mut(&peer)
+            .unwrap()
+            .set_custody_subnets(HashSet::from([sampling_subnet]));
+
+        // PeerDAS is scheduled, but not yet active.
+        peer_manager.update_current_epoch(types::Epoch::new(0));
+        peer_manager.heartbeat();
+        let coverage = peer_manager.network_globals.subnet_coverage();
+        assert!(coverage.data_column.is_empty());
+        assert!(coverage.sampling_subnets.is_empty());
+
+        peer_manager.update_current_epoch(types::Epoch::new(1));
+        peer_manager.heartbeat();
+        let coverage = peer_manager.network_globals.subnet_coverage();
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::DataColumn(sampling_subnet)),
+            Some(1)
+        );
+        assert_eq!(
+            coverage.sampling_subnets,
+            peer_manager.network_globals.sampling_subnets
+        );
+        for subnet in coverage.uncovered_subnets() {
+            if let Subnet::DataColumn(id) = subnet {
+                assert!(coverage.sampling_subnets.contains(&id));
+            }
+        }
+    }
+
+    /// Test the pruning logic to keep the peers custodying our sampling data column subnets.
+    ///
+    /// Create 6 peers, all on attestation subnet 1, of which peers 0 and 1 custody one of our
+    /// sampling subnets.
+    ///
+    /// Prune 3 peers: should not be Peer0 or Peer1 because that would lower our peer count on the
+    /// sampling subnet below `MIN_SUBNET_COVERAGE_PEERS`.
+    #[tokio::test]
+    async fn test_peer_manager_prune_subnet_peers_data_column_custody() {
+        let target = 3;
+        let mut spec = E::default_spec();
+        spec.eip7594_fork_epoch = Some(types::Epoch::new(0));
+        let mut peer_manager = build_peer_manager_with_spec(target, false, spec).await;
+        peer_manager.update_current_epoch(types::Epoch::new(0));
+        let sampling_subnet = peer_manager.network_globals.sampling_subnets[0];
+
+        let peers = (0..6)
+            .map(|_| connect_attestation_subnet_peer(&mut peer_manager, 1))
+            .collect::<Vec<_>>();
+        for peer in &peers[..MIN_SUBNET_COVERAGE_PEERS] {
+            peer_manager
+                .network_globals
+                .peers
+                .write()
+                .peer_info_mut(peer)
+                .unwrap()
+                .set_custody_subnets(HashSet::from([sampling_subnet]));
+        }
+
+        // Perform the heartbeat.
+        peer_manager.heartbeat();
+
+        assert_eq!(
+            peer_manager.network_globals.connected_or_dialing_peers(),
+            target
+        );
+        let peer_db = peer_manager.network_globals.peers.read();
+        assert!(peer_db.is_connected(&peers[0]));
+        assert!(peer_db.is_connected(&peers[1]));
+    }
+
     // Test properties PeerManager should have using randomly generated input.
     #[cfg(test)]
     mod property_based_tests {
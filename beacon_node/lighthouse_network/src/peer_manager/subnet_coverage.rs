@@ -0,0 +1,236 @@
+//! A model of how well our connected peers cover every long-lived subnet.
+//!
+//! The `PeerManager` rebuilds the model on each heartbeat, uses it to plan discovery queries for
+//! the subnets with the fewest peers and publishes it in the `NetworkGlobals` for the HTTP API.
+//! Data column subnets are only modelled once PeerDAS is active, and discovery is only planned for
+//! the data column subnets we sample.
+use crate::peer_manager::peerdb::PeerDB;
+use crate::{Subnet, SubnetDiscovery};
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+use types::{ChainSpec, DataColumnSubnetId, EthSpec, SubnetId, SyncSubnetId, Unsigned};
+
+/// The number of good peers on each attestation subnet, sync committee subnet and data column
+/// subnet.
+///
+/// Peers are counted on attestation and sync committee subnets as in
+/// `PeerDB::good_peers_on_subnet`, which is also how discovery decides whether a subnet query is
+/// still required. Peers are counted on data column subnets as in
+/// `PeerDB::good_custody_subnet_peer`, since custody is not advertised in the metadata bitfields.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SubnetCoverage {
+    /// The minimum number of peers we aim to maintain on each subnet.
+    pub min_peers: usize,
+    /// The number of peers on each attestation subnet, indexed by subnet id.
+    pub attestation: Vec<usize>,
+    /// The number of peers on each sync committee subnet, indexed by subnet id.
+    pub sync_committee: Vec<usize>,
+    /// The number of peers custodying each data column subnet, indexed by subnet id.
+    ///
+    /// Empty unless PeerDAS is active.
+    pub data_column: Vec<usize>,
+    /// The data column subnets we sample, which are the only data column subnets we need peers
+    /// on.
+    pub sampling_subnets: Vec<DataColumnSubnetId>,
+}
+
+impl SubnetCoverage {
+    /// Count the peers in `peers` on every subnet.
+    ///
+    /// `sampling_subnets` is `None` until PeerDAS is active, in which case data column subnets are
+    /// not modelled.
+    pub fn new<E: EthSpec>(
+        peers: &PeerDB<E>,
+        min_peers: usize,
+        sampling_subnets: Option<&[DataColumnSubnetId]>,
+        spec: &ChainSpec,
+    ) -> Self {
+        let count = |subnet| peers.good_peers_on_subnet(subnet).count();
+
+        let attestation = (0..E::SubnetBitfieldLength::to_u64())
+            .map(|id| count(Subnet::Attestation(SubnetId::new(id))))
+            .collect();
+        let sync_committee = (0..E::SyncCommitteeSubnetCount::to_u64())
+            .map(|id| count(Subnet::SyncCommittee(SyncSubnetId::new(id))))
+            .collect();
+        let data_column = if sampling_subnets.is_some() {
+            (0..spec.data_column_sidecar_subnet_count)
+                .map(|id| {
+                    peers
+                        .good_custody_subnet_peer(DataColumnSubnetId::new(id))
+                        .count()
+                })
+                .collect()
+        } else {
+            vec![]
+        };
+
+        SubnetCoverage {
+            min_peers,
+            attestation,
+            sync_committee,
+            data_column,
+            sampling_subnets: sampling_subnets.map(<[_]>::to_vec).unwrap_or_default(),
+        }
+    }
+
+    /// Returns the number of peers on `subnet`, or none if the subnet is not modelled.
+    pub fn peers_on_subnet(&self, subnet: Subnet) -> Option<usize> {
+        match subnet {
+            Subnet::Attestation(id) => self.attestation.get(*id as usize),
+            Subnet::SyncCommittee(id) => self.sync_committee.get(*id as usize),
+            Subnet::DataColumn(id) => self.data_column.get(*id as usize),
+        }
+        .copied()
+    }
+
+    /// Iterates over every modelled subnet along with its number of peers.
+    pub fn iter(&self) -> impl Iterator<Item = (Subnet, usize)> + '_ {
+        let attestation = self
+            .attestation
+            .iter()
+            .enumerate()
+            .map(|(id, peers)| (Subnet::Attestation(SubnetId::new(id as u64)), *peers));
+        let sync_committee = self
+            .sync_committee
+            .iter()
+            .enumerate()
+            .map(|(id, peers)| (Subnet::SyncCommittee(SyncSubnetId::new(id as u64)), *peers));
+        let data_column = self.data_column.iter().enumerate().map(|(id, peers)| {
+            (
+                Subnet::DataColumn(DataColumnSubnetId::new(id as u64)),
+                *peers,
+            )
+        });
+        attestation.chain(sync_committee).chain(data_column)
+    }
+
+    /// Returns the subnets we need peers on with fewer than `min_peers` peers.
+    ///
+    /// Data column subnets we do not sample are never uncovered.
+    pub fn uncovered_subnets(&self) -> impl Iterator<Item = Subnet> + '_ {
+        self.iter_uncovered().map(|(subnet, _)| subnet)
+    }
+
+    fn iter_uncovered(&self) -> impl Iterator<Item = (Subnet, usize)> + '_ {
+        self.iter().filter(|(subnet, peers)| {
+            let required = match subnet {
+                Subnet::DataColumn(id) => self.sampling_subnets.contains(id),
+                Subnet::Attestation(_) | Subnet::SyncCommittee(_) => true,
+            };
+            required && *peers < self.min_peers
+        })
+    }
+
+    /// Plan discovery queries for at most `max_subnets` of the uncovered subnets, the least
+    /// covered first.
+    ///
+    /// Subnets with an equal number of peers are chosen at random, so that a subnet on which no
+    /// peers can be found does not starve the others.
+    pub fn plan_discoveries(&self, max_subnets: usize) -> Vec<SubnetDiscovery> {
+        let mut uncovered = self.iter_uncovered().collect::<Vec<_>>();
+        uncovered.shuffle(&mut rand::thread_rng());
+        uncovered.sort_by_key(|(_, peers)| *peers);
+
+        uncovered
+            .into_iter()
+            .take(max_subnets)
+            .map(|(subnet, _)| SubnetDiscovery {
+                subnet,
+                min_ttl: None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Epoch, MainnetEthSpec as E};
+
+    fn coverage() -> SubnetCoverage {
+        SubnetCoverage {
+            min_peers: 2,
+            attestation: vec![3, 1, 2, 0],
+            sync_committee: vec![2, 1],
+            data_column: vec![0, 4, 1],
+            sampling_subnets: vec![DataColumnSubnetId::new(0), DataColumnSubnetId::new(1)],
+        }
+    }
+
+    #[test]
+    fn empty_peer_db() {
+        let log = logging::test_logger();
+        let peers = PeerDB::<E>::new(vec![], false, &log);
+
+        let mut spec = E::default_spec();
+        spec.eip7594_fork_epoch = Some(Epoch::new(0));
+        let coverage = SubnetCoverage::new(&peers, 2, None, &spec);
+        assert_eq!(coverage.attestation, vec![0; 64]);
+        assert_eq!(coverage.sync_committee, vec![0; 4]);
+        assert!(coverage.data_column.is_empty());
+        assert_eq!(coverage.uncovered_subnets().count(), 64 + 4);
+
+        let sampling_subnets = [DataColumnSubnetId::new(3)];
+        let coverage = SubnetCoverage::new(&peers, 2, Some(&sampling_subnets), &spec);
+        assert_eq!(
+            coverage.data_column,
+            vec![0; spec.data_column_sidecar_subnet_count as usize]
+        );
+        assert_eq!(coverage.sampling_subnets, sampling_subnets);
+        assert_eq!(coverage.uncovered_subnets().count(), 64 + 4 + 1);
+    }
+
+    #[test]
+    fn peers_on_subnet() {
+        let coverage = coverage();
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::Attestation(SubnetId::new(0))),
+            Some(3)
+        );
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::SyncCommittee(SyncSubnetId::new(1))),
+            Some(1)
+        );
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::DataColumn(DataColumnSubnetId::new(1))),
+            Some(4)
+        );
+        assert_eq!(
+            coverage.peers_on_subnet(Subnet::DataColumn(DataColumnSubnetId::new(3))),
+            None
+        );
+    }
+
+    #[test]
+    fn uncovered_subnets() {
+        // Data column subnet 2 has too few peers, but is not one of our sampling subnets.
+        assert_eq!(
+            coverage().uncovered_subnets().collect::<Vec<_>>(),
+            vec![
+                Subnet::Attestation(SubnetId::new(1)),
+                Subnet::Attestation(SubnetId::new(3)),
+                Subnet::SyncCommittee(SyncSubnetId::new(1)),
+                Subnet::DataColumn(DataColumnSubnetId::new(0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn plan_discoveries_least_covered_first() {
+        let coverage = coverage();
+
+        let planned = coverage
+            .plan_discoveries(2)
+            .into_iter()
+            .map(|discovery| discovery.subnet)
+            .collect::<Vec<_>>();
+        assert_eq!(planned.len(), 2);
+        assert!(planned.contains(&Subnet::Attestation(SubnetId::new(3))));
+        assert!(planned.contains(&Subnet::DataColumn(DataColumnSubnetId::new(0))));
+
+        let planned = coverage.plan_discoveries(usize::MAX);
+        assert_eq!(planned.len(), 4);
+        assert!(planned.iter().all(|discovery| discovery.min_ttl.is_none()));
+    }
+}
@@ -40,7 +40,8 @@ use std::pin::Pin;
 use std::sync::Arc;
 use std::time::Duration;
 use types::{
-    consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, EnrForkId, EthSpec, ForkContext, Slot, SubnetId,
+    consts::altair::SYNC_COMMITTEE_SUBNET_COUNT, EnrForkId, Epoch, EthSpec, ForkContext, Slot,
+    SubnetId,
 };
 use types::{ChainSpec, ForkName};
 use utils::{build_transport, strip_peer_id, Context as ServiceContext};
@@ -1124,6 +1125,11 @@ impl<E: EthSpec> Network<E> {
         self.enr_fork_id = enr_fork_id;
     }
 
+    /// Informs the peer manager of the current epoch, which determines whether PeerDAS is active.
+    pub fn update_current_epoch(&mut self, current_epoch: Epoch) {
+        self.peer_manager_mut().update_current_epoch(current_epoch);
+    }
+
     /* Private internal functions */
 
     /// Updates the current meta data of the node to match the local ENR.
@@ -1,5 +1,6 @@
 //! A collection of variables that are accessible outside of the network thread itself.
 use crate::peer_manager::peerdb::PeerDB;
+use crate::peer_manager::SubnetCoverage;
 use crate::rpc::{MetaData, MetaDataV3};
 use crate::types::{BackFillState, SyncState};
 use crate::{Client, Enr, EnrExt, GossipTopic, Multiaddr, NetworkConfig, PeerId};
@@ -26,6 +27,8 @@ pub struct NetworkGlobals<E: EthSpec> {
     pub sync_state: RwLock<SyncState>,
     /// The current state of the backfill sync.
     pub backfill_state: RwLock<BackFillState>,
+    /// The coverage of each long-lived subnet by our peers, as of the last peer manager heartbeat.
+    pub subnet_coverage: RwLock<SubnetCoverage>,
     /// The computed sampling subnets and columns is stored to avoid re-computing.
     pub sampling_subnets: Vec<DataColumnSubnetId>,
     pub sampling_columns: Vec<ColumnIndex>,
@@ -83,6 +86,7 @@ impl<E: EthSpec> NetworkGlobals<E> {
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
             backfill_state: RwLock::new(BackFillState::Paused),
+            subnet_coverage: RwLock::new(SubnetCoverage::default()),
             sampling_subnets,
             sampling_columns,
             config,
@@ -136,6 +140,11 @@ impl<E: EthSpec> NetworkGlobals<E> {
         self.backfill_state.read().clone()
     }
 
+    /// Returns the coverage of each long-lived subnet by our peers.
+    pub fn subnet_coverage(&self) -> SubnetCoverage {
+        self.subnet_coverage.read().clone()
+    }
+
     /// Returns a `Client` type if one is known for the `PeerId`.
     pub fn client(&self, peer_id: &PeerId) -> Client {
         self.peers
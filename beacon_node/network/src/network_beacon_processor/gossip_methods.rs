@@ -16,8 +16,8 @@ use beacon_chain::{
     observed_operations::ObservationOutcome,
     sync_committee_verification::{self, Error as SyncCommitteeError},
     validator_monitor::{get_block_delay_ms, get_slot_delay_ms},
-    AvailabilityProcessingStatus, BeaconChainError, BeaconChainTypes, BlockError, ForkChoiceError,
-    GossipVerifiedBlock, NotifyExecutionLayer,
+    AvailabilityProcessingStatus, BeaconChainError, BeaconChainTypes, BlockError,
+    DataComponentKind, ForkChoiceError, GossipVerifiedBlock, NotifyExecutionLayer,
 };
 use lighthouse_network::{Client, MessageAcceptance, MessageId, PeerAction, PeerId, ReportSource};
 use operation_pool::ReceivedPreCapella;
@@ -628,6 +628,17 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     &metrics::BEACON_PROCESSOR_GOSSIP_DATA_COLUMN_SIDECAR_VERIFIED_TOTAL,
                 );
 
+                self.chain
+                    .block_times_cache
+                    .write()
+                    .set_time_data_component_observed(
+                        block_root,
+                        slot,
+                        DataComponentKind::DataColumn,
+                        index,
+                        seen_duration,
+                    );
+
                 debug!(
                     self.log,
                     "Successfully verified gossip data column sidecar";
@@ -762,6 +773,17 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
             Ok(gossip_verified_blob) => {
                 metrics::inc_counter(&metrics::BEACON_PROCESSOR_GOSSIP_BLOB_VERIFIED_TOTAL);
 
+                self.chain
+                    .block_times_cache
+                    .write()
+                    .set_time_data_component_observed(
+                        root,
+                        slot,
+                        DataComponentKind::Blob,
+                        index,
+                        seen_duration,
+                    );
+
                 if delay >= self.chain.slot_clock.unagg_attestation_production_delay() {
                     metrics::inc_counter(&metrics::BEACON_BLOB_GOSSIP_ARRIVED_LATE_TOTAL);
                     debug!(
@@ -1152,6 +1174,13 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
 
         let verified_block = match verification_result {
             Ok(verified_block) => {
+                if let Some(gossip_verified) = self.chain.slot_clock.now_duration() {
+                    self.chain
+                        .block_times_cache
+                        .write()
+                        .set_time_gossip_verified(block_root, block.slot(), gossip_verified);
+                }
+
                 if block_delay >= self.chain.slot_clock.unagg_attestation_production_delay() {
                     metrics::inc_counter(&metrics::BEACON_BLOCK_DELAY_GOSSIP_ARRIVED_LATE_TOTAL);
                     debug!(
@@ -37,6 +37,7 @@ use store::MemoryStore;
 use task_executor::TaskExecutor;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::{self, error::TrySendError};
+use tracing::{info_span, Instrument};
 use types::*;
 
 pub use sync_methods::ChainSegmentProcessId;
@@ -187,8 +188,19 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
         block: Arc<SignedBeaconBlock<T::EthSpec>>,
         seen_timestamp: Duration,
     ) -> Result<(), Error<T::EthSpec>> {
+        // Each gossip block starts a new trace, which covers its time in the beacon processor
+        // queue as well as its verification and import.
+        let block_span = info_span!(
+            parent: None,
+            "gossip_block",
+            slot = %block.slot(),
+            peer = %peer_id,
+        );
+        let queue_span = info_span!(parent: &block_span, "beacon_processor_queue");
+
         let processor = self.clone();
         let process_fn = async move {
+            drop(queue_span);
             let reprocess_tx = processor.reprocess_tx.clone();
             let invalid_block_storage = processor.invalid_block_storage.clone();
             let duplicate_cache = processor.duplicate_cache.clone();
@@ -204,7 +216,8 @@ impl<T: BeaconChainTypes> NetworkBeaconProcessor<T> {
                     seen_timestamp,
                 )
                 .await
-        };
+        }
+        .instrument(block_span);
 
         self.try_send(BeaconWorkEvent {
             drop_during_sync: false,
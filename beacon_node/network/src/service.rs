@@ -446,7 +446,10 @@ impl<T: BeaconChainTypes> NetworkService<T> {
                         metrics::update_sync_metrics(&self.network_globals);
                     }
 
-                    _ = self.gossipsub_parameter_update.tick() => self.update_gossipsub_parameters(),
+                    _ = self.gossipsub_parameter_update.tick() => {
+                        self.update_gossipsub_parameters();
+                        self.update_current_epoch();
+                    }
 
                     // handle a message sent to the network
                     Some(msg) = self.network_recv.recv() => self.on_network_msg(msg, &mut shutdown_sender).await,
@@ -894,6 +897,12 @@ impl<T: BeaconChainTypes> NetworkService<T> {
         }
     }
 
+    fn update_current_epoch(&mut self) {
+        if let Ok(epoch) = self.beacon_chain.epoch() {
+            self.libp2p.update_current_epoch(epoch);
+        }
+    }
+
     fn update_next_fork(&mut self) {
         let new_enr_fork_id = self.beacon_chain.enr_fork_id();
         let new_fork_digest = new_enr_fork_id.fork_digest;
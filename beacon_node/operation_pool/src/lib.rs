@@ -4,6 +4,7 @@ mod attester_slashing;
 mod bls_to_execution_changes;
 mod max_cover;
 mod metrics;
+mod packing_strategy;
 mod persistence;
 mod reward_cache;
 mod sync_aggregate_id;
@@ -12,6 +13,9 @@ pub use crate::bls_to_execution_changes::ReceivedPreCapella;
 pub use attestation::{earliest_attestation_validators, AttMaxCover};
 pub use attestation_storage::{CompactAttestationRef, SplitAttestation};
 pub use max_cover::MaxCover;
+pub use packing_strategy::{
+    BranchAndBound, Greedy, PackingStrategy, PackingStrategyKind, DEFAULT_PACKING_TIME_BUDGET,
+};
 pub use persistence::{
     PersistedOperationPool, PersistedOperationPoolV15, PersistedOperationPoolV20,
 };
@@ -253,6 +257,26 @@ impl<E: EthSpec> OperationPool<E> {
         prev_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
         curr_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
         spec: &ChainSpec,
+    ) -> Result<Vec<Attestation<E>>, OpPoolError> {
+        self.get_attestations_with_strategy(
+            state,
+            prev_epoch_validity_filter,
+            curr_epoch_validity_filter,
+            &Greedy,
+            spec,
+        )
+    }
+
+    /// Get a list of attestations for inclusion in a block, chosen by `strategy`.
+    ///
+    /// See `get_attestations` for the meaning of the validity filters.
+    pub fn get_attestations_with_strategy(
+        &self,
+        state: &BeaconState<E>,
+        prev_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
+        curr_epoch_validity_filter: impl for<'a> FnMut(&CompactAttestationRef<'a, E>) -> bool + Send,
+        strategy: &impl PackingStrategy,
+        spec: &ChainSpec,
     ) -> Result<Vec<Attestation<E>>, OpPoolError> {
         let fork_name = state.fork_name_unchecked();
         if !matches!(state, BeaconState::Base(_)) {
@@ -337,12 +361,12 @@ impl<E: EthSpec> OperationPool<E> {
                 if prev_epoch_key == curr_epoch_key {
                     vec![]
                 } else {
-                    maximum_cover(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
+                    strategy.pack(prev_epoch_att, prev_epoch_limit, "prev_epoch_attestations")
                 }
             },
             move || {
                 let _timer = metrics::start_timer(&metrics::ATTESTATION_CURR_EPOCH_PACKING_TIME);
-                maximum_cover(curr_epoch_att, curr_epoch_limit, "curr_epoch_attestations")
+                strategy.pack(curr_epoch_att, curr_epoch_limit, "curr_epoch_attestations")
             },
         );
 
@@ -38,3 +38,10 @@ pub static MAX_COVER_NON_ZERO_ITEMS: LazyLock<Result<IntGaugeVec>> = LazyLock::n
         &["label"],
     )
 });
+pub static PACKING_STRATEGY_TIMEOUTS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "op_pool_packing_strategy_timeouts_total",
+        "Number of times an optimal packing search ran out of time and fell back to its best solution",
+        &["label"],
+    )
+});
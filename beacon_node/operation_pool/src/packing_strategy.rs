@@ -0,0 +1,315 @@
+use crate::max_cover::{maximum_cover, MaxCover};
+use crate::metrics;
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The default time budget for `BranchAndBound`, per call to `pack`.
+pub const DEFAULT_PACKING_TIME_BUDGET: Duration = Duration::from_millis(50);
+
+/// An algorithm for choosing which items to include in a block, given a limit on their number.
+///
+/// Implementations receive items whose scores reflect their value in isolation, and are expected
+/// to use `MaxCover::update_covering_set` to account for the overlap between the items they select.
+pub trait PackingStrategy: Send + Sync {
+    /// A short name for the strategy, used in metrics and API responses.
+    fn name(&self) -> &'static str;
+
+    /// Select at most `limit` of `items`, aiming to maximise the total score of the solution.
+    ///
+    /// The returned items must have their covering sets updated for the items selected before
+    /// them, and be sorted by descending score, as expected by `max_cover::merge_solutions`.
+    fn pack<I, T>(&self, items: I, limit: usize, label: &str) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+        T: MaxCover;
+}
+
+/// The greedy approximation computed by `max_cover::maximum_cover`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Greedy;
+
+impl PackingStrategy for Greedy {
+    fn name(&self) -> &'static str {
+        "greedy"
+    }
+
+    fn pack<I, T>(&self, items: I, limit: usize, label: &str) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+        T: MaxCover,
+    {
+        maximum_cover(items, limit, label)
+    }
+}
+
+/// An exact maximum cover computed by branch-and-bound, starting from the greedy solution.
+///
+/// The search is abandoned once `time_budget` has elapsed, in which case the best solution found
+/// so far is returned. This is never worse than the greedy solution.
+#[derive(Debug, Clone, Copy)]
+pub struct BranchAndBound {
+    pub time_budget: Duration,
+}
+
+impl PackingStrategy for BranchAndBound {
+    fn name(&self) -> &'static str {
+        "optimal"
+    }
+
+    fn pack<I, T>(&self, items: I, limit: usize, label: &str) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+        T: MaxCover,
+    {
+        let items = items.into_iter().collect::<Vec<_>>();
+        let greedy = maximum_cover(items.clone(), limit, label);
+
+        let mut search = Search {
+            deadline: Instant::now() + self.time_budget,
+            limit,
+            best_score: total_score(&greedy),
+            best: greedy,
+            timed_out: false,
+        };
+        search.branch(items, &mut vec![], 0);
+
+        if search.timed_out {
+            metrics::inc_counter_vec(&metrics::PACKING_STRATEGY_TIMEOUTS, &[label]);
+        }
+
+        search.best
+    }
+}
+
+fn total_score<T: MaxCover>(solution: &[T]) -> usize {
+    solution.iter().map(MaxCover::score).sum()
+}
+
+struct Search<T> {
+    deadline: Instant,
+    limit: usize,
+    best: Vec<T>,
+    best_score: usize,
+    timed_out: bool,
+}
+
+impl<T: MaxCover> Search<T> {
+    /// Explore all solutions which extend `chosen` using only `candidates`.
+    ///
+    /// Scores can only decrease as items are added to a solution, so the sum of the highest
+    /// remaining scores is an upper bound on the value of any extension. Subtrees which can't beat
+    /// the best solution found so far are pruned.
+    fn branch(&mut self, mut candidates: Vec<T>, chosen: &mut Vec<T>, score: usize) {
+        if score > self.best_score {
+            self.best_score = score;
+            self.best = chosen.clone();
+        }
+
+        candidates.retain(|item| item.score() != 0);
+        candidates.sort_by_key(|item| Reverse(item.score()));
+
+        // Branch on including each candidate in turn, in order of descending score. Having
+        // explored the solutions which include a candidate, all later branches exclude it.
+        for i in 0..candidates.len() {
+            let remaining = self.limit.saturating_sub(chosen.len());
+            let bound = score
+                + candidates[i..]
+                    .iter()
+                    .take(remaining)
+                    .map(MaxCover::score)
+                    .sum::<usize>();
+            if remaining == 0 || bound <= self.best_score {
+                return;
+            }
+            if Instant::now() >= self.deadline {
+                self.timed_out = true;
+                return;
+            }
+
+            let next = candidates[i].clone();
+            let rest = candidates[i + 1..]
+                .iter()
+                .cloned()
+                .map(|mut item| {
+                    item.update_covering_set(next.intermediate(), next.covering_set());
+                    item
+                })
+                .collect();
+
+            chosen.push(next.clone());
+            self.branch(rest, chosen, score + next.score());
+            chosen.pop();
+
+            if self.timed_out {
+                return;
+            }
+        }
+    }
+}
+
+/// The strategy to use when packing attestations into blocks.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PackingStrategyKind {
+    #[default]
+    Greedy,
+    Optimal {
+        time_budget: Duration,
+    },
+}
+
+impl PackingStrategyKind {
+    /// Every strategy, for comparing their performance.
+    pub fn all(time_budget: Duration) -> [Self; 2] {
+        [Self::Greedy, Self::Optimal { time_budget }]
+    }
+}
+
+impl PackingStrategy for PackingStrategyKind {
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Greedy => Greedy.name(),
+            Self::Optimal { time_budget } => BranchAndBound {
+                time_budget: *time_budget,
+            }
+            .name(),
+        }
+    }
+
+    fn pack<I, T>(&self, items: I, limit: usize, label: &str) -> Vec<T>
+    where
+        I: IntoIterator<Item = T>,
+        T: MaxCover,
+    {
+        match self {
+            Self::Greedy => Greedy.pack(items, limit, label),
+            Self::Optimal { time_budget } => BranchAndBound {
+                time_budget: *time_budget,
+            }
+            .pack(items, limit, label),
+        }
+    }
+}
+
+impl fmt::Display for PackingStrategyKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Parses `greedy` or `optimal`, using the default time budget for the latter.
+impl FromStr for PackingStrategyKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "greedy" => Ok(Self::Greedy),
+            "optimal" => Ok(Self::Optimal {
+                time_budget: DEFAULT_PACKING_TIME_BUDGET,
+            }),
+            other => Err(format!(
+                "unknown packing strategy: {}, expected greedy or optimal",
+                other
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// A cover item whose elements all have the same weight.
+    #[derive(Debug, Clone, PartialEq)]
+    struct Item(HashSet<usize>);
+
+    impl MaxCover for Item {
+        type Object = Self;
+        type Intermediate = Self;
+        type Set = HashSet<usize>;
+
+        fn intermediate(&self) -> &Self {
+            self
+        }
+
+        fn convert_to_object(item: &Self) -> Self {
+            item.clone()
+        }
+
+        fn covering_set(&self) -> &HashSet<usize> {
+            &self.0
+        }
+
+        fn update_covering_set(&mut self, _: &Self, covered: &HashSet<usize>) {
+            self.0.retain(|x| !covered.contains(x));
+        }
+
+        fn score(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    fn item(elements: impl IntoIterator<Item = usize>) -> Item {
+        Item(elements.into_iter().collect())
+    }
+
+    /// Greedy takes the largest item first, which prevents it from taking both of the disjoint
+    /// items which together cover everything.
+    fn greedy_trap() -> Vec<Item> {
+        vec![item(0..8), item([0, 1, 2, 3, 8]), item([4, 5, 6, 7, 9])]
+    }
+
+    #[test]
+    fn greedy_is_suboptimal() {
+        let solution = Greedy.pack(greedy_trap(), 2, "test");
+        assert_eq!(total_score(&solution), 9);
+    }
+
+    #[test]
+    fn branch_and_bound_is_optimal() {
+        let strategy = BranchAndBound {
+            time_budget: Duration::from_secs(60),
+        };
+        let solution = strategy.pack(greedy_trap(), 2, "test");
+        assert_eq!(total_score(&solution), 10);
+        assert_eq!(solution, vec![item([0, 1, 2, 3, 8]), item([4, 5, 6, 7, 9])]);
+    }
+
+    #[test]
+    fn branch_and_bound_without_budget_returns_greedy() {
+        let strategy = BranchAndBound {
+            time_budget: Duration::ZERO,
+        };
+        let solution = strategy.pack(greedy_trap(), 2, "test");
+        assert_eq!(solution, Greedy.pack(greedy_trap(), 2, "test"));
+    }
+
+    #[test]
+    fn branch_and_bound_respects_limit() {
+        let strategy = BranchAndBound {
+            time_budget: Duration::from_secs(60),
+        };
+        let items = (0..10).map(|i| item(i * 3..i * 3 + 2 + i % 2));
+        let solution = strategy.pack(items, 4, "test");
+        assert_eq!(solution.len(), 4);
+        assert_eq!(total_score(&solution), 12);
+    }
+
+    #[test]
+    fn parse_strategy() {
+        assert_eq!(
+            "greedy".parse::<PackingStrategyKind>(),
+            Ok(PackingStrategyKind::Greedy)
+        );
+        assert_eq!(
+            "optimal".parse::<PackingStrategyKind>(),
+            Ok(PackingStrategyKind::Optimal {
+                time_budget: DEFAULT_PACKING_TIME_BUDGET
+            })
+        );
+        assert!("random".parse::<PackingStrategyKind>().is_err());
+    }
+}
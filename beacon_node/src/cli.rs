@@ -598,6 +598,75 @@ pub fn cli_app() -> Command {
                 .display_order(0)
                 .default_value_if("enable_http", ArgPredicate::IsPresent, "true")
         )
+        .arg(
+            Arg::new("http-api-token-file")
+                .long("http-api-token-file")
+                .requires("enable_http")
+                .value_name("PATH")
+                .help("Path to a YAML file of API tokens, each granting the read-only, validator \
+                    or admin role. When provided, requests must present a token with the role \
+                    required by the endpoint in an \"Authorization: Bearer <token>\" header.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-api-public-role")
+                .long("http-api-public-role")
+                .requires("http-api-token-file")
+                .value_name("ROLE")
+                .value_parser(["read-only", "validator", "admin"])
+                .help("The role granted to HTTP API requests which do not present a token. If \
+                    not provided, all requests must present a token.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-rate-limit")
+                .long("http-rate-limit")
+                .requires("enable_http")
+                .value_name("COST_PER_SECOND")
+                .help("Limit the cost of HTTP API requests each client may make per second. \
+                    Cheap requests such as /eth/v1/node/version cost 1, while requests for \
+                    validator lists, rewards and full states cost more, especially for historic \
+                    states. Clients are identified by their API token if --http-api-token-file \
+                    is provided and by their IP address or IPv6 /64 prefix otherwise. Requests \
+                    with a validator or admin token are not limited. Requests exceeding the \
+                    limit are rejected with a 429 status code.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-rate-limit-burst")
+                .long("http-rate-limit-burst")
+                .requires("http-rate-limit")
+                .value_name("COST")
+                .help("The maximum cost of HTTP API requests each client may make in a burst. \
+                    Defaults to 10 times the --http-rate-limit.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-response-cache-size")
+                .long("http-response-cache-size")
+                .requires("enable_http")
+                .value_name("MEGABYTES")
+                .help("The maximum size of the cache of HTTP API responses about states, such as \
+                    validator lists and committees. Responses about finalized states are kept \
+                    until evicted, and responses about other states until the head changes. \
+                    Set to 0 to disable the cache. [default: 64]")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-enable-graphql")
+                .long("http-enable-graphql")
+                .requires("enable_http")
+                .help("Serve GraphQL queries for blocks, states, validators, committees and block \
+                    rewards at /lighthouse/graphql.")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .display_order(0)
+        )
         /* Prometheus metrics HTTP server related arguments */
         .arg(
             Arg::new("metrics")
@@ -836,7 +905,10 @@ pub fn cli_app() -> Command {
                 .alias("execution-endpoints")
                 .help("Server endpoint for an execution layer JWT-authenticated HTTP \
                        JSON-RPC connection. Uses the same endpoint to populate the \
-                       deposit cache.")
+                       deposit cache. Multiple comma-separated endpoints may be provided, \
+                       in which case the first is the primary engine and the others are \
+                       secondary engines. Every engine is sent each new payload and fork \
+                       choice update, while payloads are built on the healthiest engine.")
                 .required(true)
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -847,7 +919,10 @@ pub fn cli_app() -> Command {
                 .value_name("EXECUTION-JWT")
                 .alias("jwt-secrets")
                 .help("File path which contains the hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. If \
+                       multiple endpoints are provided, either a single file path shared by \
+                       every endpoint or one comma-separated file path per endpoint may be \
+                       provided.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -858,12 +933,51 @@ pub fn cli_app() -> Command {
                 .value_name("EXECUTION-JWT-SECRET-KEY")
                 .alias("jwt-secret-key")
                 .help("Hex-encoded JWT secret for the \
-                       execution endpoint provided in the --execution-endpoint flag.")
+                       execution endpoint provided in the --execution-endpoint flag. The \
+                       secret is shared by every endpoint.")
                 .requires("execution-endpoint")
                 .conflicts_with("execution-jwt")
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("execution-verifier-endpoint")
+                .long("execution-verifier-endpoint")
+                .value_name("EXECUTION-ENDPOINT")
+                .help("Server endpoint for an execution engine which is sent every new payload \
+                       and fork choice update in order to cross-validate the responses of the \
+                       engines provided in the --execution-endpoint flag. This should be a \
+                       different client to the other engines. If the verifier disagrees about the \
+                       validity of a payload, the block is imported optimistically and a critical \
+                       log and `payload_disagreement` event are emitted. The verifier is given 4 \
+                       seconds to respond.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-verifier-jwt")
+                .long("execution-verifier-jwt")
+                .value_name("EXECUTION-JWT")
+                .help("File path which contains the hex-encoded JWT secret for the execution \
+                       endpoint provided in the --execution-verifier-endpoint flag. Defaults to \
+                       the JWT secret of the primary execution endpoint.")
+                .requires("execution-verifier-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("execution-record-file")
+                .long("execution-record-file")
+                .value_name("PATH")
+                .help("Append every engine API request and response to this file so that they \
+                       can be replayed with `lcli mock-el --replay`. JWT tokens are not recorded, \
+                       but the file may contain other sensitive information and will grow \
+                       without bound. Intended for debugging only.")
+                .requires("execution-endpoint")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("execution-jwt-id")
                 .long("execution-jwt-id")
@@ -904,7 +1018,9 @@ pub fn cli_app() -> Command {
                 .long("builder")
                 .alias("payload-builder")
                 .alias("payload-builders")
-                .help("The URL of a service compatible with the MEV-boost API.")
+                .help("The URL of a service compatible with the MEV-boost API. Multiple \
+                       comma-separated relay URLs may be provided, in which case every relay \
+                       is queried in parallel and the most valuable valid bid is used.")
                 .requires("execution-endpoint")
                 .action(ArgAction::Set)
                 .display_order(0)
@@ -1403,6 +1519,75 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("fork-choice-snapshot-slots")
+                .long("fork-choice-snapshot-slots")
+                .value_name("SLOTS")
+                .help("Keep a snapshot of fork choice taken at the start of each slot for this \
+                       many slots, for use in analysing re-orgs after the fact. Snapshots are \
+                       stored in the database and served by the /lighthouse/fork_choice \
+                       endpoints. Snapshots are disabled when set to 0.")
+                .default_value("0")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("op-pool-persist-interval")
+                .long("op-pool-persist-interval")
+                .value_name("SLOTS")
+                .help("Write the operation pool to disk every this many slots, so that pending \
+                       slashings, exits and other operations survive a crash. The pool is always \
+                       written on shutdown. Periodic writes are disabled when set to 0.")
+                .default_value("32")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("block-timings-log-dir")
+                .long("block-timings-log-dir")
+                .value_name("DIR")
+                .help("Write the time at which each block reached every stage of gossip, \
+                       verification and import to daily JSON-lines files in this directory, \
+                       for latency analysis over long periods. Blocks are written two epochs \
+                       after their slot. Recent timings are also served by the \
+                       /lighthouse/block_timings endpoint.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("block-timings-log-max-files")
+                .long("block-timings-log-max-files")
+                .value_name("COUNT")
+                .help("The number of daily files to keep in the --block-timings-log-dir.")
+                .requires("block-timings-log-dir")
+                .default_value("30")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("attestation-packing-strategy")
+                .long("attestation-packing-strategy")
+                .value_name("STRATEGY")
+                .help("The algorithm used to choose which attestations to include in blocks \
+                       produced by this node. `greedy` is fast but may miss the best \
+                       combination of aggregates. `optimal` searches for the best combination \
+                       for up to --attestation-packing-time-budget, falling back to the best \
+                       solution found so far.")
+                .value_parser(["greedy", "optimal"])
+                .default_value("greedy")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("attestation-packing-time-budget")
+                .long("attestation-packing-time-budget")
+                .value_name("MILLISECONDS")
+                .help("The maximum time to spend searching for the optimal set of attestations \
+                       for each epoch, when using the `optimal` packing strategy.")
+                .default_value("50")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("paranoid-block-proposal")
                 .long("paranoid-block-proposal")
@@ -1,8 +1,8 @@
 use account_utils::{read_input_from_user, STDIN_INPUTS_FLAG};
 use beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, ReOrgThreshold, DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR,
-    DEFAULT_RE_ORG_HEAD_THRESHOLD, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
-    DEFAULT_RE_ORG_PARENT_THRESHOLD,
+    DisallowedReOrgOffsets, PackingStrategyKind, ReOrgThreshold,
+    DEFAULT_PREPARE_PAYLOAD_LOOKAHEAD_FACTOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
+    DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION, DEFAULT_RE_ORG_PARENT_THRESHOLD,
 };
 use beacon_chain::graffiti_calculator::GraffitiOrigin;
 use beacon_chain::TrustedSetup;
@@ -14,13 +14,12 @@ use directory::{DEFAULT_BEACON_NODE_DIR, DEFAULT_NETWORK_DIR, DEFAULT_ROOT_DIR};
 use environment::RuntimeContext;
 use execution_layer::DEFAULT_JWT_FILE;
 use genesis::Eth1Endpoint;
-use http_api::TlsConfig;
+use http_api::{RateLimitConfig, TlsConfig, DEFAULT_RATE_LIMIT_BURST_FACTOR};
 use lighthouse_network::ListenAddress;
 use lighthouse_network::{multiaddr::Protocol, Enr, Multiaddr, NetworkConfig, PeerIdSerialized};
 use sensitive_url::SensitiveUrl;
 use slog::{info, warn, Logger};
 use std::cmp::max;
-use std::fmt::Debug;
 use std::fs;
 use std::io::IsTerminal;
 use std::net::Ipv6Addr;
@@ -177,6 +176,28 @@ pub fn get_config<E: EthSpec>(
 
         client_config.http_api.enable_light_client_server =
             cli_args.get_flag("light-client-server");
+
+        client_config.http_api.auth_token_file =
+            clap_utils::parse_optional(cli_args, "http-api-token-file")?;
+        client_config.http_api.public_role =
+            clap_utils::parse_optional(cli_args, "http-api-public-role")?;
+
+        if let Some(cost_per_second) = clap_utils::parse_optional(cli_args, "http-rate-limit")? {
+            let burst = clap_utils::parse_optional(cli_args, "http-rate-limit-burst")?
+                .unwrap_or(cost_per_second * DEFAULT_RATE_LIMIT_BURST_FACTOR);
+            client_config.http_api.rate_limit = Some(RateLimitConfig {
+                cost_per_second,
+                burst,
+            });
+        }
+
+        client_config.http_api.enable_graphql = cli_args.get_flag("http-enable-graphql");
+
+        if let Some(megabytes) =
+            clap_utils::parse_optional::<usize>(cli_args, "http-response-cache-size")?
+        {
+            client_config.http_api.response_cache_size = megabytes * 1024 * 1024;
+        }
     }
 
     if cli_args.get_flag("light-client-server") {
@@ -290,22 +311,41 @@ pub fn get_config<E: EthSpec>(
     let endpoints: String = clap_utils::parse_required(cli_args, "execution-endpoint")?;
     let mut el_config = execution_layer::Config::default();
 
-    // Parse a single execution endpoint, logging warnings if multiple endpoints are supplied.
-    let execution_endpoint = parse_only_one_value(
-        endpoints.as_str(),
-        SensitiveUrl::parse,
-        "--execution-endpoint",
-        log,
-    )?;
+    // Parse the execution endpoints. The first endpoint is the primary engine and any others are
+    // used as secondary engines in the order provided.
+    let mut execution_endpoints = endpoints
+        .split(',')
+        .map(SensitiveUrl::parse)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("--execution-endpoint contains an invalid value {:?}", e))?
+        .into_iter();
+    let execution_endpoint = execution_endpoints
+        .next()
+        .ok_or("Must provide at least one value to --execution-endpoint")?;
 
     // JWTs are required if `--execution-endpoint` is supplied. They can be either passed via
     // file_path or directly as string.
 
     let secret_file: PathBuf;
-    // Parse a single JWT secret from a given file_path, logging warnings if multiple are supplied.
+    let mut secondary_secret_files = vec![];
+    // Parse the JWT secrets from the given file_paths. A single secret is shared by every engine,
+    // otherwise one secret must be supplied per engine.
     if let Some(secret_files) = cli_args.get_one::<String>("execution-jwt") {
-        secret_file =
-            parse_only_one_value(secret_files, PathBuf::from_str, "--execution-jwt", log)?;
+        let mut secret_files = secret_files
+            .split(',')
+            .map(PathBuf::from_str)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("--execution-jwt contains an invalid value {:?}", e))?;
+        if secret_files.len() > 1 && secret_files.len() != execution_endpoints.len() + 1 {
+            return Err(format!(
+                "--execution-jwt must contain either 1 value or 1 value per execution endpoint, \
+                 got {} values for {} endpoints",
+                secret_files.len(),
+                execution_endpoints.len() + 1
+            ));
+        }
+        secondary_secret_files = secret_files.split_off(1);
+        secret_file = secret_files.remove(0);
 
     // Check if the JWT secret key is passed directly via cli flag and persist it to the default
     // file location.
@@ -327,11 +367,43 @@ pub fn get_config<E: EthSpec>(
         return Err("Error! Please set either --execution-jwt file_path or --execution-jwt-secret-key directly via cli when using --execution-endpoint".to_string());
     }
 
-    // Parse and set the payload builder, if any.
-    if let Some(endpoint) = cli_args.get_one::<String>("builder") {
-        let payload_builder =
-            parse_only_one_value(endpoint, SensitiveUrl::parse, "--builder", log)?;
-        el_config.builder_url = Some(payload_builder);
+    // Any engine without its own secret uses the secret of the primary engine.
+    let mut secondary_secret_files = secondary_secret_files.into_iter();
+    el_config.secondary_engines = execution_endpoints
+        .map(
+            |execution_endpoint| execution_layer::SecondaryEngineConfig {
+                execution_endpoint,
+                secret_file: Some(
+                    secondary_secret_files
+                        .next()
+                        .unwrap_or_else(|| secret_file.clone()),
+                ),
+            },
+        )
+        .collect();
+
+    if let Some(verifier_endpoint) = cli_args.get_one::<String>("execution-verifier-endpoint") {
+        el_config.verifier_engine = Some(execution_layer::SecondaryEngineConfig {
+            execution_endpoint: SensitiveUrl::parse(verifier_endpoint).map_err(|e| {
+                format!(
+                    "--execution-verifier-endpoint contains an invalid value {:?}",
+                    e
+                )
+            })?,
+            secret_file: clap_utils::parse_optional(cli_args, "execution-verifier-jwt")?,
+        });
+    }
+
+    el_config.engine_api_record_file =
+        clap_utils::parse_optional(cli_args, "execution-record-file")?;
+
+    // Parse and set the payload builders, if any.
+    if let Some(endpoints) = cli_args.get_one::<String>("builder") {
+        el_config.builder_urls = endpoints
+            .split(',')
+            .map(SensitiveUrl::parse)
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("--builder contains an invalid value {:?}", e))?;
 
         el_config.builder_user_agent = clap_utils::parse_optional(cli_args, "builder-user-agent")?;
 
@@ -820,10 +892,32 @@ pub fn get_config<E: EthSpec>(
         client_config.chain.fork_choice_before_proposal_timeout_ms = timeout;
     }
 
+    client_config.chain.fork_choice_snapshot_slots =
+        clap_utils::parse_required(cli_args, "fork-choice-snapshot-slots")?;
+
+    client_config.chain.op_pool_persist_interval_slots =
+        clap_utils::parse_required(cli_args, "op-pool-persist-interval")?;
+
+    client_config.chain.block_timings_log_dir =
+        clap_utils::parse_optional(cli_args, "block-timings-log-dir")?;
+    client_config.chain.block_timings_log_max_files =
+        clap_utils::parse_required(cli_args, "block-timings-log-max-files")?;
+
     client_config.chain.always_reset_payload_statuses = cli_args.get_flag("reset-payload-statuses");
 
     client_config.chain.paranoid_block_proposal = cli_args.get_flag("paranoid-block-proposal");
 
+    client_config.chain.attestation_packing_strategy =
+        match parse_required::<PackingStrategyKind>(cli_args, "attestation-packing-strategy")? {
+            PackingStrategyKind::Optimal { .. } => PackingStrategyKind::Optimal {
+                time_budget: Duration::from_millis(parse_required(
+                    cli_args,
+                    "attestation-packing-time-budget",
+                )?),
+            },
+            strategy => strategy,
+        };
+
     /*
      * Builder fallback configs.
      */
@@ -1483,41 +1577,6 @@ pub fn get_data_dir(cli_args: &ArgMatches) -> PathBuf {
         .unwrap_or_else(|| PathBuf::from("."))
 }
 
-/// Parses the `cli_value` as a comma-separated string of values to be parsed with `parser`.
-///
-/// If there is more than one value, log a warning. If there are no values, return an error.
-pub fn parse_only_one_value<F, T, U>(
-    cli_value: &str,
-    parser: F,
-    flag_name: &str,
-    log: &Logger,
-) -> Result<T, String>
-where
-    F: Fn(&str) -> Result<T, U>,
-    U: Debug,
-{
-    let values = cli_value
-        .split(',')
-        .map(parser)
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("{} contains an invalid value {:?}", flag_name, e))?;
-
-    if values.len() > 1 {
-        warn!(
-            log,
-            "Multiple values provided";
-            "info" => "multiple values are deprecated, only the first value will be used",
-            "count" => values.len(),
-            "flag" => flag_name
-        );
-    }
-
-    values
-        .into_iter()
-        .next()
-        .ok_or(format!("Must provide at least one value to {}", flag_name))
-}
-
 /// Remove chain, freezer and blobs db.
 fn purge_db(chain_db: PathBuf, freezer_db: PathBuf, blobs_db: PathBuf) -> Result<(), String> {
     // Remove the chain_db.
@@ -349,6 +349,9 @@ pub enum DBColumn {
     /// For helping persist eagerly computed light client bootstrap data
     #[strum(serialize = "scm")]
     SyncCommittee,
+    /// For historical snapshots of fork choice, keyed by slot.
+    #[strum(serialize = "fcs")]
+    ForkChoiceSnapshot,
 }
 
 /// A block from the database, which might have an execution payload or not.
@@ -399,7 +402,8 @@ impl DBColumn {
             | Self::BeaconStateDiff
             | Self::SyncCommittee
             | Self::SyncCommitteeBranch
-            | Self::LightClientUpdate => 8,
+            | Self::LightClientUpdate
+            | Self::ForkChoiceSnapshot => 8,
             Self::BeaconDataColumn => DATA_COLUMN_DB_KEY_SIZE,
         }
     }
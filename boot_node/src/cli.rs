@@ -1,6 +1,6 @@
 //! Simple logic for spawning a Lighthouse BootNode.
 
-use clap::{Arg, ArgAction, Command};
+use clap::{builder::ArgPredicate, Arg, ArgAction, Command};
 use clap_utils::{get_color_style, FLAG_HEADER};
 
 // TODO: Add DOS prevention CLI params
@@ -128,4 +128,63 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("disable-dht-persistence")
+                .long("disable-dht-persistence")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Do not save the routing table to the network directory, or load it from \
+                      there on start-up. By default the routing table is saved periodically and on \
+                      shutdown, so that a restarted boot node can serve peers straight away.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http")
+                .long("http")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Enable the HTTP server, which serves Prometheus metrics at /metrics and, \
+                      with --crawler, the ENRs recorded by the crawler.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-address")
+                .long("http-address")
+                .value_name("ADDRESS")
+                .requires("http")
+                .help("The address the HTTP server will listen on.")
+                .default_value_if("http", ArgPredicate::IsPresent, "127.0.0.1")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("http-port")
+                .long("http-port")
+                .value_name("PORT")
+                .requires("http")
+                .help("The TCP port the HTTP server will listen on.")
+                .default_value_if("http", ArgPredicate::IsPresent, "5054")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("crawler")
+                .long("crawler")
+                .action(ArgAction::SetTrue)
+                .help_heading(FLAG_HEADER)
+                .help("Walk the DHT with random lookups and record every ENR seen in a local \
+                      database, along with its fork digest, subnets, custody columns, client and \
+                      addresses.")
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("crawler-db")
+                .long("crawler-db")
+                .value_name("PATH")
+                .help("The SQLite database in which the crawler records ENRs. Defaults to \
+                      crawler.sqlite in the network directory.")
+                .requires("crawler")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
 }
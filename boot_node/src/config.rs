@@ -1,3 +1,4 @@
+use crate::persisted_dht::DHT_FILENAME;
 use beacon_node::{get_data_dir, set_network_config};
 use bytes::Bytes;
 use clap::ArgMatches;
@@ -9,11 +10,14 @@ use lighthouse_network::{
 };
 use serde::{Deserialize, Serialize};
 use ssz::Encode;
-use std::net::{SocketAddrV4, SocketAddrV6};
+use std::net::{IpAddr, SocketAddr, SocketAddrV4, SocketAddrV6};
 use std::time::Duration;
 use std::{marker::PhantomData, path::PathBuf};
 use types::EthSpec;
 
+/// The file in the network directory in which the crawler records ENRs, by default.
+pub const CRAWLER_DB_FILENAME: &str = "crawler.sqlite";
+
 /// A set of configuration parameters for the bootnode, established from CLI arguments.
 pub struct BootNodeConfig<E: EthSpec> {
     // TODO: Generalise to multiaddr
@@ -21,6 +25,12 @@ pub struct BootNodeConfig<E: EthSpec> {
     pub local_enr: Enr,
     pub local_key: CombinedKey,
     pub discv5_config: discv5::Config,
+    /// The file in which the routing table is persisted, if persistence is enabled.
+    pub dht_path: Option<PathBuf>,
+    /// The socket on which to serve metrics and crawler records, if the HTTP server is enabled.
+    pub http_listen_socket: Option<SocketAddr>,
+    /// The database in which the crawler records ENRs, if the crawler is enabled.
+    pub crawler_db: Option<PathBuf>,
     phantom: PhantomData<E>,
 }
 
@@ -164,11 +174,34 @@ impl<E: EthSpec> BootNodeConfig<E> {
             local_enr
         };
 
+        let dht_path = (!matches.get_flag("disable-dht-persistence"))
+            .then(|| network_config.network_dir.join(DHT_FILENAME));
+
+        let http_listen_socket = if matches.get_flag("http") {
+            let address: IpAddr = clap_utils::parse_required(matches, "http-address")?;
+            let port: u16 = clap_utils::parse_required(matches, "http-port")?;
+            Some(SocketAddr::new(address, port))
+        } else {
+            None
+        };
+
+        let crawler_db = if matches.get_flag("crawler") {
+            Some(
+                clap_utils::parse_optional(matches, "crawler-db")?
+                    .unwrap_or_else(|| network_config.network_dir.join(CRAWLER_DB_FILENAME)),
+            )
+        } else {
+            None
+        };
+
         Ok(BootNodeConfig {
             boot_nodes,
             local_enr,
             local_key,
             discv5_config: network_config.discv5_config,
+            dht_path,
+            http_listen_socket,
+            crawler_db,
             phantom: PhantomData,
         })
     }
@@ -186,6 +219,9 @@ pub struct BootNodeConfigSerialization {
     pub local_enr: Enr,
     pub disable_packet_filter: bool,
     pub enable_enr_auto_update: bool,
+    pub dht_path: Option<PathBuf>,
+    pub http_listen_socket: Option<SocketAddr>,
+    pub crawler_db: Option<PathBuf>,
 }
 
 impl BootNodeConfigSerialization {
@@ -197,6 +233,9 @@ impl BootNodeConfigSerialization {
             local_enr,
             local_key: _,
             discv5_config,
+            dht_path,
+            http_listen_socket,
+            crawler_db,
             phantom: _,
         } = config;
 
@@ -225,6 +264,9 @@ impl BootNodeConfigSerialization {
             local_enr: local_enr.clone(),
             disable_packet_filter: !discv5_config.enable_packet_filter,
             enable_enr_auto_update: discv5_config.enr_update,
+            dht_path: dht_path.clone(),
+            http_listen_socket: *http_listen_socket,
+            crawler_db: crawler_db.clone(),
         }
     }
 }
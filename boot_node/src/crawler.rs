@@ -0,0 +1,618 @@
+//! Walks the DHT and records every ENR seen, for a census of the network.
+//!
+//! The crawler repeatedly looks up random node ids, which visits nodes across the whole keyspace
+//! rather than only those near the boot node. Each ENR seen, whether from a lookup or from the
+//! normal operation of the server, is recorded in a SQLite database keyed by node id, so the
+//! database holds the latest ENR of every node seen along with when it was first and last seen.
+use crate::metrics;
+use bytes::Bytes;
+use lighthouse_network::discv5::{enr::NodeId, Discv5, Enr};
+use lighthouse_network::{EnrExt, Eth2Enr};
+use parking_lot::Mutex;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use serde::{Deserialize, Serialize};
+use slog::{debug, Logger};
+use std::collections::BTreeMap;
+use std::marker::PhantomData;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use types::{ChainSpec, DataColumnSubnetId, EthSpec, Unsigned};
+
+/// The ENR key of the client name and version, as specified in EIP-7636.
+pub const CLIENT_ENR_KEY: &str = "client";
+
+/// The delay between random lookups.
+const QUERY_DELAY: Duration = Duration::from_secs(1);
+
+/// The number of ENRs which may wait to be recorded.
+const RECORD_QUEUE_LEN: usize = 4_096;
+
+/// The most ENRs recorded in a single transaction.
+const MAX_RECORD_BATCH: usize = 256;
+
+/// Name used in the `fork_digest` label of metrics for nodes without an `eth2` field.
+const NO_FORK_DIGEST: &str = "none";
+
+/// Name used in the census for nodes without a client hint.
+const UNKNOWN_CLIENT: &str = "unknown";
+
+const COLUMNS: &str = "node_id, peer_id, seq, enr, fork_digest, next_fork_version, \
+    next_fork_epoch, attnets, syncnets, custody_subnet_count, custody_columns, client, ip4, tcp4, \
+    udp4, ip6, tcp6, udp6, first_seen, last_seen";
+
+/// Everything the crawler records about a node, from the latest ENR seen for it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EnrRecord {
+    pub node_id: String,
+    pub peer_id: String,
+    pub seq: u64,
+    /// The base64-encoded ENR.
+    pub enr: String,
+    pub fork_digest: Option<String>,
+    pub next_fork_version: Option<String>,
+    pub next_fork_epoch: Option<u64>,
+    /// The attestation subnets the node is subscribed to.
+    pub attnets: Option<Vec<usize>>,
+    /// The sync committee subnets the node is subscribed to.
+    pub syncnets: Option<Vec<usize>>,
+    pub custody_subnet_count: Option<u64>,
+    /// The data columns the node custodies, derived from its node id and custody subnet count.
+    pub custody_columns: Option<Vec<u64>>,
+    pub client: Option<String>,
+    pub ip4: Option<Ipv4Addr>,
+    pub tcp4: Option<u16>,
+    pub udp4: Option<u16>,
+    pub ip6: Option<Ipv6Addr>,
+    pub tcp6: Option<u16>,
+    pub udp6: Option<u16>,
+    /// The time the node was first seen, in seconds since the UNIX epoch.
+    pub first_seen: u64,
+    /// The time the node was last seen, in seconds since the UNIX epoch.
+    pub last_seen: u64,
+}
+
+impl EnrRecord {
+    pub fn from_enr<E: EthSpec>(enr: &Enr, spec: &ChainSpec, seen_at: u64) -> Self {
+        let eth2 = enr.eth2().ok();
+        let custody_subnet_count = enr.custody_subnet_count::<E>(spec).ok();
+        let custody_columns = custody_subnet_count.and_then(|count| {
+            DataColumnSubnetId::compute_custody_columns::<E>(enr.node_id().raw(), count, spec)
+                .ok()
+                .map(Iterator::collect)
+        });
+
+        Self {
+            node_id: format!("0x{}", hex::encode(enr.node_id().raw())),
+            peer_id: enr.peer_id().to_string(),
+            seq: enr.seq(),
+            enr: enr.to_base64(),
+            fork_digest: eth2
+                .as_ref()
+                .map(|eth2| format!("0x{}", hex::encode(eth2.fork_digest))),
+            next_fork_version: eth2
+                .as_ref()
+                .map(|eth2| format!("0x{}", hex::encode(eth2.next_fork_version))),
+            next_fork_epoch: eth2.as_ref().map(|eth2| eth2.next_fork_epoch.as_u64()),
+            attnets: enr
+                .attestation_bitfield::<E>()
+                .ok()
+                .map(|bitfield| set_bits(bitfield.iter())),
+            syncnets: enr
+                .sync_committee_bitfield::<E>()
+                .ok()
+                .map(|bitfield| set_bits(bitfield.iter())),
+            custody_subnet_count,
+            custody_columns,
+            client: client_hint(enr),
+            ip4: enr.ip4(),
+            tcp4: enr.tcp4(),
+            udp4: enr.udp4(),
+            ip6: enr.ip6(),
+            tcp6: enr.tcp6(),
+            udp6: enr.udp6(),
+            first_seen: seen_at,
+            last_seen: seen_at,
+        }
+    }
+
+    fn from_row(row: &Row) -> rusqlite::Result<Self> {
+        Ok(Self {
+            node_id: row.get(0)?,
+            peer_id: row.get(1)?,
+            seq: row.get(2)?,
+            enr: row.get(3)?,
+            fork_digest: row.get(4)?,
+            next_fork_version: row.get(5)?,
+            next_fork_epoch: from_string(row, 6)?,
+            attnets: from_json(row, 7)?,
+            syncnets: from_json(row, 8)?,
+            custody_subnet_count: row.get(9)?,
+            custody_columns: from_json(row, 10)?,
+            client: row.get(11)?,
+            ip4: from_string(row, 12)?,
+            tcp4: row.get(13)?,
+            udp4: row.get(14)?,
+            ip6: from_string(row, 15)?,
+            tcp6: row.get(16)?,
+            udp6: row.get(17)?,
+            first_seen: row.get(18)?,
+            last_seen: row.get(19)?,
+        })
+    }
+}
+
+/// The nodes recorded with a fork digest, summarised for network-health dashboards.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ForkDigestCensus {
+    pub fork_digest: Option<String>,
+    pub nodes: usize,
+    pub ipv4_nodes: usize,
+    pub ipv6_nodes: usize,
+    /// The number of nodes running each client, by the client name in their ENR.
+    pub clients: BTreeMap<String, usize>,
+    /// The number of nodes subscribed to each attestation subnet.
+    pub attnets: Vec<usize>,
+    /// The number of nodes subscribed to each sync committee subnet.
+    pub syncnets: Vec<usize>,
+    /// The number of nodes advertising each custody subnet count.
+    pub custody_subnet_counts: BTreeMap<u64, usize>,
+    /// The number of nodes custodying each data column.
+    pub custody_columns: Vec<usize>,
+}
+
+/// Filters the records returned by `Crawler::records` and `Crawler::census`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RecordsQuery {
+    pub fork_digest: Option<String>,
+    /// Only include nodes seen within this many seconds.
+    pub max_age: Option<u64>,
+}
+
+pub struct Crawler<E: EthSpec> {
+    conn: Mutex<Connection>,
+    spec: ChainSpec,
+    _phantom: PhantomData<E>,
+}
+
+impl<E: EthSpec> Crawler<E> {
+    /// Open the database at `path`, creating it if it does not exist.
+    pub fn open(path: &Path, spec: ChainSpec) -> Result<Self, String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+        }
+        let conn = Connection::open(path)
+            .map_err(|e| format!("Unable to open crawler database: {:?}", e))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS enrs (
+                node_id TEXT PRIMARY KEY,
+                peer_id TEXT NOT NULL,
+                seq INTEGER NOT NULL,
+                enr TEXT NOT NULL,
+                fork_digest TEXT,
+                next_fork_version TEXT,
+                next_fork_epoch TEXT,
+                attnets TEXT,
+                syncnets TEXT,
+                custody_subnet_count INTEGER,
+                custody_columns TEXT,
+                client TEXT,
+                ip4 TEXT,
+                tcp4 INTEGER,
+                udp4 INTEGER,
+                ip6 TEXT,
+                tcp6 INTEGER,
+                udp6 INTEGER,
+                first_seen INTEGER NOT NULL,
+                last_seen INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS enrs_fork_digest ON enrs (fork_digest);
+            CREATE INDEX IF NOT EXISTS enrs_last_seen ON enrs (last_seen);",
+        )
+        .map_err(|e| format!("Unable to initialize crawler database: {:?}", e))?;
+
+        Ok(Self {
+            conn: Mutex::new(conn),
+            spec,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Record that `enrs` were seen at `seen_at`, in seconds since the UNIX epoch.
+    ///
+    /// An ENR older than the one already recorded for the node is ignored.
+    pub fn record(&self, enrs: &[Enr], seen_at: u64) -> Result<(), String> {
+        let mut conn = self.conn.lock();
+        let txn = conn
+            .transaction()
+            .map_err(|e| format!("Unable to begin transaction: {:?}", e))?;
+        for enr in enrs {
+            let record = EnrRecord::from_enr::<E>(enr, &self.spec, seen_at);
+            txn.execute(
+                &format!(
+                    "INSERT INTO enrs ({COLUMNS})
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15,
+                             ?16, ?17, ?18, ?19, ?20)
+                     ON CONFLICT (node_id) DO UPDATE SET
+                         peer_id = excluded.peer_id,
+                         seq = excluded.seq,
+                         enr = excluded.enr,
+                         fork_digest = excluded.fork_digest,
+                         next_fork_version = excluded.next_fork_version,
+                         next_fork_epoch = excluded.next_fork_epoch,
+                         attnets = excluded.attnets,
+                         syncnets = excluded.syncnets,
+                         custody_subnet_count = excluded.custody_subnet_count,
+                         custody_columns = excluded.custody_columns,
+                         client = excluded.client,
+                         ip4 = excluded.ip4,
+                         tcp4 = excluded.tcp4,
+                         udp4 = excluded.udp4,
+                         ip6 = excluded.ip6,
+                         tcp6 = excluded.tcp6,
+                         udp6 = excluded.udp6,
+                         last_seen = max(last_seen, excluded.last_seen)
+                     WHERE excluded.seq >= enrs.seq"
+                ),
+                params![
+                    record.node_id,
+                    record.peer_id,
+                    record.seq,
+                    record.enr,
+                    record.fork_digest,
+                    record.next_fork_version,
+                    record.next_fork_epoch.map(|epoch| epoch.to_string()),
+                    to_json(&record.attnets),
+                    to_json(&record.syncnets),
+                    record.custody_subnet_count,
+                    to_json(&record.custody_columns),
+                    record.client,
+                    record.ip4.map(|ip| ip.to_string()),
+                    record.tcp4,
+                    record.udp4,
+                    record.ip6.map(|ip| ip.to_string()),
+                    record.tcp6,
+                    record.udp6,
+                    record.first_seen,
+                    record.last_seen,
+                ],
+            )
+            .map_err(|e| format!("Unable to record ENR: {:?}", e))?;
+        }
+        txn.commit()
+            .map_err(|e| format!("Unable to commit transaction: {:?}", e))?;
+        metrics::inc_counter_by(
+            &metrics::BOOT_NODE_CRAWLER_ENRS_SEEN_TOTAL,
+            enrs.len() as u64,
+        );
+        Ok(())
+    }
+
+    /// Returns the record of a single node, by its node id.
+    pub fn record_by_node_id(&self, node_id: &str) -> Result<Option<EnrRecord>, String> {
+        self.conn
+            .lock()
+            .query_row(
+                &format!("SELECT {COLUMNS} FROM enrs WHERE node_id = ?1"),
+                params![node_id],
+                EnrRecord::from_row,
+            )
+            .optional()
+            .map_err(|e| format!("Unable to read crawler database: {:?}", e))
+    }
+
+    /// Returns the records matching `query`, most recently seen first.
+    pub fn records(&self, query: &RecordsQuery, now: u64) -> Result<Vec<EnrRecord>, String> {
+        let min_last_seen = query
+            .max_age
+            .map_or(0, |max_age| now.saturating_sub(max_age));
+        let conn = self.conn.lock();
+        let mut stmt = conn
+            .prepare(&format!(
+                "SELECT {COLUMNS} FROM enrs
+                 WHERE last_seen >= ?1 AND (?2 IS NULL OR fork_digest = ?2)
+                 ORDER BY last_seen DESC"
+            ))
+            .map_err(|e| format!("Unable to read crawler database: {:?}", e))?;
+        let records = stmt
+            .query_map(
+                params![min_last_seen, query.fork_digest],
+                EnrRecord::from_row,
+            )
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Unable to read crawler database: {:?}", e))?;
+        Ok(records)
+    }
+
+    /// Returns a census of the nodes matching `query`, for each fork digest.
+    pub fn census(&self, query: &RecordsQuery, now: u64) -> Result<Vec<ForkDigestCensus>, String> {
+        let mut census = BTreeMap::<Option<String>, ForkDigestCensus>::new();
+        for record in self.records(query, now)? {
+            let entry =
+                census
+                    .entry(record.fork_digest.clone())
+                    .or_insert_with(|| ForkDigestCensus {
+                        fork_digest: record.fork_digest.clone(),
+                        nodes: 0,
+                        ipv4_nodes: 0,
+                        ipv6_nodes: 0,
+                        clients: BTreeMap::new(),
+                        attnets: vec![0; E::SubnetBitfieldLength::to_usize()],
+                        syncnets: vec![0; E::SyncCommitteeSubnetCount::to_usize()],
+                        custody_subnet_counts: BTreeMap::new(),
+                        custody_columns: vec![0; self.spec.number_of_columns],
+                    });
+            entry.nodes += 1;
+            entry.ipv4_nodes += record.ip4.is_some() as usize;
+            entry.ipv6_nodes += record.ip6.is_some() as usize;
+            let client = record
+                .client
+                .as_deref()
+                .and_then(|client| client.split('/').next())
+                .unwrap_or(UNKNOWN_CLIENT);
+            *entry.clients.entry(client.to_string()).or_default() += 1;
+            count_indices(&mut entry.attnets, record.attnets.iter().flatten().copied());
+            count_indices(
+                &mut entry.syncnets,
+                record.syncnets.iter().flatten().copied(),
+            );
+            if let Some(count) = record.custody_subnet_count {
+                *entry.custody_subnet_counts.entry(count).or_default() += 1;
+            }
+            count_indices(
+                &mut entry.custody_columns,
+                record
+                    .custody_columns
+                    .iter()
+                    .flatten()
+                    .map(|column| *column as usize),
+            );
+        }
+        Ok(census.into_values().collect())
+    }
+
+    /// Update the metric of the number of nodes recorded with each fork digest.
+    pub fn update_metrics(&self) -> Result<(), String> {
+        let conn = self.conn.lock();
+        let mut stmt = conn
+            .prepare("SELECT fork_digest, COUNT(*) FROM enrs GROUP BY fork_digest")
+            .map_err(|e| format!("Unable to read crawler database: {:?}", e))?;
+        let counts = stmt
+            .query_map(params![], |row| {
+                Ok((row.get::<_, Option<String>>(0)?, row.get::<_, i64>(1)?))
+            })
+            .and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+            .map_err(|e| format!("Unable to read crawler database: {:?}", e))?;
+        for (fork_digest, count) in counts {
+            metrics::set_gauge_vec(
+                &metrics::BOOT_NODE_CRAWLER_NODES,
+                &[fork_digest.as_deref().unwrap_or(NO_FORK_DIGEST)],
+                count,
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Spawn the task which records ENRs in the database, returning the queue of ENRs to record.
+///
+/// The task is the only writer to the database, and records the ENRs waiting in the queue in a
+/// single transaction.
+pub fn spawn_recorder<E: EthSpec>(crawler: Arc<Crawler<E>>, log: Logger) -> mpsc::Sender<Enr> {
+    let (tx, mut rx) = mpsc::channel(RECORD_QUEUE_LEN);
+    tokio::spawn(async move {
+        let mut batch = Vec::with_capacity(MAX_RECORD_BATCH);
+        while rx.recv_many(&mut batch, MAX_RECORD_BATCH).await > 0 {
+            let enrs = std::mem::take(&mut batch);
+            let crawler = crawler.clone();
+            let result =
+                tokio::task::spawn_blocking(move || crawler.record(&enrs, unix_time_now())).await;
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => slog::warn!(log, "Unable to record ENRs"; "error" => e),
+                Err(e) => slog::warn!(log, "Unable to record ENRs"; "error" => ?e),
+            }
+        }
+    });
+    tx
+}
+
+/// Look up random node ids forever, sending every ENR found to `recorder`.
+pub async fn crawl(discv5: Arc<Discv5>, recorder: mpsc::Sender<Enr>, log: Logger) {
+    loop {
+        match discv5.find_node(NodeId::random()).await {
+            Ok(enrs) => {
+                metrics::inc_counter_vec(&metrics::BOOT_NODE_CRAWLER_QUERIES_TOTAL, &["success"]);
+                debug!(log, "Crawler lookup complete"; "enrs" => enrs.len());
+                for enr in enrs {
+                    // Lookups wait for the recorder to catch up, rather than dropping ENRs.
+                    if recorder.send(enr).await.is_err() {
+                        return;
+                    }
+                }
+            }
+            Err(e) => {
+                metrics::inc_counter_vec(&metrics::BOOT_NODE_CRAWLER_QUERIES_TOTAL, &["failure"]);
+                debug!(log, "Crawler lookup failed"; "error" => ?e);
+            }
+        }
+        tokio::time::sleep(QUERY_DELAY).await;
+    }
+}
+
+/// Returns the client name and version from the `client` field of `enr`, joined by `/`.
+pub fn client_hint(enr: &Enr) -> Option<String> {
+    let fields = enr.get_decodable::<Vec<Bytes>>(CLIENT_ENR_KEY)?.ok()?;
+    let fields = fields
+        .iter()
+        .map(|field| String::from_utf8_lossy(field))
+        .collect::<Vec<_>>();
+    (!fields.is_empty()).then(|| fields.join("/"))
+}
+
+/// Seconds since the UNIX epoch.
+pub fn unix_time_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+fn set_bits(bits: impl Iterator<Item = bool>) -> Vec<usize> {
+    bits.enumerate()
+        .filter_map(|(index, set)| set.then_some(index))
+        .collect()
+}
+
+fn count_indices(counts: &mut [usize], indices: impl Iterator<Item = usize>) {
+    for index in indices {
+        if let Some(count) = counts.get_mut(index) {
+            *count += 1;
+        }
+    }
+}
+
+fn to_json<T: Serialize>(value: &Option<T>) -> Option<String> {
+    value
+        .as_ref()
+        .and_then(|value| serde_json::to_string(value).ok())
+}
+
+fn from_json<T: for<'de> Deserialize<'de>>(row: &Row, index: usize) -> rusqlite::Result<Option<T>> {
+    row.get::<_, Option<String>>(index)?
+        .map(|json| {
+            serde_json::from_str(&json).map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    index,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        })
+        .transpose()
+}
+
+fn from_string<T>(row: &Row, index: usize) -> rusqlite::Result<Option<T>>
+where
+    T: std::str::FromStr,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    row.get::<_, Option<String>>(index)?
+        .map(|string| {
+            string.parse().map_err(|e| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    index,
+                    rusqlite::types::Type::Text,
+                    Box::new(e),
+                )
+            })
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::discv5::enr::CombinedKey;
+    use ssz::Encode;
+    use tempfile::TempDir;
+    use types::{BitVector, EnrForkId, Epoch, MainnetEthSpec};
+
+    type E = MainnetEthSpec;
+
+    fn enr(key: &CombinedKey, seq: u64, fork_digest: [u8; 4], attnets: &[usize]) -> Enr {
+        let mut bitfield = BitVector::<<E as EthSpec>::SubnetBitfieldLength>::new();
+        for subnet in attnets {
+            bitfield.set(*subnet, true).unwrap();
+        }
+        let fork_id = EnrForkId {
+            fork_digest,
+            next_fork_version: [0; 4],
+            next_fork_epoch: Epoch::new(u64::MAX),
+        };
+        let mut builder = Enr::builder();
+        builder
+            .seq(seq)
+            .ip4(Ipv4Addr::new(10, 0, 0, 1))
+            .udp4(9000)
+            .add_value::<Bytes>("eth2", &fork_id.as_ssz_bytes().into())
+            .add_value::<Bytes>("attnets", &bitfield.as_ssz_bytes().into())
+            .add_value("csc", &4u64)
+            .add_value(
+                CLIENT_ENR_KEY,
+                &vec![Bytes::from("Lighthouse"), Bytes::from("v6.0.1")],
+            );
+        builder.build(key).unwrap()
+    }
+
+    #[test]
+    fn record_fields() {
+        let spec = E::default_spec();
+        let key = CombinedKey::generate_secp256k1();
+        let record = EnrRecord::from_enr::<E>(&enr(&key, 1, [1, 2, 3, 4], &[0, 5]), &spec, 10);
+
+        assert_eq!(record.fork_digest.as_deref(), Some("0x01020304"));
+        assert_eq!(record.attnets, Some(vec![0, 5]));
+        assert_eq!(record.syncnets, None);
+        assert_eq!(record.custody_subnet_count, Some(4));
+        assert_eq!(
+            record.custody_columns.map(|columns| columns.len()),
+            Some(4 * spec.data_columns_per_subnet())
+        );
+        assert_eq!(record.client.as_deref(), Some("Lighthouse/v6.0.1"));
+        assert_eq!(record.ip4, Some(Ipv4Addr::new(10, 0, 0, 1)));
+        assert_eq!(record.udp4, Some(9000));
+        assert_eq!((record.first_seen, record.last_seen), (10, 10));
+    }
+
+    #[test]
+    fn record_and_census() {
+        let dir = TempDir::new().unwrap();
+        let crawler =
+            Crawler::<E>::open(&dir.path().join("crawler.sqlite"), E::default_spec()).unwrap();
+        let key_a = CombinedKey::generate_secp256k1();
+        let key_b = CombinedKey::generate_secp256k1();
+        let newer_b = enr(&key_b, 2, [1, 1, 1, 1], &[1]);
+
+        crawler
+            .record(
+                &[
+                    enr(&key_a, 2, [1, 1, 1, 1], &[0]),
+                    enr(&key_b, 1, [2, 2, 2, 2], &[0, 1]),
+                ],
+                10,
+            )
+            .unwrap();
+        // A newer ENR replaces the record, but the node keeps its first seen time.
+        crawler.record(&[newer_b.clone()], 20).unwrap();
+        // An older ENR is ignored.
+        crawler
+            .record(&[enr(&key_a, 1, [2, 2, 2, 2], &[1])], 30)
+            .unwrap();
+
+        let records = crawler.records(&RecordsQuery::default(), 40).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].enr, newer_b.to_base64());
+        assert_eq!((records[0].first_seen, records[0].last_seen), (10, 20));
+        assert_eq!(records[1].attnets, Some(vec![0]));
+        assert_eq!(records[1].last_seen, 10);
+
+        let recent = RecordsQuery {
+            fork_digest: None,
+            max_age: Some(25),
+        };
+        assert_eq!(crawler.records(&recent, 40).unwrap().len(), 1);
+
+        let census = crawler.census(&RecordsQuery::default(), 40).unwrap();
+        assert_eq!(census.len(), 1);
+        assert_eq!(census[0].fork_digest.as_deref(), Some("0x01010101"));
+        assert_eq!(census[0].nodes, 2);
+        assert_eq!(census[0].ipv4_nodes, 2);
+        assert_eq!(census[0].clients.get("Lighthouse"), Some(&2));
+        assert_eq!(&census[0].attnets[..3], &[1, 1, 0]);
+        assert_eq!(census[0].custody_subnet_counts.get(&4), Some(&2));
+    }
+}
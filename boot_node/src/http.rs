@@ -0,0 +1,137 @@
+//! An HTTP server for the metrics of the boot node and the records of the crawler.
+//!
+//! It serves:
+//!
+//! - `GET /metrics`: Prometheus metrics.
+//! - `GET /crawler/enrs`: the records of the nodes seen by the crawler, optionally filtered by
+//!   `fork_digest` and by `max_age` in seconds since the node was last seen.
+//! - `GET /crawler/enrs/{node_id}`: the record of a single node.
+//! - `GET /crawler/census`: a summary of the recorded nodes for each fork digest, accepting the
+//!   same filters.
+use crate::crawler::{unix_time_now, Crawler, RecordsQuery};
+use crate::metrics;
+use lighthouse_version::version_with_platform;
+use slog::{info, Logger};
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use types::EthSpec;
+use warp::{http::Response, Filter};
+use warp_utils::reject::{custom_not_found, custom_server_error};
+use warp_utils::task::blocking_json_task;
+
+/// Start the HTTP server on `listen_socket`, returning the bound socket and the server future.
+pub fn serve<E: EthSpec>(
+    listen_socket: SocketAddr,
+    crawler: Option<Arc<Crawler<E>>>,
+    log: Logger,
+) -> Result<(SocketAddr, impl Future<Output = ()>), String> {
+    let optional_crawler_filter = warp::any().map(move || crawler.clone());
+    let crawler_filter =
+        optional_crawler_filter
+            .clone()
+            .and_then(|crawler: Option<Arc<Crawler<E>>>| async move {
+                crawler.ok_or_else(|| custom_not_found("The crawler is not enabled".to_string()))
+            });
+
+    // GET metrics
+    let get_metrics = warp::path("metrics")
+        .and(warp::path::end())
+        .and(optional_crawler_filter)
+        .then(|crawler: Option<Arc<Crawler<E>>>| async move {
+            let result = tokio::task::spawn_blocking(move || gather_prometheus_metrics(crawler))
+                .await
+                .map_err(|e| format!("{:?}", e))
+                .and_then(|result| result);
+            match result {
+                Ok(body) => Response::builder()
+                    .status(200)
+                    .header("Content-Type", "text/plain")
+                    .body(body)
+                    .unwrap(),
+                Err(e) => Response::builder()
+                    .status(500)
+                    .header("Content-Type", "text/plain")
+                    .body(format!("Unable to gather metrics: {}", e))
+                    .unwrap(),
+            }
+        });
+
+    // GET crawler/enrs
+    let get_enrs = warp::path("crawler")
+        .and(warp::path("enrs"))
+        .and(warp::path::end())
+        .and(warp::query::<RecordsQuery>())
+        .and(crawler_filter.clone())
+        .then(|query: RecordsQuery, crawler: Arc<Crawler<E>>| {
+            blocking_json_task(move || {
+                let data = crawler
+                    .records(&query, unix_time_now())
+                    .map_err(custom_server_error)?;
+                Ok(serde_json::json!({ "data": data }))
+            })
+        });
+
+    // GET crawler/enrs/{node_id}
+    let get_enr = warp::path("crawler")
+        .and(warp::path("enrs"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(crawler_filter.clone())
+        .then(|node_id: String, crawler: Arc<Crawler<E>>| {
+            blocking_json_task(move || {
+                let data = crawler
+                    .record_by_node_id(&node_id)
+                    .map_err(custom_server_error)?
+                    .ok_or_else(|| custom_not_found(format!("Unknown node: {}", node_id)))?;
+                Ok(serde_json::json!({ "data": data }))
+            })
+        });
+
+    // GET crawler/census
+    let get_census = warp::path("crawler")
+        .and(warp::path("census"))
+        .and(warp::path::end())
+        .and(warp::query::<RecordsQuery>())
+        .and(crawler_filter)
+        .then(|query: RecordsQuery, crawler: Arc<Crawler<E>>| {
+            blocking_json_task(move || {
+                let data = crawler
+                    .census(&query, unix_time_now())
+                    .map_err(custom_server_error)?;
+                Ok(serde_json::json!({ "data": data }))
+            })
+        });
+
+    let routes = warp::get()
+        .and(get_metrics.or(get_enrs).or(get_enr).or(get_census))
+        .recover(warp_utils::reject::handle_rejection)
+        .map(|reply| warp::reply::with_header(reply, "Server", &version_with_platform()));
+
+    let (listening_socket, server) = warp::serve(routes)
+        .try_bind_ephemeral(listen_socket)
+        .map_err(|e| format!("Unable to start HTTP server: {}", e))?;
+
+    info!(
+        log,
+        "HTTP server started";
+        "listen_address" => listening_socket.to_string(),
+    );
+
+    Ok((listening_socket, server))
+}
+
+fn gather_prometheus_metrics<E: EthSpec>(
+    crawler: Option<Arc<Crawler<E>>>,
+) -> Result<String, String> {
+    lighthouse_network::scrape_discovery_metrics();
+    if let Some(crawler) = crawler {
+        crawler.update_metrics()?;
+    }
+
+    let mut buffer = String::new();
+    metrics::TextEncoder::new()
+        .encode_utf8(&metrics::gather(), &mut buffer)
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(buffer)
+}
@@ -1,10 +1,17 @@
 //! Creates a simple DISCV5 server which can be used to bootstrap an Eth2 network.
+//!
+//! The server persists its routing table across restarts, can serve Prometheus metrics over HTTP
+//! and can crawl the DHT to record every ENR it sees.
 use clap::ArgMatches;
 use slog::{o, Drain, Level, Logger};
 
 use eth2_network_config::Eth2NetworkConfig;
 mod cli;
 pub mod config;
+pub mod crawler;
+mod http;
+mod metrics;
+pub mod persisted_dht;
 mod server;
 pub use cli::cli_app;
 use config::BootNodeConfig;
@@ -0,0 +1,49 @@
+pub use metrics::*;
+use std::sync::LazyLock;
+
+pub static BOOT_NODE_CONNECTED_PEERS: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "boot_node_connected_peers",
+        "Number of connected nodes in the routing table",
+    )
+});
+pub static BOOT_NODE_ROUTING_TABLE_NODES: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "boot_node_routing_table_nodes",
+        "Number of connected nodes in the routing table, by the addresses they declare",
+        &["reachability"],
+    )
+});
+pub static BOOT_NODE_DHT_PERSISTED_ENRS: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "boot_node_dht_persisted_enrs",
+        "Number of ENRs in the routing table when it was last persisted",
+    )
+});
+pub static BOOT_NODE_CRAWLER_QUERIES_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "boot_node_crawler_queries_total",
+        "Count of random lookups made by the crawler, by outcome",
+        &["outcome"],
+    )
+});
+pub static BOOT_NODE_CRAWLER_ENRS_SEEN_TOTAL: LazyLock<Result<IntCounter>> = LazyLock::new(|| {
+    try_create_int_counter(
+        "boot_node_crawler_enrs_seen_total",
+        "Count of ENRs seen by the crawler, including those seen before",
+    )
+});
+pub static BOOT_NODE_CRAWLER_ENRS_DROPPED_TOTAL: LazyLock<Result<IntCounter>> =
+    LazyLock::new(|| {
+        try_create_int_counter(
+            "boot_node_crawler_enrs_dropped_total",
+            "Count of discovered ENRs not recorded because the crawler's queue was full",
+        )
+    });
+pub static BOOT_NODE_CRAWLER_NODES: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "boot_node_crawler_nodes",
+        "Number of nodes recorded by the crawler, by fork digest",
+        &["fork_digest"],
+    )
+});
@@ -0,0 +1,70 @@
+//! Persistence of the routing table across restarts.
+//!
+//! The ENRs are stored one per line, base64-encoded, in the same format as the local ENR.
+use lighthouse_network::discv5::Enr;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The file in the network directory in which the routing table is persisted.
+pub const DHT_FILENAME: &str = "dht.dat";
+
+/// Load the ENRs persisted at `path`, returning none if nothing has been persisted yet.
+///
+/// ENRs which cannot be decoded are skipped, since a stale entry should not prevent start-up.
+pub fn load_dht(path: &Path) -> Result<Vec<Enr>, String> {
+    if !path.exists() {
+        return Ok(vec![]);
+    }
+    let contents = fs::read_to_string(path)
+        .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+    Ok(contents
+        .lines()
+        .filter_map(|line| Enr::from_str(line.trim()).ok())
+        .collect())
+}
+
+/// Persist `enrs` to `path`, replacing anything persisted before.
+pub fn persist_dht(path: &Path, enrs: &[Enr]) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("Unable to create {}: {}", dir.display(), e))?;
+    }
+    let contents = enrs
+        .iter()
+        .map(|enr| enr.to_base64() + "\n")
+        .collect::<String>();
+
+    // Write to a temporary file first so that an interrupted write cannot lose the routing table.
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, contents)
+        .map_err(|e| format!("Unable to write {}: {}", temp_path.display(), e))?;
+    fs::rename(&temp_path, path).map_err(|e| format!("Unable to write {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lighthouse_network::discv5::enr::CombinedKey;
+    use tempfile::TempDir;
+
+    #[test]
+    fn persist_and_load() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("network").join(DHT_FILENAME);
+        assert_eq!(load_dht(&path).unwrap(), vec![]);
+
+        let enrs = (0..3)
+            .map(|_| {
+                Enr::builder()
+                    .build(&CombinedKey::generate_secp256k1())
+                    .unwrap()
+            })
+            .collect::<Vec<_>>();
+        persist_dht(&path, &enrs).unwrap();
+        assert_eq!(load_dht(&path).unwrap(), enrs);
+
+        persist_dht(&path, &enrs[..1]).unwrap();
+        assert_eq!(load_dht(&path).unwrap(), enrs[..1]);
+    }
+}
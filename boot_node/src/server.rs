@@ -2,15 +2,25 @@
 
 use super::BootNodeConfig;
 use crate::config::BootNodeConfigSerialization;
+use crate::crawler::{self, Crawler};
+use crate::persisted_dht::{load_dht, persist_dht};
+use crate::{http, metrics};
 use clap::ArgMatches;
 use eth2_network_config::Eth2NetworkConfig;
 use lighthouse_network::{
     discv5::{self, enr::NodeId, Discv5},
     EnrExt, Eth2Enr,
 };
-use slog::info;
+use slog::{info, warn};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc::error::TrySendError;
 use types::EthSpec;
 
+/// The interval at which the routing table is persisted, in addition to on shutdown.
+const DHT_PERSIST_INTERVAL: Duration = Duration::from_secs(300);
+
 pub async fn run<E: EthSpec>(
     lh_matches: &ArgMatches,
     bn_matches: &ArgMatches,
@@ -37,6 +47,9 @@ pub async fn run<E: EthSpec>(
         local_enr,
         local_key,
         discv5_config,
+        dht_path,
+        http_listen_socket,
+        crawler_db,
         ..
     } = config;
 
@@ -86,6 +99,22 @@ pub async fn run<E: EthSpec>(
         }
     }
 
+    // Repopulate the routing table with the ENRs persisted before the last shutdown
+    if let Some(dht_path) = &dht_path {
+        match load_dht(dht_path) {
+            Ok(enrs) => {
+                info!(log, "Loading peers into the routing table"; "peers" => enrs.len());
+                for enr in enrs {
+                    if enr.node_id() != local_enr.node_id() {
+                        // Entries are rejected once their bucket is full, which is expected.
+                        let _ = discv5.add_enr(enr);
+                    }
+                }
+            }
+            Err(e) => warn!(log, "Unable to load the routing table"; "error" => e),
+        }
+    }
+
     // start the server
     if let Err(e) = discv5.start().await {
         return Err(format!("Could not start discv5 server: {e:?}"));
@@ -97,9 +126,39 @@ pub async fn run<E: EthSpec>(
         let _ = discv5.find_node(NodeId::random()).await;
     }
 
+    let discv5 = Arc::new(discv5);
+
+    let (crawler, recorder) = match crawler_db {
+        Some(crawler_db) => {
+            let spec = eth2_network_config.chain_spec::<E>()?;
+            let crawler = Arc::new(Crawler::<E>::open(&crawler_db, spec)?);
+            info!(log, "Crawler started"; "database" => %crawler_db.display());
+            let recorder = crawler::spawn_recorder(crawler.clone(), log.clone());
+            tokio::spawn(crawler::crawl(
+                discv5.clone(),
+                recorder.clone(),
+                log.clone(),
+            ));
+            (Some(crawler), Some(recorder))
+        }
+        None => (None, None),
+    };
+
+    if let Some(http_listen_socket) = http_listen_socket {
+        let (_, server) = http::serve(http_listen_socket, crawler.clone(), log.clone())?;
+        tokio::spawn(server);
+    }
+
     // respond with metrics every 10 seconds
     let mut metric_interval = tokio::time::interval(tokio::time::Duration::from_secs(10));
 
+    let mut dht_persist_interval = tokio::time::interval(DHT_PERSIST_INTERVAL);
+    // The first tick completes immediately, when there is nothing new to persist.
+    dht_persist_interval.tick().await;
+
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+
     // get an event stream
     let mut event_stream = match discv5.event_stream().await {
         Ok(stream) => stream,
@@ -128,6 +187,16 @@ pub async fn run<E: EthSpec>(
                     }
                 }
 
+                metrics::set_gauge(&metrics::BOOT_NODE_CONNECTED_PEERS, discv5.connected_peers() as i64);
+                for (reachability, nodes) in [
+                    ("ipv4", ipv4_only_reachable),
+                    ("ipv6", ipv6_only_reachable),
+                    ("dual_stack", ipv4_ipv6_reachable),
+                    ("unreachable", unreachable_nodes),
+                ] {
+                    metrics::set_gauge_vec(&metrics::BOOT_NODE_ROUTING_TABLE_NODES, &[reachability], nodes as i64);
+                }
+
                 // display server metrics
                 let metrics = discv5.metrics();
                 info!(
@@ -142,11 +211,28 @@ pub async fn run<E: EthSpec>(
                 );
 
             }
+            _ = dht_persist_interval.tick() => {
+                if let Some(dht_path) = &dht_path {
+                    save_dht(&discv5, dht_path, &log);
+                }
+            }
+            _ = &mut shutdown => {
+                if let Some(dht_path) = &dht_path {
+                    save_dht(&discv5, dht_path, &log);
+                }
+                info!(log, "Shutting down boot node");
+                return Ok(());
+            }
             Some(event) = event_stream.recv() => {
                 match event {
-                    discv5::Event::Discovered(_enr) => {
-                        // An ENR has been obtained by the server
-                        // Ignore these events here
+                    discv5::Event::Discovered(enr) => {
+                        // An ENR has been obtained by the server, which the crawler records.
+                        // The ENR is dropped if the crawler is behind, as it will be seen again.
+                        if let Some(recorder) = &recorder {
+                            if let Err(TrySendError::Full(_)) = recorder.try_send(enr) {
+                                metrics::inc_counter(&metrics::BOOT_NODE_CRAWLER_ENRS_DROPPED_TOTAL);
+                            }
+                        }
                     }
                     discv5::Event::SocketUpdated(socket_addr) => {
                         info!(log, "Advertised socket address updated"; "socket_addr" => %socket_addr);
@@ -157,3 +243,42 @@ pub async fn run<E: EthSpec>(
         }
     }
 }
+
+/// Persist the ENRs of the routing table to `dht_path`.
+fn save_dht(discv5: &Discv5, dht_path: &Path, log: &slog::Logger) {
+    let enrs = discv5.table_entries_enr();
+    match persist_dht(dht_path, &enrs) {
+        Ok(()) => {
+            metrics::set_gauge(&metrics::BOOT_NODE_DHT_PERSISTED_ENRS, enrs.len() as i64);
+            info!(log, "Saved routing table"; "peers" => enrs.len());
+        }
+        Err(e) => warn!(log, "Failed to persist routing table"; "error" => e),
+    }
+}
+
+/// Resolves when the process is asked to shut down.
+#[cfg(unix)]
+async fn shutdown_signal() {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    match (
+        signal(SignalKind::terminate()),
+        signal(SignalKind::interrupt()),
+    ) {
+        (Ok(mut terminate), Ok(mut interrupt)) => {
+            tokio::select! {
+                _ = terminate.recv() => {}
+                _ = interrupt.recv() => {}
+            }
+        }
+        _ => {
+            let _ = tokio::signal::ctrl_c().await;
+        }
+    }
+}
+
+/// Resolves when the process is asked to shut down.
+#[cfg(not(unix))]
+async fn shutdown_signal() {
+    let _ = tokio::signal::ctrl_c().await;
+}
@@ -62,6 +62,10 @@ pub enum PasswordStorage {
 #[derive(Clone, PartialEq, Serialize, Deserialize, Hash, Eq)]
 pub struct Web3SignerDefinition {
     pub url: String,
+    /// Additional Web3Signer URLs which hold the same key, used in order whenever `url` (and any
+    /// prior fallback) is unavailable.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallback_urls: Vec<String>,
     /// Path to a .pem file.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub root_certificate_path: Option<PathBuf>,
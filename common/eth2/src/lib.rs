@@ -16,6 +16,7 @@ pub mod types;
 
 use self::mixin::{RequestAccept, ResponseOptional};
 use self::types::{Error as ResponseError, *};
+use account_utils::ZeroizeString;
 use derivative::Derivative;
 use futures::Stream;
 use futures_util::StreamExt;
@@ -155,13 +156,16 @@ impl Timeouts {
 
 /// A wrapper around `reqwest::Client` which provides convenience methods for interfacing with a
 /// Lighthouse Beacon Node HTTP server (`http_api`).
-#[derive(Clone, Debug, Derivative)]
-#[derivative(PartialEq)]
+#[derive(Clone, Derivative)]
+#[derivative(PartialEq, Debug)]
 pub struct BeaconNodeHttpClient {
     #[derivative(PartialEq = "ignore")]
     client: reqwest::Client,
     server: SensitiveUrl,
     timeouts: Timeouts,
+    /// The token sent as a `Bearer` token in the `Authorization` header of every request.
+    #[derivative(PartialEq = "ignore", Debug = "ignore")]
+    api_token: Option<ZeroizeString>,
 }
 
 impl Eq for BeaconNodeHttpClient {}
@@ -184,6 +188,7 @@ impl BeaconNodeHttpClient {
             client: reqwest::Client::new(),
             server,
             timeouts,
+            api_token: None,
         }
     }
 
@@ -196,9 +201,36 @@ impl BeaconNodeHttpClient {
             client,
             server,
             timeouts,
+            api_token: None,
         }
     }
 
+    /// Send `token` as a `Bearer` token in the `Authorization` header of every request, for beacon
+    /// nodes which restrict their API with `--http-api-token-file`.
+    pub fn with_api_token(mut self, token: ZeroizeString) -> Self {
+        self.api_token = Some(token);
+        self
+    }
+
+    /// Add the `Authorization` header, if there is an API token, and the headers which continue
+    /// the trace of the current span on the beacon node.
+    fn with_request_headers(&self, builder: RequestBuilder) -> RequestBuilder {
+        let builder = match &self.api_token {
+            Some(token) => builder.bearer_auth(token.as_str()),
+            None => builder,
+        };
+        trace_context::current_trace_context()
+            .into_iter()
+            .fold(builder, |builder, (name, value)| {
+                builder.header(name, value)
+            })
+    }
+
+    /// The URL of the beacon node.
+    pub fn server(&self) -> &SensitiveUrl {
+        &self.server
+    }
+
     /// Return the path with the standard `/eth/vX` prefix applied.
     fn eth_path(&self, version: EndpointVersion) -> Result<Url, Error> {
         let mut path = self.server.full.clone();
@@ -223,7 +255,9 @@ impl BeaconNodeHttpClient {
         url: U,
         builder: impl FnOnce(RequestBuilder) -> RequestBuilder,
     ) -> Result<Response, Error> {
-        let response = builder(self.client.get(url)).send().await?;
+        let response = builder(self.with_request_headers(self.client.get(url)))
+            .send()
+            .await?;
         ok_or_error(response).await
     }
 
@@ -387,7 +421,7 @@ impl BeaconNodeHttpClient {
         body: &T,
         timeout: Option<Duration>,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.post(url);
+        let mut builder = self.with_request_headers(self.client.post(url));
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -404,7 +438,7 @@ impl BeaconNodeHttpClient {
         timeout: Option<Duration>,
         fork: ForkName,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.post(url);
+        let mut builder = self.with_request_headers(self.client.post(url));
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -422,7 +456,7 @@ impl BeaconNodeHttpClient {
         url: U,
         body: &T,
     ) -> Result<Response, Error> {
-        let builder = self.client.post(url);
+        let builder = self.with_request_headers(self.client.post(url));
         let mut headers = HeaderMap::new();
 
         headers.insert(
@@ -433,6 +467,26 @@ impl BeaconNodeHttpClient {
         ok_or_error(response).await
     }
 
+    /// Generic POST function with an SSZ body, supporting arbitrary responses and timeouts.
+    #[cfg(feature = "lighthouse")]
+    async fn post_generic_with_ssz_body<T: Into<Body>, U: IntoUrl>(
+        &self,
+        url: U,
+        body: T,
+        timeout: Option<Duration>,
+    ) -> Result<Response, Error> {
+        let mut builder = self.with_request_headers(self.client.post(url));
+        if let Some(timeout) = timeout {
+            builder = builder.timeout(timeout);
+        }
+        let response = builder
+            .header("Content-Type", "application/octet-stream")
+            .body(body)
+            .send()
+            .await?;
+        ok_or_error(response).await
+    }
+
     /// Generic POST function supporting arbitrary responses and timeouts.
     async fn post_generic_with_consensus_version_and_ssz_body<T: Into<Body>, U: IntoUrl>(
         &self,
@@ -441,7 +495,7 @@ impl BeaconNodeHttpClient {
         timeout: Option<Duration>,
         fork: ForkName,
     ) -> Result<Response, Error> {
-        let mut builder = self.client.post(url);
+        let mut builder = self.with_request_headers(self.client.post(url));
         if let Some(timeout) = timeout {
             builder = builder.timeout(timeout);
         }
@@ -1574,56 +1628,61 @@ impl BeaconNodeHttpClient {
             .map(|opt| opt.map(|r| r.data))
     }
 
-    /// `POST beacon/rewards/sync_committee`
+    /// `POST beacon/rewards/sync_committee/{block_id}`
     pub async fn post_beacon_rewards_sync_committee(
         &self,
-        rewards: &[Option<Vec<lighthouse::SyncCommitteeReward>>],
-    ) -> Result<(), Error> {
+        block_id: BlockId,
+        validators: &[ValidatorId],
+    ) -> Result<
+        ExecutionOptimisticFinalizedResponse<Option<Vec<lighthouse::SyncCommitteeReward>>>,
+        Error,
+    > {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("sync_committee");
-
-        self.post(path, &rewards).await?;
+            .push("sync_committee")
+            .push(&block_id.to_string());
 
-        Ok(())
+        self.post_with_response(path, &validators).await
     }
 
-    /// `GET beacon/rewards/blocks`
-    pub async fn get_beacon_rewards_blocks(&self, epoch: Epoch) -> Result<(), Error> {
+    /// `GET beacon/rewards/blocks/{block_id}`
+    pub async fn get_beacon_rewards_blocks(
+        &self,
+        block_id: BlockId,
+    ) -> Result<ExecutionOptimisticFinalizedResponse<lighthouse::StandardBlockReward>, Error> {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("blocks");
-
-        path.query_pairs_mut()
-            .append_pair("epoch", &epoch.to_string());
+            .push("blocks")
+            .push(&block_id.to_string());
 
         self.get(path).await
     }
 
-    /// `POST beacon/rewards/attestations`
+    /// `POST beacon/rewards/attestations/{epoch}`
     pub async fn post_beacon_rewards_attestations(
         &self,
-        attestations: &[ValidatorId],
-    ) -> Result<(), Error> {
+        epoch: Epoch,
+        validators: &[ValidatorId],
+    ) -> Result<ExecutionOptimisticFinalizedResponse<lighthouse::StandardAttestationRewards>, Error>
+    {
         let mut path = self.eth_path(V1)?;
 
         path.path_segments_mut()
             .map_err(|()| Error::InvalidUrl(self.server.clone()))?
             .push("beacon")
             .push("rewards")
-            .push("attestations");
-
-        self.post(path, &attestations).await?;
+            .push("attestations")
+            .push(&epoch.to_string());
 
-        Ok(())
+        self.post_with_response(path, &validators).await
     }
 
     // GET builder/states/{state_id}/expected_withdrawals
@@ -1784,7 +1843,11 @@ impl BeaconNodeHttpClient {
             .push("node")
             .push("health");
 
-        let status = self.client.get(path).send().await?.status();
+        let status = self
+            .with_request_headers(self.client.get(path))
+            .send()
+            .await?
+            .status();
         if status == StatusCode::OK || status == StatusCode::PARTIAL_CONTENT {
             Ok(status)
         } else {
@@ -2593,8 +2656,7 @@ impl BeaconNodeHttpClient {
         path.query_pairs_mut().append_pair("topics", &topic_string);
 
         Ok(self
-            .client
-            .get(path)
+            .with_request_headers(self.client.get(path))
             .send()
             .await?
             .bytes_stream()
@@ -4,12 +4,17 @@ mod attestation_performance;
 pub mod attestation_rewards;
 mod block_packing_efficiency;
 mod block_rewards;
+mod block_timings;
+mod fork_choice_snapshot;
+mod optimal_block_rewards;
 mod standard_block_rewards;
 mod sync_committee_rewards;
 
 use crate::{
+    mixin::RequestAccept,
     types::{
-        DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock, GenericResponse, ValidatorId,
+        Accept, BlockId, DepositTreeSnapshot, Epoch, EthSpec, FinalizedExecutionBlock,
+        GenericResponse, ValidatorId,
     },
     BeaconNodeHttpClient, DepositData, Error, Eth1Data, Hash256, Slot,
 };
@@ -24,10 +29,19 @@ pub use attestation_performance::{
 };
 pub use attestation_rewards::StandardAttestationRewards;
 pub use block_packing_efficiency::{
-    BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, UniqueAttestation,
+    BlockPackingEfficiency, BlockPackingEfficiencyQuery, ProposerInfo, StrategyPackingEfficiency,
+    UniqueAttestation,
 };
 pub use block_rewards::{AttestationRewards, BlockReward, BlockRewardMeta, BlockRewardsQuery};
-pub use lighthouse_network::{types::SyncState, PeerInfo};
+pub use block_timings::{BlockTimings, DataComponentKind, DataComponentTiming};
+pub use fork_choice_snapshot::{
+    ForkChoiceDiffFormat, ForkChoiceDiffQuery, ForkChoiceSnapshot, ForkChoiceSnapshotDiff,
+    ForkChoiceSnapshotNode, ForkChoiceWeightChange,
+};
+pub use lighthouse_network::{types::SyncState, PeerInfo, SubnetCoverage};
+pub use optimal_block_rewards::{
+    OptimalBlockReward, OptimalBlockRewards, OptimalBlockRewardsQuery, ProposerRewardGap,
+};
 pub use standard_block_rewards::StandardBlockReward;
 pub use sync_committee_rewards::SyncCommitteeReward;
 
@@ -356,6 +370,19 @@ impl From<Eth1Block> for FinalizedExecutionBlock {
     }
 }
 
+/// The health of a single execution engine, as reported by `lighthouse/execution_engines`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionEngineHealth {
+    /// The engine URL, with any credentials redacted.
+    pub endpoint: String,
+    /// `true` if this is the first configured engine.
+    pub primary: bool,
+    /// `true` if this engine is currently preferred for building payloads.
+    pub active: bool,
+    /// One of `synced`, `syncing`, `offline` or `auth_failed`.
+    pub state: String,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DatabaseInfo {
     pub schema_version: u64,
@@ -365,6 +392,25 @@ pub struct DatabaseInfo {
     pub blob_info: BlobInfo,
 }
 
+/// The number of operations of a single type processed by `POST lighthouse/op_pool`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OpPoolImportCount {
+    /// Operations which passed verification and were added to the pool.
+    pub imported: usize,
+    /// Operations which were already known, are no longer includable or failed verification.
+    pub rejected: usize,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpPoolImportSummary {
+    pub attestations: OpPoolImportCount,
+    pub sync_contributions: OpPoolImportCount,
+    pub attester_slashings: OpPoolImportCount,
+    pub proposer_slashings: OpPoolImportCount,
+    pub voluntary_exits: OpPoolImportCount,
+    pub bls_to_execution_changes: OpPoolImportCount,
+}
+
 impl BeaconNodeHttpClient {
     /// `GET lighthouse/health`
     pub async fn get_lighthouse_health(&self) -> Result<GenericResponse<Health>, Error> {
@@ -390,14 +436,43 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/execution_engines`
+    pub async fn get_lighthouse_execution_engines(
+        &self,
+    ) -> Result<GenericResponse<Vec<ExecutionEngineHealth>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("execution_engines");
+
+        self.get(path).await
+    }
+
     /*
      * Note:
      *
-     * The `lighthouse/peers` endpoints do not have functions here. We are yet to implement
-     * `Deserialize` on the `PeerInfo` struct since it contains use of `Instant`. This could be
-     * fairly simply achieved, if desired.
+     * The `lighthouse/peers` and `lighthouse/peers/connected` endpoints do not have functions
+     * here. We are yet to implement `Deserialize` on the `PeerInfo` struct since it contains use of
+     * `Instant`. This could be fairly simply achieved, if desired.
      */
 
+    /// `GET lighthouse/peers/subnet_coverage`
+    pub async fn get_lighthouse_peers_subnet_coverage(
+        &self,
+    ) -> Result<GenericResponse<SubnetCoverage>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("peers")
+            .push("subnet_coverage");
+
+        self.get(path).await
+    }
+
     /// `GET lighthouse/proto_array`
     pub async fn get_lighthouse_proto_array(&self) -> Result<GenericResponse<ProtoArray>, Error> {
         let mut path = self.server.full.clone();
@@ -410,6 +485,62 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET lighthouse/fork_choice/snapshots/{slot}`
+    pub async fn get_lighthouse_fork_choice_snapshot(
+        &self,
+        slot: Slot,
+    ) -> Result<GenericResponse<ForkChoiceSnapshot>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("snapshots")
+            .push(&slot.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/fork_choice/diff?from,to`
+    pub async fn get_lighthouse_fork_choice_diff(
+        &self,
+        from: Slot,
+        to: Slot,
+    ) -> Result<GenericResponse<ForkChoiceSnapshotDiff>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("fork_choice")
+            .push("diff");
+
+        path.query_pairs_mut()
+            .append_pair("from", &from.to_string())
+            .append_pair("to", &to.to_string());
+
+        self.get(path).await
+    }
+
+    /// `GET lighthouse/block_timings/{block_id}`
+    ///
+    /// Returns `Ok(None)` on a 404 error.
+    pub async fn get_lighthouse_block_timings(
+        &self,
+        block_id: BlockId,
+    ) -> Result<Option<GenericResponse<BlockTimings>>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("block_timings")
+            .push(&block_id.to_string());
+
+        self.get_opt(path).await
+    }
+
     /// `GET lighthouse/validator_inclusion/{epoch}/global`
     pub async fn get_lighthouse_validator_inclusion_global(
         &self,
@@ -528,6 +659,44 @@ impl BeaconNodeHttpClient {
         self.post_with_response(path, &()).await
     }
 
+    /// `GET lighthouse/op_pool`
+    ///
+    /// Returns the SSZ bytes of a `PersistedOperationPool`.
+    pub async fn get_lighthouse_op_pool_ssz(&self) -> Result<Vec<u8>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("op_pool");
+
+        let response = self
+            .get_response(path, |builder| builder.accept(Accept::Ssz))
+            .await?;
+        Ok(response.bytes().await?.into())
+    }
+
+    /// `POST lighthouse/op_pool`
+    ///
+    /// `pool_ssz` must be the SSZ bytes of a `PersistedOperationPool`.
+    pub async fn post_lighthouse_op_pool_ssz(
+        &self,
+        pool_ssz: Vec<u8>,
+    ) -> Result<GenericResponse<OpPoolImportSummary>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("op_pool");
+
+        self.post_generic_with_ssz_body(path, pool_ssz, None)
+            .await?
+            .json()
+            .await
+            .map_err(Into::into)
+    }
+
     /*
      Analysis endpoints.
     */
@@ -553,6 +722,33 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/analysis/optimal_block_rewards?start_slot,end_slot
+    pub async fn get_lighthouse_analysis_optimal_block_rewards(
+        &self,
+        start_slot: Slot,
+        end_slot: Slot,
+        packing_time_budget_ms: Option<u64>,
+    ) -> Result<OptimalBlockRewards, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("optimal_block_rewards");
+
+        path.query_pairs_mut()
+            .append_pair("start_slot", &start_slot.to_string())
+            .append_pair("end_slot", &end_slot.to_string());
+
+        if let Some(time_budget) = packing_time_budget_ms {
+            path.query_pairs_mut()
+                .append_pair("packing_time_budget_ms", &time_budget.to_string());
+        }
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/block_packing?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_block_packing(
         &self,
@@ -574,6 +770,36 @@ impl BeaconNodeHttpClient {
         self.get(path).await
     }
 
+    /// `GET` lighthouse/analysis/block_packing_efficiency?start_epoch,end_epoch,compare_strategies
+    ///
+    /// Each block is also re-packed with every packing strategy, for comparison.
+    pub async fn get_lighthouse_analysis_block_packing_with_strategies(
+        &self,
+        start_epoch: Epoch,
+        end_epoch: Epoch,
+        packing_time_budget_ms: Option<u64>,
+    ) -> Result<Vec<BlockPackingEfficiency>, Error> {
+        let mut path = self.server.full.clone();
+
+        path.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.server.clone()))?
+            .push("lighthouse")
+            .push("analysis")
+            .push("block_packing_efficiency");
+
+        path.query_pairs_mut()
+            .append_pair("start_epoch", &start_epoch.to_string())
+            .append_pair("end_epoch", &end_epoch.to_string())
+            .append_pair("compare_strategies", "true");
+
+        if let Some(time_budget) = packing_time_budget_ms {
+            path.query_pairs_mut()
+                .append_pair("packing_time_budget_ms", &time_budget.to_string());
+        }
+
+        self.get(path).await
+    }
+
     /// `GET` lighthouse/analysis/attestation_performance/{index}?start_epoch,end_epoch
     pub async fn get_lighthouse_analysis_attestation_performance(
         &self,
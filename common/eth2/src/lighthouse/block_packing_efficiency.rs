@@ -25,10 +25,25 @@ pub struct BlockPackingEfficiency {
     pub available_attestations: usize,
     pub included_attestations: usize,
     pub prior_skip_slots: u64,
+    /// The number of new attestations each packing strategy would have included, if requested.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub strategies: Vec<StrategyPackingEfficiency>,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct StrategyPackingEfficiency {
+    pub strategy: String,
+    pub included_attestations: usize,
 }
 
 #[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
 pub struct BlockPackingEfficiencyQuery {
     pub start_epoch: Epoch,
     pub end_epoch: Epoch,
+    /// Re-pack each block with every packing strategy, for comparison with the original block.
+    #[serde(default)]
+    pub compare_strategies: bool,
+    /// The time budget for the `optimal` strategy when comparing strategies.
+    #[serde(default)]
+    pub packing_time_budget_ms: Option<u64>,
 }
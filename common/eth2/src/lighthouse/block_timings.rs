@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot};
+
+/// The times at which a block reached each stage of gossip, verification and import.
+///
+/// All timestamps are in milliseconds since the UNIX epoch, and are `None` if the block did not
+/// reach the stage (or reached it before the node started tracking the block).
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct BlockTimings {
+    pub block_root: Hash256,
+    pub slot: Slot,
+    /// The peer which first sent the block on gossip.
+    pub peer_id: Option<String>,
+    /// The client of the peer which first sent the block on gossip.
+    pub peer_client: Option<String>,
+    /// The block was first seen, on gossip, RPC or the HTTP API.
+    pub observed: Option<u64>,
+    /// Gossip verification completed, including verification of the proposer signature.
+    pub gossip_verified: Option<u64>,
+    /// Consensus verification completed, including verification of all signatures in the block.
+    pub consensus_verified: Option<u64>,
+    /// The execution payload was sent to the execution layer with `newPayload`.
+    pub started_execution: Option<u64>,
+    /// The execution layer responded to `newPayload`.
+    pub payload_verified: Option<u64>,
+    /// Both the state transition and execution payload verification completed.
+    pub executed: Option<u64>,
+    /// The blobs of the block became available.
+    pub all_blobs_observed: Option<u64>,
+    /// The data availability check completed, and the block was ready to import.
+    pub available: Option<u64>,
+    /// The block was applied to fork choice.
+    pub fork_choice_applied: Option<u64>,
+    /// The block could be attested to, via the early attester cache.
+    pub attestable: Option<u64>,
+    /// The block was written to the database.
+    pub imported: Option<u64>,
+    /// The block became the canonical head.
+    pub set_as_head: Option<u64>,
+    /// The blobs and data columns received on gossip, in order of arrival.
+    pub data_components: Vec<DataComponentTiming>,
+}
+
+/// The arrival of a blob or data column on gossip.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DataComponentTiming {
+    pub kind: DataComponentKind,
+    pub index: u64,
+    /// Milliseconds since the UNIX epoch.
+    pub observed: u64,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DataComponentKind {
+    Blob,
+    DataColumn,
+}
@@ -0,0 +1,272 @@
+use serde::{Deserialize, Serialize};
+use ssz_derive::{Decode, Encode};
+use std::collections::HashMap;
+use std::fmt::Write;
+use types::{Checkpoint, Epoch, Hash256, Slot};
+
+/// A compact record of the fork choice tree at the start of a slot.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ForkChoiceSnapshot {
+    /// The slot at the start of which the snapshot was taken.
+    pub slot: Slot,
+    /// The head block selected by fork choice.
+    pub head_root: Hash256,
+    /// The block which received proposer boost, or the zero hash if none.
+    pub proposer_boost_root: Hash256,
+    pub justified_checkpoint: Checkpoint,
+    pub finalized_checkpoint: Checkpoint,
+    pub nodes: Vec<ForkChoiceSnapshotNode>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct ForkChoiceSnapshotNode {
+    pub slot: Slot,
+    pub root: Hash256,
+    pub parent_root: Option<Hash256>,
+    pub justified_epoch: Epoch,
+    pub finalized_epoch: Epoch,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub weight: u64,
+}
+
+/// The change in fork choice between two snapshots.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ForkChoiceSnapshotDiff {
+    pub from_slot: Slot,
+    pub to_slot: Slot,
+    pub from_head_root: Hash256,
+    pub to_head_root: Hash256,
+    pub from_justified_checkpoint: Checkpoint,
+    pub to_justified_checkpoint: Checkpoint,
+    pub from_finalized_checkpoint: Checkpoint,
+    pub to_finalized_checkpoint: Checkpoint,
+    /// Nodes present in the later snapshot but not the earlier one.
+    pub added: Vec<ForkChoiceSnapshotNode>,
+    /// Nodes present in the earlier snapshot but not the later one (usually due to pruning).
+    pub removed: Vec<ForkChoiceSnapshotNode>,
+    /// Nodes present in both snapshots whose weight has changed.
+    pub weight_changes: Vec<ForkChoiceWeightChange>,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ForkChoiceWeightChange {
+    pub slot: Slot,
+    pub root: Hash256,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub from_weight: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub to_weight: u64,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ForkChoiceDiffFormat {
+    #[default]
+    Json,
+    Dot,
+}
+
+/// Query parameters for `GET lighthouse/fork_choice/diff`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForkChoiceDiffQuery {
+    pub from: Slot,
+    pub to: Slot,
+    #[serde(default)]
+    pub format: ForkChoiceDiffFormat,
+}
+
+impl ForkChoiceSnapshot {
+    /// Returns the changes required to get from `self` to `to`.
+    pub fn diff(&self, to: &Self) -> ForkChoiceSnapshotDiff {
+        let from_nodes = self.nodes_by_root();
+        let to_nodes = to.nodes_by_root();
+
+        let added = to
+            .nodes
+            .iter()
+            .filter(|node| !from_nodes.contains_key(&node.root))
+            .cloned()
+            .collect();
+        let removed = self
+            .nodes
+            .iter()
+            .filter(|node| !to_nodes.contains_key(&node.root))
+            .cloned()
+            .collect();
+        let weight_changes = self
+            .nodes
+            .iter()
+            .filter_map(|from_node| {
+                let to_node = to_nodes.get(&from_node.root)?;
+                (from_node.weight != to_node.weight).then(|| ForkChoiceWeightChange {
+                    slot: from_node.slot,
+                    root: from_node.root,
+                    from_weight: from_node.weight,
+                    to_weight: to_node.weight,
+                })
+            })
+            .collect();
+
+        ForkChoiceSnapshotDiff {
+            from_slot: self.slot,
+            to_slot: to.slot,
+            from_head_root: self.head_root,
+            to_head_root: to.head_root,
+            from_justified_checkpoint: self.justified_checkpoint,
+            to_justified_checkpoint: to.justified_checkpoint,
+            from_finalized_checkpoint: self.finalized_checkpoint,
+            to_finalized_checkpoint: to.finalized_checkpoint,
+            added,
+            removed,
+            weight_changes,
+        }
+    }
+
+    /// Renders the union of `self` and `to` as a Graphviz DOT graph.
+    ///
+    /// Added nodes are green and removed nodes are red and dashed. Both heads are drawn with a bold
+    /// outline, with blue text for the head of `self` and green text for the head of `to`. Nodes
+    /// whose weight changed are labelled with both weights.
+    pub fn diff_dot(&self, to: &Self) -> String {
+        let from_nodes = self.nodes_by_root();
+        let to_nodes = to.nodes_by_root();
+
+        let all_nodes = self.nodes.iter().chain(
+            to.nodes
+                .iter()
+                .filter(|node| !from_nodes.contains_key(&node.root)),
+        );
+
+        let mut dot = String::new();
+        let _ = writeln!(dot, "digraph fork_choice {{");
+        let _ = writeln!(dot, "  rankdir=LR;");
+        let _ = writeln!(dot, "  node [shape=box, fontname=\"monospace\"];");
+        let _ = writeln!(
+            dot,
+            "  label=\"fork choice from slot {} to slot {}\";",
+            self.slot, to.slot
+        );
+
+        for node in all_nodes.clone() {
+            let weight = match (from_nodes.get(&node.root), to_nodes.get(&node.root)) {
+                (Some(from), Some(to)) if from.weight != to.weight => {
+                    format!("weight {} -> {}", from.weight, to.weight)
+                }
+                _ => format!("weight {}", node.weight),
+            };
+            let mut attributes = vec![format!(
+                "label=\"slot {}\\n{}\\n{}\"",
+                node.slot,
+                short_root(node.root),
+                weight
+            )];
+            if !from_nodes.contains_key(&node.root) {
+                attributes.push("color=green".into());
+            } else if !to_nodes.contains_key(&node.root) {
+                attributes.push("color=red".into());
+                attributes.push("style=dashed".into());
+            }
+            if node.root == to.head_root {
+                attributes.push("penwidth=3".into());
+                attributes.push("fontcolor=green".into());
+            } else if node.root == self.head_root {
+                attributes.push("penwidth=3".into());
+                attributes.push("fontcolor=blue".into());
+            }
+            let _ = writeln!(dot, "  \"{:?}\" [{}];", node.root, attributes.join(", "));
+        }
+
+        for node in all_nodes {
+            if let Some(parent_root) = node.parent_root {
+                if from_nodes.contains_key(&parent_root) || to_nodes.contains_key(&parent_root) {
+                    let _ = writeln!(dot, "  \"{:?}\" -> \"{:?}\";", parent_root, node.root);
+                }
+            }
+        }
+
+        let _ = writeln!(dot, "}}");
+        dot
+    }
+
+    fn nodes_by_root(&self) -> HashMap<Hash256, &ForkChoiceSnapshotNode> {
+        self.nodes.iter().map(|node| (node.root, node)).collect()
+    }
+}
+
+/// Returns the first four bytes of `root` as hex, which is enough to tell nodes apart in a graph.
+fn short_root(root: Hash256) -> String {
+    format!("{:?}", root).chars().take(10).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::FixedBytesExtended;
+
+    fn node(slot: u64, root: u64, parent: Option<u64>, weight: u64) -> ForkChoiceSnapshotNode {
+        ForkChoiceSnapshotNode {
+            slot: Slot::new(slot),
+            root: Hash256::from_low_u64_be(root),
+            parent_root: parent.map(Hash256::from_low_u64_be),
+            justified_epoch: Epoch::new(0),
+            finalized_epoch: Epoch::new(0),
+            weight,
+        }
+    }
+
+    fn snapshot(slot: u64, head: u64, nodes: Vec<ForkChoiceSnapshotNode>) -> ForkChoiceSnapshot {
+        ForkChoiceSnapshot {
+            slot: Slot::new(slot),
+            head_root: Hash256::from_low_u64_be(head),
+            proposer_boost_root: Hash256::zero(),
+            justified_checkpoint: Checkpoint::default(),
+            finalized_checkpoint: Checkpoint::default(),
+            nodes,
+        }
+    }
+
+    #[test]
+    fn diff_reorg() {
+        let from = snapshot(
+            2,
+            2,
+            vec![
+                node(0, 1, None, 10),
+                node(1, 2, Some(1), 6),
+                node(1, 3, Some(1), 4),
+            ],
+        );
+        let to = snapshot(
+            3,
+            4,
+            vec![
+                node(1, 2, Some(1), 4),
+                node(1, 3, Some(1), 4),
+                node(2, 4, Some(3), 4),
+            ],
+        );
+
+        let diff = from.diff(&to);
+        assert_eq!(diff.from_head_root, Hash256::from_low_u64_be(2));
+        assert_eq!(diff.to_head_root, Hash256::from_low_u64_be(4));
+        assert_eq!(diff.added, vec![node(2, 4, Some(3), 4)]);
+        assert_eq!(diff.removed, vec![node(0, 1, None, 10)]);
+        assert_eq!(
+            diff.weight_changes,
+            vec![ForkChoiceWeightChange {
+                slot: Slot::new(1),
+                root: Hash256::from_low_u64_be(2),
+                from_weight: 6,
+                to_weight: 4,
+            }]
+        );
+
+        let dot = from.diff_dot(&to);
+        assert!(dot.starts_with("digraph fork_choice {"));
+        assert!(dot.contains("weight 6 -> 4"));
+        // Every node in either snapshot is rendered exactly once.
+        assert_eq!(dot.matches("label=\"slot").count(), 4);
+        // The edge from the pruned root is kept since it appears in the earlier snapshot.
+        assert_eq!(dot.matches(" -> \"").count(), 3);
+    }
+}
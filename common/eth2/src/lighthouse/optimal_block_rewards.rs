@@ -0,0 +1,55 @@
+use crate::lighthouse::BlockRewardMeta;
+use serde::{Deserialize, Serialize};
+use types::{Hash256, Slot};
+
+/// The attestation rewards earned by a block, compared with the best block its proposer could
+/// have packed from the attestations later seen on chain.
+///
+/// All rewards in GWei.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OptimalBlockReward {
+    /// Block root of the block that these rewards are for.
+    pub block_root: Hash256,
+    /// Metadata about the block, particularly reward-relevant metadata.
+    pub meta: BlockRewardMeta,
+    /// Rewards earned by the attestations actually included in the block.
+    pub actual_attestation_rewards: u64,
+    /// Rewards which could have been earned by an optimally packed block.
+    ///
+    /// This is never less than `actual_attestation_rewards`.
+    pub optimal_attestation_rewards: u64,
+    /// The rewards left on the table by the proposer.
+    pub reward_gap: u64,
+    /// The number of attestations which were available for packing.
+    pub candidate_attestations: usize,
+}
+
+/// The total rewards left on the table by a single proposer over a range of blocks.
+#[derive(Debug, Default, PartialEq, Clone, Serialize, Deserialize)]
+pub struct ProposerRewardGap {
+    pub proposer_index: u64,
+    /// The number of blocks proposed in the range.
+    pub blocks: usize,
+    pub actual_attestation_rewards: u64,
+    pub optimal_attestation_rewards: u64,
+    pub reward_gap: u64,
+}
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OptimalBlockRewards {
+    pub blocks: Vec<OptimalBlockReward>,
+    /// Totals for each proposer in `blocks`, sorted by descending `reward_gap`.
+    pub proposers: Vec<ProposerRewardGap>,
+}
+
+/// Query parameters for the `/lighthouse/analysis/optimal_block_rewards` endpoint.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct OptimalBlockRewardsQuery {
+    /// Lower slot limit for blocks returned (inclusive).
+    pub start_slot: Slot,
+    /// Upper slot limit for blocks returned (inclusive).
+    pub end_slot: Slot,
+    /// The time budget for packing each block, in milliseconds.
+    #[serde(default)]
+    pub packing_time_budget_ms: Option<u64>,
+}
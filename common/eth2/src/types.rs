@@ -995,6 +995,17 @@ pub struct BlockGossip {
     pub slot: Slot,
     pub block: Hash256,
 }
+
+/// Emitted when the verifier execution engine disagrees with the primary execution engine about
+/// the validity of a block's execution payload.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct SsePayloadDisagreement {
+    pub slot: Slot,
+    pub block: Hash256,
+    pub execution_block_hash: ExecutionBlockHash,
+    pub primary_status: String,
+    pub verifier_status: String,
+}
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct SseChainReorg {
     pub slot: Slot,
@@ -1127,6 +1138,7 @@ pub enum EventKind<E: EthSpec> {
     AttesterSlashing(Box<AttesterSlashing<E>>),
     BlsToExecutionChange(Box<SignedBlsToExecutionChange>),
     BlockGossip(Box<BlockGossip>),
+    PayloadDisagreement(Box<SsePayloadDisagreement>),
 }
 
 impl<E: EthSpec> EventKind<E> {
@@ -1150,6 +1162,7 @@ impl<E: EthSpec> EventKind<E> {
             EventKind::AttesterSlashing(_) => "attester_slashing",
             EventKind::BlsToExecutionChange(_) => "bls_to_execution_change",
             EventKind::BlockGossip(_) => "block_gossip",
+            EventKind::PayloadDisagreement(_) => "payload_disagreement",
         }
     }
 
@@ -1248,6 +1261,11 @@ impl<E: EthSpec> EventKind<E> {
             "block_gossip" => Ok(EventKind::BlockGossip(serde_json::from_str(data).map_err(
                 |e| ServerError::InvalidServerSentEvent(format!("Block Gossip: {:?}", e)),
             )?)),
+            "payload_disagreement" => Ok(EventKind::PayloadDisagreement(
+                serde_json::from_str(data).map_err(|e| {
+                    ServerError::InvalidServerSentEvent(format!("Payload Disagreement: {:?}", e))
+                })?,
+            )),
             _ => Err(ServerError::InvalidServerSentEvent(
                 "Could not parse event tag".to_string(),
             )),
@@ -1283,6 +1301,7 @@ pub enum EventTopic {
     ProposerSlashing,
     BlsToExecutionChange,
     BlockGossip,
+    PayloadDisagreement,
 }
 
 impl FromStr for EventTopic {
@@ -1308,6 +1327,7 @@ impl FromStr for EventTopic {
             "proposer_slashing" => Ok(EventTopic::ProposerSlashing),
             "bls_to_execution_change" => Ok(EventTopic::BlsToExecutionChange),
             "block_gossip" => Ok(EventTopic::BlockGossip),
+            "payload_disagreement" => Ok(EventTopic::PayloadDisagreement),
             _ => Err("event topic cannot be parsed.".to_string()),
         }
     }
@@ -1334,6 +1354,7 @@ impl fmt::Display for EventTopic {
             EventTopic::ProposerSlashing => write!(f, "proposer_slashing"),
             EventTopic::BlsToExecutionChange => write!(f, "bls_to_execution_change"),
             EventTopic::BlockGossip => write!(f, "block_gossip"),
+            EventTopic::PayloadDisagreement => write!(f, "payload_disagreement"),
         }
     }
 }
@@ -3,12 +3,12 @@ use slog::Logger;
 use slog_term::Decorator;
 use std::io::{Result, Write};
 use std::path::PathBuf;
-use std::sync::LazyLock;
+use std::sync::{LazyLock, OnceLock};
 use std::time::{Duration, Instant};
 use tracing_appender::non_blocking::NonBlocking;
 use tracing_appender::rolling::{RollingFileAppender, Rotation};
 use tracing_logging_layer::LoggingLayer;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, Layer};
 
 pub const MAX_MESSAGE_WIDTH: usize = 40;
 
@@ -16,9 +16,12 @@ pub mod async_record;
 mod sse_logging_components;
 mod tracing_logging_layer;
 mod tracing_metrics_layer;
+mod tracing_otlp_layer;
 
 pub use sse_logging_components::SSELoggingComponents;
+pub use trace_context::{current_trace_context, set_remote_parent};
 pub use tracing_metrics_layer::MetricsLayer;
+pub use tracing_otlp_layer::{create_otlp_layer, OtlpConfig, TRACED_TARGETS};
 
 /// The minimum interval between log messages indicating that a queue is full.
 const LOG_DEBOUNCE_INTERVAL: Duration = Duration::from_secs(30);
@@ -214,7 +217,11 @@ impl TimeLatch {
     }
 }
 
-pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
+/// Kept so that pending spans can be flushed on shutdown, see `shutdown_otlp_tracing`.
+static OTLP_TRACER_PROVIDER: OnceLock<opentelemetry_sdk::trace::SdkTracerProvider> =
+    OnceLock::new();
+
+pub fn create_tracing_layer(base_tracing_log_path: PathBuf, otlp_config: Option<&OtlpConfig>) {
     let mut tracing_log_path = PathBuf::new();
 
     // Ensure that `tracing_log_path` only contains directories.
@@ -270,18 +277,57 @@ pub fn create_tracing_layer(base_tracing_log_path: PathBuf) {
         _discv5_guard,
     };
 
-    if let Err(e) = tracing_subscriber::fmt()
-        .with_env_filter(filter_layer)
-        .with_writer(std::io::sink)
-        .finish()
-        .with(MetricsLayer)
-        .with(custom_layer)
+    // The filter only applies to the dependency logs, since the exported spans are filtered
+    // separately.
+    let dependency_layer = MetricsLayer
+        .and_then(custom_layer)
+        .with_filter(filter_layer);
+    let otlp_layer = otlp_config.and_then(build_otlp_layer);
+
+    if let Err(e) = tracing_subscriber::registry()
+        .with(dependency_layer)
+        .with(otlp_layer)
         .try_init()
     {
         eprintln!("Failed to initialize dependency logging {e}");
     }
 }
 
+/// Export spans to an OpenTelemetry collector, for processes which don't log dependencies with
+/// `create_tracing_layer`.
+pub fn create_otlp_tracing_layer(otlp_config: &OtlpConfig) {
+    let otlp_layer = build_otlp_layer(otlp_config);
+
+    if let Err(e) = tracing_subscriber::registry().with(otlp_layer).try_init() {
+        eprintln!("Failed to initialize OpenTelemetry tracing {e}");
+    }
+}
+
+fn build_otlp_layer<S>(otlp_config: &OtlpConfig) -> Option<impl Layer<S>>
+where
+    S: tracing_core::Subscriber + for<'span> tracing_subscriber::registry::LookupSpan<'span>,
+{
+    match create_otlp_layer(otlp_config) {
+        Ok((layer, provider)) => {
+            let _ = OTLP_TRACER_PROVIDER.set(provider);
+            Some(layer)
+        }
+        Err(e) => {
+            eprintln!("Failed to initialize OpenTelemetry tracing {e}");
+            None
+        }
+    }
+}
+
+/// Flush the spans which have not yet been exported to the OpenTelemetry collector.
+pub fn shutdown_otlp_tracing() {
+    if let Some(provider) = OTLP_TRACER_PROVIDER.get() {
+        if let Err(e) = provider.shutdown() {
+            eprintln!("Failed to shut down OpenTelemetry tracing {e:?}");
+        }
+    }
+}
+
 /// Return a logger suitable for test usage.
 ///
 /// By default no logs will be printed, but they can be enabled via
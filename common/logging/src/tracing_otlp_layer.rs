@@ -0,0 +1,84 @@
+//! Exports tracing spans to an OpenTelemetry collector using OTLP over HTTP.
+//!
+//! Only the spans of Lighthouse crates are exported, see `TRACED_TARGETS`. Trace context is
+//! propagated between processes by the `trace_context` crate, using the propagator installed here.
+use opentelemetry::trace::TracerProvider;
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, SdkTracerProvider};
+use opentelemetry_sdk::Resource;
+use tracing_subscriber::filter::{LevelFilter, Targets};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+/// The crates whose `INFO` spans are exported.
+pub const TRACED_TARGETS: &[&str] = &[
+    "beacon_chain",
+    "network",
+    "http_api",
+    "execution_layer",
+    "eth2",
+    "validator_services",
+];
+
+/// The path of the OTLP/HTTP traces endpoint of a collector.
+const TRACES_PATH: &str = "/v1/traces";
+
+/// Configuration for exporting spans to an OpenTelemetry collector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OtlpConfig {
+    /// The base URL of the collector, e.g. `http://localhost:4318`.
+    pub collector_url: String,
+    /// The `service.name` attached to all spans.
+    pub service_name: String,
+    /// The fraction of new traces which are exported. Traces continued from a remote parent
+    /// follow the sampling decision of the parent.
+    pub sample_ratio: f64,
+}
+
+/// Build a layer which exports the spans of `TRACED_TARGETS` to the collector in `config`.
+///
+/// The returned provider must be kept alive for as long as spans are exported, and should be shut
+/// down to flush pending spans.
+pub fn create_otlp_layer<S>(
+    config: &OtlpConfig,
+) -> Result<(impl Layer<S>, SdkTracerProvider), String>
+where
+    S: tracing_core::Subscriber + for<'span> LookupSpan<'span>,
+{
+    let endpoint = format!(
+        "{}{}",
+        config.collector_url.trim_end_matches('/'),
+        TRACES_PATH
+    );
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .with_endpoint(endpoint)
+        .build()
+        .map_err(|e| format!("Unable to build OTLP exporter: {:?}", e))?;
+
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sample_ratio,
+        ))))
+        .with_resource(
+            Resource::builder()
+                .with_service_name(config.service_name.clone())
+                .build(),
+        )
+        .build();
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let targets = TRACED_TARGETS
+        .iter()
+        .fold(Targets::new(), |targets, target| {
+            targets.with_target(*target, LevelFilter::INFO)
+        });
+    let layer = tracing_opentelemetry::layer()
+        .with_tracer(provider.tracer("lighthouse"))
+        .with_filter(targets);
+
+    Ok((layer, provider))
+}
@@ -0,0 +1,106 @@
+use logging::{create_otlp_layer, current_trace_context, set_remote_parent, OtlpConfig};
+use opentelemetry::trace::TraceContextExt;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Start a stand-in for an OpenTelemetry collector, which sends the path of every request it
+/// receives down the returned channel.
+fn start_collector() -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let (tx, rx) = mpsc::channel();
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = line.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\nConnection: close\r\n\r\n")
+                .unwrap();
+            let path = request_line.split_whitespace().nth(1).unwrap_or_default();
+            let _ = tx.send(path.to_string());
+        }
+    });
+
+    (url, rx)
+}
+
+fn otlp_config(collector_url: String) -> OtlpConfig {
+    OtlpConfig {
+        collector_url,
+        service_name: "lighthouse-test".to_string(),
+        sample_ratio: 1.0,
+    }
+}
+
+#[test]
+fn spans_are_exported_to_collector() {
+    let (collector_url, requests) = start_collector();
+    let (layer, provider) = create_otlp_layer(&otlp_config(collector_url)).unwrap();
+
+    tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+        let _span = tracing::info_span!(target: "beacon_chain", "process_block").entered();
+    });
+    provider.force_flush().unwrap();
+
+    let path = requests.recv_timeout(Duration::from_secs(10)).unwrap();
+    assert_eq!(path, "/v1/traces");
+
+    provider.shutdown().unwrap();
+}
+
+#[test]
+fn trace_context_is_propagated() {
+    let (collector_url, _requests) = start_collector();
+    let (layer, provider) = create_otlp_layer(&otlp_config(collector_url)).unwrap();
+
+    tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+        let client_span = tracing::info_span!(target: "validator_services", "propose_block");
+        let headers = client_span.in_scope(current_trace_context);
+        assert!(headers.contains_key("traceparent"));
+
+        // Header names are case-insensitive.
+        let request_headers = headers
+            .iter()
+            .map(|(name, value)| (name.to_uppercase(), value.clone()))
+            .collect::<Vec<_>>();
+        let server_span = tracing::info_span!(target: "http_api", "http_request");
+        set_remote_parent(
+            &server_span,
+            request_headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str())),
+        );
+
+        assert_eq!(
+            server_span.context().span().span_context().trace_id(),
+            client_span.context().span().span_context().trace_id(),
+        );
+    });
+
+    provider.shutdown().unwrap();
+}
@@ -1,5 +1,7 @@
 //! A library to provide fast and efficient LRU Cache's.
 
+mod size;
 mod time;
 
+pub use size::{LRUSizeCache, MAX_ENTRY_FRACTION};
 pub use time::LRUTimeCache;
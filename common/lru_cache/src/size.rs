@@ -0,0 +1,155 @@
+//! This implements an LRU cache which is bounded by the total size of its values, rather than by
+//! the number of values it holds.
+use lru::LruCache;
+use std::borrow::Borrow;
+use std::hash::Hash;
+
+/// The largest fraction of the cache which may be used by a single value.
+///
+/// This prevents a single large value from evicting every other value.
+pub const MAX_ENTRY_FRACTION: usize = 8;
+
+struct Entry<Value> {
+    value: Value,
+    size_bytes: usize,
+}
+
+pub struct LRUSizeCache<Key, Value> {
+    /// The cached values, in order of use.
+    entries: LruCache<Key, Entry<Value>>,
+    /// The total size of the cached values.
+    size_bytes: usize,
+    /// The size beyond which the least recently used values are evicted.
+    max_size_bytes: usize,
+}
+
+impl<Key, Value> LRUSizeCache<Key, Value>
+where
+    Key: Eq + Hash + Clone,
+{
+    pub fn new(max_size_bytes: usize) -> Self {
+        LRUSizeCache {
+            entries: LruCache::unbounded(),
+            size_bytes: 0,
+            max_size_bytes,
+        }
+    }
+
+    /// The size of the largest value which will be cached.
+    pub fn max_entry_bytes(&self) -> usize {
+        self.max_size_bytes / MAX_ENTRY_FRACTION
+    }
+
+    /// Returns the value of `key`, marking it as the most recently used.
+    pub fn get<Q>(&mut self, key: &Q) -> Option<&Value>
+    where
+        Key: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.entries.get(key).map(|entry| &entry.value)
+    }
+
+    /// Inserts `value`, which is `size_bytes` large, evicting the least recently used values until
+    /// the cache is within its size bound.
+    ///
+    /// Values larger than `Self::max_entry_bytes` are not inserted. Returns `true` if `value` was
+    /// inserted.
+    pub fn insert(&mut self, key: Key, value: Value, size_bytes: usize) -> bool {
+        if size_bytes > self.max_entry_bytes() {
+            return false;
+        }
+
+        self.size_bytes += size_bytes;
+        if let Some(previous) = self.entries.put(key, Entry { value, size_bytes }) {
+            self.size_bytes -= previous.size_bytes;
+        }
+        while self.size_bytes > self.max_size_bytes {
+            let Some((_, evicted)) = self.entries.pop_lru() else {
+                break;
+            };
+            self.size_bytes -= evicted.size_bytes;
+        }
+        true
+    }
+
+    /// Removes every value whose key does not satisfy `keep`.
+    pub fn retain(&mut self, mut keep: impl FnMut(&Key) -> bool) {
+        let removed = self
+            .entries
+            .iter()
+            .filter(|(key, _)| !keep(key))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in removed {
+            if let Some(entry) = self.entries.pop(&key) {
+                self.size_bytes -= entry.size_bytes;
+            }
+        }
+    }
+
+    /// The number of cached values.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The total size of the cached values.
+    pub fn size_bytes(&self) -> usize {
+        self.size_bytes
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let mut cache = LRUSizeCache::new(4 * MAX_ENTRY_FRACTION);
+        for i in 0..MAX_ENTRY_FRACTION {
+            assert!(cache.insert(i, i, 4));
+        }
+        // Use the first value, so that the second is the least recently used.
+        assert_eq!(cache.get(&0), Some(&0));
+        assert!(cache.insert(MAX_ENTRY_FRACTION, MAX_ENTRY_FRACTION, 4));
+
+        assert_eq!(cache.get(&0), Some(&0));
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.len(), MAX_ENTRY_FRACTION);
+        assert_eq!(cache.size_bytes(), 4 * MAX_ENTRY_FRACTION);
+    }
+
+    #[test]
+    fn rejects_large_values() {
+        let mut cache = LRUSizeCache::new(4 * MAX_ENTRY_FRACTION);
+        assert!(!cache.insert(0, 0, 5));
+        assert!(cache.is_empty());
+        assert_eq!(cache.size_bytes(), 0);
+    }
+
+    #[test]
+    fn replaces_values() {
+        let mut cache = LRUSizeCache::new(4 * MAX_ENTRY_FRACTION);
+        assert!(cache.insert(0, 0, 4));
+        assert!(cache.insert(0, 1, 2));
+        assert_eq!(cache.get(&0), Some(&1));
+        assert_eq!(cache.size_bytes(), 2);
+    }
+
+    #[test]
+    fn retain() {
+        let mut cache = LRUSizeCache::new(4 * MAX_ENTRY_FRACTION);
+        for i in 0..4 {
+            cache.insert(i, i, 3);
+        }
+        cache.retain(|key| key % 2 == 0);
+
+        assert_eq!(cache.get(&0), Some(&0));
+        assert_eq!(cache.get(&1), None);
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.size_bytes(), 6);
+    }
+}
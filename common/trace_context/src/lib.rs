@@ -0,0 +1,35 @@
+//! Propagates the trace context of tracing spans between processes with the W3C `traceparent`
+//! header, so that the spans of a request from the validator client to the beacon node form a
+//! single trace.
+//!
+//! This only depends on the OpenTelemetry API, so that HTTP clients can propagate trace context
+//! without depending on an exporter. The propagator is installed by `logging::create_otlp_layer`,
+//! and until then no headers are produced.
+use std::collections::HashMap;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Returns the headers which propagate the trace context of the current span to another process.
+///
+/// The result is empty if the current span is not being exported.
+pub fn current_trace_context() -> HashMap<String, String> {
+    let context = tracing::Span::current().context();
+    let mut headers = HashMap::new();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut headers)
+    });
+    headers
+}
+
+/// Make the trace context propagated in the `headers` of a request the parent of `span`.
+pub fn set_remote_parent<'a>(
+    span: &tracing::Span,
+    headers: impl IntoIterator<Item = (&'a str, &'a str)>,
+) {
+    let headers = headers
+        .into_iter()
+        .map(|(name, value)| (name.to_lowercase(), value.to_string()))
+        .collect::<HashMap<_, _>>();
+    let context =
+        opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(&headers));
+    span.set_parent(context);
+}
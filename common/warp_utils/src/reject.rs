@@ -2,6 +2,7 @@ use eth2::types::{ErrorMessage, Failure, IndexedErrorMessage};
 use std::convert::Infallible;
 use std::error::Error;
 use std::fmt;
+use warp::http::header::{HeaderValue, RETRY_AFTER};
 use warp::{http::StatusCode, reject::Reject, reply::Response, Reply};
 
 #[derive(Debug)]
@@ -127,6 +128,15 @@ pub fn not_synced(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(NotSynced(msg))
 }
 
+#[derive(Debug)]
+pub struct ServiceUnavailable(pub String);
+
+impl Reject for ServiceUnavailable {}
+
+pub fn service_unavailable(msg: String) -> warp::reject::Rejection {
+    warp::reject::custom(ServiceUnavailable(msg))
+}
+
 #[derive(Debug)]
 pub struct InvalidAuthorization(pub String);
 
@@ -136,6 +146,27 @@ pub fn invalid_auth(msg: String) -> warp::reject::Rejection {
     warp::reject::custom(InvalidAuthorization(msg))
 }
 
+#[derive(Debug)]
+pub struct MissingAuthorization;
+
+impl Reject for MissingAuthorization {}
+
+pub fn missing_auth() -> warp::reject::Rejection {
+    warp::reject::custom(MissingAuthorization)
+}
+
+#[derive(Debug)]
+pub struct RateLimited {
+    /// The number of seconds after which the request may succeed.
+    pub retry_after: u64,
+}
+
+impl Reject for RateLimited {}
+
+pub fn rate_limited(retry_after: u64) -> warp::reject::Rejection {
+    warp::reject::custom(RateLimited { retry_after })
+}
+
 #[derive(Debug)]
 pub struct UnsupportedMediaType(pub String);
 
@@ -162,6 +193,7 @@ pub fn indexed_bad_request(message: String, failures: Vec<Failure>) -> warp::rej
 pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply, Infallible> {
     let code;
     let message;
+    let mut retry_after = None;
 
     if let Some(e) = err.find::<crate::reject::IndexedBadRequestErrors>() {
         message = format!("BAD_REQUEST: {}", e.message);
@@ -225,9 +257,22 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
     } else if let Some(e) = err.find::<crate::reject::NotSynced>() {
         code = StatusCode::SERVICE_UNAVAILABLE;
         message = format!("SERVICE_UNAVAILABLE: beacon node is syncing: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::ServiceUnavailable>() {
+        code = StatusCode::SERVICE_UNAVAILABLE;
+        message = format!("SERVICE_UNAVAILABLE: {}", e.0);
     } else if let Some(e) = err.find::<crate::reject::InvalidAuthorization>() {
         code = StatusCode::FORBIDDEN;
         message = format!("FORBIDDEN: Invalid auth token: {}", e.0);
+    } else if let Some(e) = err.find::<crate::reject::RateLimited>() {
+        code = StatusCode::TOO_MANY_REQUESTS;
+        message = format!(
+            "TOO_MANY_REQUESTS: rate limit exceeded, retry after {} seconds",
+            e.retry_after
+        );
+        retry_after = Some(e.retry_after);
+    } else if err.find::<crate::reject::MissingAuthorization>().is_some() {
+        code = StatusCode::UNAUTHORIZED;
+        message = "UNAUTHORIZED: missing Authorization header".to_string();
     } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
         if e.name().eq("Authorization") {
             code = StatusCode::UNAUTHORIZED;
@@ -253,7 +298,14 @@ pub async fn handle_rejection(err: warp::Rejection) -> Result<impl warp::Reply,
         stacktraces: vec![],
     });
 
-    Ok(warp::reply::with_status(json, code))
+    let mut response = warp::reply::with_status(json, code).into_response();
+    if let Some(retry_after) = retry_after {
+        response
+            .headers_mut()
+            .insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    }
+
+    Ok(response)
 }
 
 /// Convert a warp `Rejection` into a `Response`.
@@ -715,14 +715,7 @@ where
             }));
         }
 
-        // Add proposer score boost if the block is timely.
-        let is_before_attesting_interval =
-            block_delay < Duration::from_secs(spec.seconds_per_slot / INTERVALS_PER_SLOT);
-
-        let is_first_block = self.fc_store.proposer_boost_root().is_zero();
-        if current_slot == block.slot() && is_before_attesting_interval && is_first_block {
-            self.fc_store.set_proposer_boost_root(block_root);
-        }
+        self.apply_proposer_boost(current_slot, block.slot(), block_root, block_delay, spec);
 
         // Update store with checkpoints if necessary
         self.update_checkpoints(
@@ -889,6 +882,58 @@ where
         Ok(())
     }
 
+    /// Add `block` to fork choice without verifying it against its state.
+    ///
+    /// Only the time and proposer boost rules of `Self::on_block` are applied, and the
+    /// checkpoints of `block` are taken as-is. This allows tools which have no states available,
+    /// such as `lcli fork-choice-sim`, to replay blocks. Only available with the `simulator`
+    /// feature.
+    #[cfg(feature = "simulator")]
+    pub fn on_unverified_block(
+        &mut self,
+        system_time_current_slot: Slot,
+        block: ProtoBlock,
+        block_delay: Duration,
+        spec: &ChainSpec,
+    ) -> Result<(), Error<T::Error>> {
+        if self.proto_array.contains_block(&block.root) {
+            return Ok(());
+        }
+
+        let current_slot = self.update_time(system_time_current_slot)?;
+        if block.slot > current_slot {
+            return Err(Error::InvalidBlock(InvalidBlock::FutureSlot {
+                current_slot,
+                block_slot: block.slot,
+            }));
+        }
+
+        self.apply_proposer_boost(current_slot, block.slot, block.root, block_delay, spec);
+        self.update_checkpoints(block.justified_checkpoint, block.finalized_checkpoint)?;
+        self.proto_array.process_block::<E>(block, current_slot)?;
+
+        Ok(())
+    }
+
+    /// Add proposer score boost to `block_root` if it is the first timely block of the current
+    /// slot.
+    fn apply_proposer_boost(
+        &mut self,
+        current_slot: Slot,
+        block_slot: Slot,
+        block_root: Hash256,
+        block_delay: Duration,
+        spec: &ChainSpec,
+    ) {
+        let is_before_attesting_interval =
+            block_delay < Duration::from_secs(spec.seconds_per_slot / INTERVALS_PER_SLOT);
+
+        let is_first_block = self.fc_store.proposer_boost_root().is_zero();
+        if current_slot == block_slot && is_before_attesting_interval && is_first_block {
+            self.fc_store.set_proposer_boost_root(block_root);
+        }
+    }
+
     /// Update checkpoints in store if necessary
     fn update_checkpoints(
         &mut self,
@@ -1493,6 +1538,17 @@ pub struct PersistedForkChoice {
     queued_attestations: Vec<QueuedAttestation>,
 }
 
+#[cfg(feature = "simulator")]
+impl PersistedForkChoice {
+    /// Persists `proto_array` without any queued attestations.
+    pub fn from_proto_array(proto_array: &ProtoArrayForkChoice) -> Self {
+        Self {
+            proto_array_bytes: proto_array.as_bytes(),
+            queued_attestations: vec![],
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use types::MainnetEthSpec;
@@ -188,29 +188,13 @@ impl ForkChoiceTestDefinition {
                     justified_checkpoint,
                     finalized_checkpoint,
                 } => {
-                    let block = Block {
+                    let block = scripted_block(
                         slot,
                         root,
-                        parent_root: Some(parent_root),
-                        state_root: Hash256::zero(),
-                        target_root: Hash256::zero(),
-                        current_epoch_shuffling_id: AttestationShufflingId::from_components(
-                            Epoch::new(0),
-                            Hash256::zero(),
-                        ),
-                        next_epoch_shuffling_id: AttestationShufflingId::from_components(
-                            Epoch::new(0),
-                            Hash256::zero(),
-                        ),
+                        parent_root,
                         justified_checkpoint,
                         finalized_checkpoint,
-                        // All blocks are imported optimistically.
-                        execution_status: ExecutionStatus::Optimistic(
-                            ExecutionBlockHash::from_root(root),
-                        ),
-                        unrealized_justified_checkpoint: None,
-                        unrealized_finalized_checkpoint: None,
-                    };
+                    );
                     fork_choice
                         .process_block::<MainnetEthSpec>(block, slot)
                         .unwrap_or_else(|e| {
@@ -285,6 +269,33 @@ impl ForkChoiceTestDefinition {
     }
 }
 
+/// Builds a `Block` for a scripted `ProcessBlock` operation, with junk shufflings and an
+/// optimistic execution status.
+fn scripted_block(
+    slot: Slot,
+    root: Hash256,
+    parent_root: Hash256,
+    justified_checkpoint: Checkpoint,
+    finalized_checkpoint: Checkpoint,
+) -> Block {
+    let junk_shuffling_id = AttestationShufflingId::from_components(Epoch::new(0), Hash256::zero());
+    Block {
+        slot,
+        root,
+        parent_root: Some(parent_root),
+        state_root: Hash256::zero(),
+        target_root: Hash256::zero(),
+        current_epoch_shuffling_id: junk_shuffling_id.clone(),
+        next_epoch_shuffling_id: junk_shuffling_id,
+        justified_checkpoint,
+        finalized_checkpoint,
+        // All blocks are imported optimistically.
+        execution_status: ExecutionStatus::Optimistic(ExecutionBlockHash::from_root(root)),
+        unrealized_justified_checkpoint: None,
+        unrealized_finalized_checkpoint: None,
+    }
+}
+
 /// Gives a root that is not the zero hash (unless i is `usize::MAX)`.
 fn get_root(i: u64) -> Hash256 {
     Hash256::from_low_u64_be(i + 1)
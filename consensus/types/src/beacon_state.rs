@@ -30,6 +30,7 @@ pub use crate::beacon_state::balance::Balance;
 pub use crate::beacon_state::exit_cache::ExitCache;
 pub use crate::beacon_state::progressive_balances_cache::*;
 pub use crate::beacon_state::slashings_cache::SlashingsCache;
+pub use crate::beacon_state::ssz_write::SszWrite;
 pub use eth_spec::*;
 pub use iter::BlockRootsIter;
 pub use milhouse::{interface::Interface, List, Vector};
@@ -42,6 +43,7 @@ mod iter;
 mod progressive_balances_cache;
 mod pubkey_cache;
 mod slashings_cache;
+mod ssz_write;
 mod tests;
 
 pub const CACHED_EPOCHS: usize = 3;
@@ -2480,6 +2482,46 @@ impl<E: EthSpec> BeaconState<E> {
         ))
     }
 
+    /// Write the SSZ encoding of the state to `writer`, identical to `as_ssz_bytes`.
+    ///
+    /// Lists are written item by item, so the memory used does not grow with the size of the
+    /// state. See `SszWrite`.
+    pub fn ssz_write<W: std::io::Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        macro_rules! write_fields {
+            ($map_fields:ident, $state:expr) => {{
+                let mut fixed_len = 0_usize;
+                $map_fields!($state, |_, field| {
+                    fixed_len = fixed_len.saturating_add(ssz_write::fixed_part_len(field));
+                });
+
+                let mut result = Ok(());
+                let mut offset = fixed_len;
+                $map_fields!($state, |_, field| {
+                    if result.is_ok() {
+                        result = ssz_write::write_fixed_part(field, &mut offset, writer);
+                    }
+                });
+                $map_fields!($state, |_, field| {
+                    if result.is_ok() {
+                        result = ssz_write::write_variable_part(field, writer);
+                    }
+                });
+                result
+            }};
+        }
+
+        match self {
+            BeaconState::Base(state) => write_fields!(map_beacon_state_base_fields, state),
+            BeaconState::Altair(state) => write_fields!(map_beacon_state_altair_fields, state),
+            BeaconState::Bellatrix(state) => {
+                write_fields!(map_beacon_state_bellatrix_fields, state)
+            }
+            BeaconState::Capella(state) => write_fields!(map_beacon_state_capella_fields, state),
+            BeaconState::Deneb(state) => write_fields!(map_beacon_state_deneb_fields, state),
+            BeaconState::Electra(state) => write_fields!(map_beacon_state_electra_fields, state),
+        }
+    }
+
     #[allow(clippy::arithmetic_side_effects)]
     pub fn apply_pending_mutations(&mut self) -> Result<(), Error> {
         match self {
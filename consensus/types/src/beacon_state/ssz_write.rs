@@ -0,0 +1,111 @@
+//! Incremental SSZ encoding of `BeaconState` fields.
+//!
+//! A mainnet state is hundreds of megabytes when encoded, most of which is the validator registry
+//! and the lists of per-validator values. These are written item by item, so that a state can be
+//! encoded to a file or socket without first encoding it into memory.
+use crate::*;
+use milhouse::Value;
+use ssz::{encode_length, Encode, BYTES_PER_LENGTH_OFFSET};
+use std::io::{self, Write};
+use std::sync::Arc;
+
+/// A value which can be SSZ-encoded directly to a writer.
+///
+/// Writes are small (a single list item for lists), so unbuffered writers should be wrapped in an
+/// `io::BufWriter`.
+pub trait SszWrite: Encode {
+    /// Write the SSZ encoding of `self` to `writer`.
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.as_ssz_bytes())
+    }
+}
+
+impl<T: Value, N: Unsigned> SszWrite for List<T, N> {
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_items(self.len(), || self.iter(), writer)
+    }
+}
+
+impl<T: Value, N: Unsigned> SszWrite for Vector<T, N> {
+    fn ssz_write<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write_items(self.len(), || self.iter(), writer)
+    }
+}
+
+impl SszWrite for u64 {}
+impl SszWrite for Slot {}
+impl SszWrite for Epoch {}
+impl SszWrite for Hash256 {}
+impl SszWrite for Fork {}
+impl SszWrite for BeaconBlockHeader {}
+impl SszWrite for Eth1Data {}
+impl SszWrite for Checkpoint {}
+impl<N: Unsigned + Clone> SszWrite for BitVector<N> {}
+impl<E: EthSpec> SszWrite for Arc<SyncCommittee<E>> {}
+impl<E: EthSpec> SszWrite for ExecutionPayloadHeaderBellatrix<E> {}
+impl<E: EthSpec> SszWrite for ExecutionPayloadHeaderCapella<E> {}
+impl<E: EthSpec> SszWrite for ExecutionPayloadHeaderDeneb<E> {}
+impl<E: EthSpec> SszWrite for ExecutionPayloadHeaderElectra<E> {}
+
+/// Write the `len` items returned by `iter` as an SSZ list or vector.
+///
+/// Variable-length items are preceded by their offsets, so the items are iterated twice.
+fn write_items<'a, T, I, W>(len: usize, iter: impl Fn() -> I, writer: &mut W) -> io::Result<()>
+where
+    T: Encode + 'a,
+    I: Iterator<Item = &'a T>,
+    W: Write,
+{
+    if !T::is_ssz_fixed_len() {
+        let mut offset = BYTES_PER_LENGTH_OFFSET.saturating_mul(len);
+        for item in iter() {
+            writer.write_all(&encode_length(offset))?;
+            offset = offset.saturating_add(item.ssz_bytes_len());
+        }
+    }
+
+    let mut buf = vec![];
+    for item in iter() {
+        buf.clear();
+        item.ssz_append(&mut buf);
+        writer.write_all(&buf)?;
+    }
+    Ok(())
+}
+
+/// The length of `field` in the fixed-length part of its container.
+pub(crate) fn fixed_part_len<T: Encode>(_field: &T) -> usize {
+    if T::is_ssz_fixed_len() {
+        T::ssz_fixed_len()
+    } else {
+        BYTES_PER_LENGTH_OFFSET
+    }
+}
+
+/// Write the fixed-length part of `field`: its encoding if it is fixed-length, otherwise its
+/// `offset` within the container, which is advanced past the field.
+pub(crate) fn write_fixed_part<T: SszWrite, W: Write>(
+    field: &T,
+    offset: &mut usize,
+    writer: &mut W,
+) -> io::Result<()> {
+    if T::is_ssz_fixed_len() {
+        field.ssz_write(writer)
+    } else {
+        writer.write_all(&encode_length(*offset))?;
+        *offset = offset.saturating_add(field.ssz_bytes_len());
+        Ok(())
+    }
+}
+
+/// Write the variable-length part of `field`, which is empty if `field` is fixed-length.
+pub(crate) fn write_variable_part<T: SszWrite, W: Write>(
+    field: &T,
+    writer: &mut W,
+) -> io::Result<()> {
+    if T::is_ssz_fixed_len() {
+        Ok(())
+    } else {
+        field.ssz_write(writer)
+    }
+}
@@ -2,9 +2,10 @@
 use crate::test_utils::*;
 use beacon_chain::test_utils::{BeaconChainHarness, EphemeralHarnessType};
 use beacon_chain::types::{
-    test_utils::TestRandom, BeaconState, BeaconStateAltair, BeaconStateBase, BeaconStateError,
-    ChainSpec, Domain, Epoch, EthSpec, FixedBytesExtended, Hash256, Keypair, MainnetEthSpec,
-    MinimalEthSpec, RelativeEpoch, Slot, Vector,
+    test_utils::TestRandom, BeaconState, BeaconStateAltair, BeaconStateBase, BeaconStateElectra,
+    BeaconStateError, ChainSpec, Domain, Epoch, EthSpec, FixedBytesExtended, Hash256, Keypair,
+    MainnetEthSpec, MinimalEthSpec, PendingAttestation, RelativeEpoch, Slot, SszWrite, Validator,
+    Vector,
 };
 use ssz::Encode;
 use std::ops::Mul;
@@ -403,3 +404,38 @@ fn decode_base_and_altair() {
             .expect_err("bad altair state cannot be decoded");
     }
 }
+
+#[test]
+fn ssz_write_matches_as_ssz_bytes() {
+    type E = MinimalEthSpec;
+    let rng = &mut XorShiftRng::from_seed([42; 16]);
+
+    // Lists are empty in random states, so add items to those which are streamed.
+    let mut base = BeaconStateBase::<E>::random_for_test(rng);
+    let mut altair = BeaconStateAltair::<E>::random_for_test(rng);
+    let mut electra = BeaconStateElectra::<E>::random_for_test(rng);
+    for _ in 0..3 {
+        let validator = Validator::random_for_test(rng);
+        let balance = u64::random_for_test(rng);
+        base.validators.push(validator.clone()).unwrap();
+        base.balances.push(balance).unwrap();
+        // Pending attestations are variable-length, so are preceded by offsets.
+        base.previous_epoch_attestations
+            .push(PendingAttestation::random_for_test(rng))
+            .unwrap();
+        altair.validators.push(validator.clone()).unwrap();
+        altair.inactivity_scores.push(balance).unwrap();
+        electra.validators.push(validator).unwrap();
+        electra.balances.push(balance).unwrap();
+    }
+
+    for state in [
+        BeaconState::Base(base),
+        BeaconState::Altair(altair),
+        BeaconState::Electra(electra),
+    ] {
+        let mut bytes = vec![];
+        state.ssz_write(&mut bytes).unwrap();
+        assert_eq!(bytes, state.as_ssz_bytes());
+    }
+}
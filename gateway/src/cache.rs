@@ -0,0 +1,171 @@
+//! A cache of responses about finalized data, which are shared between all clients.
+//!
+//! A response is cached if its request can only ever refer to the same data, and the beacon node
+//! reports that the data is finalized. Such responses never become stale, so they are only evicted
+//! to keep the cache within its memory bound.
+use crate::metrics;
+use bytes::Bytes;
+use lru_cache::LRUSizeCache;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use warp::http::{HeaderMap, Method};
+
+/// Block and state identifiers which refer to different data as the chain progresses.
+const MUTABLE_IDS: &[&str] = &["head", "finalized", "justified"];
+
+#[derive(Clone)]
+pub struct CachedResponse {
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+pub struct ResponseCache {
+    entries: Mutex<LRUSizeCache<String, CachedResponse>>,
+}
+
+impl ResponseCache {
+    pub fn new(max_size_bytes: usize) -> Self {
+        Self {
+            entries: Mutex::new(LRUSizeCache::new(max_size_bytes)),
+        }
+    }
+
+    /// The largest response which will be cached.
+    pub fn max_entry_bytes(&self) -> usize {
+        self.entries.lock().max_entry_bytes()
+    }
+
+    pub fn get(&self, key: &str) -> Option<CachedResponse> {
+        let response = self.entries.lock().get(key).cloned();
+        let outcome = if response.is_some() { "hit" } else { "miss" };
+        metrics::inc_counter_vec(&metrics::GATEWAY_CACHE_REQUESTS_TOTAL, &[outcome]);
+        response
+    }
+
+    /// Add `response` to the cache if it is about finalized data.
+    pub fn insert_if_finalized(&self, key: String, response: CachedResponse) {
+        if response.body.len() > self.max_entry_bytes() || !is_finalized(&response.body) {
+            return;
+        }
+
+        let mut entries = self.entries.lock();
+        let size_bytes = response.body.len();
+        entries.insert(key, response, size_bytes);
+        metrics::set_gauge(&metrics::GATEWAY_CACHE_ENTRIES, entries.len() as i64);
+        metrics::set_gauge(
+            &metrics::GATEWAY_CACHE_SIZE_BYTES,
+            entries.size_bytes() as i64,
+        );
+    }
+}
+
+/// Returns the cache key of a request to `network`, or `None` if its response may change.
+pub fn cache_key(
+    network: &str,
+    method: &Method,
+    path_and_query: &str,
+    headers: &HeaderMap,
+) -> Option<String> {
+    if *method != Method::GET {
+        return None;
+    }
+    let path = path_and_query.split('?').next().unwrap_or_default();
+    if path
+        .split('/')
+        .any(|segment| MUTABLE_IDS.contains(&segment))
+    {
+        return None;
+    }
+    // Responses are only cached as JSON, which is what clients receive when they do not ask for
+    // another encoding.
+    let accept = headers
+        .get(warp::http::header::ACCEPT)
+        .and_then(|accept| accept.to_str().ok())
+        .unwrap_or("application/json");
+    if !accept.contains("application/json") && !accept.contains("*/*") {
+        return None;
+    }
+    Some(format!("{} {}", network, path_and_query))
+}
+
+/// Returns `true` if `body` is a JSON response about finalized data.
+fn is_finalized(body: &[u8]) -> bool {
+    #[derive(Deserialize)]
+    struct Metadata {
+        #[serde(default)]
+        finalized: bool,
+    }
+    serde_json::from_slice::<Metadata>(body).is_ok_and(|metadata| metadata.finalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lru_cache::MAX_ENTRY_FRACTION;
+    use warp::http::HeaderValue;
+
+    fn response(body: &'static str) -> CachedResponse {
+        CachedResponse {
+            headers: HeaderMap::new(),
+            body: Bytes::from_static(body.as_bytes()),
+        }
+    }
+
+    #[test]
+    fn only_immutable_json_requests_are_cacheable() {
+        let json = HeaderMap::new();
+        let key = |method: Method, path: &str| cache_key("mainnet", &method, path, &json);
+
+        assert_eq!(
+            key(Method::GET, "/eth/v2/beacon/blocks/100"),
+            Some("mainnet /eth/v2/beacon/blocks/100".to_string())
+        );
+        assert!(key(Method::GET, "/eth/v1/beacon/states/100/committees?epoch=3").is_some());
+        assert!(key(Method::GET, "/eth/v2/beacon/blocks/head").is_none());
+        assert!(key(Method::GET, "/eth/v1/beacon/states/finalized/validators").is_none());
+        assert!(key(Method::GET, "/eth/v1/beacon/states/justified/root").is_none());
+        assert!(key(Method::POST, "/eth/v1/beacon/states/100/validators").is_none());
+
+        let mut ssz = HeaderMap::new();
+        ssz.insert(
+            warp::http::header::ACCEPT,
+            HeaderValue::from_static("application/octet-stream"),
+        );
+        assert!(cache_key("mainnet", &Method::GET, "/eth/v2/beacon/blocks/100", &ssz).is_none());
+    }
+
+    #[test]
+    fn only_finalized_responses_are_cached() {
+        let cache = ResponseCache::new(1024);
+        cache.insert_if_finalized(
+            "finalized".into(),
+            response(r#"{"finalized":true,"data":{}}"#),
+        );
+        cache.insert_if_finalized(
+            "not_finalized".into(),
+            response(r#"{"finalized":false,"data":{}}"#),
+        );
+        cache.insert_if_finalized("no_metadata".into(), response(r#"{"data":{}}"#));
+
+        assert!(cache.get("finalized").is_some());
+        assert!(cache.get("not_finalized").is_none());
+        assert!(cache.get("no_metadata").is_none());
+    }
+
+    #[test]
+    fn evict_least_recently_used() {
+        let body = r#"{"finalized":true,"data":"0123456789012345678901234567890123456789"}"#;
+        let cache = ResponseCache::new(body.len() * MAX_ENTRY_FRACTION);
+        let capacity = MAX_ENTRY_FRACTION;
+        for i in 0..capacity {
+            cache.insert_if_finalized(i.to_string(), response(body));
+        }
+        // Use the first entry, so that the second is the least recently used.
+        assert!(cache.get("0").is_some());
+        cache.insert_if_finalized(capacity.to_string(), response(body));
+
+        assert!(cache.get("0").is_some());
+        assert!(cache.get("1").is_none());
+        assert!(cache.get(&capacity.to_string()).is_some());
+    }
+}
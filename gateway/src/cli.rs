@@ -0,0 +1,91 @@
+use clap::{Arg, ArgAction, Command};
+use clap_utils::{get_color_style, FLAG_HEADER};
+
+pub fn cli_app() -> Command {
+    Command::new("gateway")
+        .about(
+            "Serve the standard beacon node HTTP API for several networks by forwarding \
+            requests to the healthiest of each network's beacon nodes. Networks are selected by \
+            the first segment of the request path or by the Host header. Responses about \
+            finalized data are cached by the gateway.",
+        )
+        .styles(get_color_style())
+        .display_order(0)
+        .arg(
+            Arg::new("help")
+                .long("help")
+                .short('h')
+                .help("Prints help information")
+                .action(ArgAction::HelpLong)
+                .display_order(0)
+                .help_heading(FLAG_HEADER),
+        )
+        .arg(
+            Arg::new("networks")
+                .long("networks")
+                .value_name("PATH")
+                .help(
+                    "Path to a YAML file listing the networks to serve. Each network has a \
+                    `name`, a list of `beacon_nodes` with a `url` and an optional `metrics_url`, \
+                    and optionally a list of `hosts`, the name of a built-in `network` and a \
+                    `testnet_dir`.",
+                )
+                .required(true)
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("listen-address")
+                .long("listen-address")
+                .value_name("ADDRESS")
+                .help("The address to listen for HTTP requests on.")
+                .default_value("127.0.0.1")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("port")
+                .long("port")
+                .value_name("PORT")
+                .help("The TCP port to listen for HTTP requests on.")
+                .default_value("5052")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("cache-size")
+                .long("cache-size")
+                .value_name("MEGABYTES")
+                .help(
+                    "The maximum size of the cache of responses about finalized data. Set to 0 \
+                    to disable the cache.",
+                )
+                .default_value("256")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("backend-timeout")
+                .long("backend-timeout")
+                .value_name("SECONDS")
+                .help(
+                    "The time to wait for a beacon node to respond before forwarding the request \
+                    to the next beacon node of the network.",
+                )
+                .default_value("12")
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+        .arg(
+            Arg::new("beacon-nodes-sync-tolerances")
+                .long("beacon-nodes-sync-tolerances")
+                .value_name("SYNC_TOLERANCES")
+                .help(
+                    "A comma-separated list of 3 values which sets the size of the `Synced`, \
+                    `Small` and `Medium` sync distance ranges used to order each network's beacon \
+                    nodes by health, as for the validator client. [default: 8,8,48]",
+                )
+                .action(ArgAction::Set)
+                .display_order(0),
+        )
+}
@@ -0,0 +1,197 @@
+use beacon_node_fallback::beacon_node_health::BeaconNodeSyncDistanceTiers;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::File;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// The first path segment of the gateway's own endpoints, which may not be used as a network name.
+pub const GATEWAY_PATH_PREFIX: &str = "gateway";
+
+/// A beacon node serving a network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BeaconNodeConfig {
+    /// The URL of the beacon node's HTTP API.
+    pub url: SensitiveUrl,
+    /// The URL of the beacon node's metrics server, if its metrics should be aggregated.
+    #[serde(default)]
+    pub metrics_url: Option<SensitiveUrl>,
+}
+
+/// A network served by the gateway.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkConfig {
+    /// The name of the network, which is also the path prefix it is served under.
+    pub name: String,
+    /// The name of the built-in network configuration to use. Defaults to `name`.
+    #[serde(default)]
+    pub network: Option<String>,
+    /// A directory containing a custom network configuration, as for `--testnet-dir`.
+    #[serde(default)]
+    pub testnet_dir: Option<PathBuf>,
+    /// Hosts which serve the network without a path prefix.
+    #[serde(default)]
+    pub hosts: Vec<String>,
+    pub beacon_nodes: Vec<BeaconNodeConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    pub listen_addr: IpAddr,
+    pub listen_port: u16,
+    /// The maximum size of the cache of responses about finalized data, in bytes.
+    pub cache_size: usize,
+    /// The time to wait for a beacon node to respond before trying the next.
+    pub backend_timeout: Duration,
+    pub beacon_node_fallback: beacon_node_fallback::Config,
+    pub networks: Vec<NetworkConfig>,
+}
+
+impl Config {
+    pub fn from_cli(cli_args: &ArgMatches) -> Result<Self, String> {
+        let networks_path: PathBuf = parse_required(cli_args, "networks")?;
+        let networks_file = File::open(&networks_path)
+            .map_err(|e| format!("Unable to open {}: {}", networks_path.display(), e))?;
+        let networks: Vec<NetworkConfig> = serde_yaml::from_reader(networks_file)
+            .map_err(|e| format!("Unable to parse {}: {}", networks_path.display(), e))?;
+
+        let sync_tolerances = parse_optional::<String>(cli_args, "beacon-nodes-sync-tolerances")?
+            .map(|tolerances| BeaconNodeSyncDistanceTiers::from_str(&tolerances))
+            .transpose()?
+            .unwrap_or_default();
+
+        let config = Self {
+            listen_addr: parse_required(cli_args, "listen-address")?,
+            listen_port: parse_required(cli_args, "port")?,
+            cache_size: parse_required::<usize>(cli_args, "cache-size")? * 1024 * 1024,
+            backend_timeout: Duration::from_secs(parse_required(cli_args, "backend-timeout")?),
+            beacon_node_fallback: beacon_node_fallback::Config { sync_tolerances },
+            networks,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Check that every request can be routed to exactly one network.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.networks.is_empty() {
+            return Err("No networks configured".to_string());
+        }
+
+        let mut names = HashSet::new();
+        let mut hosts = HashSet::new();
+        for network in &self.networks {
+            if network.name.is_empty() || network.name.contains('/') {
+                return Err(format!("Invalid network name: {:?}", network.name));
+            }
+            if network.name == GATEWAY_PATH_PREFIX {
+                return Err(format!(
+                    "The network name {:?} is reserved",
+                    GATEWAY_PATH_PREFIX
+                ));
+            }
+            if !names.insert(network.name.as_str()) {
+                return Err(format!("Duplicate network name: {}", network.name));
+            }
+            if network.beacon_nodes.is_empty() {
+                return Err(format!("No beacon nodes configured for {}", network.name));
+            }
+            for host in &network.hosts {
+                if !hosts.insert(host.to_ascii_lowercase()) {
+                    return Err(format!("Duplicate host: {}", host));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(networks: &str) -> Config {
+        Config {
+            listen_addr: IpAddr::from([127, 0, 0, 1]),
+            listen_port: 5052,
+            cache_size: 0,
+            backend_timeout: Duration::from_secs(12),
+            beacon_node_fallback: beacon_node_fallback::Config::default(),
+            networks: serde_yaml::from_str(networks).unwrap(),
+        }
+    }
+
+    #[test]
+    fn parse_networks() {
+        let config = config(
+            r#"
+            - name: mainnet
+              hosts: [mainnet.example.com]
+              beacon_nodes:
+                - url: http://localhost:5052
+                  metrics_url: http://localhost:5054
+                - url: http://localhost:6052
+            - name: devnet
+              testnet_dir: /tmp/devnet
+              beacon_nodes:
+                - url: http://localhost:7052
+            "#,
+        );
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config.networks.len(), 2);
+        assert_eq!(config.networks[0].beacon_nodes.len(), 2);
+        assert!(config.networks[0].beacon_nodes[1].metrics_url.is_none());
+        assert_eq!(
+            config.networks[1].testnet_dir,
+            Some(PathBuf::from("/tmp/devnet"))
+        );
+    }
+
+    #[test]
+    fn reject_ambiguous_networks() {
+        let duplicate_name = config(
+            r#"
+            - name: mainnet
+              beacon_nodes: [{ url: "http://localhost:5052" }]
+            - name: mainnet
+              beacon_nodes: [{ url: "http://localhost:6052" }]
+            "#,
+        );
+        assert!(duplicate_name.validate().is_err());
+
+        let duplicate_host = config(
+            r#"
+            - name: mainnet
+              hosts: [beacon.example.com]
+              beacon_nodes: [{ url: "http://localhost:5052" }]
+            - name: holesky
+              hosts: [Beacon.Example.com]
+              beacon_nodes: [{ url: "http://localhost:6052" }]
+            "#,
+        );
+        assert!(duplicate_host.validate().is_err());
+
+        let reserved_name = config(
+            r#"
+            - name: gateway
+              beacon_nodes: [{ url: "http://localhost:5052" }]
+            "#,
+        );
+        assert!(reserved_name.validate().is_err());
+
+        let no_beacon_nodes = config(
+            r#"
+            - name: mainnet
+              beacon_nodes: []
+            "#,
+        );
+        assert!(no_beacon_nodes.validate().is_err());
+    }
+}
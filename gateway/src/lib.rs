@@ -0,0 +1,281 @@
+//! A gateway which serves the standard beacon node HTTP API for several networks.
+//!
+//! Each network is served by one or more beacon nodes. Requests are routed to a network by the
+//! first segment of their path (e.g. `/holesky/eth/v1/node/version`) or by their `Host` header,
+//! and forwarded to the healthiest of the network's beacon nodes, falling back to the others if it
+//! fails. Responses about finalized data are cached.
+//!
+//! The gateway also serves:
+//!
+//! - `/gateway/health`: the health of each network's beacon nodes.
+//! - `/gateway/metrics`: the metrics of the gateway and of each beacon node with a `metrics_url`,
+//!   labelled by network and beacon node.
+mod cache;
+mod cli;
+pub mod config;
+mod metrics;
+mod network;
+mod proxy;
+
+use bytes::Bytes;
+use cache::ResponseCache;
+pub use cli::cli_app;
+pub use config::Config;
+use config::GATEWAY_PATH_PREFIX;
+use environment::RuntimeContext;
+use futures::future::join_all;
+use network::Network;
+use serde::Serialize;
+use slog::{crit, info};
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use types::EthSpec;
+use warp::http::{HeaderMap, Method};
+use warp::path::FullPath;
+use warp::reply::Response;
+use warp::{Filter, Reply};
+
+/// The time to wait for a beacon node's metrics.
+const METRICS_SCRAPE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The largest request body which is forwarded to the beacon nodes.
+///
+/// The largest beacon API request bodies are blocks published with their blobs. A block is at most
+/// 10 MiB when SSZ encoded, as bounded by the gossip message size limit, and roughly twice that
+/// when JSON encoded.
+const MAX_REQUEST_BODY_BYTES: u64 = 32 * 1024 * 1024;
+
+#[derive(Serialize)]
+struct NetworkHealth {
+    name: String,
+    /// Whether the genesis of the network is known, so that requests can be served.
+    ready: bool,
+    beacon_nodes: Vec<beacon_node_fallback::CandidateInfo>,
+}
+
+/// Start serving the networks in `config`, returning once the server is listening.
+///
+/// Networks are served as soon as their genesis is fetched from one of their beacon nodes, until
+/// which time requests to them are rejected.
+pub async fn run<E: EthSpec>(context: RuntimeContext<E>, config: Config) -> Result<(), String> {
+    let executor = context.executor;
+    let log = executor.log().clone();
+
+    let networks = config
+        .networks
+        .iter()
+        .map(|network_config| Arc::new(Network::new(network_config)))
+        .collect::<Vec<_>>();
+    for (network, network_config) in networks.iter().zip(&config.networks) {
+        let log = log.clone();
+        let start = network
+            .clone()
+            .start(network_config.clone(), config.clone(), executor.clone());
+        executor.spawn(
+            async move {
+                if let Err(e) = start.await {
+                    crit!(log, "Unable to serve network"; "error" => e);
+                }
+            },
+            "gateway_network",
+        );
+    }
+    let networks = Arc::new(networks);
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(config.backend_timeout)
+        .build()
+        .map_err(|e| format!("Unable to build HTTP client: {:?}", e))?;
+    let cache = (config.cache_size > 0).then(|| Arc::new(ResponseCache::new(config.cache_size)));
+    let backend_timeout = config.backend_timeout;
+
+    let inner_networks = networks.clone();
+    let networks_filter = warp::any().map(move || inner_networks.clone());
+    let inner_client = client.clone();
+    let client_filter = warp::any().map(move || inner_client.clone());
+
+    // GET gateway/health
+    let get_health = warp::path(GATEWAY_PATH_PREFIX)
+        .and(warp::path("health"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(networks_filter.clone())
+        .then(|networks: Arc<Vec<Arc<Network>>>| async move {
+            let mut health = Vec::with_capacity(networks.len());
+            for network in networks.iter() {
+                let beacon_nodes = match network.backends() {
+                    Some(backends) => backends.candidate_info().await.0,
+                    None => vec![],
+                };
+                health.push(NetworkHealth {
+                    name: network.name.clone(),
+                    ready: network.backends().is_some(),
+                    beacon_nodes,
+                });
+            }
+            warp::reply::json(&serde_json::json!({ "data": health })).into_response()
+        });
+
+    // GET gateway/metrics
+    let get_metrics = warp::path(GATEWAY_PATH_PREFIX)
+        .and(warp::path("metrics"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(networks_filter.clone())
+        .and(client_filter.clone())
+        .then(
+            |networks: Arc<Vec<Arc<Network>>>, client: reqwest::Client| async move {
+                match gather_metrics(&networks, &client).await {
+                    Ok(text) => {
+                        warp::reply::with_header(text, "Content-Type", "text/plain; version=0.0.4")
+                            .into_response()
+                    }
+                    Err(e) => {
+                        warp::reply::with_status(e, warp::http::StatusCode::INTERNAL_SERVER_ERROR)
+                            .into_response()
+                    }
+                }
+            },
+        );
+
+    // `GET` and `HEAD` requests are forwarded without a body, whilst other requests must declare
+    // the length of their body.
+    let body_filter = warp::get()
+        .or(warp::head())
+        .unify()
+        .map(Bytes::new)
+        .or(warp::body::content_length_limit(MAX_REQUEST_BODY_BYTES).and(warp::body::bytes()))
+        .unify();
+
+    // Everything else is forwarded to the beacon nodes of a network.
+    let proxy = warp::method()
+        .and(warp::path::full())
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::headers_cloned())
+        .and(body_filter)
+        .and(networks_filter)
+        .and(client_filter)
+        .then(
+            move |method: Method,
+                  path: FullPath,
+                  query: String,
+                  headers: HeaderMap,
+                  body: Bytes,
+                  networks: Arc<Vec<Arc<Network>>>,
+                  client: reqwest::Client| {
+                let cache = cache.clone();
+                async move {
+                    let host = headers
+                        .get(warp::http::header::HOST)
+                        .and_then(|host| host.to_str().ok());
+                    let Some((network, path)) = proxy::route(&networks, host, path.as_str()) else {
+                        return warp::reply::with_status(
+                            "unknown network",
+                            warp::http::StatusCode::NOT_FOUND,
+                        )
+                        .into_response();
+                    };
+                    let path_and_query = if query.is_empty() {
+                        path
+                    } else {
+                        format!("{}?{}", path, query)
+                    };
+                    let request = proxy::Request {
+                        method,
+                        path_and_query,
+                        headers,
+                        body,
+                    };
+                    proxy::forward(network, request, &client, cache.as_deref(), backend_timeout)
+                        .await
+                }
+            },
+        );
+
+    let routes = get_health.or(get_metrics).unify().or(proxy).unify();
+
+    let listen_addr = SocketAddr::new(config.listen_addr, config.listen_port);
+    let exit = executor.exit();
+    let (listen_addr, server) = warp::serve(routes)
+        .try_bind_with_graceful_shutdown(listen_addr, async {
+            exit.await;
+        })
+        .map_err(|e| format!("Unable to start gateway: {}", e))?;
+    executor.spawn_without_exit(server, "gateway");
+
+    info!(
+        log,
+        "Gateway started";
+        "listen_address" => %listen_addr,
+        "networks" => networks.len(),
+    );
+    Ok(())
+}
+
+/// Returns the metrics of the gateway and of each beacon node with a metrics URL.
+async fn gather_metrics(
+    networks: &[Arc<Network>],
+    client: &reqwest::Client,
+) -> Result<String, String> {
+    let mut merger = metrics::MetricsMerger::default();
+
+    let scrapes = networks.iter().flat_map(|network| {
+        network.beacon_nodes.iter().filter_map(|beacon_node| {
+            let metrics_url = beacon_node.metrics_url.as_ref()?;
+            let scrape = async move {
+                let mut url = metrics_url.full.clone();
+                url.set_path(&format!("{}/metrics", url.path().trim_end_matches('/')));
+                let response = client
+                    .get(url)
+                    .timeout(METRICS_SCRAPE_TIMEOUT)
+                    .send()
+                    .await
+                    .and_then(|response| response.error_for_status())
+                    .ok()?;
+                response.text().await.ok()
+            };
+            Some(async move { (network, &beacon_node.url, scrape.await) })
+        })
+    });
+    for (network, beacon_node_url, text) in join_all(scrapes).await {
+        metrics::set_gauge_vec(
+            &metrics::GATEWAY_METRICS_SCRAPE_SUCCESS,
+            &[&network.name, beacon_node_url.as_ref()],
+            text.is_some() as i64,
+        );
+        if let Some(text) = text {
+            merger.add(
+                &text,
+                &[
+                    ("network", &network.name),
+                    ("beacon_node", beacon_node_url.as_ref()),
+                ],
+            );
+        }
+    }
+
+    for network in networks {
+        if let Some(backends) = network.backends() {
+            let (_, num_available, num_synced) = backends.candidate_info().await;
+            metrics::set_gauge_vec(
+                &metrics::GATEWAY_BEACON_NODES_AVAILABLE,
+                &[&network.name],
+                num_available as i64,
+            );
+            metrics::set_gauge_vec(
+                &metrics::GATEWAY_BEACON_NODES_SYNCED,
+                &[&network.name],
+                num_synced as i64,
+            );
+        }
+    }
+
+    let mut text = String::new();
+    metrics::TextEncoder::new()
+        .encode_utf8(&metrics::gather(), &mut text)
+        .map_err(|e| format!("Unable to encode metrics: {:?}", e))?;
+    merger.add(&text, &[]);
+
+    Ok(merger.finish())
+}
@@ -0,0 +1,195 @@
+pub use metrics::*;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+pub static GATEWAY_REQUESTS_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "gateway_requests_total",
+        "Count of requests forwarded to each network, by response status",
+        &["network", "status"],
+    )
+});
+pub static GATEWAY_CACHE_REQUESTS_TOTAL: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "gateway_cache_requests_total",
+        "Count of cacheable requests, by whether they were served from the cache",
+        &["outcome"],
+    )
+});
+pub static GATEWAY_CACHE_ENTRIES: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "gateway_cache_entries",
+        "Number of responses about finalized data in the cache",
+    )
+});
+pub static GATEWAY_CACHE_SIZE_BYTES: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "gateway_cache_size_bytes",
+        "Total size of the responses about finalized data in the cache",
+    )
+});
+pub static GATEWAY_BEACON_NODES_AVAILABLE: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "gateway_beacon_nodes_available",
+        "Number of each network's beacon nodes which are online and compatible",
+        &["network"],
+    )
+});
+pub static GATEWAY_BEACON_NODES_SYNCED: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "gateway_beacon_nodes_synced",
+        "Number of each network's beacon nodes which are synced",
+        &["network"],
+    )
+});
+pub static GATEWAY_METRICS_SCRAPE_SUCCESS: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "gateway_metrics_scrape_success",
+        "Whether the metrics of each beacon node were scraped successfully",
+        &["network", "beacon_node"],
+    )
+});
+
+/// The lines of a metric family in the Prometheus text format.
+#[derive(Default)]
+struct Family {
+    /// The `# HELP` and `# TYPE` lines.
+    metadata: Vec<String>,
+    samples: Vec<String>,
+}
+
+/// Merges the metrics of several processes into a single Prometheus text exposition.
+///
+/// The samples of each metric family must be contiguous, so families with the same name are
+/// combined rather than the texts being concatenated. Samples from each process are distinguished
+/// by the labels given for that process.
+#[derive(Default)]
+pub struct MetricsMerger {
+    families: BTreeMap<String, Family>,
+}
+
+impl MetricsMerger {
+    /// Add the metrics in `text`, adding `labels` to each sample.
+    pub fn add(&mut self, text: &str, labels: &[(&str, &str)]) {
+        let labels = labels
+            .iter()
+            .map(|(name, value)| format!("{}=\"{}\"", name, escape_label_value(value)))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let mut family_name = None;
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(comment) = line.strip_prefix('#') {
+                let mut words = comment.split_whitespace();
+                if let (Some("HELP" | "TYPE"), Some(name)) = (words.next(), words.next()) {
+                    let family = self.families.entry(name.to_string()).or_default();
+                    // Processes running the same software describe their metrics identically.
+                    if family.metadata.len() < 2 && !family.metadata.iter().any(|l| l == line) {
+                        family.metadata.push(line.to_string());
+                    }
+                    family_name = Some(name.to_string());
+                }
+                continue;
+            }
+
+            let name_end = line.find(['{', ' ']).unwrap_or(line.len());
+            let sample_name = &line[..name_end];
+            // Samples such as `_bucket` and `_sum` belong to the preceding family.
+            let family = match &family_name {
+                Some(family_name) if sample_name.starts_with(family_name.as_str()) => family_name,
+                _ => sample_name,
+            };
+            let sample = add_labels(line, name_end, &labels);
+            self.families
+                .entry(family.to_string())
+                .or_default()
+                .samples
+                .push(sample);
+        }
+    }
+
+    pub fn finish(self) -> String {
+        let mut text = String::new();
+        for family in self.families.into_values() {
+            for line in family.metadata.into_iter().chain(family.samples) {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        text
+    }
+}
+
+fn add_labels(sample: &str, name_end: usize, labels: &str) -> String {
+    if labels.is_empty() {
+        return sample.to_string();
+    }
+    let (name, rest) = sample.split_at(name_end);
+    match rest.strip_prefix('{') {
+        Some(rest) if rest.starts_with('}') => format!("{}{{{}{}", name, labels, rest),
+        Some(rest) => format!("{}{{{},{}", name, labels, rest),
+        None => format!("{}{{{}}}{}", name, labels, rest),
+    }
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_families() {
+        let backend = "# HELP beacon_head_slot Slot of the head block\n\
+            # TYPE beacon_head_slot gauge\n\
+            beacon_head_slot 100\n\
+            # HELP http_api_paths_total Count of HTTP requests received\n\
+            # TYPE http_api_paths_total counter\n\
+            http_api_paths_total{path=\"/eth/v1/node/version\"} 3\n";
+        let gateway = "# HELP gateway_cache_entries Number of responses\n\
+            # TYPE gateway_cache_entries gauge\n\
+            gateway_cache_entries 7\n";
+
+        let mut merger = MetricsMerger::default();
+        merger.add(backend, &[("network", "mainnet"), ("beacon_node", "bn-1")]);
+        merger.add(backend, &[("network", "holesky"), ("beacon_node", "bn-2")]);
+        merger.add(gateway, &[]);
+
+        assert_eq!(
+            merger.finish(),
+            "# HELP beacon_head_slot Slot of the head block\n\
+            # TYPE beacon_head_slot gauge\n\
+            beacon_head_slot{network=\"mainnet\",beacon_node=\"bn-1\"} 100\n\
+            beacon_head_slot{network=\"holesky\",beacon_node=\"bn-2\"} 100\n\
+            # HELP gateway_cache_entries Number of responses\n\
+            # TYPE gateway_cache_entries gauge\n\
+            gateway_cache_entries 7\n\
+            # HELP http_api_paths_total Count of HTTP requests received\n\
+            # TYPE http_api_paths_total counter\n\
+            http_api_paths_total{network=\"mainnet\",beacon_node=\"bn-1\",path=\"/eth/v1/node/version\"} 3\n\
+            http_api_paths_total{network=\"holesky\",beacon_node=\"bn-2\",path=\"/eth/v1/node/version\"} 3\n"
+        );
+    }
+
+    #[test]
+    fn histogram_samples_belong_to_family() {
+        let text = "# TYPE block_delay_seconds histogram\n\
+            block_delay_seconds_bucket{le=\"1\"} 2\n\
+            block_delay_seconds_sum 1.5\n\
+            block_delay_seconds_count 2\n";
+        let mut merger = MetricsMerger::default();
+        merger.add(text, &[("network", "a\"b")]);
+        assert_eq!(
+            merger.finish(),
+            "# TYPE block_delay_seconds histogram\n\
+            block_delay_seconds_bucket{network=\"a\\\"b\",le=\"1\"} 2\n\
+            block_delay_seconds_sum{network=\"a\\\"b\"} 1.5\n\
+            block_delay_seconds_count{network=\"a\\\"b\"} 2\n"
+        );
+    }
+}
@@ -0,0 +1,194 @@
+//! The beacon nodes serving each network, ordered by health.
+//!
+//! Each network has its own `ChainSpec` and possibly its own `EthSpec`, so each is given its own
+//! `BeaconNodeFallback`, as used by the validator client. A network's beacon nodes cannot be
+//! ordered by health until its genesis time is known, so the fallback is only made available once
+//! genesis has been fetched from one of them.
+use crate::config::{BeaconNodeConfig, Config, NetworkConfig};
+use beacon_node_fallback::{
+    start_fallback_updater_service, BeaconNodeFallback, CandidateBeaconNode, CandidateInfo, Errors,
+};
+use environment::RuntimeContext;
+use eth2::{BeaconNodeHttpClient, Timeouts};
+use eth2_config::Eth2Config;
+use eth2_network_config::Eth2NetworkConfig;
+use slog::{info, warn};
+use slot_clock::{SlotClock, SystemTimeSlotClock};
+use std::fmt::Debug;
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+use task_executor::TaskExecutor;
+use types::{EthSpec, EthSpecId, GnosisEthSpec, MainnetEthSpec, MinimalEthSpec};
+
+/// The delay between attempts to fetch the genesis of a network.
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// The beacon nodes of a network, for each `EthSpec` a network may use.
+pub enum Backends {
+    Mainnet(Arc<BeaconNodeFallback<SystemTimeSlotClock, MainnetEthSpec>>),
+    Minimal(Arc<BeaconNodeFallback<SystemTimeSlotClock, MinimalEthSpec>>),
+    Gnosis(Arc<BeaconNodeFallback<SystemTimeSlotClock, GnosisEthSpec>>),
+}
+
+macro_rules! map_backends {
+    ($backends:expr, |$fallback:ident| $body:expr) => {
+        match $backends {
+            Backends::Mainnet($fallback) => $body,
+            Backends::Minimal($fallback) => $body,
+            Backends::Gnosis($fallback) => $body,
+        }
+    };
+}
+
+impl Backends {
+    /// Run `func` against each beacon node in order of health, returning the first success.
+    pub async fn first_success<F, O, Err, R>(&self, func: F) -> Result<O, Errors<Err>>
+    where
+        F: Fn(BeaconNodeHttpClient) -> R,
+        R: Future<Output = Result<O, Err>>,
+        Err: Debug,
+    {
+        map_backends!(self, |fallback| fallback.first_success(func).await)
+    }
+
+    /// The health of each beacon node, and the number which are available and synced.
+    pub async fn candidate_info(&self) -> (Vec<CandidateInfo>, usize, usize) {
+        map_backends!(self, |fallback| fallback.get_notifier_info().await)
+    }
+}
+
+pub struct Network {
+    pub name: String,
+    pub hosts: Vec<String>,
+    pub beacon_nodes: Vec<BeaconNodeConfig>,
+    backends: OnceLock<Backends>,
+}
+
+impl Network {
+    pub fn new(config: &NetworkConfig) -> Self {
+        Self {
+            name: config.name.clone(),
+            hosts: config.hosts.clone(),
+            beacon_nodes: config.beacon_nodes.clone(),
+            backends: OnceLock::new(),
+        }
+    }
+
+    /// The beacon nodes of the network, or `None` if its genesis is not yet known.
+    pub fn backends(&self) -> Option<&Backends> {
+        self.backends.get()
+    }
+
+    /// Load the configuration of the network, wait for its genesis and then start ordering its
+    /// beacon nodes by health.
+    pub async fn start(
+        self: Arc<Self>,
+        network_config: NetworkConfig,
+        config: Config,
+        executor: TaskExecutor,
+    ) -> Result<(), String> {
+        let eth2_network_config = match &network_config.testnet_dir {
+            Some(testnet_dir) => Eth2NetworkConfig::load(testnet_dir.clone())?,
+            None => {
+                let name = network_config.network.as_ref().unwrap_or(&self.name);
+                Eth2NetworkConfig::constant(name)?
+                    .ok_or_else(|| format!("Unknown network: {}", name))?
+            }
+        };
+
+        let backends = match eth2_network_config.eth_spec_id()? {
+            EthSpecId::Mainnet => Backends::Mainnet(
+                self.connect::<MainnetEthSpec>(eth2_network_config, &config, executor)
+                    .await?,
+            ),
+            EthSpecId::Minimal => Backends::Minimal(
+                self.connect::<MinimalEthSpec>(eth2_network_config, &config, executor)
+                    .await?,
+            ),
+            EthSpecId::Gnosis => Backends::Gnosis(
+                self.connect::<GnosisEthSpec>(eth2_network_config, &config, executor)
+                    .await?,
+            ),
+        };
+        self.backends
+            .set(backends)
+            .map_err(|_| format!("Network {} was started twice", self.name))
+    }
+
+    async fn connect<E: EthSpec>(
+        &self,
+        eth2_network_config: Eth2NetworkConfig,
+        config: &Config,
+        executor: TaskExecutor,
+    ) -> Result<Arc<BeaconNodeFallback<SystemTimeSlotClock, E>>, String> {
+        let log = executor.log().clone();
+        let spec = Arc::new(eth2_network_config.chain_spec::<E>()?);
+
+        let candidates = self
+            .beacon_nodes
+            .iter()
+            .enumerate()
+            .map(|(index, beacon_node)| {
+                let client = BeaconNodeHttpClient::new(
+                    beacon_node.url.clone(),
+                    Timeouts::set_all(config.backend_timeout),
+                );
+                CandidateBeaconNode::new(client, index)
+            })
+            .collect();
+        let mut fallback = BeaconNodeFallback::new(
+            candidates,
+            config.beacon_node_fallback,
+            vec![],
+            spec.clone(),
+            log.clone(),
+        );
+
+        let genesis = loop {
+            match fallback
+                .first_success(|node| async move { node.get_beacon_genesis().await })
+                .await
+            {
+                Ok(genesis) => break genesis.data,
+                Err(errors) => {
+                    warn!(
+                        log,
+                        "Unable to fetch genesis";
+                        "network" => &self.name,
+                        "retry in" => format!("{} seconds", RETRY_DELAY.as_secs()),
+                        "error" => %errors,
+                    );
+                }
+            }
+            tokio::time::sleep(RETRY_DELAY).await;
+        };
+
+        fallback.set_slot_clock(SystemTimeSlotClock::new(
+            spec.genesis_slot,
+            Duration::from_secs(genesis.genesis_time),
+            Duration::from_secs(spec.seconds_per_slot),
+        ));
+        let fallback = Arc::new(fallback);
+
+        let context = RuntimeContext {
+            executor: executor.clone_with_name(format!("gateway_{}", self.name)),
+            eth_spec_instance: E::default(),
+            eth2_config: Eth2Config {
+                eth_spec_id: E::spec_name(),
+                spec,
+            },
+            eth2_network_config: Some(Arc::new(eth2_network_config)),
+            sse_logging_components: None,
+        };
+        start_fallback_updater_service(context, fallback.clone())?;
+
+        info!(
+            log,
+            "Serving network";
+            "network" => &self.name,
+            "beacon_nodes" => self.beacon_nodes.len(),
+        );
+        Ok(fallback)
+    }
+}
@@ -0,0 +1,294 @@
+//! Forwarding of requests to the beacon nodes of a network.
+use crate::cache::{cache_key, CachedResponse, ResponseCache};
+use crate::metrics;
+use crate::network::Network;
+use bytes::Bytes;
+use eth2::BeaconNodeHttpClient;
+use std::sync::Arc;
+use std::time::Duration;
+use warp::http::header::{self, HeaderMap, HeaderName};
+use warp::http::{Method, StatusCode};
+use warp::hyper::Body;
+use warp::reply::Response;
+
+/// Headers which only apply to a single connection, and so are not forwarded.
+const HOP_BY_HOP_HEADERS: &[HeaderName] = &[
+    header::CONNECTION,
+    header::CONTENT_LENGTH,
+    header::HOST,
+    header::PROXY_AUTHENTICATE,
+    header::PROXY_AUTHORIZATION,
+    header::TE,
+    header::TRAILER,
+    header::TRANSFER_ENCODING,
+    header::UPGRADE,
+];
+
+pub struct Request {
+    pub method: Method,
+    pub path_and_query: String,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+#[derive(Debug)]
+enum ForwardError {
+    Timeout,
+    Request(reqwest::Error),
+    /// The beacon node failed to serve the request, and it may be served by another.
+    Status(reqwest::Response),
+}
+
+/// Returns the network which serves requests to `host` and `path`, and the path to forward.
+///
+/// Requests to a host configured for a network are forwarded unchanged. Otherwise the first path
+/// segment selects the network, and is removed from the forwarded path.
+pub fn route<'a>(
+    networks: &'a [Arc<Network>],
+    host: Option<&str>,
+    path: &str,
+) -> Option<(&'a Arc<Network>, String)> {
+    if let Some(host) = host {
+        let hostname = host.rsplit_once(':').map_or(host, |(hostname, port)| {
+            // Leave IPv6 addresses such as `[::1]` intact.
+            if port.chars().all(|c| c.is_ascii_digit()) {
+                hostname
+            } else {
+                host
+            }
+        });
+        if let Some(network) = networks.iter().find(|network| {
+            network
+                .hosts
+                .iter()
+                .any(|network_host| network_host.eq_ignore_ascii_case(hostname))
+        }) {
+            return Some((network, path.to_string()));
+        }
+    }
+
+    let path = path.strip_prefix('/').unwrap_or(path);
+    let (name, rest) = path.split_once('/').unwrap_or((path, ""));
+    networks
+        .iter()
+        .find(|network| network.name == name)
+        .map(|network| (network, format!("/{}", rest)))
+}
+
+/// Forward `request` to the healthiest beacon node of `network` which can serve it.
+pub async fn forward(
+    network: &Network,
+    request: Request,
+    client: &reqwest::Client,
+    cache: Option<&ResponseCache>,
+    timeout: Duration,
+) -> Response {
+    let response = forward_uncounted(network, request, client, cache, timeout).await;
+    metrics::inc_counter_vec(
+        &metrics::GATEWAY_REQUESTS_TOTAL,
+        &[&network.name, response.status().as_str()],
+    );
+    response
+}
+
+async fn forward_uncounted(
+    network: &Network,
+    request: Request,
+    client: &reqwest::Client,
+    cache: Option<&ResponseCache>,
+    timeout: Duration,
+) -> Response {
+    let Some(backends) = network.backends() else {
+        return error_response(
+            StatusCode::SERVICE_UNAVAILABLE,
+            format!("{} is not yet connected to a beacon node", network.name),
+        );
+    };
+
+    let key = cache.and_then(|_| {
+        cache_key(
+            &network.name,
+            &request.method,
+            &request.path_and_query,
+            &request.headers,
+        )
+    });
+    if let (Some(cache), Some(key)) = (cache, &key) {
+        if let Some(cached) = cache.get(key) {
+            let mut response = Response::new(Body::from(cached.body));
+            *response.headers_mut() = cached.headers;
+            return response;
+        }
+    }
+
+    let result = backends
+        .first_success(|beacon_node| send(client, beacon_node, &request, timeout))
+        .await;
+    let response = match result {
+        Ok(response) => response,
+        Err(errors) => {
+            // Return the last response from a beacon node, if any responded.
+            let last_response = errors
+                .0
+                .into_iter()
+                .rev()
+                .find_map(|(_, error)| match error {
+                    beacon_node_fallback::Error::RequestFailed(ForwardError::Status(response)) => {
+                        Some(response)
+                    }
+                    _ => None,
+                });
+            match last_response {
+                Some(response) => response,
+                None => {
+                    return error_response(
+                        StatusCode::BAD_GATEWAY,
+                        format!("no beacon node of {} responded", network.name),
+                    )
+                }
+            }
+        }
+    };
+
+    let status = response.status();
+    let headers = forwarded_headers(response.headers());
+    let cacheable_len = match (cache, &key) {
+        (Some(cache), Some(_)) if status == StatusCode::OK => response
+            .content_length()
+            .filter(|len| *len as usize <= cache.max_entry_bytes()),
+        _ => None,
+    };
+
+    let mut reply = if let (Some(cache), Some(key), Some(_)) = (cache, key, cacheable_len) {
+        // Responses which may be cached are small, so they are read before being sent.
+        match response.bytes().await {
+            Ok(body) => {
+                cache.insert_if_finalized(
+                    key,
+                    CachedResponse {
+                        headers: headers.clone(),
+                        body: body.clone(),
+                    },
+                );
+                Response::new(Body::from(body))
+            }
+            Err(e) => {
+                return error_response(
+                    StatusCode::BAD_GATEWAY,
+                    format!("unable to read response: {}", e),
+                )
+            }
+        }
+    } else {
+        Response::new(Body::wrap_stream(response.bytes_stream()))
+    };
+    *reply.status_mut() = status;
+    *reply.headers_mut() = headers;
+    reply
+}
+
+async fn send(
+    client: &reqwest::Client,
+    beacon_node: BeaconNodeHttpClient,
+    request: &Request,
+    timeout: Duration,
+) -> Result<reqwest::Response, ForwardError> {
+    let url = format!(
+        "{}{}",
+        beacon_node.server().full.as_str().trim_end_matches('/'),
+        request.path_and_query
+    );
+    let builder = client
+        .request(request.method.clone(), url)
+        .headers(forwarded_headers(&request.headers))
+        .body(request.body.clone());
+
+    let response = tokio::time::timeout(timeout, builder.send())
+        .await
+        .map_err(|_| ForwardError::Timeout)?
+        .map_err(ForwardError::Request)?;
+    if response.status().is_server_error() {
+        Err(ForwardError::Status(response))
+    } else {
+        Ok(response)
+    }
+}
+
+fn forwarded_headers(headers: &HeaderMap) -> HeaderMap {
+    let mut headers = headers.clone();
+    for name in HOP_BY_HOP_HEADERS {
+        headers.remove(name);
+    }
+    headers
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    let body = serde_json::json!({
+        "code": status.as_u16(),
+        "message": message,
+        "stacktraces": [],
+    });
+    let mut response = Response::new(Body::from(body.to_string()));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::NetworkConfig;
+
+    fn networks() -> Vec<Arc<Network>> {
+        let configs: Vec<NetworkConfig> = serde_yaml::from_str(
+            r#"
+            - name: mainnet
+              hosts: [mainnet.example.com]
+              beacon_nodes: [{ url: "http://localhost:5052" }]
+            - name: holesky
+              beacon_nodes: [{ url: "http://localhost:6052" }]
+            "#,
+        )
+        .unwrap();
+        configs
+            .iter()
+            .map(|config| Arc::new(Network::new(config)))
+            .collect()
+    }
+
+    fn route_name(host: Option<&str>, path: &str) -> Option<(String, String)> {
+        route(&networks(), host, path).map(|(network, path)| (network.name.clone(), path))
+    }
+
+    #[test]
+    fn route_by_path_prefix() {
+        assert_eq!(
+            route_name(None, "/holesky/eth/v1/node/version"),
+            Some(("holesky".into(), "/eth/v1/node/version".into()))
+        );
+        assert_eq!(
+            route_name(Some("localhost:5052"), "/mainnet/eth/v1/node/version"),
+            Some(("mainnet".into(), "/eth/v1/node/version".into()))
+        );
+        assert_eq!(route_name(None, "/sepolia/eth/v1/node/version"), None);
+        assert_eq!(route_name(None, "/eth/v1/node/version"), None);
+    }
+
+    #[test]
+    fn route_by_host() {
+        for host in ["mainnet.example.com", "Mainnet.Example.com:443"] {
+            assert_eq!(
+                route_name(Some(host), "/eth/v1/node/version"),
+                Some(("mainnet".into(), "/eth/v1/node/version".into()))
+            );
+        }
+        // The path is forwarded unchanged, even if it starts with a network name.
+        assert_eq!(
+            route_name(Some("mainnet.example.com"), "/holesky/eth/v1/node/version"),
+            Some(("mainnet".into(), "/holesky/eth/v1/node/version".into()))
+        );
+    }
+}
@@ -0,0 +1,87 @@
+//! # Block Reward Gaps
+//!
+//! Use this tool to audit whether proposers are leaving attestation rewards on the table.
+//!
+//! For each block in a slot range the beacon node rebuilds the operation pool from the attestations
+//! later included on chain, packs the best block it can, and compares its rewards with those of the
+//! block that was actually proposed. Attestations which were gossiped but never included on chain
+//! are unknown to the beacon node, so the optimal rewards are a lower bound.
+//!
+//! The beacon node must have the states for the range available, so an archive node is
+//! recommended for old slots.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! lcli block-reward-gaps \
+//!     --beacon-url http://localhost:5052 \
+//!     --start-slot 8000001 \
+//!     --end-slot 8000064 \
+//!     --output-path /tmp/block_reward_gaps.json
+//! ```
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2::{BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{EthSpec, Slot};
+
+/// Packing blocks optimally is slow, so allow plenty of time for large ranges.
+const HTTP_TIMEOUT: Duration = Duration::from_secs(600);
+
+pub fn run<E: EthSpec>(env: Environment<E>, matches: &ArgMatches) -> Result<(), String> {
+    let beacon_url: SensitiveUrl = parse_required(matches, "beacon-url")?;
+    let start_slot: Slot = parse_required(matches, "start-slot")?;
+    let end_slot: Slot = parse_required(matches, "end-slot")?;
+    let packing_time_budget: Option<u64> = parse_optional(matches, "packing-time-budget")?;
+    let output_path: Option<PathBuf> = parse_optional(matches, "output-path")?;
+    let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+
+    let rewards = env
+        .core_context()
+        .executor
+        .handle()
+        .ok_or("shutdown in progress")?
+        .block_on(client.get_lighthouse_analysis_optimal_block_rewards(
+            start_slot,
+            end_slot,
+            packing_time_budget,
+        ))
+        .map_err(|e| format!("Failed to fetch optimal block rewards: {:?}", e))?;
+
+    if let Some(output_path) = output_path {
+        let file = File::create(&output_path)
+            .map_err(|e| format!("Unable to create {}: {:?}", output_path.display(), e))?;
+        serde_json::to_writer_pretty(file, &rewards.blocks)
+            .map_err(|e| format!("Unable to write to {}: {:?}", output_path.display(), e))?;
+    }
+
+    let actual_total: u64 = rewards
+        .blocks
+        .iter()
+        .map(|block| block.actual_attestation_rewards)
+        .sum();
+    let gap_total: u64 = rewards.blocks.iter().map(|block| block.reward_gap).sum();
+    println!(
+        "{} blocks: {} gwei earned, {} gwei missed",
+        rewards.blocks.len(),
+        actual_total,
+        gap_total
+    );
+
+    println!("proposer_index,blocks,actual_gwei,optimal_gwei,missed_gwei");
+    for proposer in rewards.proposers {
+        println!(
+            "{},{},{},{},{}",
+            proposer.proposer_index,
+            proposer.blocks,
+            proposer.actual_attestation_rewards,
+            proposer.optimal_attestation_rewards,
+            proposer.reward_gap
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,677 @@
+//! # Fork Choice Simulator
+//!
+//! Use this tool to explore how fork choice would respond to a scripted sequence of blocks,
+//! attestations and slot ticks, starting from the fork choice of a real node. This is useful for
+//! understanding how proposer boost, the re-org thresholds and late blocks interact.
+//!
+//! The starting point is either:
+//!
+//! - A `PersistedForkChoice` SSZ file, such as one exported from a stopped beacon node with
+//!   `lighthouse db inspect --column frk --output values`.
+//! - A JSON dump of the `/lighthouse/proto_array` endpoint. Such dumps do not include votes or
+//!   balances, so the scenario should provide `justified_balances` for attestations to carry any
+//!   weight.
+//!
+//! The scenario is replayed through the same `ForkChoice` used by the beacon node, so slot ticks
+//! expire proposer boost and pull up checkpoints at epoch boundaries, and attestations from the
+//! current slot are queued until the following slot. Since no states are available, blocks are
+//! imported optimistically and take their checkpoints from the scenario.
+//!
+//! ## Scenario format
+//!
+//! ```yaml
+//! # Optional, defaults to the balances of the loaded fork choice.
+//! justified_balances: [32000000000, 32000000000, 32000000000, 32000000000]
+//! # Optional, default to the spec and the beacon node defaults respectively.
+//! proposer_score_boost: 40
+//! re_org_head_threshold: 20
+//! re_org_parent_threshold: 160
+//! re_org_max_epochs_since_finalization: 2
+//! steps:
+//!   # Advance to the start of slot 101, clearing proposer boost.
+//!   - tick: { slot: 101 }
+//!   # A block which arrives late in its slot, so is not boosted.
+//!   - block: { slot: 101, root: "0x01..", parent_root: "0x02..", timely: false }
+//!   # Attestations default to the current slot, so they count from the next tick onwards.
+//!   - attestation: { validator_indices: [0, 1], block_root: "0x02.." }
+//!   - find_head
+//!   # Print the weights of every block from slot 100 onwards.
+//!   - weights: { from_slot: 100 }
+//!   - tick: { slot: 102 }
+//!   # Decide whether the proposer at the current slot would re-org the head.
+//!   - proposer_head
+//! ```
+//!
+//! ## Example
+//!
+//! ```ignore
+//! lcli fork-choice-sim \
+//!     --proto-array-path proto_array.json \
+//!     --scenario-path late_block.yaml
+//! ```
+use beacon_chain::chain_config::{
+    DEFAULT_RE_ORG_HEAD_THRESHOLD, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
+    DEFAULT_RE_ORG_PARENT_THRESHOLD,
+};
+use beacon_chain::PersistedForkChoice;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use eth2::types::GenericResponse;
+use eth2_network_config::Eth2NetworkConfig;
+use fork_choice::{
+    AttestationFromBlock, ForkChoice, ForkChoiceStore, ProposerHeadError, ResetPayloadStatuses,
+};
+use proto_array::core::{ProtoArray, SszContainer};
+use proto_array::{
+    Block as ProtoBlock, DisallowedReOrgOffsets, ExecutionStatus, JustifiedBalances,
+    ProtoArrayForkChoice, ReOrgThreshold,
+};
+use serde::Deserialize;
+use sloggers::{null::NullLoggerBuilder, Build};
+use ssz::Decode;
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::path::PathBuf;
+use std::time::Duration;
+use types::{
+    AbstractExecPayload, AggregateSignature, AttestationData, AttestationShufflingId,
+    BeaconBlockRef, BeaconState, ChainSpec, Checkpoint, Epoch, EthSpec, ExecutionBlockHash,
+    FixedBytesExtended, Hash256, IndexedAttestation, IndexedAttestationElectra, Slot, VariableList,
+};
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Scenario {
+    #[serde(default)]
+    justified_balances: Option<Vec<u64>>,
+    #[serde(default)]
+    proposer_score_boost: Option<u64>,
+    #[serde(default)]
+    re_org_head_threshold: Option<u64>,
+    #[serde(default)]
+    re_org_parent_threshold: Option<u64>,
+    #[serde(default)]
+    re_org_max_epochs_since_finalization: Option<Epoch>,
+    #[serde(default)]
+    re_org_disallowed_offsets: Option<Vec<u64>>,
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum Step {
+    /// Advance the current slot, expiring proposer boost and dequeuing attestations.
+    Tick { slot: Slot },
+    /// Import a block. Timely blocks in the current slot receive proposer boost, if no other
+    /// block has already received it.
+    Block {
+        slot: Slot,
+        root: Hash256,
+        parent_root: Hash256,
+        #[serde(default)]
+        timely: bool,
+        #[serde(default)]
+        justified_checkpoint: Option<Checkpoint>,
+        #[serde(default)]
+        finalized_checkpoint: Option<Checkpoint>,
+    },
+    /// Apply a vote for `block_root` from each of `validator_indices`, made at `slot` (defaults to
+    /// the current slot).
+    Attestation {
+        validator_indices: Vec<u64>,
+        block_root: Hash256,
+        #[serde(default)]
+        slot: Option<Slot>,
+    },
+    /// Run fork choice and print the head.
+    FindHead,
+    /// Print the weight of every block at or after `from_slot`.
+    Weights {
+        #[serde(default)]
+        from_slot: Slot,
+    },
+    /// Decide whether the proposer at the current slot would re-org the head.
+    ProposerHead,
+}
+
+/// An in-memory `ForkChoiceStore`.
+///
+/// There are no states available, so the justified balances are not updated when the justified
+/// checkpoint changes.
+struct SimStore {
+    current_slot: Slot,
+    justified_checkpoint: Checkpoint,
+    finalized_checkpoint: Checkpoint,
+    unrealized_justified_checkpoint: Checkpoint,
+    unrealized_finalized_checkpoint: Checkpoint,
+    justified_balances: JustifiedBalances,
+    proposer_boost_root: Hash256,
+    equivocating_indices: BTreeSet<u64>,
+}
+
+impl SimStore {
+    /// Splits a persisted beacon node fork choice into the fork choice and its store.
+    fn from_persisted(
+        persisted: PersistedForkChoice,
+    ) -> Result<(fork_choice::PersistedForkChoice, Self), String> {
+        let store = persisted.fork_choice_store;
+        let sim_store = Self {
+            current_slot: store.time,
+            justified_checkpoint: store.justified_checkpoint,
+            finalized_checkpoint: store.finalized_checkpoint,
+            unrealized_justified_checkpoint: store.unrealized_justified_checkpoint,
+            unrealized_finalized_checkpoint: store.unrealized_finalized_checkpoint,
+            justified_balances: JustifiedBalances::from_effective_balances(
+                store.justified_balances,
+            )
+            .map_err(|e| format!("Invalid justified balances: {:?}", e))?,
+            proposer_boost_root: store.proposer_boost_root,
+            equivocating_indices: store.equivocating_indices,
+        };
+        Ok((persisted.fork_choice, sim_store))
+    }
+
+    fn from_proto_array(proto_array: &ProtoArray) -> Self {
+        let current_slot = proto_array
+            .nodes
+            .iter()
+            .map(|node| node.slot)
+            .max()
+            .unwrap_or_default();
+        Self {
+            current_slot,
+            justified_checkpoint: proto_array.justified_checkpoint,
+            finalized_checkpoint: proto_array.finalized_checkpoint,
+            unrealized_justified_checkpoint: proto_array.justified_checkpoint,
+            unrealized_finalized_checkpoint: proto_array.finalized_checkpoint,
+            justified_balances: JustifiedBalances::default(),
+            proposer_boost_root: proto_array.previous_proposer_boost.root,
+            equivocating_indices: BTreeSet::new(),
+        }
+    }
+}
+
+impl<E: EthSpec> ForkChoiceStore<E> for SimStore {
+    type Error = String;
+
+    fn get_current_slot(&self) -> Slot {
+        self.current_slot
+    }
+
+    fn set_current_slot(&mut self, slot: Slot) {
+        self.current_slot = slot;
+    }
+
+    fn on_verified_block<Payload: AbstractExecPayload<E>>(
+        &mut self,
+        _block: BeaconBlockRef<E, Payload>,
+        _block_root: Hash256,
+        _state: &BeaconState<E>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn justified_checkpoint(&self) -> &Checkpoint {
+        &self.justified_checkpoint
+    }
+
+    fn justified_balances(&self) -> &JustifiedBalances {
+        &self.justified_balances
+    }
+
+    fn finalized_checkpoint(&self) -> &Checkpoint {
+        &self.finalized_checkpoint
+    }
+
+    fn unrealized_justified_checkpoint(&self) -> &Checkpoint {
+        &self.unrealized_justified_checkpoint
+    }
+
+    fn unrealized_finalized_checkpoint(&self) -> &Checkpoint {
+        &self.unrealized_finalized_checkpoint
+    }
+
+    fn proposer_boost_root(&self) -> Hash256 {
+        self.proposer_boost_root
+    }
+
+    fn set_finalized_checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.finalized_checkpoint = checkpoint;
+    }
+
+    fn set_justified_checkpoint(&mut self, checkpoint: Checkpoint) -> Result<(), Self::Error> {
+        self.justified_checkpoint = checkpoint;
+        Ok(())
+    }
+
+    fn set_unrealized_justified_checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.unrealized_justified_checkpoint = checkpoint;
+    }
+
+    fn set_unrealized_finalized_checkpoint(&mut self, checkpoint: Checkpoint) {
+        self.unrealized_finalized_checkpoint = checkpoint;
+    }
+
+    fn set_proposer_boost_root(&mut self, proposer_boost_root: Hash256) {
+        self.proposer_boost_root = proposer_boost_root;
+    }
+
+    fn equivocating_indices(&self) -> &BTreeSet<u64> {
+        &self.equivocating_indices
+    }
+
+    fn extend_equivocating_indices(&mut self, indices: impl IntoIterator<Item = u64>) {
+        self.equivocating_indices.extend(indices);
+    }
+}
+
+struct Simulator<E: EthSpec> {
+    fork_choice: ForkChoice<SimStore, E>,
+    current_slot: Slot,
+    head: Hash256,
+}
+
+impl<E: EthSpec> Simulator<E> {
+    fn new(
+        persisted: fork_choice::PersistedForkChoice,
+        store: SimStore,
+        spec: &ChainSpec,
+    ) -> Result<Self, String> {
+        let log = NullLoggerBuilder
+            .build()
+            .map_err(|e| format!("Error on NullLoggerBuilder: {:?}", e))?;
+        let current_slot = store.current_slot;
+        let mut fork_choice = ForkChoice::from_persisted(
+            persisted,
+            ResetPayloadStatuses::OnlyWithInvalidPayload,
+            store,
+            spec,
+            &log,
+        )
+        .map_err(|e| format!("Unable to load fork choice: {:?}", e))?;
+        let head = fork_choice
+            .get_head(current_slot, spec)
+            .map_err(|e| format!("Unable to find head: {:?}", e))?;
+        Ok(Self {
+            fork_choice,
+            current_slot,
+            head,
+        })
+    }
+
+    fn describe(&self, root: Hash256) -> String {
+        let slot = self
+            .fork_choice
+            .get_block(&root)
+            .map_or_else(|| "unknown".to_string(), |block| block.slot.to_string());
+        let weight = self.fork_choice.get_block_weight(&root).unwrap_or_default();
+        format!("{:?} (slot {}, weight {})", root, slot, weight)
+    }
+
+    /// Returns the root of the checkpoint block at the start of `slot`'s epoch, for a block at
+    /// `slot` with parent `parent_root`.
+    fn target_root(&self, slot: Slot, root: Hash256, parent_root: Hash256) -> Hash256 {
+        let target_slot = slot
+            .epoch(E::slots_per_epoch())
+            .start_slot(E::slots_per_epoch());
+        if slot == target_slot {
+            return root;
+        }
+        match self.fork_choice.proto_array().get_block(&parent_root) {
+            Some(parent) if parent.slot >= target_slot => parent.target_root,
+            _ => parent_root,
+        }
+    }
+
+    fn apply(&mut self, step: Step, scenario: &Scenario, spec: &ChainSpec) -> Result<(), String> {
+        match step {
+            Step::Tick { slot } => {
+                if slot < self.current_slot {
+                    return Err(format!(
+                        "cannot tick backwards from slot {} to {}",
+                        self.current_slot, slot
+                    ));
+                }
+                self.current_slot = self
+                    .fork_choice
+                    .update_time(slot)
+                    .map_err(|e| format!("Unable to tick: {:?}", e))?;
+                println!("tick: slot {}", slot);
+            }
+            Step::Block {
+                slot,
+                root,
+                parent_root,
+                timely,
+                justified_checkpoint,
+                finalized_checkpoint,
+            } => {
+                // Shufflings are only used for attestation verification, which the simulator
+                // skips.
+                let junk_shuffling_id =
+                    AttestationShufflingId::from_components(Epoch::new(0), Hash256::zero());
+                let block = ProtoBlock {
+                    slot,
+                    root,
+                    parent_root: Some(parent_root),
+                    state_root: Hash256::zero(),
+                    target_root: self.target_root(slot, root, parent_root),
+                    current_epoch_shuffling_id: junk_shuffling_id.clone(),
+                    next_epoch_shuffling_id: junk_shuffling_id,
+                    justified_checkpoint: justified_checkpoint
+                        .unwrap_or_else(|| self.fork_choice.justified_checkpoint()),
+                    finalized_checkpoint: finalized_checkpoint
+                        .unwrap_or_else(|| self.fork_choice.finalized_checkpoint()),
+                    execution_status: ExecutionStatus::Optimistic(ExecutionBlockHash::from_root(
+                        root,
+                    )),
+                    unrealized_justified_checkpoint: None,
+                    unrealized_finalized_checkpoint: None,
+                };
+
+                // Timely blocks arrive at the start of the slot, others after the attestation
+                // deadline.
+                let block_delay = if timely {
+                    Duration::ZERO
+                } else {
+                    Duration::from_secs(spec.seconds_per_slot)
+                };
+                self.fork_choice
+                    .on_unverified_block(self.current_slot, block, block_delay, spec)
+                    .map_err(|e| format!("Unable to import block: {:?}", e))?;
+
+                let boosted = self.fork_choice.proposer_boost_root() == root;
+                println!(
+                    "block: {:?} at slot {} on parent {:?}{}",
+                    root,
+                    slot,
+                    parent_root,
+                    if boosted { " (boosted)" } else { "" }
+                );
+            }
+            Step::Attestation {
+                validator_indices,
+                block_root,
+                slot,
+            } => {
+                let slot = slot.unwrap_or(self.current_slot);
+                let target_epoch = slot.epoch(E::slots_per_epoch());
+                let block = self
+                    .fork_choice
+                    .get_block(&block_root)
+                    .ok_or_else(|| format!("Unknown block {:?}", block_root))?;
+                let target_root = if target_epoch > block.slot.epoch(E::slots_per_epoch()) {
+                    block_root
+                } else {
+                    block.target_root
+                };
+                let num_votes = validator_indices.len();
+                let attestation = IndexedAttestation::<E>::Electra(IndexedAttestationElectra {
+                    attesting_indices: VariableList::new(validator_indices)
+                        .map_err(|e| format!("Too many validator indices: {:?}", e))?,
+                    data: AttestationData {
+                        slot,
+                        index: 0,
+                        beacon_block_root: block_root,
+                        source: self.fork_choice.justified_checkpoint(),
+                        target: Checkpoint {
+                            epoch: target_epoch,
+                            root: target_root,
+                        },
+                    },
+                    signature: AggregateSignature::empty(),
+                });
+                self.fork_choice
+                    .on_attestation(
+                        self.current_slot,
+                        attestation.to_ref(),
+                        AttestationFromBlock::False,
+                    )
+                    .map_err(|e| format!("Unable to apply attestation: {:?}", e))?;
+                println!(
+                    "attestation: {} votes for {:?} at slot {}",
+                    num_votes, block_root, slot
+                );
+            }
+            Step::FindHead => {
+                let previous_head = self.head;
+                self.head = self
+                    .fork_choice
+                    .get_head(self.current_slot, spec)
+                    .map_err(|e| format!("Unable to find head: {:?}", e))?;
+                println!("head: {}", self.describe(self.head));
+                if previous_head != self.head
+                    && !self
+                        .fork_choice
+                        .proto_array()
+                        .is_descendant(previous_head, self.head)
+                {
+                    println!("  re-org from {}", self.describe(previous_head));
+                }
+            }
+            Step::Weights { from_slot } => {
+                println!("weights from slot {}:", from_slot);
+                for node in self
+                    .fork_choice
+                    .proto_array()
+                    .core_proto_array()
+                    .nodes
+                    .iter()
+                    .filter(|node| node.slot >= from_slot)
+                {
+                    println!(
+                        "  {:?} (slot {}, weight {}){}",
+                        node.root,
+                        node.slot,
+                        node.weight,
+                        if node.root == self.head { " head" } else { "" }
+                    );
+                }
+            }
+            Step::ProposerHead => {
+                let disallowed_offsets = match &scenario.re_org_disallowed_offsets {
+                    Some(offsets) => DisallowedReOrgOffsets::new::<E>(offsets.clone())
+                        .map_err(|e| format!("Invalid disallowed offsets: {:?}", e))?,
+                    None => DisallowedReOrgOffsets::default(),
+                };
+                let result = self.fork_choice.get_proposer_head(
+                    self.current_slot,
+                    self.head,
+                    scenario
+                        .re_org_head_threshold
+                        .map_or(DEFAULT_RE_ORG_HEAD_THRESHOLD, ReOrgThreshold),
+                    scenario
+                        .re_org_parent_threshold
+                        .map_or(DEFAULT_RE_ORG_PARENT_THRESHOLD, ReOrgThreshold),
+                    &disallowed_offsets,
+                    scenario
+                        .re_org_max_epochs_since_finalization
+                        .unwrap_or(DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION),
+                );
+                match result {
+                    Ok(info) => println!(
+                        "proposer head at slot {}: re-org {:?} (weight {} < {}) onto parent {:?} \
+                         (weight {} > {})",
+                        self.current_slot,
+                        info.head_node.root,
+                        info.head_node.weight,
+                        info.re_org_head_weight_threshold,
+                        info.parent_node.root,
+                        info.parent_node.weight,
+                        info.re_org_parent_weight_threshold,
+                    ),
+                    Err(ProposerHeadError::DoNotReOrg(reason)) => println!(
+                        "proposer head at slot {}: build on {:?}, not re-orging: {}",
+                        self.current_slot, self.head, reason
+                    ),
+                    Err(ProposerHeadError::Error(e)) => {
+                        return Err(format!("Unable to compute proposer head: {:?}", e))
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn run_scenario(&mut self, mut scenario: Scenario, spec: &ChainSpec) -> Result<(), String> {
+        let steps = std::mem::take(&mut scenario.steps);
+        for (i, step) in steps.into_iter().enumerate() {
+            self.apply(step, &scenario, spec)
+                .map_err(|e| format!("Step {} failed: {}", i, e))?;
+        }
+        Ok(())
+    }
+}
+
+pub fn run<E: EthSpec>(
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let mut spec = network_config.chain_spec::<E>()?;
+
+    let fork_choice_path: Option<PathBuf> = parse_optional(matches, "fork-choice-path")?;
+    let proto_array_path: Option<PathBuf> = parse_optional(matches, "proto-array-path")?;
+    let scenario_path: PathBuf = parse_required(matches, "scenario-path")?;
+
+    let (persisted, mut store) = match (fork_choice_path, proto_array_path) {
+        (Some(path), None) => {
+            let bytes = std::fs::read(&path)
+                .map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?;
+            let persisted = PersistedForkChoice::from_ssz_bytes(&bytes)
+                .map_err(|e| format!("Unable to decode fork choice: {:?}", e))?;
+            SimStore::from_persisted(persisted)?
+        }
+        (None, Some(path)) => {
+            let bytes = std::fs::read(&path)
+                .map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?;
+            // Accept both the raw API response and its `data` field.
+            let proto_array = serde_json::from_slice::<GenericResponse<ProtoArray>>(&bytes)
+                .map(|response| response.data)
+                .or_else(|_| serde_json::from_slice::<ProtoArray>(&bytes))
+                .map_err(|e| format!("Unable to parse proto array: {:?}", e))?;
+            let store = SimStore::from_proto_array(&proto_array);
+            // Votes and balances are not part of a `ProtoArray`, so they start empty and the
+            // node weights are left as they were when the proto array was captured.
+            let container = SszContainer {
+                votes: vec![],
+                balances: vec![],
+                prune_threshold: proto_array.prune_threshold,
+                justified_checkpoint: proto_array.justified_checkpoint,
+                finalized_checkpoint: proto_array.finalized_checkpoint,
+                nodes: proto_array.nodes,
+                indices: proto_array.indices.into_iter().collect(),
+                previous_proposer_boost: proto_array.previous_proposer_boost,
+            };
+            let proto_array = ProtoArrayForkChoice::try_from(container)
+                .map_err(|e| format!("Invalid proto array: {:?}", e))?;
+            let persisted = fork_choice::PersistedForkChoice::from_proto_array(&proto_array);
+            (persisted, store)
+        }
+        _ => return Err("Provide exactly one of --fork-choice-path or --proto-array-path".into()),
+    };
+
+    let file = File::open(&scenario_path)
+        .map_err(|e| format!("Unable to open {}: {:?}", scenario_path.display(), e))?;
+    let mut scenario: Scenario =
+        serde_yaml::from_reader(file).map_err(|e| format!("Invalid scenario: {:?}", e))?;
+
+    if let Some(balances) = scenario.justified_balances.take() {
+        store.justified_balances = JustifiedBalances::from_effective_balances(balances)
+            .map_err(|e| format!("Invalid justified balances: {:?}", e))?;
+    }
+    if scenario.proposer_score_boost.is_some() {
+        spec.proposer_score_boost = scenario.proposer_score_boost;
+    }
+
+    let mut simulator = Simulator::<E>::new(persisted, store, &spec)?;
+    println!(
+        "loaded fork choice with {} blocks at slot {}, justified epoch {}, finalized epoch {}",
+        simulator.fork_choice.proto_array().len(),
+        simulator.current_slot,
+        simulator.fork_choice.justified_checkpoint().epoch,
+        simulator.fork_choice.finalized_checkpoint().epoch,
+    );
+    println!("initial head: {}", simulator.describe(simulator.head));
+
+    simulator.run_scenario(scenario, &spec)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::MainnetEthSpec;
+
+    type E = MainnetEthSpec;
+
+    fn root(i: u64) -> Hash256 {
+        Hash256::from_low_u64_be(i)
+    }
+
+    fn genesis_simulator(spec: &ChainSpec, balances: Vec<u64>) -> Simulator<E> {
+        let genesis_checkpoint = Checkpoint {
+            epoch: Epoch::new(0),
+            root: root(1),
+        };
+        let shuffling_id = AttestationShufflingId::from_components(Epoch::new(0), root(1));
+        let proto_array = ProtoArrayForkChoice::new::<E>(
+            Slot::new(0),
+            Slot::new(0),
+            Hash256::zero(),
+            genesis_checkpoint,
+            genesis_checkpoint,
+            shuffling_id.clone(),
+            shuffling_id,
+            ExecutionStatus::irrelevant(),
+        )
+        .unwrap();
+        let mut store = SimStore::from_proto_array(proto_array.core_proto_array());
+        store.justified_balances = JustifiedBalances::from_effective_balances(balances).unwrap();
+        let persisted = fork_choice::PersistedForkChoice::from_proto_array(&proto_array);
+        Simulator::new(persisted, store, spec).unwrap()
+    }
+
+    #[test]
+    fn queued_attestation_overrides_proposer_boost_next_slot() {
+        let spec = E::default_spec();
+        let mut simulator = genesis_simulator(&spec, vec![32_000_000_000; 4]);
+        assert_eq!(simulator.head, root(1));
+
+        let scenario: Scenario = serde_yaml::from_str(&format!(
+            r#"
+steps:
+  - tick: {{ slot: 1 }}
+  - block: {{ slot: 1, root: "{a:?}", parent_root: "{genesis:?}", timely: true }}
+  - block: {{ slot: 1, root: "{b:?}", parent_root: "{genesis:?}" }}
+  - attestation: {{ validator_indices: [0], block_root: "{b:?}" }}
+  - find_head
+"#,
+            genesis = root(1),
+            a = root(2),
+            b = root(3),
+        ))
+        .unwrap();
+        simulator.run_scenario(scenario, &spec).unwrap();
+
+        // The vote for B is queued until slot 2, so the boosted block A is the head.
+        assert_eq!(simulator.fork_choice.proposer_boost_root(), root(2));
+        assert_eq!(simulator.head, root(2));
+
+        let scenario: Scenario = serde_yaml::from_str(
+            r#"
+steps:
+  - tick: { slot: 2 }
+  - find_head
+"#,
+        )
+        .unwrap();
+        simulator.run_scenario(scenario, &spec).unwrap();
+
+        // The boost has expired and the vote for B now counts.
+        assert!(simulator.fork_choice.proposer_boost_root().is_zero());
+        assert_eq!(simulator.head, root(3));
+        assert_eq!(
+            simulator.fork_choice.get_block_weight(&root(3)),
+            Some(32_000_000_000)
+        );
+    }
+}
@@ -0,0 +1,407 @@
+//! # Inspect
+//!
+//! Use this tool to explore a `BeaconState` interactively.
+//!
+//! The state can be loaded from an SSZ file, from a beacon node API, or from the database of a
+//! stopped Lighthouse beacon node. Once loaded, commands are read from stdin, one per line. Run
+//! `help` for the list of commands.
+//!
+//! Fields are queried with a path through the JSON representation of the state, for example
+//! `validators[123].effective_balance` or `fork.current_version`.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! lcli inspect --state-path /tmp/state.ssz
+//!
+//! lcli inspect --beacon-url http://localhost:5052 --state-id finalized
+//!
+//! lcli --network holesky inspect --datadir ~/.lighthouse/holesky --state-id 3014656
+//! ```
+//!
+//! A session might look like:
+//!
+//! ```ignore
+//! > slot
+//! "3014656"
+//! > validators[123].effective_balance
+//! "32000000000"
+//! > mark
+//! > advance 32
+//! > diff
+//! ```
+use crate::transition_blocks::load_from_ssz_with;
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use compare_fields::{CompareFields, Comparison};
+use environment::Environment;
+use eth2::{types::StateId, BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use eth2_network_config::Eth2NetworkConfig;
+use serde_json::Value;
+use state_processing::per_slot_processing;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use store::{HotColdDB, LevelDB, StoreConfig};
+use types::{BeaconState, ChainSpec, Epoch, EthSpec, Hash256, RelativeEpoch, Slot};
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The maximum number of differing children of a list to print in a diff.
+const MAX_DIFF_CHILDREN: usize = 16;
+
+const HELP: &str = "\
+Commands:
+  <path>                     Print a field, e.g. `validators[123].effective_balance`
+  len <path>                 Print the length of a list field
+  root                       Print the state root
+  proposer [slot]            Print the proposer at a slot in the current epoch
+  committee <slot> <index>   Print a beacon committee
+  committees <slot>          Print all beacon committees at a slot
+  shuffling <epoch>          Print the shuffled active validators for an epoch
+  advance [n]                Advance the state by n slots (default 1)
+  advance-to <slot>          Advance the state to a slot
+  mark                       Remember the current state, for `diff`
+  diff [path]                Compare the current state with the marked state, or an SSZ file
+  help                       Print this message
+  exit                       Quit";
+
+pub fn run<E: EthSpec>(
+    env: Environment<E>,
+    network_config: Eth2NetworkConfig,
+    matches: &ArgMatches,
+) -> Result<(), String> {
+    let spec = Arc::new(network_config.chain_spec::<E>()?);
+    let state = load_state(&env, &spec, matches)?;
+
+    let mut inspector = Inspector {
+        state,
+        state_json: None,
+        marked: None,
+        spec,
+    };
+
+    println!(
+        "Loaded {} state at slot {}. Type `help` for commands.",
+        inspector.state.fork_name_unchecked(),
+        inspector.state.slot()
+    );
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    loop {
+        print!("> ");
+        io::stdout()
+            .flush()
+            .map_err(|e| format!("Unable to write to stdout: {:?}", e))?;
+
+        let Some(line) = lines.next() else {
+            break;
+        };
+        let line = line.map_err(|e| format!("Unable to read from stdin: {:?}", e))?;
+        let args = line.split_whitespace().collect::<Vec<_>>();
+
+        match args.as_slice() {
+            [] => continue,
+            ["exit"] | ["quit"] => break,
+            args => {
+                if let Err(e) = inspector.execute(args) {
+                    println!("Error: {}", e);
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn load_state<E: EthSpec>(
+    env: &Environment<E>,
+    spec: &Arc<ChainSpec>,
+    matches: &ArgMatches,
+) -> Result<BeaconState<E>, String> {
+    let state_path: Option<PathBuf> = parse_optional(matches, "state-path")?;
+    let beacon_url: Option<SensitiveUrl> = parse_optional(matches, "beacon-url")?;
+    let datadir: Option<PathBuf> = parse_optional(matches, "datadir")?;
+
+    match (state_path, beacon_url, datadir) {
+        (Some(state_path), None, None) => {
+            load_from_ssz_with(&state_path, spec, BeaconState::from_ssz_bytes)
+        }
+        (None, Some(beacon_url), None) => {
+            let state_id: StateId = parse_required(matches, "state-id")?;
+            let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+            env.core_context()
+                .executor
+                .handle()
+                .ok_or("shutdown in progress")?
+                .block_on(client.get_debug_beacon_states::<E>(state_id))
+                .map_err(|e| format!("Failed to download state: {:?}", e))?
+                .ok_or_else(|| format!("Unable to locate state at {:?}", state_id))
+                .map(|response| response.data)
+        }
+        (None, None, Some(datadir)) => {
+            let state_id: String = parse_required(matches, "state-id")?;
+            load_state_from_db(env, spec, &datadir, &state_id)
+        }
+        _ => Err("must supply exactly one of --state-path, --beacon-url or --datadir".into()),
+    }
+}
+
+/// Load a state from the database of a beacon node, by state root or by finalized slot.
+///
+/// The beacon node must not be running, since the database can only be opened by one process.
+fn load_state_from_db<E: EthSpec>(
+    env: &Environment<E>,
+    spec: &Arc<ChainSpec>,
+    datadir: &Path,
+    state_id: &str,
+) -> Result<BeaconState<E>, String> {
+    // The layout of the beacon node's `--datadir`.
+    let beacon_dir = datadir.join("beacon");
+    let db = HotColdDB::<E, LevelDB<E>, LevelDB<E>>::open(
+        &beacon_dir.join("chain_db"),
+        &beacon_dir.join("freezer_db"),
+        &beacon_dir.join("blobs_db"),
+        |_, from, to| {
+            if from == to {
+                Ok(())
+            } else {
+                Err(store::Error::SchemaMigrationError(format!(
+                    "database schema is v{}, expected v{}",
+                    from.as_u64(),
+                    to.as_u64()
+                )))
+            }
+        },
+        StoreConfig::default(),
+        spec.clone(),
+        env.core_context().log().clone(),
+    )
+    .map_err(|e| format!("Unable to open database: {:?}", e))?;
+
+    if state_id.starts_with("0x") {
+        let state_root: Hash256 = state_id
+            .parse()
+            .map_err(|e| format!("Invalid state root {}: {:?}", state_id, e))?;
+        db.get_state(&state_root, None)
+            .map_err(|e| format!("Unable to load state: {:?}", e))?
+            .ok_or_else(|| format!("No state with root {:?}", state_root))
+    } else {
+        let slot: Slot = state_id
+            .parse()
+            .map_err(|e| format!("Invalid state ID {}: {:?}", state_id, e))?;
+        let split_slot = db.get_split_slot();
+        if slot >= split_slot {
+            return Err(format!(
+                "Only finalized states can be loaded by slot, use a state root for slots \
+                 after {}",
+                split_slot
+            ));
+        }
+        db.load_cold_state_by_slot(slot)
+            .map_err(|e| format!("Unable to load state: {:?}", e))
+    }
+}
+
+struct Inspector<E: EthSpec> {
+    state: BeaconState<E>,
+    /// The JSON representation of `state`, computed when first queried.
+    state_json: Option<Value>,
+    /// The state saved by `mark`.
+    marked: Option<BeaconState<E>>,
+    spec: Arc<ChainSpec>,
+}
+
+impl<E: EthSpec> Inspector<E> {
+    fn execute(&mut self, args: &[&str]) -> Result<(), String> {
+        match args {
+            ["help"] => println!("{}", HELP),
+            ["len", path] => match self.query(path)? {
+                Value::Array(values) => println!("{}", values.len()),
+                _ => return Err(format!("{} is not a list", path)),
+            },
+            ["root"] => {
+                let root = self
+                    .state
+                    .update_tree_hash_cache()
+                    .map_err(|e| format!("Unable to compute state root: {:?}", e))?;
+                println!("{:?}", root);
+            }
+            ["proposer"] => self.print_proposer(self.state.slot())?,
+            ["proposer", slot] => self.print_proposer(parse_arg(slot)?)?,
+            ["committee", slot, index] => {
+                let slot: Slot = parse_arg(slot)?;
+                self.build_committee_cache(slot.epoch(E::slots_per_epoch()))?;
+                let committee = self
+                    .state
+                    .get_beacon_committee(slot, parse_arg(index)?)
+                    .map_err(|e| format!("Unable to get committee: {:?}", e))?;
+                println!("{:?}", committee.committee);
+            }
+            ["committees", slot] => {
+                let slot: Slot = parse_arg(slot)?;
+                self.build_committee_cache(slot.epoch(E::slots_per_epoch()))?;
+                let committees = self
+                    .state
+                    .get_beacon_committees_at_slot(slot)
+                    .map_err(|e| format!("Unable to get committees: {:?}", e))?;
+                for committee in committees {
+                    println!("{}: {:?}", committee.index, committee.committee);
+                }
+            }
+            ["shuffling", epoch] => {
+                let relative_epoch = self.build_committee_cache(parse_arg(epoch)?)?;
+                let shuffling = self
+                    .state
+                    .get_shuffling(relative_epoch)
+                    .map_err(|e| format!("Unable to get shuffling: {:?}", e))?;
+                println!("{:?}", shuffling);
+            }
+            ["advance"] => self.advance_to(self.state.slot() + 1)?,
+            ["advance", slots] => {
+                let slots: u64 = parse_arg(slots)?;
+                self.advance_to(self.state.slot() + slots)?;
+            }
+            ["advance-to", slot] => self.advance_to(parse_arg(slot)?)?,
+            ["mark"] => self.marked = Some(self.state.clone()),
+            ["diff"] => {
+                let marked = self
+                    .marked
+                    .as_ref()
+                    .ok_or("No state has been marked, use `mark` first")?;
+                print_diff(&marked.compare_fields(&self.state));
+            }
+            ["diff", path] => {
+                let other = load_from_ssz_with(
+                    &PathBuf::from(path),
+                    &self.spec,
+                    BeaconState::from_ssz_bytes,
+                )?;
+                print_diff(&other.compare_fields(&self.state));
+            }
+            [path] => {
+                let value = self.query(path)?;
+                let json = serde_json::to_string_pretty(value)
+                    .map_err(|e| format!("Unable to encode JSON: {:?}", e))?;
+                println!("{}", json);
+            }
+            _ => return Err("Unknown command, type `help` for commands".into()),
+        }
+        Ok(())
+    }
+
+    /// Look up `path` in the JSON representation of the state.
+    fn query(&mut self, path: &str) -> Result<&Value, String> {
+        if self.state_json.is_none() {
+            let json = serde_json::to_value(&self.state)
+                .map_err(|e| format!("Unable to encode state as JSON: {:?}", e))?;
+            self.state_json = Some(json);
+        }
+        let mut value = self.state_json.as_ref().ok_or("state not encoded")?;
+
+        for segment in path.split('.') {
+            let (field, indices) = segment.split_once('[').unwrap_or((segment, ""));
+            if !field.is_empty() {
+                value = value
+                    .get(field)
+                    .ok_or_else(|| format!("No field named {} in {}", field, path))?;
+            }
+            if indices.is_empty() {
+                continue;
+            }
+            for index in format!("[{}", indices).split('[').skip(1) {
+                let index: usize = index
+                    .strip_suffix(']')
+                    .ok_or_else(|| format!("Unclosed index in {}", path))
+                    .and_then(parse_arg)?;
+                value = value
+                    .get(index)
+                    .ok_or_else(|| format!("Index {} out of bounds in {}", index, path))?;
+            }
+        }
+
+        Ok(value)
+    }
+
+    fn print_proposer(&self, slot: Slot) -> Result<(), String> {
+        let proposer = self
+            .state
+            .get_beacon_proposer_index(slot, &self.spec)
+            .map_err(|e| format!("Unable to compute proposer: {:?}", e))?;
+        println!("{}", proposer);
+        Ok(())
+    }
+
+    fn build_committee_cache(&mut self, epoch: Epoch) -> Result<RelativeEpoch, String> {
+        let relative_epoch = RelativeEpoch::from_epoch(self.state.current_epoch(), epoch)
+            .map_err(|e| format!("Epoch {} is not adjacent to the state: {:?}", epoch, e))?;
+        self.state
+            .build_committee_cache(relative_epoch, &self.spec)
+            .map_err(|e| format!("Unable to build committee cache: {:?}", e))?;
+        Ok(relative_epoch)
+    }
+
+    /// Advance the state through skip slots until it reaches `slot`.
+    fn advance_to(&mut self, slot: Slot) -> Result<(), String> {
+        if slot < self.state.slot() {
+            return Err(format!(
+                "Cannot rewind the state from slot {} to {}",
+                self.state.slot(),
+                slot
+            ));
+        }
+
+        while self.state.slot() < slot {
+            per_slot_processing(&mut self.state, None, &self.spec)
+                .map_err(|e| format!("Failed to process slot: {:?}", e))?;
+        }
+        self.state_json = None;
+
+        println!("Advanced to slot {}", self.state.slot());
+        Ok(())
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(arg: &str) -> Result<T, String>
+where
+    T::Err: std::fmt::Debug,
+{
+    arg.parse()
+        .map_err(|e| format!("Invalid argument {}: {:?}", arg, e))
+}
+
+/// Print the fields which differ between two states.
+fn print_diff(comparisons: &[Comparison]) {
+    let mut equal = true;
+    for comparison in comparisons.iter().filter(|c| c.not_equal()) {
+        equal = false;
+        match comparison {
+            Comparison::Child(field) => {
+                println!("{}: {} -> {}", field.field_name, field.a, field.b);
+            }
+            Comparison::Parent {
+                field_name,
+                children,
+                ..
+            } => {
+                let differing = children
+                    .iter()
+                    .filter(|c| c.not_equal())
+                    .collect::<Vec<_>>();
+                println!("{}: {} elements differ", field_name, differing.len());
+                for child in differing.iter().take(MAX_DIFF_CHILDREN) {
+                    println!("  [{}]: {} -> {}", child.field_name, child.a, child.b);
+                }
+                if differing.len() > MAX_DIFF_CHILDREN {
+                    println!("  ...");
+                }
+            }
+        }
+    }
+
+    if equal {
+        println!("States are equal");
+    }
+}
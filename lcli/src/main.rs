@@ -1,10 +1,16 @@
+mod block_reward_gaps;
 mod block_root;
 mod check_deposit_data;
+mod fork_choice_sim;
 mod generate_bootnode_enr;
 mod http_sync;
 mod indexed_attestations;
+mod inspect;
 mod mnemonic_validators;
 mod mock_el;
+mod mock_relay;
+mod new_devnet;
+mod op_pool;
 mod parse_ssz;
 mod skip_slots;
 mod state_root;
@@ -552,6 +558,451 @@ fn main() {
                                 until Prague is triggered on mainnet.")
                         .display_order(0)
                 )
+                .arg(
+                    Arg::new("replay")
+                        .long("replay")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to a file of engine API requests and responses recorded by \
+                                the beacon node's --execution-record-file flag. If provided, \
+                                every request is served from the recording, matched by method \
+                                and params, and requests which were not recorded fail.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("mock-relay")
+                .about("Creates a mock builder relay which can be configured to misbehave. This is \
+                NOT SAFE and should only be used for testing builder fallback behaviour on \
+                testnets. Do not use in production. Do not use on mainnet.")
+                .arg(
+                    Arg::new("execution-endpoint")
+                        .long("execution-endpoint")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Engine API endpoint of the execution node used to build payloads.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("execution-jwt")
+                        .long("execution-jwt")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to the JWT secret for the execution endpoint.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .help("URL of the beacon node whose head the relay builds upon.")
+                        .default_value("http://localhost:5052")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("listen-address")
+                        .long("listen-address")
+                        .value_name("IP_ADDRESS")
+                        .action(ArgAction::Set)
+                        .help("The server will listen on this address.")
+                        .default_value("127.0.0.1")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("listen-port")
+                        .long("listen-port")
+                        .value_name("PORT")
+                        .action(ArgAction::Set)
+                        .help("The server will listen on this port.")
+                        .default_value("18550")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("behaviours")
+                        .long("behaviours")
+                        .value_name("BEHAVIOURS")
+                        .action(ArgAction::Set)
+                        .help("Comma-separated list of adversarial behaviours. Supported values are \
+                            withhold-payload, invalid-signature, wrong-parent-hash, timeout, \
+                            exceed-gas-limit and equivocate. The relay is honest by default.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("fork-choice-sim")
+                .about("Applies a scripted sequence of blocks, attestations and slot ticks to a \
+                        fork choice loaded from a beacon node, printing the resulting heads, \
+                        weights and proposer re-org decisions.")
+                .arg(
+                    Arg::new("fork-choice-path")
+                        .long("fork-choice-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .conflicts_with("proto-array-path")
+                        .required_unless_present("proto-array-path")
+                        .help("Path to an SSZ-encoded PersistedForkChoice, as exported by \
+                                `lighthouse db inspect --column frk --output values`.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("proto-array-path")
+                        .long("proto-array-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to a JSON response from the /lighthouse/proto_array \
+                                endpoint.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("scenario-path")
+                        .long("scenario-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to a YAML file describing the steps to simulate.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("export-op-pool")
+                .about("Downloads the operation pool of a beacon node as SSZ.")
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("URL to a Lighthouse beacon-API provider.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to output the SSZ-encoded operation pool.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("import-op-pool")
+                .about("Uploads an operation pool exported by export-op-pool to a beacon node, \
+                        which verifies each operation before adding it to its own pool.")
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("URL to a Lighthouse beacon-API provider.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("input-path")
+                        .long("input-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("Path to an SSZ-encoded operation pool.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("inspect")
+                .about("Loads a BeaconState and starts an interactive shell for querying it, \
+                        computing committees and proposers, advancing it through slots and \
+                        comparing it with other states.")
+                .arg(
+                    Arg::new("state-path")
+                        .long("state-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .conflicts_with_all(["beacon-url", "datadir"])
+                        .help("Path to an SSZ-encoded BeaconState.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .conflicts_with("datadir")
+                        .help("URL to a beacon-API provider to download the state from.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("datadir")
+                        .long("datadir")
+                        .value_name("DIR")
+                        .action(ArgAction::Set)
+                        .help("The --datadir of a stopped Lighthouse beacon node to load the \
+                               state from.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("state-id")
+                        .long("state-id")
+                        .value_name("STATE_ID")
+                        .action(ArgAction::Set)
+                        .help("The state to load with --beacon-url, as per beacon-API standards, \
+                               or with --datadir, as a state root or a finalized slot.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("new-devnet")
+                .about("Produces a network configuration directory for a local devnet at the \
+                        --testnet-dir, including a genesis state built from interop or \
+                        mnemonic-derived validator keys.")
+                .arg(
+                    Arg::new("force")
+                        .long("force")
+                        .short('f')
+                        .action(ArgAction::SetTrue)
+                        .help_heading(FLAG_HEADER)
+                        .help("Overwrites any previous testnet configurations.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("validator-count")
+                        .long("validator-count")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The number of validators in the genesis state.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("mnemonic-phrase")
+                        .long("mnemonic-phrase")
+                        .value_name("PHRASE")
+                        .action(ArgAction::Set)
+                        .help("Derive the validator keys from this mnemonic, using the EIP-2334 \
+                               paths. The interop keypairs are used if this is not provided.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("genesis-time")
+                        .long("genesis-time")
+                        .value_name("UNIX_SECONDS")
+                        .action(ArgAction::Set)
+                        .help("The genesis time of the network. Defaults to the current time plus \
+                               the genesis delay.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("config-name")
+                        .long("config-name")
+                        .value_name("NAME")
+                        .action(ArgAction::Set)
+                        .help("The CONFIG_NAME of the network.")
+                        .default_value("devnet")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("genesis-fork-version")
+                        .long("genesis-fork-version")
+                        .value_name("HEX")
+                        .action(ArgAction::Set)
+                        .help("The genesis fork version, as 0x-prefixed hex.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("altair-fork-epoch")
+                        .long("altair-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to activate the Altair fork. The fork is never \
+                               activated if this is not provided.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("bellatrix-fork-epoch")
+                        .long("bellatrix-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to activate the Bellatrix fork. The fork is \
+                               never activated if this is not provided.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("capella-fork-epoch")
+                        .long("capella-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to activate the Capella fork. The fork is never \
+                               activated if this is not provided.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("deneb-fork-epoch")
+                        .long("deneb-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to activate the Deneb fork. The fork is never \
+                               activated if this is not provided.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("electra-fork-epoch")
+                        .long("electra-fork-epoch")
+                        .value_name("EPOCH")
+                        .action(ArgAction::Set)
+                        .help("The epoch at which to activate the Electra fork. The fork is never \
+                               activated if this is not provided.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("terminal-total-difficulty")
+                        .long("terminal-total-difficulty")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .help("The terminal total difficulty of the execution chain.")
+                        .default_value("0")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("seconds-per-slot")
+                        .long("seconds-per-slot")
+                        .value_name("SECONDS")
+                        .action(ArgAction::Set)
+                        .help("The number of seconds per slot.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("seconds-per-eth1-block")
+                        .long("seconds-per-eth1-block")
+                        .value_name("SECONDS")
+                        .action(ArgAction::Set)
+                        .help("The number of seconds per block on the execution chain.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("eth1-follow-distance")
+                        .long("eth1-follow-distance")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .help("The number of execution blocks to follow behind the head when \
+                               voting on eth1 data.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("genesis-delay")
+                        .long("genesis-delay")
+                        .value_name("SECONDS")
+                        .action(ArgAction::Set)
+                        .help("The delay between the execution block which triggers genesis and \
+                               the genesis time.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("eth1-block-hash")
+                        .long("eth1-block-hash")
+                        .value_name("HASH")
+                        .action(ArgAction::Set)
+                        .help("The hash of the genesis block of the execution chain, which is \
+                               used as the eth1 block hash and the block hash of the genesis \
+                               execution payload header.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("execution-payload-header")
+                        .long("execution-payload-header")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to an SSZ-encoded execution payload header to use in the \
+                               genesis state, instead of an empty header.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("deposit-contract-address")
+                        .long("deposit-contract-address")
+                        .value_name("ETH1_ADDRESS")
+                        .action(ArgAction::Set)
+                        .help("The address of the deposit contract.")
+                        .default_value("0x4242424242424242424242424242424242424242")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("deposit-contract-deploy-block")
+                        .long("deposit-contract-deploy-block")
+                        .value_name("BLOCK_NUMBER")
+                        .action(ArgAction::Set)
+                        .help("The block at which the deposit contract was deployed.")
+                        .default_value("0")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("deposit-chain-id")
+                        .long("deposit-chain-id")
+                        .value_name("INTEGER")
+                        .action(ArgAction::Set)
+                        .help("The chain and network ID of the execution chain hosting the \
+                               deposit contract.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("boot-enr")
+                        .long("boot-enr")
+                        .value_name("ENRS")
+                        .action(ArgAction::Set)
+                        .help("Comma-separated ENRs of the boot nodes, such as those created by \
+                               generate-bootnode-enr.")
+                        .display_order(0)
+                )
+        )
+        .subcommand(
+            Command::new("block-reward-gaps")
+                .about("Compares the attestation rewards of historical blocks with the rewards of \
+                        optimally packed blocks, reporting the rewards missed by each proposer.")
+                .arg(
+                    Arg::new("beacon-url")
+                        .long("beacon-url")
+                        .value_name("URL")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("URL to a Lighthouse beacon-API provider.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("start-slot")
+                        .long("start-slot")
+                        .value_name("SLOT")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The first slot to analyse (inclusive).")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("end-slot")
+                        .long("end-slot")
+                        .value_name("SLOT")
+                        .action(ArgAction::Set)
+                        .required(true)
+                        .help("The last slot to analyse (inclusive).")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("packing-time-budget")
+                        .long("packing-time-budget")
+                        .value_name("MILLISECONDS")
+                        .action(ArgAction::Set)
+                        .help("The time the beacon node may spend searching for the optimal \
+                               packing of each block.")
+                        .display_order(0)
+                )
+                .arg(
+                    Arg::new("output-path")
+                        .long("output-path")
+                        .value_name("PATH")
+                        .action(ArgAction::Set)
+                        .help("Path to output the per-block results as JSON.")
+                        .display_order(0)
+                )
         )
         .subcommand(
             Command::new("http-sync")
@@ -727,6 +1178,31 @@ fn run<E: EthSpec>(env_builder: EnvironmentBuilder<E>, matches: &ArgMatches) ->
         }
         Some(("mock-el", matches)) => mock_el::run::<E>(env, matches)
             .map_err(|e| format!("Failed to run mock-el command: {}", e)),
+        Some(("mock-relay", matches)) => mock_relay::run::<E>(env, matches)
+            .map_err(|e| format!("Failed to run mock-relay command: {}", e)),
+        Some(("fork-choice-sim", matches)) => {
+            let network_config = get_network_config()?;
+            fork_choice_sim::run::<E>(network_config, matches)
+                .map_err(|e| format!("Failed to run fork-choice-sim command: {}", e))
+        }
+        Some(("inspect", matches)) => {
+            let network_config = get_network_config()?;
+            inspect::run::<E>(env, network_config, matches)
+                .map_err(|e| format!("Failed to run inspect command: {}", e))
+        }
+        Some(("new-devnet", matches)) => {
+            let testnet_dir = testnet_dir
+                .clone()
+                .ok_or("new-devnet requires --testnet-dir")?;
+            new_devnet::run::<E>(testnet_dir, matches)
+                .map_err(|e| format!("Failed to run new-devnet command: {}", e))
+        }
+        Some(("block-reward-gaps", matches)) => block_reward_gaps::run::<E>(env, matches)
+            .map_err(|e| format!("Failed to run block-reward-gaps command: {}", e)),
+        Some(("export-op-pool", matches)) => op_pool::run_export::<E>(env, matches)
+            .map_err(|e| format!("Failed to run export-op-pool command: {}", e)),
+        Some(("import-op-pool", matches)) => op_pool::run_import::<E>(env, matches)
+            .map_err(|e| format!("Failed to run import-op-pool command: {}", e)),
         Some(("http-sync", matches)) => {
             let network_config = get_network_config()?;
             http_sync::run::<E>(env, network_config, matches)
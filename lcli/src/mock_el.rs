@@ -3,6 +3,7 @@ use clap_utils::{parse_optional, parse_required};
 use environment::Environment;
 use execution_layer::{
     auth::JwtKey,
+    recording::read_records,
     test_utils::{
         Config, MockExecutionConfig, MockServer, DEFAULT_JWT_SECRET, DEFAULT_TERMINAL_BLOCK,
     },
@@ -19,6 +20,7 @@ pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<
     let shanghai_time = parse_required(matches, "shanghai-time")?;
     let cancun_time = parse_optional(matches, "cancun-time")?;
     let prague_time = parse_optional(matches, "prague-time")?;
+    let replay_path: Option<PathBuf> = parse_optional(matches, "replay")?;
 
     let handle = env.core_context().executor.handle().unwrap();
     let spec = &E::default_spec();
@@ -50,6 +52,16 @@ pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<
         server.all_payloads_valid();
     }
 
+    if let Some(replay_path) = replay_path {
+        let records = read_records(&replay_path)?;
+        eprintln!(
+            "Replaying {} recorded engine API responses from {}",
+            records.len(),
+            replay_path.display()
+        );
+        server.replay(records);
+    }
+
     eprintln!(
         "This tool is for TESTING PURPOSES ONLY. Do not use in production or on mainnet. \
         It cannot perform validator duties. It may cause nodes to follow an invalid chain."
@@ -0,0 +1,69 @@
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required};
+use environment::Environment;
+use eth2::SensitiveUrl;
+use execution_layer::test_utils::{serve_mock_builder, Behaviour, MockBuilder};
+use std::net::Ipv4Addr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use types::*;
+
+pub fn run<E: EthSpec>(mut env: Environment<E>, matches: &ArgMatches) -> Result<(), String> {
+    let execution_endpoint: SensitiveUrl = parse_required(matches, "execution-endpoint")?;
+    let jwt_path: PathBuf = parse_required(matches, "execution-jwt")?;
+    let beacon_url: SensitiveUrl = parse_required(matches, "beacon-url")?;
+    let listen_addr: Ipv4Addr = parse_required(matches, "listen-address")?;
+    let listen_port: u16 = parse_required(matches, "listen-port")?;
+    let behaviours = parse_optional::<String>(matches, "behaviours")?
+        .map(|behaviours| {
+            behaviours
+                .split(',')
+                .map(|behaviour| {
+                    Behaviour::from_str(behaviour.trim())
+                        .map_err(|_| format!("Unknown behaviour: {}", behaviour))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let context = env.core_context();
+    let spec = env.eth2_config.spec.clone();
+
+    let builder: MockBuilder<E> = MockBuilder::new_with_execution_endpoint(
+        execution_endpoint,
+        jwt_path,
+        beacon_url,
+        spec,
+        context.executor.clone(),
+    )?;
+    for behaviour in &behaviours {
+        builder.set_behaviour(*behaviour);
+    }
+
+    let (listen_socket, server) = serve_mock_builder(listen_addr, listen_port, builder)
+        .map_err(|e| format!("Unable to start mock relay: {:?}", e))?;
+    context.executor.spawn(server, "mock_relay");
+
+    eprintln!(
+        "This tool is for TESTING PURPOSES ONLY. Do not use in production or on mainnet. \
+        It may cause proposals to be missed."
+    );
+    if behaviours.is_empty() {
+        eprintln!("Relay is behaving honestly");
+    } else {
+        let behaviours = behaviours
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        eprintln!("Relay is behaving adversarially: {}", behaviours);
+    }
+    eprintln!("Server listening on {}", listen_socket);
+
+    let shutdown_reason = env.block_until_shutdown_requested()?;
+
+    eprintln!("Shutting down: {:?}", shutdown_reason);
+
+    Ok(())
+}
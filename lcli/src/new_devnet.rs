@@ -0,0 +1,276 @@
+//! # New Devnet
+//!
+//! Use this tool to create the `--testnet-dir` for a local devnet, without relying on external
+//! tools to produce the genesis state.
+//!
+//! The directory contains:
+//!
+//! - `config.yaml`, built from the `--spec` preset and the fork schedule given on the command
+//!   line. Forks which are not given an epoch are never activated.
+//! - `genesis.ssz`, built with `state_processing::genesis` from either the interop keypairs or
+//!   keys derived from a mnemonic.
+//! - `deposit_contract_block.txt` and `boot_enr.yaml`.
+//!
+//! Beacon nodes and validator clients can then be started with `--testnet-dir`, and validator
+//! keys created with `lcli mnemonic-validators` or `lighthouse account validator recover`.
+//!
+//! ## Example
+//!
+//! ```ignore
+//! lcli --spec minimal --testnet-dir /tmp/devnet new-devnet \
+//!     --validator-count 64 \
+//!     --altair-fork-epoch 0 \
+//!     --bellatrix-fork-epoch 0 \
+//!     --capella-fork-epoch 0 \
+//!     --deneb-fork-epoch 0 \
+//!     --electra-fork-epoch 2 \
+//!     --boot-enr enr:-Iq4QJk4WqRkjsX5c2CXtOra6HnxN-BMXnWhmhEQO9Bn9iABTJGdjUOurM7Btj1ouKaFkvTRoju5vz2GPmVON2dffQKGAX53x8JigmlkgnY0gmlwhLKAlv6Jc2VjcDI1NmsxoQK6S-Cii_KmfFdUJL2TANL3ksaKUnNXvTCv1tLwXs0QgIN1ZHCCIyk
+//! ```
+use clap::ArgMatches;
+use clap_utils::{parse_optional, parse_required, parse_ssz_optional};
+use eth2_network_config::{Eth2NetworkConfig, GenesisStateSource};
+use eth2_wallet::bip39::{Language, Mnemonic, Seed};
+use eth2_wallet::{recover_validator_secret_from_mnemonic, KeyType};
+use genesis::{
+    bls_withdrawal_credentials, generate_deterministic_keypairs,
+    interop_genesis_state_with_withdrawal_credentials, DEFAULT_ETH1_BLOCK_HASH,
+};
+use kzg::trusted_setup::get_trusted_setup;
+use lighthouse_network::Enr;
+use ssz::Encode;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+use types::{
+    Address, ChainSpec, Config, Epoch, EthSpec, ExecutionBlockHash, ExecutionPayloadHeader,
+    ExecutionPayloadHeaderBellatrix, ExecutionPayloadHeaderCapella, ExecutionPayloadHeaderDeneb,
+    ExecutionPayloadHeaderElectra, ForkName, Hash256, Keypair, Uint256,
+};
+
+pub fn run<E: EthSpec>(testnet_dir: PathBuf, matches: &ArgMatches) -> Result<(), String> {
+    let overwrite = matches.get_flag("force");
+    if testnet_dir.exists() && !overwrite {
+        return Err(format!(
+            "{} already exists, use --force to overwrite",
+            testnet_dir.display()
+        ));
+    }
+
+    let mut spec = E::default_spec();
+
+    // Update the spec value if the flag was provided. Otherwise, leave it as the default.
+    macro_rules! maybe_update {
+        ($flag: tt, $var: ident) => {
+            if let Some(val) = parse_optional(matches, $flag)? {
+                spec.$var = val
+            }
+        };
+    }
+
+    spec.config_name = Some(parse_required(matches, "config-name")?);
+    spec.deposit_contract_address = parse_required::<Address>(matches, "deposit-contract-address")?;
+    maybe_update!("deposit-chain-id", deposit_chain_id);
+    maybe_update!("deposit-chain-id", deposit_network_id);
+    maybe_update!("seconds-per-slot", seconds_per_slot);
+    maybe_update!("seconds-per-eth1-block", seconds_per_eth1_block);
+    maybe_update!("eth1-follow-distance", eth1_follow_distance);
+    maybe_update!("genesis-delay", genesis_delay);
+    spec.terminal_total_difficulty =
+        parse_required::<Uint256>(matches, "terminal-total-difficulty")?;
+
+    if let Some(genesis_fork_version) = parse_ssz_optional(matches, "genesis-fork-version")? {
+        spec.genesis_fork_version = genesis_fork_version;
+    }
+
+    // Forks which aren't scheduled are never activated, regardless of the preset.
+    spec.altair_fork_epoch = parse_optional(matches, "altair-fork-epoch")?;
+    spec.bellatrix_fork_epoch = parse_optional(matches, "bellatrix-fork-epoch")?;
+    spec.capella_fork_epoch = parse_optional(matches, "capella-fork-epoch")?;
+    spec.deneb_fork_epoch = parse_optional(matches, "deneb-fork-epoch")?;
+    spec.electra_fork_epoch = parse_optional(matches, "electra-fork-epoch")?;
+    validate_fork_schedule(&spec)?;
+
+    let validator_count: usize = parse_required(matches, "validator-count")?;
+    let genesis_time = match parse_optional(matches, "genesis-time")? {
+        Some(genesis_time) => genesis_time,
+        None => {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_err(|e| format!("Unable to read system time: {:?}", e))?
+                .as_secs()
+                + spec.genesis_delay
+        }
+    };
+    spec.min_genesis_time = genesis_time;
+    spec.min_genesis_active_validator_count = validator_count as u64;
+
+    let eth1_block_hash = match parse_optional::<Hash256>(matches, "eth1-block-hash")? {
+        Some(block_hash) => block_hash,
+        None => Hash256::from_slice(DEFAULT_ETH1_BLOCK_HASH),
+    };
+
+    let genesis_fork = spec.fork_name_at_epoch(Epoch::new(0));
+    let execution_payload_header =
+        match parse_optional::<PathBuf>(matches, "execution-payload-header")? {
+            Some(path) => {
+                let bytes = fs::read(&path)
+                    .map_err(|e| format!("Unable to read {}: {:?}", path.display(), e))?;
+                let header = ExecutionPayloadHeader::<E>::from_ssz_bytes(&bytes, genesis_fork)
+                    .map_err(|e| format!("Invalid execution payload header: {:?}", e))?;
+                Some(header)
+            }
+            None => default_execution_payload_header(
+                genesis_fork,
+                ExecutionBlockHash::from_root(eth1_block_hash),
+                genesis_time,
+            ),
+        };
+
+    let keypairs = match parse_optional::<String>(matches, "mnemonic-phrase")? {
+        Some(mnemonic_phrase) => mnemonic_keypairs(&mnemonic_phrase, validator_count)?,
+        None => generate_deterministic_keypairs(validator_count)
+            .into_iter()
+            .map(|keypair| (keypair.clone(), keypair))
+            .collect(),
+    };
+    let (voting_keypairs, withdrawal_credentials): (Vec<_>, Vec<_>) = keypairs
+        .into_iter()
+        .map(|(voting, withdrawal)| {
+            let credentials = bls_withdrawal_credentials(&withdrawal.pk, &spec);
+            (voting, credentials)
+        })
+        .unzip();
+
+    let genesis_state = interop_genesis_state_with_withdrawal_credentials::<E>(
+        &voting_keypairs,
+        &withdrawal_credentials,
+        genesis_time,
+        eth1_block_hash,
+        execution_payload_header,
+        &spec,
+    )?;
+
+    let boot_enr = parse_optional::<String>(matches, "boot-enr")?
+        .map(|enrs| {
+            enrs.split(',')
+                .map(|enr| {
+                    enr.parse::<Enr>()
+                        .map_err(|e| format!("Invalid boot ENR {}: {:?}", enr, e))
+                })
+                .collect::<Result<Vec<_>, _>>()
+        })
+        .transpose()?
+        .unwrap_or_default();
+
+    let network_config = Eth2NetworkConfig {
+        deposit_contract_deploy_block: parse_required(matches, "deposit-contract-deploy-block")?,
+        boot_enr: Some(boot_enr),
+        genesis_state_source: GenesisStateSource::IncludedBytes,
+        genesis_state_bytes: Some(genesis_state.as_ssz_bytes().into()),
+        config: Config::from_chain_spec::<E>(&spec),
+        kzg_trusted_setup: get_trusted_setup(),
+    };
+    network_config.write_to_file(testnet_dir.clone(), overwrite)?;
+
+    println!(
+        "Wrote devnet with {} validators and genesis time {} to {}",
+        validator_count,
+        genesis_time,
+        testnet_dir.display()
+    );
+
+    Ok(())
+}
+
+/// Check that every scheduled fork is preceded by all earlier forks, at the same epoch or before.
+fn validate_fork_schedule(spec: &ChainSpec) -> Result<(), String> {
+    let schedule = [
+        (ForkName::Altair, spec.altair_fork_epoch),
+        (ForkName::Bellatrix, spec.bellatrix_fork_epoch),
+        (ForkName::Capella, spec.capella_fork_epoch),
+        (ForkName::Deneb, spec.deneb_fork_epoch),
+        (ForkName::Electra, spec.electra_fork_epoch),
+    ];
+
+    for pair in schedule.windows(2) {
+        let (previous_fork, previous_epoch) = pair[0];
+        let (fork, epoch) = pair[1];
+        match (previous_epoch, epoch) {
+            (None, Some(_)) => {
+                return Err(format!(
+                    "{} is scheduled but {} is not, both must be scheduled",
+                    fork, previous_fork
+                ))
+            }
+            (Some(previous_epoch), Some(epoch)) if epoch < previous_epoch => {
+                return Err(format!(
+                    "{} fork epoch {} is before {} fork epoch {}",
+                    fork, epoch, previous_fork, previous_epoch
+                ))
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The execution payload header for a genesis state at `fork_name`, with its block hash set to the
+/// genesis block of the execution chain.
+fn default_execution_payload_header<E: EthSpec>(
+    fork_name: ForkName,
+    block_hash: ExecutionBlockHash,
+    timestamp: u64,
+) -> Option<ExecutionPayloadHeader<E>> {
+    let header = match fork_name {
+        ForkName::Base | ForkName::Altair => return None,
+        ForkName::Bellatrix => ExecutionPayloadHeader::Bellatrix(ExecutionPayloadHeaderBellatrix {
+            block_hash,
+            timestamp,
+            ..Default::default()
+        }),
+        ForkName::Capella => ExecutionPayloadHeader::Capella(ExecutionPayloadHeaderCapella {
+            block_hash,
+            timestamp,
+            ..Default::default()
+        }),
+        ForkName::Deneb => ExecutionPayloadHeader::Deneb(ExecutionPayloadHeaderDeneb {
+            block_hash,
+            timestamp,
+            ..Default::default()
+        }),
+        ForkName::Electra => ExecutionPayloadHeader::Electra(ExecutionPayloadHeaderElectra {
+            block_hash,
+            timestamp,
+            ..Default::default()
+        }),
+    };
+    Some(header)
+}
+
+/// Derive the voting and withdrawal keypairs of the first `count` validators from a mnemonic,
+/// using the EIP-2334 paths.
+fn mnemonic_keypairs(
+    mnemonic_phrase: &str,
+    count: usize,
+) -> Result<Vec<(Keypair, Keypair)>, String> {
+    let mnemonic = Mnemonic::from_phrase(mnemonic_phrase, Language::English)
+        .map_err(|e| format!("Unable to parse mnemonic: {:?}", e))?;
+    let seed = Seed::new(&mnemonic, "");
+
+    let derive = |index: u32, key_type: KeyType| -> Result<Keypair, String> {
+        let (secret, _) = recover_validator_secret_from_mnemonic(seed.as_bytes(), index, key_type)
+            .map_err(|e| format!("Unable to recover validator keys: {:?}", e))?;
+        account_utils::eth2_keystore::keypair_from_secret(secret.as_bytes())
+            .map_err(|e| format!("Unable to build keypair: {:?}", e))
+    };
+
+    (0..count as u32)
+        .map(|index| {
+            Ok((
+                derive(index, KeyType::Voting)?,
+                derive(index, KeyType::Withdrawal)?,
+            ))
+        })
+        .collect()
+}
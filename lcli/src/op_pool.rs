@@ -0,0 +1,84 @@
+//! # Operation Pool
+//!
+//! Use these tools to move the contents of a beacon node's operation pool to another beacon node,
+//! for example when replacing a node without waiting for pending slashings and exits to be
+//! gossiped again.
+//!
+//! The pool is exported as an SSZ-encoded `PersistedOperationPool`. The importing node verifies
+//! every operation before adding it to its own pool.
+//!
+//! ## Examples
+//!
+//! ```ignore
+//! lcli export-op-pool \
+//!     --beacon-url http://localhost:5052 \
+//!     --output-path /tmp/op_pool.ssz
+//!
+//! lcli import-op-pool \
+//!     --beacon-url http://localhost:5062 \
+//!     --input-path /tmp/op_pool.ssz
+//! ```
+use clap::ArgMatches;
+use clap_utils::parse_required;
+use environment::Environment;
+use eth2::{lighthouse::OpPoolImportCount, BeaconNodeHttpClient, SensitiveUrl, Timeouts};
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+use types::EthSpec;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(60);
+
+pub fn run_export<E: EthSpec>(env: Environment<E>, matches: &ArgMatches) -> Result<(), String> {
+    let beacon_url: SensitiveUrl = parse_required(matches, "beacon-url")?;
+    let output_path: PathBuf = parse_required(matches, "output-path")?;
+    let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+
+    let bytes = env
+        .core_context()
+        .executor
+        .handle()
+        .ok_or("shutdown in progress")?
+        .block_on(client.get_lighthouse_op_pool_ssz())
+        .map_err(|e| format!("Failed to download op pool: {:?}", e))?;
+
+    fs::write(&output_path, &bytes)
+        .map_err(|e| format!("Unable to write to {}: {:?}", output_path.display(), e))?;
+
+    println!("Wrote {} bytes to {}", bytes.len(), output_path.display());
+
+    Ok(())
+}
+
+pub fn run_import<E: EthSpec>(env: Environment<E>, matches: &ArgMatches) -> Result<(), String> {
+    let beacon_url: SensitiveUrl = parse_required(matches, "beacon-url")?;
+    let input_path: PathBuf = parse_required(matches, "input-path")?;
+    let client = BeaconNodeHttpClient::new(beacon_url, Timeouts::set_all(HTTP_TIMEOUT));
+
+    let bytes = fs::read(&input_path)
+        .map_err(|e| format!("Unable to read {}: {:?}", input_path.display(), e))?;
+
+    let summary = env
+        .core_context()
+        .executor
+        .handle()
+        .ok_or("shutdown in progress")?
+        .block_on(client.post_lighthouse_op_pool_ssz(bytes))
+        .map_err(|e| format!("Failed to import op pool: {:?}", e))?
+        .data;
+
+    let print_count = |kind: &str, count: OpPoolImportCount| {
+        println!(
+            "{kind}: {} imported, {} rejected",
+            count.imported, count.rejected
+        );
+    };
+    print_count("attestations", summary.attestations);
+    print_count("sync contributions", summary.sync_contributions);
+    print_count("attester slashings", summary.attester_slashings);
+    print_count("proposer slashings", summary.proposer_slashings);
+    print_count("voluntary exits", summary.voluntary_exits);
+    print_count("BLS to execution changes", summary.bls_to_execution_changes);
+
+    Ok(())
+}
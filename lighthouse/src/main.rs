@@ -16,6 +16,7 @@ use eth2_network_config::{Eth2NetworkConfig, DEFAULT_HARDCODED_NETWORK, HARDCODE
 use ethereum_hashing::have_sha_extensions;
 use futures::TryFutureExt;
 use lighthouse_version::VERSION;
+use logging::OtlpConfig;
 use malloc_utils::configure_memory_allocator;
 use slog::{crit, info};
 use std::backtrace::Backtrace;
@@ -387,6 +388,35 @@ fn main() {
                 .global(true)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("telemetry-collector-url")
+                .long("telemetry-collector-url")
+                .value_name("URL")
+                .help(
+                    "The URL of an OpenTelemetry collector to which tracing spans are exported \
+                    using OTLP over HTTP, e.g. http://localhost:4318. Spans cover block import \
+                    and HTTP API requests, and the trace context of requests from the validator \
+                    client to the beacon node is propagated.",
+                )
+                .action(ArgAction::Set)
+                .global(true)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("telemetry-sample-ratio")
+                .long("telemetry-sample-ratio")
+                .value_name("RATIO")
+                .help(
+                    "The fraction of traces exported to the --telemetry-collector-url, between 0 \
+                    and 1. Traces continued from another process are exported if the other \
+                    process exported them.",
+                )
+                .requires("telemetry-collector-url")
+                .action(ArgAction::Set)
+                .default_value("1")
+                .global(true)
+                .display_order(0)
+        )
         .arg(
             Arg::new("help")
             .long("help")
@@ -398,6 +428,7 @@ fn main() {
         )
         .subcommand(beacon_node::cli_app())
         .subcommand(boot_node::cli_app())
+        .subcommand(gateway::cli_app())
         .subcommand(validator_client::cli_app())
         .subcommand(account_manager::cli_app())
         .subcommand(validator_manager::cli_app());
@@ -712,8 +743,9 @@ fn run<E: EthSpec>(
             }
 
             let path = tracing_log_path.clone().unwrap();
+            let otlp_config = parse_otlp_config(matches, "lighthouse-beacon-node")?;
 
-            logging::create_tracing_layer(path);
+            logging::create_tracing_layer(path, otlp_config.as_ref());
 
             executor.clone().spawn(
                 async move {
@@ -744,6 +776,10 @@ fn run<E: EthSpec>(
                 return Ok(());
             }
 
+            if let Some(otlp_config) = parse_otlp_config(matches, "lighthouse-validator-client")? {
+                logging::create_otlp_tracing_layer(&otlp_config);
+            }
+
             executor.clone().spawn(
                 async move {
                     if let Err(e) = ProductionValidatorClient::new(context, config)
@@ -761,6 +797,35 @@ fn run<E: EthSpec>(
                 "validator_client",
             );
         }
+        Some(("gateway", matches)) => {
+            let context = environment.core_context();
+            let log = context.log().clone();
+            let executor = context.executor.clone();
+            let config = gateway::Config::from_cli(matches)
+                .map_err(|e| format!("Unable to initialize gateway config: {}", e))?;
+            // Dump configs if `dump-config` or `dump-chain-config` flags are set
+            clap_utils::check_dump_configs::<_, E>(matches, &config, &context.eth2_config.spec)?;
+
+            let shutdown_flag = matches.get_flag("immediate-shutdown");
+            if shutdown_flag {
+                info!(log, "Gateway immediate shutdown triggered.");
+                return Ok(());
+            }
+
+            executor.clone().spawn(
+                async move {
+                    if let Err(e) = gateway::run(context, config).await {
+                        crit!(log, "Failed to start gateway"; "reason" => e);
+                        // Ignore the error since it always occurs during normal operation when
+                        // shutting down.
+                        let _ = executor
+                            .shutdown_sender()
+                            .try_send(ShutdownReason::Failure("Failed to start gateway"));
+                    }
+                },
+                "gateway",
+            );
+        }
         _ => {
             crit!(log, "No subcommand supplied. See --help .");
             return Err("No subcommand supplied.".into());
@@ -776,8 +841,34 @@ fn run<E: EthSpec>(
     // Shutdown the environment once all tasks have completed.
     environment.shutdown_on_idle();
 
+    // Export any spans which are still buffered.
+    logging::shutdown_otlp_tracing();
+
     match shutdown_reason {
         ShutdownReason::Success(_) => Ok(()),
         ShutdownReason::Failure(msg) => Err(msg.to_string()),
     }
 }
+
+/// Parse the configuration for exporting the spans of `service_name` to an OpenTelemetry
+/// collector, if one was provided.
+fn parse_otlp_config(
+    matches: &ArgMatches,
+    service_name: &str,
+) -> Result<Option<OtlpConfig>, String> {
+    let Some(collector_url) = clap_utils::parse_optional(matches, "telemetry-collector-url")?
+    else {
+        return Ok(None);
+    };
+
+    let sample_ratio: f64 = clap_utils::parse_required(matches, "telemetry-sample-ratio")?;
+    if !(0.0..=1.0).contains(&sample_ratio) {
+        return Err("--telemetry-sample-ratio must be between 0 and 1".to_string());
+    }
+
+    Ok(Some(OtlpConfig {
+        collector_url,
+        service_name: service_name.to_string(),
+        sample_ratio,
+    }))
+}
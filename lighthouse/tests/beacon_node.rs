@@ -2,8 +2,8 @@ use beacon_node::ClientConfig as Config;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use beacon_node::beacon_chain::chain_config::{
-    DisallowedReOrgOffsets, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR, DEFAULT_RE_ORG_HEAD_THRESHOLD,
-    DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
+    DisallowedReOrgOffsets, PackingStrategyKind, DEFAULT_RE_ORG_CUTOFF_DENOMINATOR,
+    DEFAULT_RE_ORG_HEAD_THRESHOLD, DEFAULT_RE_ORG_MAX_EPOCHS_SINCE_FINALIZATION,
 };
 use beacon_node::beacon_chain::graffiti_calculator::GraffitiOrigin;
 use beacon_processor::BeaconProcessorConfig;
@@ -213,6 +213,90 @@ fn fork_choice_before_proposal_timeout_zero() {
         .with_config(|config| assert_eq!(config.chain.fork_choice_before_proposal_timeout_ms, 0));
 }
 
+#[test]
+fn fork_choice_snapshot_slots_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.fork_choice_snapshot_slots, 0));
+}
+
+#[test]
+fn fork_choice_snapshot_slots_flag() {
+    CommandLineTest::new()
+        .flag("fork-choice-snapshot-slots", Some("7200"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.fork_choice_snapshot_slots, 7200));
+}
+
+#[test]
+fn op_pool_persist_interval_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.op_pool_persist_interval_slots, 32));
+}
+
+#[test]
+fn op_pool_persist_interval_flag() {
+    CommandLineTest::new()
+        .flag("op-pool-persist-interval", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.chain.op_pool_persist_interval_slots, 0));
+}
+
+#[test]
+fn block_timings_log_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.chain.block_timings_log_dir, None);
+            assert_eq!(config.chain.block_timings_log_max_files, 30);
+        });
+}
+
+#[test]
+fn block_timings_log_flags() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag("block-timings-log-dir", dir.path().as_os_str().to_str())
+        .flag("block-timings-log-max-files", Some("7"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.block_timings_log_dir.as_deref(),
+                Some(dir.path())
+            );
+            assert_eq!(config.chain.block_timings_log_max_files, 7);
+        });
+}
+
+#[test]
+fn attestation_packing_strategy_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_strategy,
+                PackingStrategyKind::Greedy
+            )
+        });
+}
+
+#[test]
+fn attestation_packing_strategy_optimal() {
+    CommandLineTest::new()
+        .flag("attestation-packing-strategy", Some("optimal"))
+        .flag("attestation-packing-time-budget", Some("120"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.chain.attestation_packing_strategy,
+                PackingStrategyKind::Optimal {
+                    time_budget: Duration::from_millis(120)
+                }
+            )
+        });
+}
+
 #[test]
 fn checkpoint_sync_url_timeout_flag() {
     CommandLineTest::new()
@@ -449,8 +533,7 @@ fn eth1_cache_follow_distance_manual() {
 fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
     use sensitive_url::SensitiveUrl;
     let urls = vec!["http://sigp.io/no-way:1337", "http://infura.not_real:4242"];
-    // we don't support redundancy for execution-endpoints
-    // only the first provided endpoint is parsed.
+    // The first endpoint is the primary engine, the rest are secondary engines.
 
     let mut endpoint_arg = urls[0].to_string();
     for url in urls.iter().skip(1) {
@@ -485,11 +568,123 @@ fn run_bellatrix_execution_endpoints_flag_test(flag: &str) {
                 config.execution_endpoint.as_ref().unwrap().clone(),
                 SensitiveUrl::parse(&urls[0]).unwrap()
             );
-            // Only the first secret file should be used.
+            // The first secret file is used by the primary engine.
             assert_eq!(
                 config.secret_file.as_ref().unwrap().clone(),
                 jwts[0].clone()
             );
+            // The remaining endpoints are secondary engines with their own secret files.
+            assert_eq!(config.secondary_engines.len(), 1);
+            assert_eq!(
+                config.secondary_engines[0].execution_endpoint,
+                SensitiveUrl::parse(&urls[1]).unwrap()
+            );
+            assert_eq!(
+                config.secondary_engines[0].secret_file.as_ref().unwrap(),
+                &jwts[1]
+            );
+        });
+}
+#[test]
+fn execution_endpoints_share_single_jwt() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwt = dir.path().join("jwt-file");
+    CommandLineTest::new_with_no_execution_endpoint()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:8552/"),
+        )
+        .flag("execution-jwt", jwt.as_os_str().to_str())
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            assert_eq!(config.secondary_engines.len(), 1);
+            assert_eq!(
+                config.secondary_engines[0].secret_file.as_ref().unwrap(),
+                &jwt
+            );
+        });
+}
+#[test]
+#[should_panic]
+fn execution_endpoints_mismatched_jwts() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    let jwts = format!(
+        "{},{},{}",
+        dir.path().join("jwt-0").display(),
+        dir.path().join("jwt-1").display(),
+        dir.path().join("jwt-2").display()
+    );
+    CommandLineTest::new_with_no_execution_endpoint()
+        .flag(
+            "execution-endpoint",
+            Some("http://localhost:8551/,http://localhost:8552/"),
+        )
+        .flag("execution-jwt", Some(&jwts))
+        .run_with_zero_port();
+}
+#[test]
+fn execution_record_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "execution-record-file",
+            dir.path().join("engine-api.jsonl").as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config
+                    .execution_layer
+                    .as_ref()
+                    .unwrap()
+                    .engine_api_record_file,
+                Some(dir.path().join("engine-api.jsonl"))
+            );
+        });
+}
+#[test]
+fn execution_verifier_endpoint_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new_with_no_execution_endpoint()
+        .flag("execution-endpoint", Some("http://localhost:8551/"))
+        .flag(
+            "execution-jwt",
+            dir.path().join("jwt-file").as_os_str().to_str(),
+        )
+        .flag(
+            "execution-verifier-endpoint",
+            Some("http://localhost:9551/"),
+        )
+        .flag(
+            "execution-verifier-jwt",
+            dir.path().join("verifier-jwt-file").as_os_str().to_str(),
+        )
+        .run_with_zero_port()
+        .with_config(|config| {
+            let config = config.execution_layer.as_ref().unwrap();
+            let verifier = config.verifier_engine.as_ref().unwrap();
+            assert_eq!(
+                verifier.execution_endpoint.full.to_string(),
+                "http://localhost:9551/"
+            );
+            assert_eq!(
+                verifier.secret_file.as_ref().unwrap(),
+                &dir.path().join("verifier-jwt-file")
+            );
+        });
+}
+#[test]
+fn execution_verifier_endpoint_default() {
+    CommandLineTest::new()
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert!(config
+                .execution_layer
+                .as_ref()
+                .unwrap()
+                .verifier_engine
+                .is_none());
         });
 }
 #[test]
@@ -593,9 +788,8 @@ fn run_payload_builder_flag_test(flag: &str, builders: &str) {
         .collect();
     run_payload_builder_flag_test_with_config(flag, builders, None, None, |config| {
         let config = config.execution_layer.as_ref().unwrap();
-        // Only first provided endpoint is parsed as we don't support
-        // redundancy.
-        assert_eq!(config.builder_url, all_builders.get(0).cloned());
+        // Every provided endpoint is used as a relay.
+        assert_eq!(config.builder_urls, all_builders);
     })
 }
 fn run_payload_builder_flag_test_with_config<F: Fn(&Config)>(
@@ -2668,6 +2862,125 @@ fn http_duplicate_block_status_override() {
         });
 }
 
+#[test]
+fn http_api_token_file_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(config.http_api.auth_token_file, None);
+            assert_eq!(config.http_api.public_role, None);
+        });
+}
+
+#[test]
+fn http_api_token_file_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-api-token-file", Some("/tmp/api-tokens.yaml"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.auth_token_file,
+                Some(PathBuf::from("/tmp/api-tokens.yaml"))
+            );
+            assert_eq!(config.http_api.public_role, None);
+        });
+}
+
+#[test]
+fn http_api_public_role_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-api-token-file", Some("/tmp/api-tokens.yaml"))
+        .flag("http-api-public-role", Some("read-only"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_api.public_role.map(|role| role.to_string()),
+                Some("read-only".to_string())
+            );
+        });
+}
+
+#[test]
+fn http_enable_graphql_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(!config.http_api.enable_graphql));
+}
+
+#[test]
+fn http_enable_graphql_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-enable-graphql", None)
+        .run_with_zero_port()
+        .with_config(|config| assert!(config.http_api.enable_graphql));
+}
+
+#[test]
+fn http_response_cache_size_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.response_cache_size, 64 * 1024 * 1024));
+}
+
+#[test]
+fn http_response_cache_size_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-response-cache-size", Some("16"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.response_cache_size, 16 * 1024 * 1024));
+}
+
+#[test]
+fn http_response_cache_size_zero() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-response-cache-size", Some("0"))
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.response_cache_size, 0));
+}
+
+#[test]
+fn http_rate_limit_default() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_zero_port()
+        .with_config(|config| assert_eq!(config.http_api.rate_limit, None));
+}
+
+#[test]
+fn http_rate_limit_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-rate-limit", Some("20"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let rate_limit = config.http_api.rate_limit.unwrap();
+            assert_eq!(rate_limit.cost_per_second, 20);
+            assert_eq!(rate_limit.burst, 200);
+        });
+}
+
+#[test]
+fn http_rate_limit_burst_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-rate-limit", Some("20"))
+        .flag("http-rate-limit-burst", Some("500"))
+        .run_with_zero_port()
+        .with_config(|config| {
+            let rate_limit = config.http_api.rate_limit.unwrap();
+            assert_eq!(rate_limit.cost_per_second, 20);
+            assert_eq!(rate_limit.burst, 500);
+        });
+}
+
 #[test]
 fn genesis_state_url_default() {
     CommandLineTest::new()
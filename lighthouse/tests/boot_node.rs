@@ -1,4 +1,5 @@
-use boot_node::config::BootNodeConfigSerialization;
+use boot_node::config::{BootNodeConfigSerialization, CRAWLER_DB_FILENAME};
+use boot_node::persisted_dht::DHT_FILENAME;
 
 use crate::exec::{CommandLineTestExec, CompletedTest};
 use clap::ArgMatches;
@@ -7,7 +8,7 @@ use lighthouse_network::discovery::ENR_FILENAME;
 use lighthouse_network::Enr;
 use std::fs::File;
 use std::io::Write;
-use std::net::Ipv4Addr;
+use std::net::{Ipv4Addr, SocketAddr};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::str::FromStr;
@@ -176,6 +177,109 @@ fn network_dir_flag() {
         .with_config(|config| assert_eq!(config.local_enr, enr))
 }
 
+#[test]
+fn dht_persistence_default() {
+    CommandLineTest::new().run_with_ip().with_config(|config| {
+        let dht_path = config.dht_path.as_ref().expect("DHT should be persisted");
+        assert_eq!(dht_path.file_name().unwrap(), DHT_FILENAME);
+    });
+}
+
+#[test]
+fn dht_persistence_network_dir() {
+    let enr = Enr::from_str("enr:-IS4QHCYrYZbAKWCBRlAy5zzaDZXJBGkcnh4MHcBFZntXNFrdvJjX04jRzjzCBOonrkTfj499SZuOh8R33Ls8RRcy5wBgmlkgnY0gmlwhH8AAAGJc2VjcDI1NmsxoQPKY0yuDUmstAHYpMa2_oxVtw0RW_QAdpzBQA8yWM0xOIN1ZHCCdl8").unwrap();
+    let tmp_dir = TempDir::new().unwrap();
+    save_enr_to_disk(tmp_dir.path(), &enr).unwrap();
+
+    CommandLineTest::new()
+        .flag("network-dir", Some(tmp_dir.path().to_str().unwrap()))
+        .run()
+        .with_config(|config| assert_eq!(config.dht_path, Some(tmp_dir.path().join(DHT_FILENAME))));
+}
+
+#[test]
+fn disable_dht_persistence_flag() {
+    CommandLineTest::new()
+        .flag("disable-dht-persistence", None)
+        .run_with_ip()
+        .with_config(|config| assert_eq!(config.dht_path, None));
+}
+
+#[test]
+fn http_default() {
+    CommandLineTest::new()
+        .run_with_ip()
+        .with_config(|config| assert_eq!(config.http_listen_socket, None));
+}
+
+#[test]
+fn http_flag() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .run_with_ip()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_listen_socket,
+                Some("127.0.0.1:5054".parse::<SocketAddr>().unwrap())
+            )
+        });
+}
+
+#[test]
+fn http_address_and_port_flags() {
+    CommandLineTest::new()
+        .flag("http", None)
+        .flag("http-address", Some("0.0.0.0"))
+        .flag("http-port", Some("6054"))
+        .run_with_ip()
+        .with_config(|config| {
+            assert_eq!(
+                config.http_listen_socket,
+                Some("0.0.0.0:6054".parse::<SocketAddr>().unwrap())
+            )
+        });
+}
+
+#[test]
+#[should_panic]
+fn http_port_without_http_flag() {
+    CommandLineTest::new()
+        .flag("http-port", Some("6054"))
+        .run_with_ip();
+}
+
+#[test]
+fn crawler_default() {
+    CommandLineTest::new()
+        .run_with_ip()
+        .with_config(|config| assert_eq!(config.crawler_db, None));
+}
+
+#[test]
+fn crawler_flag() {
+    CommandLineTest::new()
+        .flag("crawler", None)
+        .run_with_ip()
+        .with_config(|config| {
+            let crawler_db = config
+                .crawler_db
+                .as_ref()
+                .expect("Crawler should be enabled");
+            assert_eq!(crawler_db.file_name().unwrap(), CRAWLER_DB_FILENAME);
+        });
+}
+
+#[test]
+fn crawler_db_flag() {
+    let tmp_dir = TempDir::new().unwrap();
+    let crawler_db = tmp_dir.path().join("enrs.sqlite");
+    CommandLineTest::new()
+        .flag("crawler", None)
+        .flag("crawler-db", crawler_db.to_str())
+        .run_with_ip()
+        .with_config(|config| assert_eq!(config.crawler_db, Some(crawler_db.clone())));
+}
+
 fn save_enr_to_disk(dir: &Path, enr: &Enr) -> Result<(), String> {
     let mut file = File::create(dir.join(Path::new(ENR_FILENAME)))
         .map_err(|e| format!("Could not create ENR file: {:?}", e))?;
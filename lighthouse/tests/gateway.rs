@@ -0,0 +1,299 @@
+use beacon_node_fallback::beacon_node_health::BeaconNodeSyncDistanceTiers;
+
+use crate::exec::{CommandLineTestExec, CompletedTest};
+use eth2::reqwest::{self, StatusCode};
+use gateway::Config;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tempfile::TempDir;
+use types::Slot;
+use warp::path::FullPath;
+use warp::reply::{Reply, Response};
+use warp::Filter;
+
+const NETWORKS: &str = r#"
+- name: mainnet
+  hosts: [mainnet.example.com]
+  beacon_nodes:
+    - url: http://localhost:5052
+      metrics_url: http://localhost:5054
+    - url: http://localhost:6052
+- name: holesky
+  beacon_nodes:
+    - url: http://localhost:7052
+"#;
+
+struct CommandLineTest {
+    cmd: Command,
+    // Holds the networks file until the command has run.
+    _dir: TempDir,
+}
+
+impl CommandLineTest {
+    fn new(networks: &str) -> CommandLineTest {
+        let dir = TempDir::new().expect("Unable to create temporary directory");
+        let networks_path = dir.path().join("networks.yaml");
+        fs::write(&networks_path, networks).expect("Unable to write networks");
+
+        let lighthouse_bin = env!("CARGO_BIN_EXE_lighthouse");
+        let path = lighthouse_bin
+            .parse::<PathBuf>()
+            .expect("should parse CARGO_TARGET_DIR");
+        let mut cmd = Command::new(path);
+        cmd.arg("gateway").arg("--networks").arg(&networks_path);
+        CommandLineTest { cmd, _dir: dir }
+    }
+
+    fn run_gateway(&mut self) -> CompletedTest<Config> {
+        self.run()
+    }
+
+    /// Start the gateway on `port`, leaving it running until the returned process is dropped.
+    fn spawn_gateway(mut self, port: u16) -> GatewayProcess {
+        let child = self
+            .cmd
+            .arg("--datadir")
+            .arg(self._dir.path())
+            .arg("--port")
+            .arg(port.to_string())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .expect("should start gateway");
+        GatewayProcess {
+            child,
+            url: format!("http://127.0.0.1:{}", port),
+            _dir: self._dir,
+        }
+    }
+}
+
+struct GatewayProcess {
+    child: Child,
+    url: String,
+    _dir: TempDir,
+}
+
+impl Drop for GatewayProcess {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+/// A beacon node which counts the requests to each path, and either serves a few fixed responses
+/// or fails every request with a `500`.
+struct MockBeaconNode {
+    url: String,
+    requests: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl MockBeaconNode {
+    fn start(failing: bool) -> Self {
+        let requests = Arc::new(Mutex::new(HashMap::<String, usize>::new()));
+        let inner_requests = requests.clone();
+        let routes = warp::path::full().map(move |path: FullPath| -> Response {
+            *inner_requests
+                .lock()
+                .unwrap()
+                .entry(path.as_str().to_string())
+                .or_default() += 1;
+            if failing {
+                return warp::reply::with_status("failed", StatusCode::INTERNAL_SERVER_ERROR)
+                    .into_response();
+            }
+            let body = match path.as_str() {
+                "/eth/v1/beacon/genesis" => json!({
+                    "data": {
+                        "genesis_time": "1606824023",
+                        "genesis_validators_root": format!("0x{}", "00".repeat(32)),
+                        "genesis_fork_version": "0x00000000",
+                    }
+                }),
+                "/eth/v1/node/version" => json!({ "data": { "version": "mock/v0.0.0" } }),
+                "/eth/v1/beacon/blocks/100/root" => json!({
+                    "execution_optimistic": false,
+                    "finalized": true,
+                    "data": { "root": format!("0x{}", "01".repeat(32)) },
+                }),
+                _ => {
+                    return warp::reply::with_status("not found", StatusCode::NOT_FOUND)
+                        .into_response()
+                }
+            };
+            warp::reply::json(&body).into_response()
+        });
+        let (addr, server) =
+            warp::serve(routes).bind_ephemeral(SocketAddr::from(([127, 0, 0, 1], 0)));
+        tokio::spawn(server);
+
+        Self {
+            url: format!("http://{}", addr),
+            requests,
+        }
+    }
+
+    fn requests(&self, path: &str) -> usize {
+        self.requests
+            .lock()
+            .unwrap()
+            .get(path)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl CommandLineTestExec for CommandLineTest {
+    type Config = Config;
+
+    fn cmd_mut(&mut self) -> &mut Command {
+        &mut self.cmd
+    }
+}
+
+#[test]
+fn networks_flag() {
+    CommandLineTest::new(NETWORKS)
+        .run_gateway()
+        .with_config(|config| {
+            assert_eq!(config.networks.len(), 2);
+            assert_eq!(config.networks[0].name, "mainnet");
+            assert_eq!(config.networks[0].hosts, vec!["mainnet.example.com"]);
+            assert_eq!(config.networks[0].beacon_nodes.len(), 2);
+            assert_eq!(config.networks[1].name, "holesky");
+        });
+}
+
+#[test]
+fn defaults() {
+    CommandLineTest::new(NETWORKS)
+        .run_gateway()
+        .with_config(|config| {
+            assert_eq!(config.listen_addr, "127.0.0.1".parse::<IpAddr>().unwrap());
+            assert_eq!(config.listen_port, 5052);
+            assert_eq!(config.cache_size, 256 * 1024 * 1024);
+            assert_eq!(config.backend_timeout, Duration::from_secs(12));
+        });
+}
+
+#[test]
+fn listen_flags() {
+    CommandLineTest::new(NETWORKS)
+        .flag("listen-address", Some("0.0.0.0"))
+        .flag("port", Some("6000"))
+        .run_gateway()
+        .with_config(|config| {
+            assert_eq!(config.listen_addr, "0.0.0.0".parse::<IpAddr>().unwrap());
+            assert_eq!(config.listen_port, 6000);
+        });
+}
+
+#[test]
+fn cache_size_and_backend_timeout_flags() {
+    CommandLineTest::new(NETWORKS)
+        .flag("cache-size", Some("0"))
+        .flag("backend-timeout", Some("4"))
+        .run_gateway()
+        .with_config(|config| {
+            assert_eq!(config.cache_size, 0);
+            assert_eq!(config.backend_timeout, Duration::from_secs(4));
+        });
+}
+
+#[test]
+fn beacon_nodes_sync_tolerances_flag() {
+    CommandLineTest::new(NETWORKS)
+        .flag("beacon-nodes-sync-tolerances", Some("4,4,4"))
+        .run_gateway()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_node_fallback.sync_tolerances,
+                BeaconNodeSyncDistanceTiers {
+                    synced: Slot::new(4),
+                    small: Slot::new(8),
+                    medium: Slot::new(12),
+                }
+            );
+        });
+}
+
+#[test]
+#[should_panic]
+fn duplicate_network_names() {
+    CommandLineTest::new(
+        r#"
+- name: mainnet
+  beacon_nodes: [{ url: "http://localhost:5052" }]
+- name: mainnet
+  beacon_nodes: [{ url: "http://localhost:6052" }]
+"#,
+    )
+    .run_gateway();
+}
+
+// Test that requests fall back to another beacon node on a server error, that finalized responses
+// are served from the cache, and that requests to a network are rejected before its genesis is
+// known.
+#[tokio::test]
+async fn forward_requests() {
+    let failing = MockBeaconNode::start(true);
+    let healthy = MockBeaconNode::start(false);
+    let no_genesis = MockBeaconNode::start(true);
+    let networks = format!(
+        r#"
+- name: mainnet
+  beacon_nodes:
+    - url: {}
+    - url: {}
+- name: holesky
+  beacon_nodes:
+    - url: {}
+"#,
+        failing.url, healthy.url, no_genesis.url
+    );
+    let port = unused_port::unused_tcp4_port().expect("should find unused port");
+    let gateway = CommandLineTest::new(&networks).spawn_gateway(port);
+    let client = reqwest::Client::new();
+    let get = |path: &str| client.get(format!("{}{}", gateway.url, path)).send();
+
+    // Wait for the genesis of mainnet to be fetched.
+    let mut ready = false;
+    for _ in 0..100 {
+        if let Ok(response) = get("/gateway/health").await {
+            let health: Value = response.json().await.expect("should decode health");
+            if health["data"][0]["ready"] == json!(true) {
+                ready = true;
+                break;
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert!(ready, "mainnet should become ready");
+
+    // The first beacon node fails, so the request is served by the second.
+    let response = get("/mainnet/eth/v1/node/version").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let version: Value = response.json().await.unwrap();
+    assert_eq!(version["data"]["version"], "mock/v0.0.0");
+    assert!(failing.requests("/eth/v1/node/version") > 0);
+    assert_eq!(healthy.requests("/eth/v1/node/version"), 1);
+
+    // The second request for finalized data is served from the cache.
+    let path = "/mainnet/eth/v1/beacon/blocks/100/root";
+    let first: Value = get(path).await.unwrap().json().await.unwrap();
+    let second: Value = get(path).await.unwrap().json().await.unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first["finalized"], json!(true));
+    assert_eq!(healthy.requests("/eth/v1/beacon/blocks/100/root"), 1);
+
+    // Holesky's beacon node never serves its genesis.
+    let response = get("/holesky/eth/v1/node/version").await.unwrap();
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(no_genesis.requests("/eth/v1/node/version"), 0);
+}
@@ -4,5 +4,6 @@ mod account_manager;
 mod beacon_node;
 mod boot_node;
 mod exec;
+mod gateway;
 mod validator_client;
 mod validator_manager;
@@ -3,6 +3,7 @@ use beacon_node_fallback::{beacon_node_health::BeaconNodeSyncDistanceTiers, ApiT
 use crate::exec::CommandLineTestExec;
 use bls::{Keypair, PublicKeyBytes};
 use initialized_validators::DEFAULT_WEB3SIGNER_KEEP_ALIVE;
+use leader_election::{LeaseBackendConfig, DEFAULT_LEASE_DURATION};
 use sensitive_url::SensitiveUrl;
 use std::fs::File;
 use std::io::Write;
@@ -129,6 +130,23 @@ fn use_long_timeouts_flag() {
         .with_config(|config| assert!(config.use_long_timeouts));
 }
 
+#[test]
+fn beacon_nodes_api_token_file_flag() {
+    let dir = TempDir::new().expect("Unable to create temporary directory");
+    CommandLineTest::new()
+        .flag(
+            "beacon-nodes-api-token-file",
+            dir.path().join("api-token.txt").to_str(),
+        )
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.beacon_nodes_api_token_file,
+                Some(dir.path().join("api-token.txt"))
+            )
+        });
+}
+
 #[test]
 fn beacon_nodes_tls_certs_flag() {
     let dir = TempDir::new().expect("Unable to create temporary directory");
@@ -426,6 +444,55 @@ fn no_doppelganger_protection_flag() {
         .with_config(|config| assert!(!config.enable_doppelganger_protection));
 }
 
+#[test]
+fn no_leader_election_flag() {
+    CommandLineTest::new()
+        .run()
+        .with_config(|config| assert!(config.leader_election.is_none()));
+}
+#[test]
+fn leader_election_lockfile_flag() {
+    CommandLineTest::new()
+        .flag("leader-election-lockfile", Some("/shared/vc.lease"))
+        .flag("leader-election-id", Some("vc-a"))
+        .run()
+        .with_config(|config| {
+            let leader_election = config.leader_election.as_ref().unwrap();
+            assert_eq!(
+                leader_election.backend,
+                LeaseBackendConfig::File(PathBuf::from("/shared/vc.lease"))
+            );
+            assert_eq!(leader_election.holder_id, "vc-a");
+            assert_eq!(leader_election.lease_duration, DEFAULT_LEASE_DURATION);
+            assert_eq!(leader_election.takeover_epochs, 1);
+        });
+}
+#[test]
+fn leader_election_url_flag() {
+    CommandLineTest::new()
+        .flag("leader-election-url", Some("http://localhost:9000"))
+        .flag("leader-election-lease-duration", Some("24"))
+        .flag("leader-election-takeover-epochs", Some("2"))
+        .run()
+        .with_config(|config| {
+            let leader_election = config.leader_election.as_ref().unwrap();
+            assert_eq!(
+                leader_election.backend,
+                LeaseBackendConfig::Http(SensitiveUrl::parse("http://localhost:9000").unwrap())
+            );
+            assert_eq!(leader_election.lease_duration, Duration::from_secs(24));
+            assert_eq!(leader_election.takeover_epochs, 2);
+        });
+}
+#[test]
+#[should_panic]
+fn leader_election_zero_takeover_epochs_flag() {
+    CommandLineTest::new()
+        .flag("leader-election-lockfile", Some("/shared/vc.lease"))
+        .flag("leader-election-takeover-epochs", Some("0"))
+        .run();
+}
+
 #[test]
 fn no_gas_limit_flag() {
     CommandLineTest::new()
@@ -661,6 +728,41 @@ fn validator_web3_signer_keep_alive_override() {
         });
 }
 
+#[test]
+fn validator_web3_signer_batch_window_default() {
+    CommandLineTest::new().run().with_config(|config| {
+        assert_eq!(config.initialized_validators.web3_signer_batch_window, None);
+        assert_eq!(
+            config.initialized_validators.web3_signer_max_batch_size,
+            128
+        );
+        assert_eq!(config.initialized_validators.web3_signer_batch_path, None);
+    });
+}
+
+#[test]
+fn validator_web3_signer_batch_window_override() {
+    CommandLineTest::new()
+        .flag("web3-signer-batch-window", Some("5"))
+        .flag("web3-signer-max-batch-size", Some("64"))
+        .flag("web3-signer-batch-path", Some("api/v1/eth2/sign/batch"))
+        .run()
+        .with_config(|config| {
+            assert_eq!(
+                config.initialized_validators.web3_signer_batch_window,
+                Some(Duration::from_millis(5))
+            );
+            assert_eq!(config.initialized_validators.web3_signer_max_batch_size, 64);
+            assert_eq!(
+                config
+                    .initialized_validators
+                    .web3_signer_batch_path
+                    .as_deref(),
+                Some("api/v1/eth2/sign/batch")
+            );
+        });
+}
+
 #[test]
 fn validator_proposer_nodes_default_empty() {
     CommandLineTest::new().run().with_config(|config| {
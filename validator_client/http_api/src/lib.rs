@@ -659,6 +659,7 @@ pub fn serve<T: 'static + SlotClock + Clone, E: EthSpec>(
                                 signing_definition: SigningDefinition::Web3Signer(
                                     Web3SignerDefinition {
                                         url: web3signer.url,
+                                        fallback_urls: vec![],
                                         root_certificate_path: web3signer.root_certificate_path,
                                         request_timeout_ms: web3signer.request_timeout_ms,
                                         client_identity_path: web3signer.client_identity_path,
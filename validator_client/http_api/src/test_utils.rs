@@ -109,6 +109,7 @@ impl ApiTester {
             Hash256::repeat_byte(42),
             spec.clone(),
             Some(Arc::new(DoppelgangerService::new(log.clone()))),
+            None,
             slot_clock.clone(),
             &config,
             test_runtime.task_executor.clone(),
@@ -22,7 +22,9 @@ use metrics::set_gauge;
 use parking_lot::{MappedMutexGuard, Mutex, MutexGuard};
 use reqwest::{Certificate, Client, Error as ReqwestError, Identity};
 use serde::{Deserialize, Serialize};
-use signing_method::SigningMethod;
+use signing_method::{
+    BatchConfig, SigningMethod, Web3SignerEndpoint, Web3SignerRoute, DEFAULT_MAX_BATCH_SIZE,
+};
 use slog::{debug, error, info, warn, Logger};
 use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
@@ -53,6 +55,12 @@ pub const DEFAULT_WEB3SIGNER_KEEP_ALIVE: Option<Duration> = Some(Duration::from_
 pub struct Config {
     pub web3_signer_keep_alive_timeout: Option<Duration>,
     pub web3_signer_max_idle_connections: Option<usize>,
+    /// If `Some`, coalesce Web3Signer requests which arrive within this window into batches.
+    pub web3_signer_batch_window: Option<Duration>,
+    /// The maximum number of requests in a single Web3Signer batch.
+    pub web3_signer_max_batch_size: usize,
+    /// The path of the batch signing endpoint of Web3Signer servers, if they provide one.
+    pub web3_signer_batch_path: Option<String>,
 }
 
 impl Default for Config {
@@ -60,10 +68,24 @@ impl Default for Config {
         Config {
             web3_signer_keep_alive_timeout: DEFAULT_WEB3SIGNER_KEEP_ALIVE,
             web3_signer_max_idle_connections: None,
+            web3_signer_batch_window: None,
+            web3_signer_max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            web3_signer_batch_path: None,
         }
     }
 }
 
+impl Config {
+    /// Returns the batching configuration for Web3Signer endpoints, if batching is enabled.
+    pub fn web3_signer_batch_config(&self) -> Option<BatchConfig> {
+        self.web3_signer_batch_window.map(|window| BatchConfig {
+            window,
+            max_batch_size: self.web3_signer_max_batch_size,
+            batch_path: self.web3_signer_batch_path.clone(),
+        })
+    }
+}
+
 pub enum OnDecryptFailure {
     /// If the key cache fails to decrypt, create a new cache.
     CreateNew,
@@ -227,7 +249,9 @@ impl InitializedValidator {
         def: ValidatorDefinition,
         key_cache: &mut KeyCache,
         key_stores: &mut HashMap<PathBuf, Keystore>,
-        web3_signer_client_map: &mut Option<HashMap<Web3SignerDefinition, Client>>,
+        web3_signer_endpoint_map: &mut Option<
+            HashMap<Web3SignerDefinition, Vec<Arc<Web3SignerEndpoint>>>,
+        >,
         config: &Config,
     ) -> Result<Self, Error> {
         if !def.enabled {
@@ -314,51 +338,58 @@ impl InitializedValidator {
                 }
             }
             SigningDefinition::Web3Signer(web3_signer) => {
-                let signing_url = build_web3_signer_url(&web3_signer.url, &def.voting_public_key)
-                    .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
-
                 let request_timeout = web3_signer
                     .request_timeout_ms
                     .map(Duration::from_millis)
                     .unwrap_or(DEFAULT_REMOTE_SIGNER_REQUEST_TIMEOUT);
 
-                // Check if a client has already been initialized for this remote signer url.
-                let http_client = if let Some(client_map) = web3_signer_client_map {
-                    match client_map.get(&web3_signer) {
-                        Some(client) => client.clone(),
-                        None => {
-                            let client = build_web3_signer_client(
-                                web3_signer.root_certificate_path.clone(),
-                                web3_signer.client_identity_path.clone(),
-                                web3_signer.client_identity_password.clone(),
-                                request_timeout,
-                                config.web3_signer_keep_alive_timeout,
-                                config.web3_signer_max_idle_connections,
-                            )?;
-                            client_map.insert(web3_signer, client.clone());
-                            client
-                        }
+                // Check if the endpoints have already been initialized for this remote signer
+                // definition. Sharing endpoints between validators allows their requests to share
+                // connections and be coalesced into batches.
+                let endpoint_map = web3_signer_endpoint_map.get_or_insert_with(HashMap::new);
+                let endpoints = match endpoint_map.get(&web3_signer) {
+                    Some(endpoints) => endpoints.clone(),
+                    None => {
+                        let client = build_web3_signer_client(
+                            web3_signer.root_certificate_path.clone(),
+                            web3_signer.client_identity_path.clone(),
+                            web3_signer.client_identity_password.clone(),
+                            request_timeout,
+                            config.web3_signer_keep_alive_timeout,
+                            config.web3_signer_max_idle_connections,
+                        )?;
+                        let endpoints = std::iter::once(&web3_signer.url)
+                            .chain(web3_signer.fallback_urls.iter())
+                            .map(|url| {
+                                let base_url = Url::parse(url)
+                                    .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+                                Ok(Arc::new(Web3SignerEndpoint::new(
+                                    base_url,
+                                    client.clone(),
+                                    config.web3_signer_batch_config(),
+                                )))
+                            })
+                            .collect::<Result<Vec<_>, Error>>()?;
+                        endpoint_map.insert(web3_signer, endpoints.clone());
+                        endpoints
                     }
-                } else {
-                    // There are no clients in the map.
-                    let mut new_web3_signer_client_map: HashMap<Web3SignerDefinition, Client> =
-                        HashMap::new();
-                    let client = build_web3_signer_client(
-                        web3_signer.root_certificate_path.clone(),
-                        web3_signer.client_identity_path.clone(),
-                        web3_signer.client_identity_password.clone(),
-                        request_timeout,
-                        config.web3_signer_keep_alive_timeout,
-                        config.web3_signer_max_idle_connections,
-                    )?;
-                    new_web3_signer_client_map.insert(web3_signer, client.clone());
-                    *web3_signer_client_map = Some(new_web3_signer_client_map);
-                    client
                 };
 
+                let signers = endpoints
+                    .into_iter()
+                    .map(|endpoint| {
+                        let signing_url =
+                            build_web3_signer_url(endpoint.base_url(), &def.voting_public_key)
+                                .map_err(|e| Error::InvalidWeb3SignerUrl(e.to_string()))?;
+                        Ok(Web3SignerRoute {
+                            signing_url,
+                            endpoint,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+
                 SigningMethod::Web3Signer {
-                    signing_url,
-                    http_client,
+                    signers,
                     voting_public_key: def.voting_public_key,
                 }
             }
@@ -409,8 +440,8 @@ pub fn load_pkcs12_identity<P: AsRef<Path>>(
         .map_err(Error::InvalidWeb3SignerClientIdentityCertificate)
 }
 
-fn build_web3_signer_url(base_url: &str, voting_public_key: &PublicKey) -> Result<Url, ParseError> {
-    Url::parse(base_url)?.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
+fn build_web3_signer_url(base_url: &Url, voting_public_key: &PublicKey) -> Result<Url, ParseError> {
+    base_url.join(&format!("api/v1/eth2/sign/{}", voting_public_key))
 }
 
 fn build_web3_signer_client(
@@ -501,8 +532,8 @@ pub struct InitializedValidators {
     validators_dir: PathBuf,
     /// The canonical set of validators.
     validators: HashMap<PublicKeyBytes, InitializedValidator>,
-    /// The clients used for communications with a remote signer.
-    web3_signer_client_map: Option<HashMap<Web3SignerDefinition, Client>>,
+    /// The endpoints used for communications with a remote signer.
+    web3_signer_endpoint_map: Option<HashMap<Web3SignerDefinition, Vec<Arc<Web3SignerEndpoint>>>>,
     /// For logging via `slog`.
     log: Logger,
     config: Config,
@@ -520,7 +551,7 @@ impl InitializedValidators {
             validators_dir,
             definitions,
             validators: HashMap::default(),
-            web3_signer_client_map: None,
+            web3_signer_endpoint_map: None,
             config,
             log,
         };
@@ -1317,7 +1348,7 @@ impl InitializedValidators {
                             def.clone(),
                             &mut key_cache,
                             &mut key_stores,
-                            &mut self.web3_signer_client_map,
+                            &mut self.web3_signer_endpoint_map,
                             &self.config,
                         )
                         .await
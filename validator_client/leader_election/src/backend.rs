@@ -0,0 +1,217 @@
+//! Storage backends for the leadership lease.
+//!
+//! Both backends implement the same compare-and-swap style operation: the lease is granted to the
+//! caller if it is vacant, expired or already held by the caller, otherwise the current holder is
+//! returned untouched.
+
+use lockfile::{Lockfile, LockfileError};
+use reqwest::{Client, StatusCode};
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// The path appended to the lease server URL.
+pub const HTTP_LEASE_PATH: &str = "lease";
+
+/// The maximum time to wait for a response from the lease server.
+pub const HTTP_LEASE_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug)]
+pub enum Error {
+    /// Another process on this host is currently reading or writing the lease file.
+    LeaseFileBusy,
+    LockfileError(LockfileError),
+    UnableToReadLease(io::Error),
+    UnableToWriteLease(io::Error),
+    InvalidLease(String),
+    HttpClient(reqwest::Error),
+    InvalidUrl(String),
+    UnexpectedStatus(StatusCode),
+}
+
+/// A lease on the right to sign, as stored in the lease file or returned by the lease server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Lease {
+    /// The identifier of the validator client holding the lease.
+    pub holder: String,
+    /// The time at which the lease expires, in milliseconds since the UNIX epoch.
+    pub expires_at_millis: u64,
+}
+
+impl Lease {
+    pub fn expires_at(&self) -> Duration {
+        Duration::from_millis(self.expires_at_millis)
+    }
+
+    pub fn is_expired(&self, now: Duration) -> bool {
+        self.expires_at() <= now
+    }
+}
+
+/// The result of an attempt to acquire or renew the lease.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LeaseOutcome {
+    /// The lease is held by us until the contained expiry.
+    Acquired(Lease),
+    /// The lease is held by another validator client.
+    HeldByOther(Lease),
+}
+
+/// The body of a request sent to the lease server.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LeaseRequest {
+    pub holder: String,
+    pub duration_millis: u64,
+}
+
+/// A lease which is stored in a file that is shared between all validator clients, e.g. on a
+/// network file system.
+///
+/// The lease file is replaced atomically (write-then-rename) and is re-read after every write to
+/// detect a concurrent writer which won the race.
+pub struct FileLeaseBackend {
+    path: PathBuf,
+}
+
+impl FileLeaseBackend {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    fn guard_path(&self) -> PathBuf {
+        self.path.with_extension("guard")
+    }
+
+    fn tmp_path(&self) -> PathBuf {
+        self.path.with_extension("tmp")
+    }
+
+    fn read_lease(&self) -> Result<Option<Lease>, Error> {
+        match fs::read(&self.path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| Error::InvalidLease(e.to_string())),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(Error::UnableToReadLease(e)),
+        }
+    }
+
+    fn write_lease(&self, lease: &Lease) -> Result<(), Error> {
+        let bytes = serde_json::to_vec(lease).map_err(|e| Error::InvalidLease(e.to_string()))?;
+        let tmp_path = self.tmp_path();
+        fs::write(&tmp_path, bytes).map_err(Error::UnableToWriteLease)?;
+        fs::rename(&tmp_path, &self.path).map_err(Error::UnableToWriteLease)
+    }
+
+    pub fn try_acquire(
+        &self,
+        holder: &str,
+        lease_duration: Duration,
+        now: Duration,
+    ) -> Result<LeaseOutcome, Error> {
+        // Serialize access between processes on the same host. Processes on other hosts are
+        // detected by re-reading the lease after writing it.
+        let _guard = Lockfile::new(self.guard_path()).map_err(|e| match e {
+            LockfileError::FileLocked(..) => Error::LeaseFileBusy,
+            e => Error::LockfileError(e),
+        })?;
+
+        if let Some(existing) = self.read_lease()? {
+            if existing.holder != holder && !existing.is_expired(now) {
+                return Ok(LeaseOutcome::HeldByOther(existing));
+            }
+        }
+
+        let lease = Lease {
+            holder: holder.to_string(),
+            expires_at_millis: (now + lease_duration).as_millis() as u64,
+        };
+        self.write_lease(&lease)?;
+
+        match self.read_lease()? {
+            Some(stored) if stored == lease => Ok(LeaseOutcome::Acquired(lease)),
+            Some(stored) => Ok(LeaseOutcome::HeldByOther(stored)),
+            None => Err(Error::InvalidLease(
+                "lease file vanished after write".into(),
+            )),
+        }
+    }
+}
+
+/// A lease which is granted by a small HTTP lease server.
+///
+/// The server is expected to accept a `POST` of a JSON `LeaseRequest` to `/lease` and respond with
+/// the JSON `Lease` that is current *after* the request has been processed. The lease has been
+/// granted to us if and only if the returned `holder` is our own identifier.
+pub struct HttpLeaseBackend {
+    client: Client,
+    url: SensitiveUrl,
+}
+
+impl HttpLeaseBackend {
+    pub fn new(url: SensitiveUrl) -> Result<Self, Error> {
+        let client = Client::builder()
+            .timeout(HTTP_LEASE_TIMEOUT)
+            .build()
+            .map_err(Error::HttpClient)?;
+        Ok(Self { client, url })
+    }
+
+    pub async fn try_acquire(
+        &self,
+        holder: &str,
+        lease_duration: Duration,
+    ) -> Result<LeaseOutcome, Error> {
+        let mut url = self.url.full.clone();
+        url.path_segments_mut()
+            .map_err(|()| Error::InvalidUrl(self.url.to_string()))?
+            .pop_if_empty()
+            .push(HTTP_LEASE_PATH);
+
+        let response = self
+            .client
+            .post(url)
+            .json(&LeaseRequest {
+                holder: holder.to_string(),
+                duration_millis: lease_duration.as_millis() as u64,
+            })
+            .send()
+            .await
+            .map_err(Error::HttpClient)?;
+
+        let status = response.status();
+        if !status.is_success() && status != StatusCode::CONFLICT {
+            return Err(Error::UnexpectedStatus(status));
+        }
+
+        let lease: Lease = response.json().await.map_err(Error::HttpClient)?;
+        if lease.holder == holder {
+            Ok(LeaseOutcome::Acquired(lease))
+        } else {
+            Ok(LeaseOutcome::HeldByOther(lease))
+        }
+    }
+}
+
+/// Dispatches to one of the supported lease backends.
+pub enum LeaseBackend {
+    File(FileLeaseBackend),
+    Http(HttpLeaseBackend),
+}
+
+impl LeaseBackend {
+    pub async fn try_acquire(
+        &self,
+        holder: &str,
+        lease_duration: Duration,
+        now: Duration,
+    ) -> Result<LeaseOutcome, Error> {
+        match self {
+            LeaseBackend::File(backend) => backend.try_acquire(holder, lease_duration, now),
+            LeaseBackend::Http(backend) => backend.try_acquire(holder, lease_duration).await,
+        }
+    }
+}
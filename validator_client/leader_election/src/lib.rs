@@ -0,0 +1,461 @@
+//! The "leader election" service allows two or more validator clients to be configured with the
+//! same validator keys in an active-passive arrangement.
+//!
+//! Each validator client repeatedly attempts to acquire (or renew) a short-lived lease which is
+//! stored either in a shared lease file or by a small HTTP lease server. Only the holder of the
+//! lease is permitted to sign messages; all other validator clients remain on standby and refuse
+//! every signing request in the `ValidatorStore`.
+//!
+//! When a standby validator client acquires a lease that was previously held by another instance
+//! (or after a restart), it waits for a *takeover delay* of at least one epoch before it begins
+//! signing. This provides a safety margin for any messages that the previous leader may have
+//! signed but not yet published before it lost the lease.
+//!
+//! The leader stops signing as soon as its own lease expires, even if it is unable to contact the
+//! lease backend. Standby instances may only acquire the lease once it has expired, so the two
+//! instances should never sign at the same time provided their clocks are reasonably in sync.
+//!
+//! ## Disclaimer
+//!
+//! Leader election does not replace slashing protection. Each validator client still maintains
+//! its own slashing protection database, which will not contain the messages signed by the other
+//! instance.
+
+mod backend;
+
+pub use backend::{
+    Error as BackendError, FileLeaseBackend, HttpLeaseBackend, Lease, LeaseBackend, LeaseOutcome,
+    LeaseRequest, HTTP_LEASE_PATH,
+};
+
+use environment::RuntimeContext;
+use parking_lot::RwLock;
+use sensitive_url::SensitiveUrl;
+use serde::{Deserialize, Serialize};
+use slog::{crit, error, info, warn, Logger};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::time::sleep;
+use types::EthSpec;
+
+/// The default duration of a lease. The leader renews its lease three times per lease duration.
+pub const DEFAULT_LEASE_DURATION: Duration = Duration::from_secs(36);
+
+/// The default number of epochs to wait after acquiring the lease before signing any messages.
+pub const DEFAULT_TAKEOVER_EPOCHS: u64 = 1;
+
+/// The number of lease renewal attempts per lease duration.
+const RENEWALS_PER_LEASE: u32 = 3;
+
+/// Where the lease is stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum LeaseBackendConfig {
+    /// A lease file shared between all validator clients.
+    File(PathBuf),
+    /// A lease server which grants leases via HTTP.
+    Http(SensitiveUrl),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Config {
+    /// The storage for the lease.
+    pub backend: LeaseBackendConfig,
+    /// An identifier which is unique to this validator client.
+    pub holder_id: String,
+    /// The duration for which each lease is valid.
+    pub lease_duration: Duration,
+    /// The number of epochs to wait after acquiring the lease before signing any messages.
+    pub takeover_epochs: u64,
+}
+
+/// The leadership status of this validator client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeaderStatus {
+    /// Another validator client holds the lease, or the lease has expired. Do not sign anything.
+    Standby,
+    /// We hold the lease, but are waiting for the takeover delay to elapse before signing.
+    AwaitingTakeover {
+        /// The time (since the UNIX epoch) at which signing will be permitted.
+        signing_from: Duration,
+    },
+    /// We hold the lease and may sign messages.
+    Leader,
+}
+
+impl LeaderStatus {
+    /// Returns `true` if signing is permitted in this state.
+    pub fn is_leader(&self) -> bool {
+        matches!(self, LeaderStatus::Leader)
+    }
+
+    /// Returns the value of this status for the `vc_leader_election_status` metric.
+    pub fn metric_value(&self) -> i64 {
+        match self {
+            LeaderStatus::Standby => 0,
+            LeaderStatus::AwaitingTakeover { .. } => 1,
+            LeaderStatus::Leader => 2,
+        }
+    }
+}
+
+/// The lease currently held by this validator client.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct HeldLease {
+    /// The expiry of the most recent lease granted to us.
+    expires_at: Duration,
+    /// The earliest time at which we may sign messages.
+    signing_from: Duration,
+}
+
+pub struct LeaderElectionService {
+    backend: LeaseBackend,
+    holder_id: String,
+    lease_duration: Duration,
+    takeover_delay: Duration,
+    held_lease: RwLock<Option<HeldLease>>,
+    log: Logger,
+}
+
+impl LeaderElectionService {
+    /// Creates a new service from `config`.
+    ///
+    /// The takeover delay is computed as `config.takeover_epochs * epoch_duration` and is never
+    /// less than one epoch.
+    pub fn new(config: &Config, epoch_duration: Duration, log: Logger) -> Result<Self, String> {
+        if config.holder_id.is_empty() {
+            return Err("Leader election holder ID must not be empty".to_string());
+        }
+        if config.lease_duration.is_zero() {
+            return Err("Leader election lease duration must be non-zero".to_string());
+        }
+
+        let backend = match &config.backend {
+            LeaseBackendConfig::File(path) => {
+                LeaseBackend::File(FileLeaseBackend::new(path.clone()))
+            }
+            LeaseBackendConfig::Http(url) => LeaseBackend::Http(
+                HttpLeaseBackend::new(url.clone())
+                    .map_err(|e| format!("Unable to create lease server client: {:?}", e))?,
+            ),
+        };
+
+        Ok(Self {
+            backend,
+            holder_id: config.holder_id.clone(),
+            lease_duration: config.lease_duration,
+            takeover_delay: epoch_duration * std::cmp::max(config.takeover_epochs, 1) as u32,
+            held_lease: RwLock::new(None),
+            log,
+        })
+    }
+
+    /// Returns the leadership status of this validator client at time `now` (since the UNIX
+    /// epoch).
+    pub fn status_at(&self, now: Duration) -> LeaderStatus {
+        match *self.held_lease.read() {
+            Some(held) if now < held.expires_at => {
+                if now >= held.signing_from {
+                    LeaderStatus::Leader
+                } else {
+                    LeaderStatus::AwaitingTakeover {
+                        signing_from: held.signing_from,
+                    }
+                }
+            }
+            _ => LeaderStatus::Standby,
+        }
+    }
+
+    /// Returns the current leadership status of this validator client.
+    pub fn status(&self) -> LeaderStatus {
+        unix_now()
+            .map(|now| self.status_at(now))
+            .unwrap_or(LeaderStatus::Standby)
+    }
+
+    /// Returns `true` if this validator client currently holds the lease and has waited out the
+    /// takeover delay.
+    pub fn allows_signing(&self) -> bool {
+        self.status().is_leader()
+    }
+
+    /// Updates the held lease following an attempt to acquire it at time `now`.
+    fn process_outcome(&self, outcome: Result<LeaseOutcome, BackendError>, now: Duration) {
+        let previous_status = self.status_at(now);
+
+        match outcome {
+            Ok(LeaseOutcome::Acquired(lease)) => {
+                let mut held_lease = self.held_lease.write();
+                let signing_from = match *held_lease {
+                    // Our previous lease is still valid, this is a renewal.
+                    Some(held) if now < held.expires_at => held.signing_from,
+                    // We are taking over from another validator client (or from a previous run of
+                    // this one). Wait for the takeover delay before signing.
+                    _ => {
+                        info!(
+                            self.log,
+                            "Acquired leadership lease";
+                            "signing_delay_secs" => self.takeover_delay.as_secs(),
+                            "holder_id" => &self.holder_id,
+                        );
+                        now + self.takeover_delay
+                    }
+                };
+                *held_lease = Some(HeldLease {
+                    expires_at: lease.expires_at(),
+                    signing_from,
+                });
+            }
+            Ok(LeaseOutcome::HeldByOther(lease)) => {
+                if self.held_lease.write().take().is_some() && previous_status.is_leader() {
+                    crit!(
+                        self.log,
+                        "Lost leadership lease";
+                        "msg" => "signing is disabled until the lease is reacquired",
+                        "new_holder" => lease.holder,
+                    );
+                }
+            }
+            Err(e) => {
+                // Keep the current lease. It will expire naturally if we are unable to renew it.
+                error!(
+                    self.log,
+                    "Unable to renew leadership lease";
+                    "error" => ?e,
+                );
+            }
+        }
+
+        let new_status = self.status_at(now);
+        if previous_status.is_leader() && !new_status.is_leader() {
+            warn!(
+                self.log,
+                "Validator client is no longer the leader";
+                "status" => ?new_status,
+            );
+        } else if !previous_status.is_leader() && new_status.is_leader() {
+            info!(
+                self.log,
+                "Validator client is now the leader";
+                "holder_id" => &self.holder_id,
+            );
+        }
+
+        validator_metrics::set_gauge(
+            &validator_metrics::LEADER_ELECTION_STATUS,
+            new_status.metric_value(),
+        );
+    }
+
+    /// Starts a reoccurring future which will attempt to acquire or renew the lease.
+    pub fn start_update_service<E: EthSpec>(
+        service: Arc<Self>,
+        context: RuntimeContext<E>,
+    ) -> Result<(), String> {
+        let renewal_interval = service.lease_duration / RENEWALS_PER_LEASE;
+
+        info!(
+            service.log,
+            "Leader election service started";
+            "holder_id" => &service.holder_id,
+            "lease_duration_secs" => service.lease_duration.as_secs(),
+            "takeover_delay_secs" => service.takeover_delay.as_secs(),
+        );
+
+        context.executor.spawn(
+            async move {
+                loop {
+                    match unix_now() {
+                        Some(now) => {
+                            let outcome = service
+                                .backend
+                                .try_acquire(&service.holder_id, service.lease_duration, now)
+                                .await;
+                            let label = match &outcome {
+                                Ok(LeaseOutcome::Acquired(_)) => "acquired",
+                                Ok(LeaseOutcome::HeldByOther(_)) => "held_by_other",
+                                Err(_) => "error",
+                            };
+                            validator_metrics::inc_counter_vec(
+                                &validator_metrics::LEADER_ELECTION_LEASE_ATTEMPTS,
+                                &[label],
+                            );
+                            service.process_outcome(outcome, now);
+                        }
+                        None => {
+                            error!(
+                                service.log,
+                                "Unable to read system time for leader election";
+                            );
+                        }
+                    }
+
+                    sleep(renewal_interval).await;
+                }
+            },
+            "leader_election",
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the current time since the UNIX epoch, or `None` if the system clock is before it.
+fn unix_now() -> Option<Duration> {
+    SystemTime::now().duration_since(UNIX_EPOCH).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use logging::test_logger;
+    use tempfile::tempdir;
+
+    const EPOCH: Duration = Duration::from_secs(384);
+    const LEASE: Duration = Duration::from_secs(36);
+
+    fn service(path: PathBuf, holder_id: &str) -> LeaderElectionService {
+        let config = Config {
+            backend: LeaseBackendConfig::File(path),
+            holder_id: holder_id.to_string(),
+            lease_duration: LEASE,
+            takeover_epochs: DEFAULT_TAKEOVER_EPOCHS,
+        };
+        LeaderElectionService::new(&config, EPOCH, test_logger()).unwrap()
+    }
+
+    fn acquire(service: &LeaderElectionService, now: Duration) {
+        let outcome = match &service.backend {
+            LeaseBackend::File(backend) => {
+                backend.try_acquire(&service.holder_id, service.lease_duration, now)
+            }
+            LeaseBackend::Http(_) => panic!("tests only use file backends"),
+        };
+        service.process_outcome(outcome, now);
+    }
+
+    #[test]
+    fn standby_before_first_acquisition() {
+        let dir = tempdir().unwrap();
+        let a = service(dir.path().join("lease"), "a");
+        assert_eq!(
+            a.status_at(Duration::from_secs(1_000)),
+            LeaderStatus::Standby
+        );
+    }
+
+    #[test]
+    fn waits_for_takeover_delay() {
+        let dir = tempdir().unwrap();
+        let a = service(dir.path().join("lease"), "a");
+        let start = Duration::from_secs(1_000);
+
+        acquire(&a, start);
+        assert_eq!(
+            a.status_at(start),
+            LeaderStatus::AwaitingTakeover {
+                signing_from: start + EPOCH
+            }
+        );
+
+        // Keep renewing until the takeover delay has elapsed.
+        let mut now = start;
+        while now < start + EPOCH {
+            now += LEASE / RENEWALS_PER_LEASE;
+            acquire(&a, now);
+        }
+        assert_eq!(a.status_at(now), LeaderStatus::Leader);
+    }
+
+    #[test]
+    fn standby_cannot_acquire_valid_lease() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lease");
+        let a = service(path.clone(), "a");
+        let b = service(path, "b");
+        let now = Duration::from_secs(1_000);
+
+        acquire(&a, now);
+        acquire(&b, now + Duration::from_secs(1));
+        assert_eq!(
+            b.status_at(now + Duration::from_secs(1)),
+            LeaderStatus::Standby
+        );
+        assert!(matches!(
+            a.status_at(now + Duration::from_secs(1)),
+            LeaderStatus::AwaitingTakeover { .. }
+        ));
+    }
+
+    #[test]
+    fn leader_stops_signing_when_lease_expires() {
+        let dir = tempdir().unwrap();
+        let a = service(dir.path().join("lease"), "a");
+        let now = Duration::from_secs(1_000);
+
+        acquire(&a, now);
+        assert_ne!(a.status_at(now), LeaderStatus::Standby);
+        assert_eq!(a.status_at(now + LEASE), LeaderStatus::Standby);
+    }
+
+    #[test]
+    fn failover_after_expiry() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("lease");
+        let a = service(path.clone(), "a");
+        let b = service(path, "b");
+        let now = Duration::from_secs(1_000);
+
+        acquire(&a, now);
+
+        // `a` stops renewing. `b` can take over once the lease has expired, but must wait for the
+        // takeover delay before signing.
+        let takeover = now + LEASE;
+        acquire(&b, takeover);
+        assert_eq!(
+            b.status_at(takeover),
+            LeaderStatus::AwaitingTakeover {
+                signing_from: takeover + EPOCH
+            }
+        );
+
+        // `a` comes back and discovers it has lost the lease.
+        acquire(&a, takeover + Duration::from_secs(1));
+        assert_eq!(
+            a.status_at(takeover + Duration::from_secs(1)),
+            LeaderStatus::Standby
+        );
+    }
+
+    #[test]
+    fn backend_errors_retain_lease_until_expiry() {
+        let dir = tempdir().unwrap();
+        let a = service(dir.path().join("lease"), "a");
+        let now = Duration::from_secs(1_000);
+
+        acquire(&a, now);
+        a.process_outcome(
+            Err(BackendError::LeaseFileBusy),
+            now + Duration::from_secs(1),
+        );
+        assert_ne!(
+            a.status_at(now + Duration::from_secs(1)),
+            LeaderStatus::Standby
+        );
+        assert_eq!(a.status_at(now + LEASE), LeaderStatus::Standby);
+    }
+
+    #[test]
+    fn takeover_epochs_are_at_least_one() {
+        let dir = tempdir().unwrap();
+        let config = Config {
+            backend: LeaseBackendConfig::File(dir.path().join("lease")),
+            holder_id: "a".to_string(),
+            lease_duration: LEASE,
+            takeover_epochs: 0,
+        };
+        let a = LeaderElectionService::new(&config, EPOCH, test_logger()).unwrap();
+        assert_eq!(a.takeover_delay, EPOCH);
+    }
+}
@@ -6,19 +6,21 @@
 use eth2_keystore::Keystore;
 use lockfile::Lockfile;
 use parking_lot::Mutex;
-use reqwest::{header::ACCEPT, Client};
 use std::path::PathBuf;
 use std::sync::Arc;
 use task_executor::TaskExecutor;
 use types::*;
-use url::Url;
-use web3signer::{ForkInfo, SigningRequest, SigningResponse};
+use web3signer::{ForkInfo, SigningRequest};
 
 pub use web3signer::Web3SignerObject;
+pub use web3signer_client::{
+    BatchConfig, Web3SignerEndpoint, Web3SignerRoute, DEFAULT_MAX_BATCH_SIZE,
+};
 
 mod web3signer;
+mod web3signer_client;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum Error {
     InconsistentDomains {
         message_type_domain: Domain,
@@ -88,8 +90,8 @@ pub enum SigningMethod {
     ///
     /// See: https://docs.web3signer.consensys.net/en/latest/
     Web3Signer {
-        signing_url: Url,
-        http_client: Client,
+        /// The Web3Signer servers which can sign for this validator, in order of preference.
+        signers: Vec<Web3SignerRoute>,
         voting_public_key: PublicKey,
     },
 }
@@ -184,9 +186,8 @@ impl SigningMethod {
                 Ok(signature)
             }
             SigningMethod::Web3Signer {
-                signing_url,
-                http_client,
-                ..
+                signers,
+                voting_public_key,
             } => {
                 let _timer = validator_metrics::start_timer_vec(
                     &validator_metrics::SIGNING_TIMES,
@@ -243,21 +244,17 @@ impl SigningMethod {
                     object,
                 };
 
-                // Request a signature from the Web3Signer instance via HTTP(S).
-                let response: SigningResponse = http_client
-                    .post(signing_url.clone())
-                    .header(ACCEPT, "application/json")
-                    .json(&request)
-                    .send()
-                    .await
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .error_for_status()
-                    .map_err(|e| Error::Web3SignerRequestFailed(e.to_string()))?
-                    .json()
-                    .await
+                let request = serde_json::to_value(&request)
                     .map_err(|e| Error::Web3SignerJsonParsingFailed(e.to_string()))?;
 
-                Ok(response.signature)
+                // Request a signature from the first available Web3Signer instance via HTTP(S).
+                web3signer_client::request_signature(
+                    signers,
+                    &voting_public_key.to_string(),
+                    &request,
+                    executor,
+                )
+                .await
             }
         }
     }
@@ -125,6 +125,17 @@ pub fn cli_app() -> Command {
                         delimiter, and must not be part of the certificate path.")
                 .display_order(0)
         )
+        .arg(
+            Arg::new("beacon-nodes-api-token-file")
+                .long("beacon-nodes-api-token-file")
+                .value_name("PATH")
+                .action(ArgAction::Set)
+                .help("Path to a file containing an API token to send to the beacon nodes (and \
+                        proposer nodes) as a Bearer token. Required when the beacon nodes \
+                        restrict their HTTP API with --http-api-token-file, in which case the \
+                        token must grant the validator role.")
+                .display_order(0)
+        )
         // This overwrites the graffiti configured in the beacon node.
         .arg(
             Arg::new("graffiti")
@@ -342,6 +353,56 @@ pub fn cli_app() -> Command {
                 .help_heading(FLAG_HEADER)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("leader-election-lockfile")
+                .long("leader-election-lockfile")
+                .value_name("PATH")
+                .help("Enables active-passive leader election between validator clients which \
+                    manage the same keys, using a lease stored in a lock file at this path. The \
+                    file must be shared between all validator clients (e.g. on a network file \
+                    system). Only the validator client holding the lease will sign messages.")
+                .conflicts_with("leader-election-url")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("leader-election-url")
+                .long("leader-election-url")
+                .value_name("URL")
+                .help("Enables active-passive leader election between validator clients which \
+                    manage the same keys, using a lease granted by the HTTP lease server at this \
+                    URL. Only the validator client holding the lease will sign messages.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("leader-election-id")
+                .long("leader-election-id")
+                .value_name("ID")
+                .help("A unique identifier for this validator client when using leader election. \
+                    Defaults to a random identifier which is regenerated on every start.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("leader-election-lease-duration")
+                .long("leader-election-lease-duration")
+                .value_name("SECONDS")
+                .help("The duration of the leader election lease. The leader renews its lease \
+                    three times per duration and stops signing as soon as it expires. \
+                    [default: 36]")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("leader-election-takeover-epochs")
+                .long("leader-election-takeover-epochs")
+                .value_name("EPOCHS")
+                .help("The number of epochs to wait after acquiring the leader election lease \
+                    before signing any messages. Must be at least 1. [default: 1]")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
         .arg(
             Arg::new("builder-proposals")
                 .long("builder-proposals")
@@ -475,4 +536,37 @@ pub fn cli_app() -> Command {
                 .action(ArgAction::Set)
                 .display_order(0)
         )
+        .arg(
+            Arg::new("web3-signer-batch-window")
+                .long("web3-signer-batch-window")
+                .value_name("MILLIS")
+                .help("Coalesce web3signer signing requests which arrive within this many \
+                       milliseconds of each other. Coalesced requests are sent to the signer's \
+                       batch endpoint if --web3-signer-batch-path is set, or otherwise sent \
+                       concurrently. Disabled by default.")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("web3-signer-max-batch-size")
+                .long("web3-signer-max-batch-size")
+                .value_name("COUNT")
+                .help("Maximum number of signing requests sent to a web3signer in a single batch.")
+                .requires("web3-signer-batch-window")
+                .default_value("128")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
+        .arg(
+            Arg::new("web3-signer-batch-path")
+                .long("web3-signer-batch-path")
+                .value_name("PATH")
+                .help("The path of the batch signing endpoint of the web3signer, relative to its \
+                       URL, e.g. 'api/v1/eth2/sign/batch'. Batch signing is a Lighthouse \
+                       extension which is not part of the Web3Signer API. Without this flag, \
+                       coalesced requests are sent concurrently to the standard endpoint.")
+                .requires("web3-signer-batch-window")
+                .action(ArgAction::Set)
+                .display_order(0)
+        )
 }
@@ -8,6 +8,11 @@ use directory::{
 use eth2::types::Graffiti;
 use graffiti_file::GraffitiFile;
 use initialized_validators::Config as InitializedValidatorsConfig;
+use leader_election::{
+    Config as LeaderElectionConfig, LeaseBackendConfig, DEFAULT_LEASE_DURATION,
+    DEFAULT_TAKEOVER_EPOCHS,
+};
+use rand::Rng;
 use sensitive_url::SensitiveUrl;
 use serde::{Deserialize, Serialize};
 use slog::{info, warn, Logger};
@@ -63,6 +68,9 @@ pub struct Config {
     /// If true, enable functionality that monitors the network for attestations or proposals from
     /// any of the validators managed by this client before starting up.
     pub enable_doppelganger_protection: bool,
+    /// Configuration for active-passive leader election between validator clients sharing the
+    /// same keys. Disabled if `None`.
+    pub leader_election: Option<LeaderElectionConfig>,
     /// If true, then we publish validator specific metrics (e.g next attestation duty slot)
     /// for all our managed validators.
     /// Note: We publish validator specific metrics for low validator counts without this flag
@@ -73,6 +81,8 @@ pub struct Config {
     /// A list of custom certificates that the validator client will additionally use when
     /// connecting to a beacon node over SSL/TLS.
     pub beacon_nodes_tls_certs: Option<Vec<PathBuf>>,
+    /// A file containing the API token sent to the beacon nodes in the `Authorization` header.
+    pub beacon_nodes_api_token_file: Option<PathBuf>,
     /// Enables broadcasting of various requests (by topic) to all beacon nodes.
     pub broadcast_topics: Vec<ApiTopic>,
     /// Enables a service which attempts to measure latency between the VC and BNs.
@@ -117,8 +127,10 @@ impl Default for Config {
             beacon_node_fallback: <_>::default(),
             monitoring_api: None,
             enable_doppelganger_protection: false,
+            leader_election: None,
             enable_high_validator_count_metrics: false,
             beacon_nodes_tls_certs: None,
+            beacon_nodes_api_token_file: None,
             builder_registration_timestamp_override: None,
             broadcast_topics: vec![ApiTopic::Subscriptions],
             enable_latency_measurement_service: true,
@@ -226,6 +238,9 @@ impl Config {
             config.beacon_nodes_tls_certs = Some(tls_certs.split(',').map(PathBuf::from).collect());
         }
 
+        config.beacon_nodes_api_token_file =
+            parse_optional(cli_args, "beacon-nodes-api-token-file")?;
+
         if cli_args.get_flag("distributed") {
             config.distributed = true;
         }
@@ -269,6 +284,17 @@ impl Config {
                 .initialized_validators
                 .web3_signer_max_idle_connections = Some(n);
         }
+        if let Some(millis) = parse_optional::<u64>(cli_args, "web3-signer-batch-window")? {
+            config.initialized_validators.web3_signer_batch_window =
+                Some(Duration::from_millis(millis));
+        }
+        config.initialized_validators.web3_signer_max_batch_size =
+            parse_required(cli_args, "web3-signer-max-batch-size")?;
+        if config.initialized_validators.web3_signer_max_batch_size == 0 {
+            return Err("web3-signer-max-batch-size cannot be 0".to_string());
+        }
+        config.initialized_validators.web3_signer_batch_path =
+            parse_optional(cli_args, "web3-signer-batch-path")?;
 
         /*
          * Http API server
@@ -369,6 +395,52 @@ impl Config {
             config.enable_doppelganger_protection = true;
         }
 
+        /*
+         * Leader election
+         */
+        let leader_election_backend =
+            if let Some(path) = parse_optional::<PathBuf>(cli_args, "leader-election-lockfile")? {
+                Some(LeaseBackendConfig::File(path))
+            } else if let Some(url) = cli_args.get_one::<String>("leader-election-url") {
+                Some(LeaseBackendConfig::Http(SensitiveUrl::parse(url).map_err(
+                    |e| format!("Unable to parse leader-election-url: {:?}", e),
+                )?))
+            } else {
+                None
+            };
+
+        if let Some(backend) = leader_election_backend {
+            let holder_id = parse_optional::<String>(cli_args, "leader-election-id")?
+                .unwrap_or_else(|| format!("{:016x}", rand::thread_rng().gen::<u64>()));
+            let lease_duration = parse_optional::<u64>(cli_args, "leader-election-lease-duration")?
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_LEASE_DURATION);
+            let takeover_epochs =
+                parse_optional::<u64>(cli_args, "leader-election-takeover-epochs")?
+                    .unwrap_or(DEFAULT_TAKEOVER_EPOCHS);
+
+            if lease_duration.is_zero() {
+                return Err("leader-election-lease-duration cannot be 0".to_string());
+            }
+            if takeover_epochs == 0 {
+                return Err("leader-election-takeover-epochs cannot be 0".to_string());
+            }
+
+            info!(
+                log,
+                "Leader election enabled";
+                "holder_id" => &holder_id,
+                "backend" => ?backend,
+            );
+
+            config.leader_election = Some(LeaderElectionConfig {
+                backend,
+                holder_id,
+                lease_duration,
+                takeover_epochs,
+            });
+        }
+
         if cli_args.get_flag("builder-proposals") {
             config.validator_store.builder_proposals = true;
         }
@@ -16,11 +16,13 @@ use beacon_node_fallback::{
 };
 
 use account_utils::validator_definitions::ValidatorDefinitions;
+use account_utils::ZeroizeString;
 use clap::ArgMatches;
 use doppelganger_service::DoppelgangerService;
 use environment::RuntimeContext;
 use eth2::{reqwest::ClientBuilder, BeaconNodeHttpClient, StatusCode, Timeouts};
 use initialized_validators::Error::UnableToOpenVotingKeystore;
+use leader_election::LeaderElectionService;
 use notifier::spawn_notifier;
 use parking_lot::RwLock;
 use reqwest::Certificate;
@@ -72,6 +74,7 @@ const HTTP_GET_DEPOSIT_SNAPSHOT_QUOTIENT: u32 = 4;
 const HTTP_GET_VALIDATOR_BLOCK_TIMEOUT_QUOTIENT: u32 = 4;
 
 const DOPPELGANGER_SERVICE_NAME: &str = "doppelganger";
+const LEADER_ELECTION_SERVICE_NAME: &str = "leader_election";
 
 #[derive(Clone)]
 pub struct ProductionValidatorClient<E: EthSpec> {
@@ -81,6 +84,7 @@ pub struct ProductionValidatorClient<E: EthSpec> {
     attestation_service: AttestationService<SystemTimeSlotClock, E>,
     sync_committee_service: SyncCommitteeService<SystemTimeSlotClock, E>,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
+    leader_election_service: Option<Arc<LeaderElectionService>>,
     preparation_service: PreparationService<SystemTimeSlotClock, E>,
     validator_store: Arc<ValidatorStore<SystemTimeSlotClock, E>>,
     slot_clock: SystemTimeSlotClock,
@@ -280,6 +284,12 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             .checked_sub(1)
             .ok_or_else(|| "No beacon nodes defined.".to_string())?;
 
+        let beacon_nodes_api_token = config
+            .beacon_nodes_api_token_file
+            .as_ref()
+            .map(load_api_token)
+            .transpose()?;
+
         let beacon_node_setup = |x: (usize, &SensitiveUrl)| {
             let i = x.0;
             let url = x.1;
@@ -328,11 +338,15 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
                 Timeouts::set_all(slot_duration)
             };
 
-            Ok(BeaconNodeHttpClient::from_components(
+            let client = BeaconNodeHttpClient::from_components(
                 url.clone(),
                 beacon_node_http_client,
                 timeouts,
-            ))
+            );
+            Ok(match &beacon_nodes_api_token {
+                Some(token) => client.with_api_token(token.clone()),
+                None => client,
+            })
         };
 
         let beacon_nodes: Vec<BeaconNodeHttpClient> = config
@@ -437,12 +451,29 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             None
         };
 
+        let leader_election_service = if let Some(leader_election_config) = &config.leader_election
+        {
+            let spec = &context.eth2_config.spec;
+            let epoch_duration = Duration::from_secs(spec.seconds_per_slot * E::slots_per_epoch());
+            Some(Arc::new(LeaderElectionService::new(
+                leader_election_config,
+                epoch_duration,
+                context
+                    .service_context(LEADER_ELECTION_SERVICE_NAME.into())
+                    .log()
+                    .clone(),
+            )?))
+        } else {
+            None
+        };
+
         let validator_store = Arc::new(ValidatorStore::new(
             validators,
             slashing_protection,
             genesis_validators_root,
             context.eth2_config.spec.clone(),
             doppelganger_service.clone(),
+            leader_election_service.clone(),
             slot_clock.clone(),
             &config.validator_store,
             context.executor.clone(),
@@ -533,6 +564,7 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             attestation_service,
             sync_committee_service,
             doppelganger_service,
+            leader_election_service,
             preparation_service,
             validator_store,
             config,
@@ -587,6 +619,17 @@ impl<E: EthSpec> ProductionValidatorClient<E> {
             None
         };
 
+        // Start competing for the lease before genesis so that the takeover delay can elapse
+        // whilst we wait.
+        if let Some(leader_election_service) = self.leader_election_service.clone() {
+            LeaderElectionService::start_update_service(
+                leader_election_service,
+                self.context
+                    .service_context(LEADER_ELECTION_SERVICE_NAME.into()),
+            )
+            .map_err(|e| format!("Unable to start leader election service: {}", e))?
+        }
+
         // Wait until genesis has occurred.
         wait_for_genesis(&self.beacon_nodes, self.genesis_time, &self.context).await?;
 
@@ -835,3 +878,10 @@ pub fn load_pem_certificate<P: AsRef<Path>>(pem_path: P) -> Result<Certificate,
         .map_err(|e| format!("Unable to read certificate file: {}", e))?;
     Certificate::from_pem(&buf).map_err(|e| format!("Unable to parse certificate: {}", e))
 }
+
+/// Read the API token sent to the beacon nodes, ignoring any trailing whitespace.
+pub fn load_api_token<P: AsRef<Path>>(token_path: P) -> Result<ZeroizeString, String> {
+    let token = std::fs::read_to_string(&token_path)
+        .map_err(|e| format!("Unable to read beacon node API token file: {}", e))?;
+    Ok(ZeroizeString::from(token.trim_end().to_string()))
+}
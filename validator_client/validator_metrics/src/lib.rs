@@ -32,6 +32,8 @@ pub const ATTESTATION_SELECTION_PROOFS: &str = "attestation_selection_proofs";
 pub const SUBSCRIPTIONS: &str = "subscriptions";
 pub const LOCAL_KEYSTORE: &str = "local_keystore";
 pub const WEB3SIGNER: &str = "web3signer";
+pub const WEB3SIGNER_SINGLE: &str = "single";
+pub const WEB3SIGNER_BATCH: &str = "batch";
 
 pub use metrics::*;
 
@@ -238,6 +240,40 @@ pub static BLOCK_SIGNING_TIMES: LazyLock<Result<Histogram>> = LazyLock::new(|| {
     )
 });
 
+/*
+ * Web3Signer
+ */
+pub static WEB3SIGNER_REQUEST_TIMES: LazyLock<Result<HistogramVec>> = LazyLock::new(|| {
+    try_create_histogram_vec(
+        "vc_web3signer_request_seconds",
+        "Duration of HTTP requests to each Web3Signer, by request kind (single or batch)",
+        &["signer", "kind"],
+    )
+});
+pub static WEB3SIGNER_REQUEST_FAILURES: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "vc_web3signer_request_failures_total",
+        "Total count of requests to each Web3Signer which failed due to the signer being unavailable",
+        &["signer"],
+    )
+});
+pub static WEB3SIGNER_CIRCUIT_OPEN: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
+    try_create_int_gauge_vec(
+        "vc_web3signer_circuit_open",
+        "Set to 1 whilst the circuit breaker for a Web3Signer is open and it is being skipped",
+        &["signer"],
+    )
+});
+pub static WEB3SIGNER_BATCH_SIZE: LazyLock<Result<Histogram>> = LazyLock::new(|| {
+    try_create_histogram_with_buckets(
+        "vc_web3signer_batch_size",
+        "Number of signing requests coalesced into each Web3Signer batch",
+        Ok(vec![
+            1.0, 2.0, 4.0, 8.0, 16.0, 32.0, 64.0, 128.0, 256.0, 512.0,
+        ]),
+    )
+});
+
 pub static ATTESTATION_DUTY: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
     try_create_int_gauge_vec(
         "vc_attestation_duty_slot",
@@ -245,6 +281,22 @@ pub static ATTESTATION_DUTY: LazyLock<Result<IntGaugeVec>> = LazyLock::new(|| {
         &["validator"],
     )
 });
+/*
+ * Leader election
+ */
+pub static LEADER_ELECTION_STATUS: LazyLock<Result<IntGauge>> = LazyLock::new(|| {
+    try_create_int_gauge(
+        "vc_leader_election_status",
+        "Leadership status of this VC: 0 = standby, 1 = awaiting takeover, 2 = leader",
+    )
+});
+pub static LEADER_ELECTION_LEASE_ATTEMPTS: LazyLock<Result<IntCounterVec>> = LazyLock::new(|| {
+    try_create_int_counter_vec(
+        "vc_leader_election_lease_attempts_total",
+        "Total count of attempts to acquire or renew the leadership lease, by outcome",
+        &["outcome"],
+    )
+});
 /*
  * BN latency
  */
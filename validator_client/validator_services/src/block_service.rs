@@ -407,6 +407,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, name = "propose_block", fields(%slot))]
     async fn publish_block(
         self,
         slot: Slot,
@@ -509,6 +510,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok(())
     }
 
+    #[tracing::instrument(skip_all, name = "publish_block")]
     async fn publish_signed_block_contents(
         &self,
         signed_block: &SignedBlock<E>,
@@ -541,6 +543,7 @@ impl<T: SlotClock + 'static, E: EthSpec> BlockService<T, E> {
         Ok::<_, BlockError>(())
     }
 
+    #[tracing::instrument(skip_all, name = "produce_block")]
     async fn get_validator_block(
         beacon_node: &BeaconNodeHttpClient,
         slot: Slot,
@@ -1072,6 +1072,17 @@ async fn fill_in_selection_proofs<T: SlotClock + 'static, E: EthSpec>(
                 continue;
             };
 
+            // A standby validator client is unable to sign selection proofs. Hold on to the duties
+            // (minus any which have already passed) and compute their proofs once this validator
+            // client is permitted to sign.
+            if !duties_service
+                .validator_store
+                .leader_election_allows_signing()
+            {
+                duties_by_slot = duties_by_slot.split_off(&current_slot);
+                continue;
+            }
+
             let selection_lookahead = if duties_service.distributed {
                 SELECTION_PROOF_SLOT_LOOKAHEAD_DVT
             } else {
@@ -1097,22 +1108,30 @@ async fn fill_in_selection_proofs<T: SlotClock + 'static, E: EthSpec>(
             // Sign selection proofs (serially).
             let duty_and_proof_results = stream::iter(relevant_duties.into_values().flatten())
                 .then(|duty| async {
-                    let opt_selection_proof = make_selection_proof(
+                    let result = make_selection_proof(
                         &duty,
                         &duties_service.validator_store,
                         &duties_service.spec,
                     )
-                    .await?;
-                    Ok((duty, opt_selection_proof))
+                    .await;
+                    (duty, result)
                 })
                 .collect::<Vec<_>>()
                 .await;
 
             // Add to attesters store.
             let mut attesters = duties_service.attesters.write();
-            for result in duty_and_proof_results {
-                let (duty, selection_proof) = match result {
-                    Ok(duty_and_proof) => duty_and_proof,
+            for (duty, result) in duty_and_proof_results {
+                let selection_proof = match result {
+                    Ok(selection_proof) => selection_proof,
+                    Err(Error::FailedToProduceSelectionProof(ValidatorStoreError::NotLeader(
+                        _,
+                    ))) => {
+                        // The leadership lease was lost part-way through the batch. Retry once
+                        // signing is permitted again.
+                        duties_by_slot.entry(duty.slot).or_default().push(duty);
+                        continue;
+                    }
                     Err(Error::FailedToProduceSelectionProof(
                         ValidatorStoreError::UnknownPubkey(pubkey),
                     )) => {
@@ -1366,7 +1385,10 @@ async fn notify_block_production_service<T: SlotClock + 'static, E: EthSpec>(
 ) {
     let non_doppelganger_proposers = block_proposers
         .iter()
-        .filter(|pubkey| validator_store.doppelganger_protection_allows_signing(**pubkey))
+        .filter(|pubkey| {
+            validator_store.leader_election_allows_signing()
+                && validator_store.doppelganger_protection_allows_signing(**pubkey)
+        })
         .copied()
         .collect::<Vec<_>>();
 
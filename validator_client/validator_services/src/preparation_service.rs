@@ -411,6 +411,10 @@ impl<T: SlotClock + 'static, E: EthSpec> PreparationService<T, E> {
 
             let signed_data = if let Some(signed_data) = cached_registration_opt {
                 signed_data
+            } else if !self.validator_store.leader_election_allows_signing() {
+                // A standby validator client can't sign registrations. They will be signed once
+                // it holds the leadership lease.
+                continue;
             } else {
                 let timestamp =
                     if let Some(timestamp) = self.builder_registration_timestamp_override {
@@ -164,6 +164,31 @@ impl<E: EthSpec> SyncDutiesMap<E> {
         (pre_compute_slot, pre_compute_duties)
     }
 
+    /// Mark the aggregation proofs of `duties` as unknown from `slot` onwards, so that they are
+    /// computed by a later call to `prepare_for_aggregator_pre_compute`.
+    fn rewind_aggregator_pre_compute(
+        &self,
+        committee_period: u64,
+        duties: &[(Slot, SyncDuty)],
+        slot: Slot,
+    ) {
+        let committees = self.committees.read();
+        let Some(committee_duties) = committees.get(&committee_period) else {
+            return;
+        };
+        let validator_duties = committee_duties.validators.read();
+
+        for (_, duty) in duties {
+            if let Some(Some(validator_duty)) = validator_duties.get(&duty.validator_index) {
+                let mut pre_compute_slot =
+                    validator_duty.aggregation_duties.pre_compute_slot.write();
+                if pre_compute_slot.map_or(false, |pre_compute_slot| pre_compute_slot >= slot) {
+                    *pre_compute_slot = slot.as_u64().checked_sub(1).map(Slot::new);
+                }
+            }
+        }
+    }
+
     fn get_or_create_committee_duties<'a, 'b>(
         &'a self,
         committee_period: u64,
@@ -339,9 +364,22 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
         sync_duties.prune(current_sync_committee_period);
     }
 
+    // A standby validator client is unable to sign selection proofs, so leave them to be
+    // pre-computed once it is permitted to sign.
+    let allows_signing = duties_service
+        .validator_store
+        .leader_election_allows_signing();
+
     // Pre-compute aggregator selection proofs for the current period.
-    let (current_pre_compute_slot, new_pre_compute_duties) = sync_duties
-        .prepare_for_aggregator_pre_compute(current_sync_committee_period, current_slot, spec);
+    let (current_pre_compute_slot, new_pre_compute_duties) = if allows_signing {
+        sync_duties.prepare_for_aggregator_pre_compute(
+            current_sync_committee_period,
+            current_slot,
+            spec,
+        )
+    } else {
+        (current_slot, vec![])
+    };
 
     if !new_pre_compute_duties.is_empty() {
         let sub_duties_service = duties_service.clone();
@@ -379,10 +417,11 @@ pub async fn poll_sync_committee_duties<T: SlotClock + 'static, E: EthSpec>(
 
     // Pre-compute aggregator selection proofs for the next period.
     let aggregate_pre_compute_lookahead_slots = sync_duties.aggregation_pre_compute_slots();
-    if (current_slot + aggregate_pre_compute_lookahead_slots)
-        .epoch(E::slots_per_epoch())
-        .sync_committee_period(spec)?
-        == next_sync_committee_period
+    if allows_signing
+        && (current_slot + aggregate_pre_compute_lookahead_slots)
+            .epoch(E::slots_per_epoch())
+            .sync_committee_period(spec)?
+            == next_sync_committee_period
     {
         let (pre_compute_slot, new_pre_compute_duties) = sync_duties
             .prepare_for_aggregator_pre_compute(next_sync_committee_period, current_slot, spec);
@@ -521,6 +560,26 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
 
     // Generate selection proofs for each validator at each slot, one slot at a time.
     for slot in (current_slot.as_u64()..=pre_compute_slot.as_u64()).map(Slot::new) {
+        // Stop if this validator client has lost the leadership lease. The remaining proofs will
+        // be computed once it is permitted to sign again.
+        if !duties_service
+            .validator_store
+            .leader_election_allows_signing()
+        {
+            debug!(
+                log,
+                "Pausing sync selection proofs on standby";
+                "period" => sync_committee_period,
+                "slot" => slot,
+            );
+            duties_service.sync_duties.rewind_aggregator_pre_compute(
+                sync_committee_period,
+                pre_compute_duties,
+                slot,
+            );
+            return;
+        }
+
         let mut validator_proofs = vec![];
         for (validator_start_slot, duty) in pre_compute_duties {
             // Proofs are already known at this slot for this validator.
@@ -564,6 +623,11 @@ pub async fn fill_in_aggregation_proofs<T: SlotClock + 'static, E: EthSpec>(
                         );
                         return None;
                     }
+                    Err(ValidatorStoreError::NotLeader(_)) => {
+                        // The lease was lost part-way through this slot, the proofs will be
+                        // recomputed once it is reacquired.
+                        return None;
+                    }
                     Err(e) => {
                         warn!(
                             log,
@@ -155,6 +155,12 @@ impl<T: SlotClock + 'static, E: EthSpec> SyncCommitteeService<T, E> {
                 .checked_sub(slot_duration / 3)
                 .unwrap_or_else(|| Duration::from_secs(0));
 
+        // A standby validator client must not sign sync committee messages.
+        if !self.validator_store.leader_election_allows_signing() {
+            debug!(log, "Not signing sync committee messages on standby"; "slot" => slot);
+            return Ok(());
+        }
+
         let Some(slot_duties) = self
             .duties_service
             .sync_duties
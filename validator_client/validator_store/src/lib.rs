@@ -1,6 +1,7 @@
 use account_utils::validator_definitions::{PasswordStorage, ValidatorDefinition};
 use doppelganger_service::{DoppelgangerService, DoppelgangerStatus, DoppelgangerValidatorStore};
 use initialized_validators::InitializedValidators;
+use leader_election::LeaderElectionService;
 use parking_lot::{Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use signing_method::{Error as SigningError, SignableMessage, SigningContext, SigningMethod};
@@ -26,6 +27,7 @@ use types::{
 #[derive(Debug, PartialEq)]
 pub enum Error {
     DoppelgangerProtected(PublicKeyBytes),
+    NotLeader(PublicKeyBytes),
     UnknownToDoppelgangerService(PublicKeyBytes),
     UnknownPubkey(PublicKeyBytes),
     Slashable(NotSafe),
@@ -84,6 +86,7 @@ pub struct ValidatorStore<T, E: EthSpec> {
     spec: Arc<ChainSpec>,
     log: Logger,
     doppelganger_service: Option<Arc<DoppelgangerService>>,
+    leader_election_service: Option<Arc<LeaderElectionService>>,
     slot_clock: T,
     fee_recipient_process: Option<Address>,
     gas_limit: Option<u64>,
@@ -111,6 +114,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         genesis_validators_root: Hash256,
         spec: Arc<ChainSpec>,
         doppelganger_service: Option<Arc<DoppelgangerService>>,
+        leader_election_service: Option<Arc<LeaderElectionService>>,
         slot_clock: T,
         config: &Config,
         task_executor: TaskExecutor,
@@ -124,6 +128,7 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             spec,
             log,
             doppelganger_service,
+            leader_election_service,
             slot_clock,
             fee_recipient_process: config.fee_recipient,
             gas_limit: config.gas_limit,
@@ -155,6 +160,11 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         self.doppelganger_service.is_some()
     }
 
+    /// Returns `true` if leader election is enabled, or else `false`.
+    pub fn leader_election_enabled(&self) -> bool {
+        self.leader_election_service.is_some()
+    }
+
     pub fn initialized_validators(&self) -> Arc<RwLock<InitializedValidators>> {
         self.validators.clone()
     }
@@ -283,9 +293,16 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .cloned()
             .collect::<Vec<_>>();
 
+        // A standby validator client must not sign with any key, so report all keys as disabled
+        // for signing whilst still allowing them to be used for non-signing tasks.
+        let is_leader = self.leader_election_allows_signing();
+
         pubkeys
             .into_iter()
             .map(|pubkey| {
+                if !is_leader {
+                    return DoppelgangerStatus::SigningDisabled(pubkey);
+                }
                 self.doppelganger_service
                     .as_ref()
                     .map(|doppelganger_service| doppelganger_service.validator_status(pubkey))
@@ -335,6 +352,18 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             })
     }
 
+    /// Check if this validator client holds the leadership lease and is permitted to sign
+    /// messages for any of its validators.
+    pub fn leader_election_allows_signing(&self) -> bool {
+        self.leader_election_service
+            .as_ref()
+            // If there's no leader election service then this is the only validator client
+            // running these keys and it is always the leader.
+            .map_or(true, |leader_election_service| {
+                leader_election_service.allows_signing()
+            })
+    }
+
     pub fn num_voting_validators(&self) -> usize {
         self.validators.read().num_enabled()
     }
@@ -349,7 +378,9 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
-        if self.doppelganger_protection_allows_signing(validator_pubkey) {
+        if !self.leader_election_allows_signing() {
+            Err(Error::NotLeader(validator_pubkey))
+        } else if self.doppelganger_protection_allows_signing(validator_pubkey) {
             self.validators
                 .read()
                 .signing_method(&validator_pubkey)
@@ -362,6 +393,8 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
     /// Returns a `SigningMethod` for `validator_pubkey` regardless of that validators doppelganger
     /// protection status.
     ///
+    /// A validator client which is on standby according to leader election is still refused.
+    ///
     /// ## Warning
     ///
     /// This method should only be used for signing non-slashable messages.
@@ -369,6 +402,10 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
         &self,
         validator_pubkey: PublicKeyBytes,
     ) -> Result<Arc<SigningMethod>, Error> {
+        if !self.leader_election_allows_signing() {
+            return Err(Error::NotLeader(validator_pubkey));
+        }
+
         self.validators
             .read()
             .signing_method(&validator_pubkey)
@@ -0,0 +1,15 @@
+CREATE TABLE validator_rewards (
+    epoch_start_slot integer CHECK (epoch_start_slot % 32 = 0) REFERENCES canonical_slots(slot) ON DELETE CASCADE,
+    index integer NOT NULL REFERENCES validators(index) ON DELETE CASCADE,
+    effective_balance bigint NOT NULL,
+    source bigint NOT NULL,
+    head bigint NOT NULL,
+    target bigint NOT NULL,
+    inactivity bigint NOT NULL,
+    ideal_source bigint NOT NULL,
+    ideal_head bigint NOT NULL,
+    ideal_target bigint NOT NULL,
+    sync_committee bigint NOT NULL,
+    proposal bigint NOT NULL,
+    PRIMARY KEY(epoch_start_slot, index)
+)
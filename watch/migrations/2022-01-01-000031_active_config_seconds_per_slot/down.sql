@@ -0,0 +1 @@
+ALTER TABLE active_config DROP COLUMN seconds_per_slot
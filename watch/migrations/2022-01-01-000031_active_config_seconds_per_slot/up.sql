@@ -0,0 +1 @@
+ALTER TABLE active_config ADD COLUMN seconds_per_slot integer
@@ -4,6 +4,7 @@ use crate::database::models::{
     WatchBeaconBlock, WatchCanonicalSlot, WatchProposerInfo, WatchValidator,
 };
 use crate::suboptimal_attestations::WatchAttestation;
+use crate::validator_rewards::{server::WatchRewardsApr, WatchValidatorRewards};
 
 use eth2::types::BlockId;
 use reqwest::Client;
@@ -175,4 +176,40 @@ impl WatchHttpClient {
 
         self.get_opt(url).await
     }
+
+    pub async fn get_validator_rewards(
+        &self,
+        index: i32,
+        epoch: u64,
+    ) -> Result<Option<WatchValidatorRewards>, Error> {
+        let url = self
+            .server
+            .join("v1/")?
+            .join("validators/")?
+            .join(&format!("{index}/"))?
+            .join("rewards/")?
+            .join(&format!("{epoch}"))?;
+
+        self.get_opt(url).await
+    }
+
+    pub async fn get_validators_apr(
+        &self,
+        indices: &[i32],
+    ) -> Result<Option<WatchRewardsApr>, Error> {
+        let validators = indices
+            .iter()
+            .map(|index| index.to_string())
+            .collect::<Vec<_>>()
+            .join(",");
+        let url = self
+            .server
+            .join("v1/")?
+            .join("validators/")?
+            .join(&format!("{validators}/"))?
+            .join("rewards/")?
+            .join("apr")?;
+
+        self.get_opt(url).await
+    }
 }
@@ -47,6 +47,13 @@ pub use crate::suboptimal_attestations::{
     WatchAttestation, WatchSuboptimalAttestation,
 };
 
+#[allow(unused_imports)]
+pub use crate::validator_rewards::{
+    delete_validator_rewards_above, get_highest_validator_rewards, get_lowest_validator_rewards,
+    get_validator_rewards_by_index, get_validator_rewards_by_pubkey,
+    get_validator_rewards_by_range, insert_batch_validator_rewards, WatchValidatorRewards,
+};
+
 #[allow(unused_imports)]
 pub use crate::blockprint::{
     get_blockprint_by_root, get_blockprint_by_slot, get_highest_blockprint, get_lowest_blockprint,
@@ -84,6 +91,7 @@ pub fn insert_active_config(
     conn: &mut PgConn,
     new_config_name: String,
     new_slots_per_epoch: u64,
+    new_seconds_per_slot: u64,
 ) -> Result<(), Error> {
     use self::active_config::dsl::*;
 
@@ -92,6 +100,7 @@ pub fn insert_active_config(
             id.eq(1),
             config_name.eq(new_config_name),
             slots_per_epoch.eq(new_slots_per_epoch as i32),
+            seconds_per_slot.eq(Some(new_seconds_per_slot as i32)),
         )])
         .on_conflict_do_nothing()
         .execute(conn)?;
@@ -99,13 +108,31 @@ pub fn insert_active_config(
     Ok(())
 }
 
+/// Set the seconds per slot of the active config, for databases created before it was stored.
+pub fn set_active_config_seconds_per_slot(
+    conn: &mut PgConn,
+    new_seconds_per_slot: u64,
+) -> Result<(), Error> {
+    use self::active_config::dsl::*;
+
+    diesel::update(active_config)
+        .filter(id.eq(1))
+        .set(seconds_per_slot.eq(Some(new_seconds_per_slot as i32)))
+        .execute(conn)?;
+
+    Ok(())
+}
+
 /// Get the active config from the database.
-pub fn get_active_config(conn: &mut PgConn) -> Result<Option<(String, i32)>, Error> {
+///
+/// The seconds per slot is `None` for databases created before it was stored, until the updater
+/// is next run.
+pub fn get_active_config(conn: &mut PgConn) -> Result<Option<(String, i32, Option<i32>)>, Error> {
     use self::active_config::dsl::*;
     Ok(active_config
-        .select((config_name, slots_per_epoch))
+        .select((config_name, slots_per_epoch, seconds_per_slot))
         .filter(id.eq(1))
-        .first::<(String, i32)>(conn)
+        .first::<(String, i32, Option<i32>)>(conn)
         .optional()?)
 }
 
@@ -5,6 +5,7 @@ diesel::table! {
         id -> Int4,
         config_name -> Text,
         slots_per_epoch -> Int4,
+        seconds_per_slot -> Nullable<Int4>,
     }
 }
 
@@ -71,6 +72,23 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    validator_rewards (epoch_start_slot, index) {
+        epoch_start_slot -> Int4,
+        index -> Int4,
+        effective_balance -> Int8,
+        source -> Int8,
+        head -> Int8,
+        target -> Int8,
+        inactivity -> Int8,
+        ideal_source -> Int8,
+        ideal_head -> Int8,
+        ideal_target -> Int8,
+        sync_committee -> Int8,
+        proposal -> Int8,
+    }
+}
+
 diesel::table! {
     validators (index) {
         index -> Int4,
@@ -88,6 +106,8 @@ diesel::joinable!(proposer_info -> beacon_blocks (slot));
 diesel::joinable!(proposer_info -> validators (proposer_index));
 diesel::joinable!(suboptimal_attestations -> canonical_slots (epoch_start_slot));
 diesel::joinable!(suboptimal_attestations -> validators (index));
+diesel::joinable!(validator_rewards -> canonical_slots (epoch_start_slot));
+diesel::joinable!(validator_rewards -> validators (index));
 
 diesel::allow_tables_to_appear_in_same_query!(
     active_config,
@@ -98,5 +118,6 @@ diesel::allow_tables_to_appear_in_same_query!(
     canonical_slots,
     proposer_info,
     suboptimal_attestations,
+    validator_rewards,
     validators,
 );
@@ -10,3 +10,4 @@ pub mod logger;
 pub mod server;
 pub mod suboptimal_attestations;
 pub mod updater;
+pub mod validator_rewards;
@@ -4,6 +4,7 @@ use crate::blockprint::blockprint_routes;
 use crate::config::Config as FullConfig;
 use crate::database::{self, PgPool};
 use crate::suboptimal_attestations::{attestation_routes, blockprint_attestation_routes};
+use crate::validator_rewards::validator_rewards_routes;
 use axum::{
     http::{StatusCode, Uri},
     routing::get,
@@ -21,17 +22,32 @@ mod config;
 mod error;
 mod handler;
 
+/// The seconds per slot of the chain, which is shared with handlers alongside the slots per epoch.
+#[derive(Debug, Clone, Copy)]
+pub struct SecondsPerSlot(pub u64);
+
 pub async fn serve(config: FullConfig) -> Result<(), Error> {
     let db = database::build_connection_pool(&config.database)?;
-    let (_, slots_per_epoch) = database::get_active_config(&mut database::get_connection(&db)?)?
-        .ok_or_else(|| {
+    let (_, slots_per_epoch, seconds_per_slot) =
+        database::get_active_config(&mut database::get_connection(&db)?)?.ok_or_else(|| {
             Error::Other(
                 "Database not found. Please run the updater prior to starting the server"
                     .to_string(),
             )
         })?;
+    let seconds_per_slot = seconds_per_slot.ok_or_else(|| {
+        Error::Other(
+            "Database config is out of date. Please run the updater prior to starting the server"
+                .to_string(),
+        )
+    })?;
 
-    let (_addr, server) = start_server(&config, slots_per_epoch as u64, db)?;
+    let (_addr, server) = start_server(
+        &config,
+        slots_per_epoch as u64,
+        SecondsPerSlot(seconds_per_slot as u64),
+        db,
+    )?;
 
     server.await?;
 
@@ -57,6 +73,7 @@ pub async fn serve(config: FullConfig) -> Result<(), Error> {
 pub fn start_server(
     config: &FullConfig,
     slots_per_epoch: u64,
+    seconds_per_slot: SecondsPerSlot,
     pool: PgPool,
 ) -> Result<
     (
@@ -104,10 +121,15 @@ pub fn start_server(
         routes = routes.merge(blockprint_attestation_routes())
     }
 
+    if config.updater.validator_rewards {
+        routes = routes.merge(validator_rewards_routes())
+    }
+
     let app = routes
         .fallback(route_not_found)
         .layer(Extension(pool))
-        .layer(Extension(slots_per_epoch));
+        .layer(Extension(slots_per_epoch))
+        .layer(Extension(seconds_per_slot));
 
     let addr = SocketAddr::new(config.server.listen_addr, config.server.listen_port);
     let listener = TcpListener::bind(addr)?;
@@ -20,6 +20,9 @@ pub const fn block_rewards() -> bool {
 pub const fn block_packing() -> bool {
     true
 }
+pub const fn validator_rewards() -> bool {
+    false
+}
 
 fn beacon_node_url() -> String {
     BEACON_NODE_URL.to_string()
@@ -48,6 +51,9 @@ pub struct Config {
     /// Whether to sync the block_packing table.
     #[serde(default = "block_packing")]
     pub block_packing: bool,
+    /// Whether to sync the validator_rewards table.
+    #[serde(default = "validator_rewards")]
+    pub validator_rewards: bool,
 }
 
 impl Default for Config {
@@ -60,6 +66,7 @@ impl Default for Config {
             proposer_info: proposer_info(),
             block_rewards: block_rewards(),
             block_packing: block_packing(),
+            validator_rewards: validator_rewards(),
         }
     }
 }
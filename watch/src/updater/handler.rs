@@ -10,9 +10,9 @@ use eth2::{
 use log::{debug, error, info, warn};
 use std::collections::HashSet;
 use std::marker::PhantomData;
-use types::{BeaconBlockHeader, EthSpec, Hash256, SignedBeaconBlock, Slot};
+use types::{BeaconBlockHeader, Epoch, EthSpec, Hash256, SignedBeaconBlock, Slot};
 
-use crate::updater::{get_beacon_block, get_header, get_validators};
+use crate::updater::{get_altair_fork_epoch, get_beacon_block, get_header, get_validators};
 
 const MAX_EXPECTED_REORG_LENGTH: u64 = 32;
 
@@ -24,20 +24,35 @@ pub async fn ensure_valid_database<E: EthSpec>(
     let mut conn = database::get_connection(pool)?;
 
     let bn_slots_per_epoch = spec.slots_per_epoch();
+    let bn_seconds_per_slot = spec.seconds_per_slot();
     let bn_config_name = spec.network.clone();
 
-    if let Some((db_config_name, db_slots_per_epoch)) = database::get_active_config(&mut conn)? {
-        if db_config_name != bn_config_name || db_slots_per_epoch != bn_slots_per_epoch as i32 {
+    if let Some((db_config_name, db_slots_per_epoch, db_seconds_per_slot)) =
+        database::get_active_config(&mut conn)?
+    {
+        if db_config_name != bn_config_name
+            || db_slots_per_epoch != bn_slots_per_epoch as i32
+            || db_seconds_per_slot.is_some_and(|seconds| seconds != bn_seconds_per_slot as i32)
+        {
             Err(Error::InvalidConfig(
                 "The config stored in the database does not match the beacon node.".to_string(),
             ))
+        } else if db_seconds_per_slot.is_none() {
+            // The database was created before the seconds per slot was stored.
+            database::set_active_config_seconds_per_slot(&mut conn, bn_seconds_per_slot)?;
+            Ok(())
         } else {
             // Configs match.
             Ok(())
         }
     } else {
         // No config exists in the DB.
-        database::insert_active_config(&mut conn, bn_config_name, bn_slots_per_epoch)?;
+        database::insert_active_config(
+            &mut conn,
+            bn_config_name,
+            bn_slots_per_epoch,
+            bn_seconds_per_slot,
+        )?;
         Ok(())
     }
 }
@@ -48,6 +63,8 @@ pub struct UpdateHandler<E: EthSpec> {
     pub blockprint: Option<WatchBlockprintClient>,
     pub config: Config,
     pub slots_per_epoch: u64,
+    /// The epoch at which the Altair fork occurs, or `None` if it is not scheduled.
+    pub altair_fork_epoch: Option<Epoch>,
     pub _phantom: PhantomData<E>,
 }
 
@@ -79,12 +96,15 @@ impl<E: EthSpec> UpdateHandler<E> {
 
         ensure_valid_database(&spec, &mut pool).await?;
 
+        let altair_fork_epoch = get_altair_fork_epoch(&bn).await?;
+
         Ok(Self {
             pool,
             bn,
             blockprint,
             config: config.updater,
             slots_per_epoch: spec.slots_per_epoch(),
+            altair_fork_epoch,
             _phantom: PhantomData,
         })
     }
@@ -157,6 +177,13 @@ impl<E: EthSpec> UpdateHandler<E> {
                     &mut conn,
                     WatchSlot::from_slot(epoch.start_slot(self.slots_per_epoch)),
                 )?;
+
+                // The same applies to validator rewards.
+                debug!("Preparing to resync validator rewards above epoch {epoch}");
+                database::delete_validator_rewards_above(
+                    &mut conn,
+                    WatchSlot::from_slot(epoch.start_slot(self.slots_per_epoch)),
+                )?;
             }
 
             // Since we are syncing backwards, `start_slot > `end_slot`.
@@ -8,7 +8,7 @@ use log::{debug, error, info};
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
 use std::time::{Duration, Instant};
-use types::{BeaconBlockHeader, EthSpec, GnosisEthSpec, MainnetEthSpec, SignedBeaconBlock};
+use types::{BeaconBlockHeader, Epoch, EthSpec, GnosisEthSpec, MainnetEthSpec, SignedBeaconBlock};
 
 pub use config::Config;
 pub use error::Error;
@@ -26,6 +26,7 @@ const GNOSIS: &str = "gnosis";
 
 pub struct WatchSpec<E: EthSpec> {
     network: String,
+    seconds_per_slot: u64,
     spec: PhantomData<E>,
 }
 
@@ -33,21 +34,27 @@ impl<E: EthSpec> WatchSpec<E> {
     fn slots_per_epoch(&self) -> u64 {
         E::slots_per_epoch()
     }
+
+    fn seconds_per_slot(&self) -> u64 {
+        self.seconds_per_slot
+    }
 }
 
 impl WatchSpec<MainnetEthSpec> {
-    pub fn mainnet(network: String) -> Self {
+    pub fn mainnet(network: String, seconds_per_slot: u64) -> Self {
         Self {
             network,
+            seconds_per_slot,
             spec: PhantomData,
         }
     }
 }
 
 impl WatchSpec<GnosisEthSpec> {
-    fn gnosis(network: String) -> Self {
+    fn gnosis(network: String, seconds_per_slot: u64) -> Self {
         Self {
             network,
+            seconds_per_slot,
             spec: PhantomData,
         }
     }
@@ -67,6 +74,21 @@ pub async fn run_updater(config: FullConfig) -> Result<(), Error> {
         })?
         .clone();
 
+    let seconds_per_slot = config_map
+        .get("SECONDS_PER_SLOT")
+        .ok_or_else(|| {
+            Error::BeaconNodeNotCompatible(
+                "No field SECONDS_PER_SLOT on beacon node spec".to_string(),
+            )
+        })?
+        .parse::<u64>()
+        .map_err(|e| {
+            Error::BeaconNodeNotCompatible(format!(
+                "Invalid SECONDS_PER_SLOT on beacon node spec: {}",
+                e
+            ))
+        })?;
+
     match config_map
         .get("PRESET_BASE")
         .ok_or_else(|| {
@@ -76,11 +98,11 @@ pub async fn run_updater(config: FullConfig) -> Result<(), Error> {
         .as_str()
     {
         MAINNET => {
-            let spec = WatchSpec::mainnet(config_name);
+            let spec = WatchSpec::mainnet(config_name, seconds_per_slot);
             run_once(bn, spec, config).await
         }
         GNOSIS => {
-            let spec = WatchSpec::gnosis(config_name);
+            let spec = WatchSpec::gnosis(config_name, seconds_per_slot);
             run_once(bn, spec, config).await
         }
         _ => unimplemented!("unsupported PRESET_BASE"),
@@ -157,6 +179,17 @@ pub async fn run_once<E: EthSpec>(
         debug!("Block packing update complete, time taken: {packing_timer_elapsed:?}");
     }
 
+    if config.updater.validator_rewards {
+        info!("Updating validator rewards");
+        let validator_rewards_timer = Instant::now();
+        watch.fill_validator_rewards().await?;
+        watch.backfill_validator_rewards().await?;
+        let validator_rewards_timer_elapsed = validator_rewards_timer.elapsed();
+        debug!(
+            "Validator rewards update complete, time taken: {validator_rewards_timer_elapsed:?}"
+        );
+    }
+
     if config.blockprint.enabled {
         info!("Updating blockprint");
         let blockprint_timer = Instant::now();
@@ -169,6 +202,27 @@ pub async fn run_once<E: EthSpec>(
     Ok(())
 }
 
+/// Queries the beacon node for the epoch of the Altair fork.
+///
+/// Returns `None` if the fork is not scheduled.
+pub async fn get_altair_fork_epoch(bn: &BeaconNodeHttpClient) -> Result<Option<Epoch>, Error> {
+    let config_map = bn.get_config_spec::<HashMap<String, String>>().await?.data;
+
+    let altair_fork_epoch = config_map
+        .get("ALTAIR_FORK_EPOCH")
+        .map(|epoch| epoch.parse::<u64>())
+        .transpose()
+        .map_err(|e| {
+            Error::BeaconNodeNotCompatible(format!(
+                "Invalid ALTAIR_FORK_EPOCH on beacon node spec: {e:?}"
+            ))
+        })?
+        .filter(|epoch| *epoch != FAR_FUTURE_EPOCH)
+        .map(Epoch::new);
+
+    Ok(altair_fork_epoch)
+}
+
 /// Queries the beacon node for a given `BlockId` and returns the `BeaconBlockHeader` if it exists.
 pub async fn get_header(
     bn: &BeaconNodeHttpClient,
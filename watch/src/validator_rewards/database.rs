@@ -0,0 +1,213 @@
+use crate::database::{
+    schema::{validator_rewards, validators},
+    watch_types::{WatchPK, WatchSlot},
+    Error, PgConn,
+};
+
+use diesel::prelude::*;
+use diesel::{Insertable, Queryable};
+use log::debug;
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+use types::Epoch;
+
+/// Batch inserts cannot exceed 65535 bound parameters. Since each row of the `validator_rewards`
+/// table has 12 columns, this translates to 5461 records.
+pub const MAX_SIZE_BATCH_INSERT_VALIDATOR_REWARDS: usize = 5461;
+
+/// The rewards earned by a single validator over a single epoch. All values are in Gwei.
+///
+/// The `ideal_*` values are the attestation rewards a perfectly performing validator with the
+/// same effective balance would have earned.
+#[derive(Debug, Clone, Queryable, Insertable, Serialize, Deserialize)]
+#[diesel(table_name = validator_rewards)]
+pub struct WatchValidatorRewards {
+    pub epoch_start_slot: WatchSlot,
+    pub index: i32,
+    pub effective_balance: i64,
+    pub source: i64,
+    pub head: i64,
+    pub target: i64,
+    pub inactivity: i64,
+    pub ideal_source: i64,
+    pub ideal_head: i64,
+    pub ideal_target: i64,
+    pub sync_committee: i64,
+    pub proposal: i64,
+}
+
+impl WatchValidatorRewards {
+    /// The net reward for attesting, including any inactivity penalty.
+    pub fn attestation_reward(&self) -> i64 {
+        self.source + self.head + self.target + self.inactivity
+    }
+
+    /// The net reward from all duties performed during the epoch.
+    pub fn total_reward(&self) -> i64 {
+        self.attestation_reward() + self.sync_committee + self.proposal
+    }
+}
+
+/// Insert a batch of values into the `validator_rewards` table.
+///
+/// Like `suboptimal_attestations`, rewards are stored per-epoch so in the case of a re-org the
+/// affected epochs are deleted completely and resynced by the update code.
+///
+/// On a conflict, it will do nothing.
+pub fn insert_batch_validator_rewards(
+    conn: &mut PgConn,
+    rewards: Vec<WatchValidatorRewards>,
+) -> Result<(), Error> {
+    use self::validator_rewards::dsl::*;
+
+    let mut count = 0;
+    let timer = Instant::now();
+
+    for chunk in rewards.chunks(MAX_SIZE_BATCH_INSERT_VALIDATOR_REWARDS) {
+        count += diesel::insert_into(validator_rewards)
+            .values(chunk)
+            .on_conflict_do_nothing()
+            .execute(conn)?;
+    }
+
+    let time_taken = timer.elapsed();
+    debug!("Validator rewards inserted, count: {count}, time taken: {time_taken:?}");
+    Ok(())
+}
+
+/// Selects the row from the `validator_rewards` table where `epoch_start_slot` is minimum.
+pub fn get_lowest_validator_rewards(
+    conn: &mut PgConn,
+) -> Result<Option<WatchValidatorRewards>, Error> {
+    use self::validator_rewards::dsl::*;
+
+    Ok(validator_rewards
+        .order_by(epoch_start_slot.asc())
+        .limit(1)
+        .first::<WatchValidatorRewards>(conn)
+        .optional()?)
+}
+
+/// Selects the row from the `validator_rewards` table where `epoch_start_slot` is maximum.
+pub fn get_highest_validator_rewards(
+    conn: &mut PgConn,
+) -> Result<Option<WatchValidatorRewards>, Error> {
+    use self::validator_rewards::dsl::*;
+
+    Ok(validator_rewards
+        .order_by(epoch_start_slot.desc())
+        .limit(1)
+        .first::<WatchValidatorRewards>(conn)
+        .optional()?)
+}
+
+/// Selects a single row from the `validator_rewards` table corresponding to a given
+/// `index_query` and `epoch_query`.
+pub fn get_validator_rewards_by_index(
+    conn: &mut PgConn,
+    index_query: i32,
+    epoch_query: Epoch,
+    slots_per_epoch: u64,
+) -> Result<Option<WatchValidatorRewards>, Error> {
+    use self::validator_rewards::dsl::*;
+    let timer = Instant::now();
+
+    let result = validator_rewards
+        .filter(epoch_start_slot.eq(WatchSlot::from_slot(
+            epoch_query.start_slot(slots_per_epoch),
+        )))
+        .filter(index.eq(index_query))
+        .first::<WatchValidatorRewards>(conn)
+        .optional()?;
+
+    let time_taken = timer.elapsed();
+    debug!("Rewards requested for validator: {index_query}, epoch: {epoch_query}, time taken: {time_taken:?}");
+    Ok(result)
+}
+
+/// Selects a single row from the `validator_rewards` table corresponding to a given
+/// `pubkey_query` and `epoch_query`.
+#[allow(dead_code)]
+pub fn get_validator_rewards_by_pubkey(
+    conn: &mut PgConn,
+    pubkey_query: WatchPK,
+    epoch_query: Epoch,
+    slots_per_epoch: u64,
+) -> Result<Option<WatchValidatorRewards>, Error> {
+    use self::validator_rewards::dsl::*;
+    use self::validators::dsl::{public_key, validators};
+    let timer = Instant::now();
+
+    let join = validators.inner_join(validator_rewards);
+
+    let result = join
+        .select((
+            epoch_start_slot,
+            index,
+            effective_balance,
+            source,
+            head,
+            target,
+            inactivity,
+            ideal_source,
+            ideal_head,
+            ideal_target,
+            sync_committee,
+            proposal,
+        ))
+        .filter(epoch_start_slot.eq(WatchSlot::from_slot(
+            epoch_query.start_slot(slots_per_epoch),
+        )))
+        .filter(public_key.eq(pubkey_query))
+        .first::<WatchValidatorRewards>(conn)
+        .optional()?;
+
+    let time_taken = timer.elapsed();
+    debug!("Rewards requested for validator: {pubkey_query}, epoch: {epoch_query}, time taken: {time_taken:?}");
+    Ok(result)
+}
+
+/// Selects all rows from the `validator_rewards` table for the validators in `indices_query`
+/// with an `epoch_start_slot` between `start_slot_query` and `end_slot_query` (inclusive).
+///
+/// Rows are ordered by `epoch_start_slot`.
+pub fn get_validator_rewards_by_range(
+    conn: &mut PgConn,
+    indices_query: &[i32],
+    start_slot_query: WatchSlot,
+    end_slot_query: WatchSlot,
+) -> Result<Vec<WatchValidatorRewards>, Error> {
+    use self::validator_rewards::dsl::*;
+    let timer = Instant::now();
+
+    let result = validator_rewards
+        .filter(index.eq_any(indices_query))
+        .filter(epoch_start_slot.ge(start_slot_query))
+        .filter(epoch_start_slot.le(end_slot_query))
+        .order_by(epoch_start_slot.asc())
+        .load::<WatchValidatorRewards>(conn)?;
+
+    let time_taken = timer.elapsed();
+    debug!(
+        "Rewards requested for {} validators, start_slot: {start_slot_query}, end_slot: {end_slot_query}, time taken: {time_taken:?}",
+        indices_query.len()
+    );
+    Ok(result)
+}
+
+/// Deletes all rows of the `validator_rewards` table which have `epoch_start_slot` greater than
+/// `epoch_start_slot_query`.
+pub fn delete_validator_rewards_above(
+    conn: &mut PgConn,
+    epoch_start_slot_query: WatchSlot,
+) -> Result<usize, Error> {
+    use self::validator_rewards::dsl::*;
+
+    let result = diesel::delete(validator_rewards)
+        .filter(epoch_start_slot.gt(epoch_start_slot_query))
+        .execute(conn)?;
+
+    debug!("Deleted validator rewards above: {epoch_start_slot_query}, rows deleted: {result}");
+    Ok(result)
+}
@@ -0,0 +1,116 @@
+pub mod database;
+pub mod server;
+pub mod updater;
+
+use crate::database::{watch_types::WatchSlot, WatchCanonicalSlot};
+use crate::updater::error::Error;
+
+pub use database::{
+    delete_validator_rewards_above, get_highest_validator_rewards, get_lowest_validator_rewards,
+    get_validator_rewards_by_index, get_validator_rewards_by_pubkey,
+    get_validator_rewards_by_range, insert_batch_validator_rewards, WatchValidatorRewards,
+};
+pub use server::validator_rewards_routes;
+
+use eth2::types::{BlockId, StateId};
+use eth2::BeaconNodeHttpClient;
+use std::collections::HashMap;
+use types::Epoch;
+
+/// Queries the beacon node for the rewards earned by every validator during `epoch`.
+///
+/// Attestation rewards are retrieved from `beacon/rewards/attestations`, while proposal and sync
+/// committee rewards are summed over every non-skipped slot in `blocks` using
+/// `beacon/rewards/blocks` and `beacon/rewards/sync_committee` respectively. Sync committee
+/// rewards are only requested if `include_sync_committee` is set, since the endpoint fails for
+/// pre-Altair blocks.
+///
+/// Will fail if `epoch` is not at least 2 epochs behind the head of the beacon node.
+pub async fn get_validator_rewards(
+    bn: &BeaconNodeHttpClient,
+    epoch: Epoch,
+    slots_per_epoch: u64,
+    blocks: &[WatchCanonicalSlot],
+    include_sync_committee: bool,
+) -> Result<Vec<WatchValidatorRewards>, Error> {
+    let attestation_rewards = bn.post_beacon_rewards_attestations(epoch, &[]).await?.data;
+
+    // The effective balances used to compute the rewards for `epoch` are those of the state at the
+    // start of the following epoch.
+    let effective_balances = bn
+        .get_beacon_states_validators(
+            StateId::Slot((epoch + 1).start_slot(slots_per_epoch)),
+            None,
+            None,
+        )
+        .await?
+        .ok_or(Error::NoValidatorsFound)?
+        .data
+        .into_iter()
+        .map(|val| (val.index, val.validator.effective_balance))
+        .collect::<HashMap<_, _>>();
+
+    let ideal_rewards = attestation_rewards
+        .ideal_rewards
+        .into_iter()
+        .map(|ideal| (ideal.effective_balance, ideal))
+        .collect::<HashMap<_, _>>();
+
+    let mut proposal_rewards: HashMap<u64, i64> = HashMap::new();
+    let mut sync_committee_rewards: HashMap<u64, i64> = HashMap::new();
+
+    // The genesis block has no parent state to compute rewards from, so it is excluded.
+    for block in blocks
+        .iter()
+        .filter(|block| !block.skipped && block.slot.as_slot() != 0)
+    {
+        let block_id = BlockId::Root(block.root.as_hash());
+
+        let block_reward = bn.get_beacon_rewards_blocks(block_id).await?.data;
+        *proposal_rewards
+            .entry(block_reward.proposer_index)
+            .or_default() += block_reward.total as i64;
+
+        if include_sync_committee {
+            let rewards = bn
+                .post_beacon_rewards_sync_committee(block_id, &[])
+                .await?
+                .data
+                .unwrap_or_default();
+            for reward in rewards {
+                *sync_committee_rewards
+                    .entry(reward.validator_index)
+                    .or_default() += reward.reward;
+            }
+        }
+    }
+
+    let epoch_start_slot = WatchSlot::from_slot(epoch.start_slot(slots_per_epoch));
+
+    Ok(attestation_rewards
+        .total_rewards
+        .into_iter()
+        .map(|total| {
+            let index = total.validator_index;
+            let effective_balance = effective_balances.get(&index).copied().unwrap_or_default();
+            let ideal = ideal_rewards.get(&effective_balance);
+            WatchValidatorRewards {
+                epoch_start_slot,
+                index: index as i32,
+                effective_balance: effective_balance as i64,
+                source: total.source,
+                head: total.head,
+                target: total.target,
+                inactivity: total.inactivity,
+                ideal_source: ideal.map_or(0, |ideal| ideal.source as i64),
+                ideal_head: ideal.map_or(0, |ideal| ideal.head as i64),
+                ideal_target: ideal.map_or(0, |ideal| ideal.target as i64),
+                sync_committee: sync_committee_rewards
+                    .get(&index)
+                    .copied()
+                    .unwrap_or_default(),
+                proposal: proposal_rewards.get(&index).copied().unwrap_or_default(),
+            }
+        })
+        .collect())
+}
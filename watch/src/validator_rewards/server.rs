@@ -0,0 +1,326 @@
+use crate::database::{
+    get_connection, get_validator_by_public_key, PgConn, PgPool, WatchPK, WatchSlot,
+};
+use crate::server::{Error, SecondsPerSlot};
+use crate::validator_rewards::database::{
+    get_highest_validator_rewards, get_validator_rewards_by_index, get_validator_rewards_by_range,
+    WatchValidatorRewards,
+};
+
+use axum::{
+    extract::{Path, Query},
+    routing::get,
+    Extension, Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::str::FromStr;
+use types::Epoch;
+
+/// The maximum number of validators which can be queried at once.
+const MAX_VALIDATORS_PER_QUERY: usize = 1024;
+
+/// The number of days of history returned when no `start_epoch` is provided.
+const DEFAULT_HISTORY_DAYS: u64 = 30;
+
+const SECONDS_PER_DAY: u64 = 86400;
+const DAYS_PER_YEAR: f64 = 365.25;
+
+/// The rewards earned by a set of validators over a single day. All values are in Gwei.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WatchDailyRewards {
+    /// The number of days since genesis.
+    pub day: u64,
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub attestation: i64,
+    pub sync_committee: i64,
+    pub proposal: i64,
+    pub total: i64,
+    pub missed: i64,
+}
+
+/// The annualised return of a set of validators over a range of epochs.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WatchRewardsApr {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    /// The total reward earned over the range, in Gwei.
+    pub total_reward: i64,
+    /// The average combined effective balance over the range, in Gwei.
+    pub average_effective_balance: u64,
+    /// The annual percentage rate implied by the rewards over the range.
+    pub apr: f64,
+}
+
+/// The attestation rewards a set of validators failed to earn over a range of epochs, relative to
+/// an ideal validator with the same effective balance. All values are in Gwei.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct WatchMissedRewards {
+    pub start_epoch: Epoch,
+    pub end_epoch: Epoch,
+    pub source: i64,
+    pub head: i64,
+    pub target: i64,
+    pub inactivity: i64,
+    pub total: i64,
+}
+
+impl WatchMissedRewards {
+    fn add(&mut self, rewards: &WatchValidatorRewards) {
+        let source = rewards.ideal_source - rewards.source;
+        let head = rewards.ideal_head - rewards.head;
+        let target = rewards.ideal_target - rewards.target;
+        let inactivity = -rewards.inactivity;
+        self.source += source;
+        self.head += head;
+        self.target += target;
+        self.inactivity += inactivity;
+        self.total += source + head + target + inactivity;
+    }
+}
+
+/// The number of epochs in a day.
+fn epochs_per_day(slots_per_epoch: u64, SecondsPerSlot(seconds_per_slot): SecondsPerSlot) -> u64 {
+    SECONDS_PER_DAY / (slots_per_epoch * seconds_per_slot)
+}
+
+/// Parses a comma-separated list of validator indices and/or public keys into indices.
+fn parse_validators(conn: &mut PgConn, validator_query: &str) -> Result<Vec<i32>, Error> {
+    let mut indices = HashSet::new();
+    for validator in validator_query.split(',') {
+        let index = if validator.starts_with("0x") {
+            let pubkey = WatchPK::from_str(validator).map_err(|_| Error::BadRequest)?;
+            get_validator_by_public_key(conn, pubkey)?
+                .ok_or(Error::NotFound)?
+                .index
+        } else {
+            i32::from_str(validator).map_err(|_| Error::BadRequest)?
+        };
+        indices.insert(index);
+    }
+
+    if indices.len() > MAX_VALIDATORS_PER_QUERY {
+        return Err(Error::BadRequest);
+    }
+
+    Ok(indices.into_iter().collect())
+}
+
+/// Determines the epoch range of a query from its optional `start_epoch` and `end_epoch`.
+///
+/// `end_epoch` defaults to the highest synced epoch and `start_epoch` defaults to
+/// `DEFAULT_HISTORY_DAYS` before `end_epoch`.
+fn parse_epoch_range(
+    conn: &mut PgConn,
+    query: &HashMap<String, u64>,
+    slots_per_epoch: u64,
+    seconds_per_slot: SecondsPerSlot,
+) -> Result<Option<(Epoch, Epoch)>, Error> {
+    let end_epoch = match query.get("end_epoch") {
+        Some(end_epoch) => Epoch::new(*end_epoch),
+        None => match get_highest_validator_rewards(conn)? {
+            Some(rewards) => rewards.epoch_start_slot.epoch(slots_per_epoch),
+            None => return Ok(None),
+        },
+    };
+    let start_epoch = match query.get("start_epoch") {
+        Some(start_epoch) => Epoch::new(*start_epoch),
+        None => end_epoch.saturating_sub(
+            DEFAULT_HISTORY_DAYS * epochs_per_day(slots_per_epoch, seconds_per_slot),
+        ),
+    };
+
+    if start_epoch > end_epoch {
+        return Err(Error::BadRequest);
+    }
+
+    Ok(Some((start_epoch, end_epoch)))
+}
+
+fn get_rewards_in_range(
+    conn: &mut PgConn,
+    indices: &[i32],
+    start_epoch: Epoch,
+    end_epoch: Epoch,
+    slots_per_epoch: u64,
+) -> Result<Vec<WatchValidatorRewards>, Error> {
+    Ok(get_validator_rewards_by_range(
+        conn,
+        indices,
+        WatchSlot::from_slot(start_epoch.start_slot(slots_per_epoch)),
+        WatchSlot::from_slot(end_epoch.start_slot(slots_per_epoch)),
+    )?)
+}
+
+pub async fn get_validator_rewards(
+    Path((validator_query, epoch_query)): Path<(String, u64)>,
+    Extension(pool): Extension<PgPool>,
+    Extension(slots_per_epoch): Extension<u64>,
+) -> Result<Json<Option<WatchValidatorRewards>>, Error> {
+    let mut conn = get_connection(&pool).map_err(Error::Database)?;
+
+    let index = if validator_query.starts_with("0x") {
+        let pubkey = WatchPK::from_str(&validator_query).map_err(|_| Error::BadRequest)?;
+        get_validator_by_public_key(&mut conn, pubkey)?
+            .ok_or(Error::NotFound)?
+            .index
+    } else {
+        i32::from_str(&validator_query).map_err(|_| Error::BadRequest)?
+    };
+
+    Ok(Json(get_validator_rewards_by_index(
+        &mut conn,
+        index,
+        Epoch::new(epoch_query),
+        slots_per_epoch,
+    )?))
+}
+
+pub async fn get_validators_daily_rewards(
+    Path(validator_query): Path<String>,
+    Query(query): Query<HashMap<String, u64>>,
+    Extension(pool): Extension<PgPool>,
+    Extension(slots_per_epoch): Extension<u64>,
+    Extension(seconds_per_slot): Extension<SecondsPerSlot>,
+) -> Result<Json<Vec<WatchDailyRewards>>, Error> {
+    let mut conn = get_connection(&pool).map_err(Error::Database)?;
+    let indices = parse_validators(&mut conn, &validator_query)?;
+    let Some((start_epoch, end_epoch)) =
+        parse_epoch_range(&mut conn, &query, slots_per_epoch, seconds_per_slot)?
+    else {
+        return Ok(Json(vec![]));
+    };
+
+    let epochs_per_day = epochs_per_day(slots_per_epoch, seconds_per_slot);
+    let mut days: BTreeMap<u64, WatchDailyRewards> = BTreeMap::new();
+
+    for rewards in
+        get_rewards_in_range(&mut conn, &indices, start_epoch, end_epoch, slots_per_epoch)?
+    {
+        let epoch = rewards.epoch_start_slot.epoch(slots_per_epoch);
+        let day = epoch.as_u64() / epochs_per_day;
+        let daily = days.entry(day).or_insert_with(|| WatchDailyRewards {
+            day,
+            start_epoch: epoch,
+            end_epoch: epoch,
+            ..Default::default()
+        });
+
+        let mut missed = WatchMissedRewards::default();
+        missed.add(&rewards);
+
+        daily.start_epoch = std::cmp::min(daily.start_epoch, epoch);
+        daily.end_epoch = std::cmp::max(daily.end_epoch, epoch);
+        daily.attestation += rewards.attestation_reward();
+        daily.sync_committee += rewards.sync_committee;
+        daily.proposal += rewards.proposal;
+        daily.total += rewards.total_reward();
+        daily.missed += missed.total;
+    }
+
+    Ok(Json(days.into_values().collect()))
+}
+
+pub async fn get_validators_apr(
+    Path(validator_query): Path<String>,
+    Query(query): Query<HashMap<String, u64>>,
+    Extension(pool): Extension<PgPool>,
+    Extension(slots_per_epoch): Extension<u64>,
+    Extension(seconds_per_slot): Extension<SecondsPerSlot>,
+) -> Result<Json<Option<WatchRewardsApr>>, Error> {
+    let mut conn = get_connection(&pool).map_err(Error::Database)?;
+    let indices = parse_validators(&mut conn, &validator_query)?;
+    let Some((start_epoch, end_epoch)) =
+        parse_epoch_range(&mut conn, &query, slots_per_epoch, seconds_per_slot)?
+    else {
+        return Ok(Json(None));
+    };
+
+    let rewards =
+        get_rewards_in_range(&mut conn, &indices, start_epoch, end_epoch, slots_per_epoch)?;
+
+    let epochs = rewards
+        .iter()
+        .map(|rewards| rewards.epoch_start_slot)
+        .collect::<HashSet<_>>()
+        .len() as u64;
+    if epochs == 0 {
+        return Ok(Json(None));
+    }
+
+    let total_reward: i64 = rewards.iter().map(|rewards| rewards.total_reward()).sum();
+    let total_effective_balance: i64 = rewards
+        .iter()
+        .map(|rewards| rewards.effective_balance)
+        .sum();
+    let average_effective_balance = total_effective_balance as u64 / epochs;
+
+    // `average_effective_balance` should never be `0` for active validators, but if it is, return
+    // `0` instead of `inf`.
+    let apr = if average_effective_balance == 0 {
+        0.0
+    } else {
+        let epochs_per_year =
+            epochs_per_day(slots_per_epoch, seconds_per_slot) as f64 * DAYS_PER_YEAR;
+        let reward_per_epoch = total_reward as f64 / epochs as f64;
+        reward_per_epoch * epochs_per_year / average_effective_balance as f64 * 100.0
+    };
+
+    Ok(Json(Some(WatchRewardsApr {
+        start_epoch,
+        end_epoch,
+        total_reward,
+        average_effective_balance,
+        apr,
+    })))
+}
+
+pub async fn get_validators_missed_rewards(
+    Path(validator_query): Path<String>,
+    Query(query): Query<HashMap<String, u64>>,
+    Extension(pool): Extension<PgPool>,
+    Extension(slots_per_epoch): Extension<u64>,
+    Extension(seconds_per_slot): Extension<SecondsPerSlot>,
+) -> Result<Json<Option<WatchMissedRewards>>, Error> {
+    let mut conn = get_connection(&pool).map_err(Error::Database)?;
+    let indices = parse_validators(&mut conn, &validator_query)?;
+    let Some((start_epoch, end_epoch)) =
+        parse_epoch_range(&mut conn, &query, slots_per_epoch, seconds_per_slot)?
+    else {
+        return Ok(Json(None));
+    };
+
+    let mut missed = WatchMissedRewards {
+        start_epoch,
+        end_epoch,
+        ..Default::default()
+    };
+    for rewards in
+        get_rewards_in_range(&mut conn, &indices, start_epoch, end_epoch, slots_per_epoch)?
+    {
+        missed.add(&rewards);
+    }
+
+    Ok(Json(Some(missed)))
+}
+
+pub fn validator_rewards_routes() -> Router {
+    Router::new()
+        .route(
+            "/v1/validators/:validator/rewards/:epoch",
+            get(get_validator_rewards),
+        )
+        .route(
+            "/v1/validators/:validator/rewards/daily",
+            get(get_validators_daily_rewards),
+        )
+        .route(
+            "/v1/validators/:validator/rewards/apr",
+            get(get_validators_apr),
+        )
+        .route(
+            "/v1/validators/:validator/rewards/missed",
+            get(get_validators_missed_rewards),
+        )
+}
@@ -0,0 +1,226 @@
+use crate::database::{self, Error as DbError, WatchSlot};
+use crate::updater::{Error, UpdateHandler};
+
+use crate::validator_rewards::get_validator_rewards;
+
+use eth2::types::EthSpec;
+use log::{debug, error, warn};
+use types::Epoch;
+
+/// Each epoch requires a request per block in addition to two requests over the whole validator
+/// set, so the number of epochs synced per run is kept small.
+const MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS: u64 = 8;
+
+impl<E: EthSpec> UpdateHandler<E> {
+    /// Forward fills the `validator_rewards` table starting from the entry with the highest slot.
+    ///
+    /// It syncs every epoch between:
+    /// `start_epoch` -> highest filled epoch + 1 (or epoch of lowest canonical slot)
+    /// `end_epoch` -> epoch of highest canonical slot - 2
+    ///
+    /// Unlike `suboptimal_attestations`, every active validator has a row for each epoch so the
+    /// highest filled epoch is always complete.
+    ///
+    /// Request range will not exceed `MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS`.
+    pub async fn fill_validator_rewards(&mut self) -> Result<(), Error> {
+        let mut conn = database::get_connection(&self.pool)?;
+
+        let highest_filled_slot_opt = if self.config.validator_rewards {
+            database::get_highest_validator_rewards(&mut conn)?
+                .map(|rewards| rewards.epoch_start_slot.as_slot())
+        } else {
+            return Err(Error::NotEnabled("validator_rewards".to_string()));
+        };
+
+        let start_epoch = if let Some(highest_filled_slot) = highest_filled_slot_opt {
+            highest_filled_slot.epoch(self.slots_per_epoch) + 1
+        } else {
+            // No rows present in the `validator_rewards` table. Use `canonical_slots` instead.
+            if let Some(lowest_canonical_slot) = database::get_lowest_canonical_slot(&mut conn)? {
+                lowest_canonical_slot
+                    .slot
+                    .as_slot()
+                    .epoch(self.slots_per_epoch)
+            } else {
+                // There are no slots in the database, do not fill the `validator_rewards` table.
+                warn!("Refusing to fill the `validator_rewards` table as there are no slots in the database");
+                return Ok(());
+            }
+        };
+
+        if let Some(highest_canonical_slot) =
+            database::get_highest_canonical_slot(&mut conn)?.map(|slot| slot.slot.as_slot())
+        {
+            // The `beacon/rewards/attestations` endpoint can only compute rewards for epochs which
+            // are more than 1 epoch old.
+            // We assume that `highest_canonical_slot` is near the head of the chain.
+            let mut end_epoch = highest_canonical_slot
+                .epoch(self.slots_per_epoch)
+                .saturating_sub(2_u64);
+
+            // If end_epoch == 0 then the chain just started so we need to wait until
+            // `current_epoch >= 2`.
+            if end_epoch == 0 {
+                debug!("Chain just begun, refusing to sync validator rewards");
+                return Ok(());
+            }
+
+            if start_epoch > end_epoch {
+                debug!("Validator rewards are up to date with the head of the database");
+                return Ok(());
+            }
+
+            // Ensure the size of the request does not exceed the maximum allowed value.
+            if start_epoch < end_epoch.saturating_sub(MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS) {
+                end_epoch = start_epoch + MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS
+            }
+
+            for epoch in start_epoch.as_u64()..=end_epoch.as_u64() {
+                self.sync_validator_rewards_for_epoch(Epoch::new(epoch))
+                    .await?;
+            }
+        } else {
+            // There are no slots in the `canonical_slots` table, but there are entries in the
+            // `validator_rewards` table. This is a critical failure. It usually means someone has
+            // manually tampered with the database tables and should not occur during normal
+            // operation.
+            error!("Database is corrupted. Please re-sync the database");
+            return Err(Error::Database(DbError::DatabaseCorrupted));
+        }
+
+        Ok(())
+    }
+
+    /// Backfill the `validator_rewards` table starting from the entry with the lowest slot.
+    ///
+    /// It syncs every epoch between:
+    /// `start_epoch` -> epoch of the lowest `canonical_slot`.
+    /// `end_epoch` -> epoch of the lowest filled `validator_rewards` - 1 (or epoch of highest
+    /// canonical slot - 2)
+    ///
+    /// Request range will not exceed `max_backfill_size_epochs` or
+    /// `MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS`.
+    pub async fn backfill_validator_rewards(&mut self) -> Result<(), Error> {
+        let mut conn = database::get_connection(&self.pool)?;
+        let max_rewards_backfill = self.config.max_backfill_size_epochs;
+
+        // Get the slot of the lowest entry in the `validator_rewards` table.
+        let lowest_filled_slot_opt = if self.config.validator_rewards {
+            database::get_lowest_validator_rewards(&mut conn)?
+                .map(|rewards| rewards.epoch_start_slot.as_slot())
+        } else {
+            return Err(Error::NotEnabled("validator_rewards".to_string()));
+        };
+
+        let end_epoch = if let Some(lowest_filled_slot) = lowest_filled_slot_opt {
+            if lowest_filled_slot.epoch(self.slots_per_epoch) == 0 {
+                debug!("Validator rewards backfill is complete");
+                return Ok(());
+            }
+            lowest_filled_slot.epoch(self.slots_per_epoch) - 1
+        } else {
+            // No entries in the `validator_rewards` table. Use `canonical_slots` instead.
+            if let Some(highest_canonical_slot) =
+                database::get_highest_canonical_slot(&mut conn)?.map(|slot| slot.slot.as_slot())
+            {
+                // Subtract 2 since `end_epoch` must be less than the current epoch - 1.
+                // We assume that `highest_canonical_slot` is near the head of the chain.
+                let end_epoch = highest_canonical_slot
+                    .epoch(self.slots_per_epoch)
+                    .saturating_sub(2_u64);
+                if end_epoch == 0 {
+                    debug!("Chain just begun, refusing to backfill validator rewards");
+                    return Ok(());
+                }
+                end_epoch
+            } else {
+                // There are no slots in the database, do not backfill the `validator_rewards`
+                // table.
+                warn!(
+                    "Refusing to backfill validator rewards as there are no slots in the database"
+                );
+                return Ok(());
+            }
+        };
+
+        if let Some(lowest_canonical_slot) =
+            database::get_lowest_canonical_slot(&mut conn)?.map(|slot| slot.slot.as_slot())
+        {
+            let mut start_epoch = lowest_canonical_slot.epoch(self.slots_per_epoch);
+
+            if start_epoch > end_epoch {
+                debug!("Validator rewards are up to date with the base of the database");
+                return Ok(());
+            }
+
+            // Ensure the request range does not exceed `max_rewards_backfill` or
+            // `MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS`.
+            if start_epoch < end_epoch.saturating_sub(max_rewards_backfill) {
+                start_epoch = end_epoch.saturating_sub(max_rewards_backfill)
+            }
+            if start_epoch < end_epoch.saturating_sub(MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS) {
+                start_epoch = end_epoch.saturating_sub(MAX_SIZE_SINGLE_REQUEST_VALIDATOR_REWARDS)
+            }
+
+            for epoch in (start_epoch.as_u64()..=end_epoch.as_u64()).rev() {
+                self.sync_validator_rewards_for_epoch(Epoch::new(epoch))
+                    .await?;
+            }
+        } else {
+            // There are no slots in the `canonical_slot` table, but there are entries in the
+            // `validator_rewards` table. This is a critical failure. It usually means someone has
+            // manually tampered with the database tables and should not occur during normal
+            // operation.
+            error!("Database is corrupted. Please re-sync the database");
+            return Err(Error::Database(DbError::DatabaseCorrupted));
+        }
+
+        Ok(())
+    }
+
+    /// Retrieve the rewards for every validator during `epoch` and insert them into the
+    /// `validator_rewards` table.
+    ///
+    /// Does nothing if the start slot of `epoch` is not present in the `canonical_slots` table.
+    async fn sync_validator_rewards_for_epoch(&mut self, epoch: Epoch) -> Result<(), Error> {
+        let mut conn = database::get_connection(&self.pool)?;
+
+        let start_slot = epoch.start_slot(self.slots_per_epoch);
+        let end_slot = epoch.end_slot(self.slots_per_epoch);
+
+        let blocks = database::get_canonical_slots_by_range(
+            &mut conn,
+            WatchSlot::from_slot(start_slot),
+            WatchSlot::from_slot(end_slot),
+        )?
+        .unwrap_or_default();
+
+        // Only insert rewards with a corresponding `canonical_slot`.
+        if !blocks
+            .iter()
+            .any(|block| block.slot.as_slot() == start_slot)
+        {
+            debug!(
+                "Refusing to sync validator rewards for epoch {epoch} as it is not in the database"
+            );
+            return Ok(());
+        }
+
+        let include_sync_committee = self
+            .altair_fork_epoch
+            .map_or(false, |altair_fork_epoch| epoch >= altair_fork_epoch);
+
+        let rewards = get_validator_rewards(
+            &self.bn,
+            epoch,
+            self.slots_per_epoch,
+            &blocks,
+            include_sync_committee,
+        )
+        .await?;
+
+        database::insert_batch_validator_rewards(&mut conn, rewards)?;
+
+        Ok(())
+    }
+}
@@ -25,7 +25,7 @@ use watch::{
     client::WatchHttpClient,
     config::Config,
     database::{self, Config as DatabaseConfig, PgPool, WatchSlot},
-    server::{start_server, Config as ServerConfig},
+    server::{start_server, Config as ServerConfig, SecondsPerSlot},
     updater::{handler::*, run_updater, Config as UpdaterConfig, WatchSpec},
 };
 
@@ -182,11 +182,23 @@ impl TesterBuilder {
             _bn_network_rx,
         }
     }
+    pub fn enable_validator_rewards(mut self) -> Self {
+        self.config.updater.validator_rewards = true;
+        self
+    }
+
     pub async fn build(self, pool: PgPool) -> Tester {
         /*
          * Spawn a Watch HTTP API.
          */
-        let (addr, watch_server) = start_server(&self.config, SLOTS_PER_EPOCH, pool).unwrap();
+        let seconds_per_slot = self.harness.spec.seconds_per_slot;
+        let (addr, watch_server) = start_server(
+            &self.config,
+            SLOTS_PER_EPOCH,
+            SecondsPerSlot(seconds_per_slot),
+            pool,
+        )
+        .unwrap();
         tokio::spawn(watch_server);
 
         /*
@@ -202,7 +214,7 @@ impl TesterBuilder {
          */
         let beacon_node_url = SensitiveUrl::parse(&self.config.updater.beacon_node_url).unwrap();
         let bn = BeaconNodeHttpClient::new(beacon_node_url, Timeouts::set_all(DEFAULT_TIMEOUT));
-        let spec = WatchSpec::mainnet("mainnet".to_string());
+        let spec = WatchSpec::mainnet("mainnet".to_string(), seconds_per_slot);
 
         /*
          * Build update service
@@ -340,6 +352,18 @@ impl Tester {
         self
     }
 
+    pub async fn fill_validator_rewards(&mut self) -> &mut Self {
+        self.updater.fill_validator_rewards().await.unwrap();
+
+        self
+    }
+
+    pub async fn backfill_validator_rewards(&mut self) -> &mut Self {
+        self.updater.backfill_validator_rewards().await.unwrap();
+
+        self
+    }
+
     pub async fn assert_canonical_slots_empty(&mut self) -> &mut Self {
         let lowest_slot = self
             .client
@@ -502,6 +526,36 @@ impl Tester {
         self
     }
 
+    pub async fn assert_validator_has_rewards(&mut self, index: i32, epoch: u64) -> &mut Self {
+        let rewards = self
+            .client
+            .get_validator_rewards(index, epoch)
+            .await
+            .unwrap()
+            .unwrap();
+
+        // All validators attest on every slot so they should be rewarded for their votes.
+        assert!(rewards.effective_balance > 0);
+        assert!(rewards.source > 0);
+        assert!(rewards.target > 0);
+
+        self
+    }
+
+    pub async fn assert_validators_have_positive_apr(&mut self, indices: &[i32]) -> &mut Self {
+        let apr = self
+            .client
+            .get_validators_apr(indices)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(apr.total_reward > 0);
+        assert!(apr.apr > 0.0);
+
+        self
+    }
+
     pub async fn assert_lowest_block_has_block_packing(&mut self) -> &mut Self {
         let mut block = self
             .client
@@ -1161,6 +1215,50 @@ async fn chain_grows_to_second_epoch() {
         .await;
 }
 
+#[cfg(unix)]
+#[tokio::test]
+async fn chain_grows_with_validator_rewards() {
+    let builder = TesterBuilder::new().await.enable_validator_rewards();
+
+    let docker = Cli::default();
+    let image = RunnableImage::from(Postgres::default())
+        .with_mapped_port((builder.config.database.port, 5432));
+    let _node = docker.run(image);
+
+    let pool = builder.initialize_database().await;
+    let mut tester = builder.build(pool).await;
+    // Apply 4 epochs of blocks to the chain.
+    tester
+        .extend_chain(SLOTS_PER_EPOCH * 4)
+        .await
+        .perform_head_update()
+        .await
+        // Fill back to genesis.
+        .perform_backfill()
+        .await
+        .perform_backfill()
+        .await
+        .assert_lowest_canonical_slot(0)
+        .await
+        // Insert all validators
+        .update_validator_set()
+        .await
+        // Insert all blocks.
+        .update_unknown_blocks()
+        .await
+        // Forward fill up to `head - 2` and backfill to genesis.
+        .fill_validator_rewards()
+        .await
+        .backfill_validator_rewards()
+        .await
+        .assert_validator_has_rewards(0, 1)
+        .await
+        .assert_validator_has_rewards(1, 2)
+        .await
+        .assert_validators_have_positive_apr(&[0, 1, 2])
+        .await;
+}
+
 #[cfg(unix)]
 #[tokio::test]
 async fn large_chain() {